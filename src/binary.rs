@@ -0,0 +1,986 @@
+use super::*;
+
+/// TypeScript statements and expressions reading and writing a single value
+/// in the protobuf wire format. `$w`, `$r` and `$v` stand for the writer,
+/// the reader and the value being written.
+struct WireCodec {
+    wire_type: u32,
+    write: String,
+    read: String,
+    is_packable: bool
+}
+
+impl WireCodec {
+    fn new(wire_type: u32, write: &str, read: &str) -> WireCodec {
+        WireCodec{
+            wire_type,
+            write: write.to_string(),
+            read: read.to_string(),
+            is_packable: wire_type != 2
+        }
+    }
+
+    fn write(&self, writer: &str, value: &str) -> String {
+        self.write.replace("$w", writer).replace("$v", value)
+    }
+
+    fn read(&self, reader: &str) -> String {
+        self.read.replace("$r", reader)
+    }
+}
+
+fn int64_codec(wire_type: u32, method: &str, options: &Options) -> WireCodec {
+    match options.int64_style {
+        Int64Style::String => WireCodec::new(
+            wire_type,
+            &format!("$w.{}(BigInt($v))", method),
+            &format!("$r.{}().toString()", method)
+        ),
+        Int64Style::BigInt => WireCodec::new(
+            wire_type,
+            &format!("$w.{}($v)", method),
+            &format!("$r.{}()", method)
+        ),
+        Int64Style::Number => WireCodec::new(
+            wire_type,
+            &format!("$w.{}(BigInt($v))", method),
+            &format!("Number($r.{}())", method)
+        )
+    }
+}
+
+fn scalar_codec(field_type: FieldDescriptorProto_Type, options: &Options) -> WireCodec {
+    let number = |wire_type, method| WireCodec::new(
+        wire_type,
+        &format!("$w.{}($v)", method),
+        &format!("$r.{}()", method)
+    );
+    match field_type {
+        FieldDescriptorProto_Type::TYPE_DOUBLE => number(1, "double"),
+        FieldDescriptorProto_Type::TYPE_FLOAT => number(5, "float"),
+        FieldDescriptorProto_Type::TYPE_INT32 => number(0, "int32"),
+        FieldDescriptorProto_Type::TYPE_UINT32 => number(0, "uint32"),
+        FieldDescriptorProto_Type::TYPE_SINT32 => number(0, "sint32"),
+        FieldDescriptorProto_Type::TYPE_FIXED32 => number(5, "fixed32"),
+        FieldDescriptorProto_Type::TYPE_SFIXED32 => number(5, "sfixed32"),
+        FieldDescriptorProto_Type::TYPE_BOOL => number(0, "bool"),
+        FieldDescriptorProto_Type::TYPE_INT64 => int64_codec(0, "int64", options),
+        FieldDescriptorProto_Type::TYPE_UINT64 => int64_codec(0, "uint64", options),
+        FieldDescriptorProto_Type::TYPE_SINT64 => int64_codec(0, "sint64", options),
+        FieldDescriptorProto_Type::TYPE_FIXED64 => int64_codec(1, "fixed64", options),
+        FieldDescriptorProto_Type::TYPE_SFIXED64 => int64_codec(1, "sfixed64", options),
+        FieldDescriptorProto_Type::TYPE_BYTES => match options.bytes_style {
+            BytesStyle::Base64String => WireCodec::new(2, "$w.bytes(base64ToBytes($v))", "bytesToBase64($r.bytes())"),
+            BytesStyle::Uint8Array => WireCodec::new(2, "$w.bytes($v)", "$r.bytes()")
+        },
+        _ => WireCodec::new(2, "$w.string($v)", "$r.string()")
+    }
+}
+
+fn well_known_codec(type_name: &str, options: &Options) -> Option<WireCodec> {
+    let ts_type = well_known_type_to_ts_type(type_name, options)?;
+    if let Some(field_type) = wrapped_type(type_name) {
+        let inner = scalar_codec(field_type, options);
+        return Some(WireCodec::new(
+            2,
+            &format!(
+                "$w.bytes(wrap($v, (w, v) => {{ w.tag(1, {}); {}; }}))",
+                inner.wire_type,
+                inner.write("w", "v")
+            ),
+            &format!(
+                "unwrap($r.bytes(), (r) => {}, {})",
+                inner.read("r"),
+                json::scalar_default(field_type, options)
+            )
+        ));
+    }
+    let overridden = options.type_overrides.get(type_name).map(|type_override| type_override.ts_type.as_str());
+    let (write, read) = match (type_name, overridden) {
+        (".google.protobuf.Timestamp", Some("Date")) => (
+            "encodeTimestamp($v.toISOString())".to_string(),
+            "new Date(decodeTimestamp($r.bytes()))".to_string()
+        ),
+        (".google.protobuf.NullValue", None) => return Some(WireCodec::new(0, "$w.int32(0)", "($r.int32(), null)")),
+        (".google.protobuf.Timestamp", _) => ("encodeTimestamp($v)".to_string(), "decodeTimestamp($r.bytes())".to_string()),
+        (".google.protobuf.Duration", _) => ("encodeDuration($v)".to_string(), "decodeDuration($r.bytes())".to_string()),
+        (".google.protobuf.Struct", _) => ("encodeStruct($v)".to_string(), "decodeStruct($r.bytes())".to_string()),
+        (".google.protobuf.Value", _) => ("encodeValue($v)".to_string(), "decodeValue($r.bytes())".to_string()),
+        (".google.protobuf.ListValue", _) => ("encodeListValue($v)".to_string(), "decodeListValue($r.bytes())".to_string()),
+        (".google.protobuf.Any", _) => ("encodeAny($v)".to_string(), "decodeAny($r.bytes())".to_string()),
+        _ => return None
+    };
+    let write = match overridden {
+        Some(_) => write.replace("($v)", "($v as never)"),
+        None => write
+    };
+    let read = match (ts_type, overridden) {
+        (TsType::String, None) => read,
+        (_, Some(_)) if read.starts_with("new ") => read,
+        (ts_type, _) => format!("{} as {}", read, ts_type)
+    };
+    Some(WireCodec::new(2, &format!("$w.bytes({})", write), &read))
+}
+
+/// Codec of `value`, a value of field `number` which only groups, being
+/// terminated by a tag, need.
+fn value_codec(
+    value: &ir::Value,
+    number: i32,
+    resolver: &TypeResolver,
+    options: &Options
+) -> WireCodec {
+    if let ir::ValueKind::Scalar(field_type) = value.kind {
+        return scalar_codec(field_type, options);
+    }
+    if let Some(codec) = well_known_codec(&value.type_name, options) {
+        return codec;
+    }
+    let prefix = resolver.function_prefix(&value.type_name);
+    match value.field_type {
+        FieldDescriptorProto_Type::TYPE_ENUM => WireCodec::new(
+            0,
+            &format!("$w.int32({}ToNumber($v))", prefix),
+            &format!("{}FromNumber($r.int32())", prefix)
+        ),
+        FieldDescriptorProto_Type::TYPE_GROUP => WireCodec{
+            is_packable: false,
+            ..WireCodec::new(
+                3,
+                &format!("$w.raw(encode{}($v)); $w.tag({}, 4)", prefix, number),
+                &format!("decode{}($r.group({}))", prefix, number)
+            )
+        },
+        _ => WireCodec::new(
+            2,
+            &format!("$w.bytes(encode{}($v))", prefix),
+            &format!("decode{}($r.bytes())", prefix)
+        )
+    }
+}
+
+/// Converts a key of a `Record` map, always a string at runtime, back to
+/// the key type expected by the key codec.
+fn record_key(field_type: FieldDescriptorProto_Type, options: &Options) -> &'static str {
+    match field_type {
+        FieldDescriptorProto_Type::TYPE_BOOL => "k === \"true\"",
+        FieldDescriptorProto_Type::TYPE_STRING => "k",
+        field_type => match scalar_to_ts_type(field_type, options) {
+            TsType::String => "k",
+            TsType::BigInt => "BigInt(k)",
+            _ => "Number(k)"
+        }
+    }
+}
+
+struct BinaryMessageFunctions<'a> {
+    prefix: String,
+    type_name: Option<String>,
+    message: &'a ir::Message,
+    types: &'a ir::Types<'a>,
+    resolver: &'a TypeResolver,
+    options: &'a Options
+}
+
+impl<'a> BinaryMessageFunctions<'a> {
+    fn map_default(&self, value: &ir::Value) -> String {
+        match (value.kind, value.field_type) {
+            (ir::ValueKind::Message, FieldDescriptorProto_Type::TYPE_MESSAGE) =>
+                format!("decode{}(new Uint8Array(0))", self.resolver.function_prefix(&value.type_name)),
+            _ => json::entry_default(value, self.types, self.resolver, self.options)
+                .unwrap_or_else(|| "undefined".to_string())
+        }
+    }
+
+    fn fmt_encode_field(&self, f: &mut fmt::Formatter, field: &ir::Field) -> fmt::Result {
+        let number = field.number;
+        let value = format!("message[\"{}\"]", field.key);
+        let codec = value_codec(&field.value, number, self.resolver, self.options);
+        match &field.cardinality {
+            ir::Cardinality::Map{ key } => {
+                let key_codec = value_codec(key, number, self.resolver, self.options);
+                let (entries, key_value) = match self.options.map_style {
+                    MapStyle::Record => (format!("Object.entries({})", value), record_key(key.field_type, self.options)),
+                    MapStyle::Map => (format!("{}.entries()", value), "k")
+                };
+                writeln!(f, "  for (const [k, x] of {}) {{", entries)?;
+                writeln!(f, "    writer.tag({}, 2);", number)?;
+                writeln!(
+                    f,
+                    "    writer.bytes(encodeNested((entry) => {{ entry.tag(1, {}); {}; entry.tag(2, {}); {}; }}));",
+                    key_codec.wire_type,
+                    key_codec.write("entry", key_value),
+                    codec.wire_type,
+                    codec.write("entry", "x")
+                )?;
+                return writeln!(f, "  }}");
+            },
+            ir::Cardinality::Repeated => {
+                if codec.is_packable && field.is_packed {
+                    writeln!(f, "  if ({}.length > 0) {{", value)?;
+                    writeln!(f, "    writer.tag({}, 2);", number)?;
+                    writeln!(
+                        f,
+                        "    writer.bytes(encodeNested((packed) => {{ for (const x of {}) {}; }}));",
+                        value,
+                        codec.write("packed", "x")
+                    )?;
+                } else {
+                    writeln!(f, "  for (const x of {}) {{", value)?;
+                    writeln!(f, "    writer.tag({}, {});", number, codec.wire_type)?;
+                    writeln!(f, "    {};", codec.write("writer", "x"))?;
+                }
+                return writeln!(f, "  }}");
+            },
+            ir::Cardinality::Single => {}
+        }
+        let (condition, value) = discriminated_oneof_access("message", field, &self.message.oneofs, self.options)
+            .unwrap_or_else(|| (json::field_is_set(field, &value, self.types, self.resolver, self.options), value));
+        if condition == "false" {
+            return Ok(());
+        }
+        writeln!(f, "  if ({}) {{", condition)?;
+        writeln!(f, "    writer.tag({}, {});", number, codec.wire_type)?;
+        writeln!(f, "    {};", codec.write("writer", &value))?;
+        writeln!(f, "  }}")
+    }
+
+    fn fmt_decode_field(&self, f: &mut fmt::Formatter, field: &ir::Field) -> fmt::Result {
+        let value = format!("message[\"{}\"]", field.key);
+        let codec = value_codec(&field.value, field.number, self.resolver, self.options);
+        match &field.cardinality {
+            ir::Cardinality::Map{ key } => {
+                writeln!(f, "      case {}: {{", field.number)?;
+                writeln!(f, "        const entry = new Reader(reader.bytes());")?;
+                writeln!(f, "        let k: unknown = {};", self.map_default(key))?;
+                writeln!(f, "        let x: unknown = {};", self.map_default(&field.value))?;
+                writeln!(f, "        while (entry.pos < entry.len) {{")?;
+                writeln!(f, "          const entryTag = entry.uint32();")?;
+                writeln!(f, "          switch (entryTag >>> 3) {{")?;
+                writeln!(f, "            case 1:")?;
+                let key_codec = value_codec(key, field.number, self.resolver, self.options);
+                writeln!(f, "              k = {};", key_codec.read("entry"))?;
+                writeln!(f, "              break;")?;
+                writeln!(f, "            case 2:")?;
+                writeln!(f, "              x = {};", codec.read("entry"))?;
+                writeln!(f, "              break;")?;
+                writeln!(f, "            default:")?;
+                writeln!(f, "              entry.skip(entryTag & 7);")?;
+                writeln!(f, "          }}")?;
+                writeln!(f, "        }}")?;
+                match self.options.map_style {
+                    MapStyle::Record => writeln!(f, "        ({} as Record<string, unknown>)[String(k)] = x;", value)?,
+                    MapStyle::Map => writeln!(f, "        ({} as Map<unknown, unknown>).set(k, x);", value)?
+                }
+                writeln!(f, "        break;")?;
+                return writeln!(f, "      }}");
+            },
+            ir::Cardinality::Repeated => {
+                writeln!(f, "      case {}:", field.number)?;
+                let push = format!("({} as unknown[]).push({})", value, codec.read("reader"));
+                if codec.is_packable {
+                    writeln!(f, "        if ((tag & 7) === 2) {{")?;
+                    writeln!(f, "          const end = reader.uint32() + reader.pos;")?;
+                    writeln!(f, "          while (reader.pos < end) {};", push)?;
+                    writeln!(f, "        }} else {{")?;
+                    writeln!(f, "          {};", push)?;
+                    writeln!(f, "        }}")?;
+                } else {
+                    writeln!(f, "        {};", push)?;
+                }
+                return writeln!(f, "        break;");
+            },
+            ir::Cardinality::Single => writeln!(f, "      case {}:", field.number)?
+        }
+        if let Some(assign) = discriminated_oneof_assign(
+            "message",
+            field,
+            &self.message.oneofs,
+            &codec.read("reader"),
+            self.options
+        ) {
+            writeln!(f, "        {};", assign)?;
+            return writeln!(f, "        break;");
+        }
+        writeln!(f, "        {} = {};", value, codec.read("reader"))?;
+        if let Some(index) = field.oneof {
+            self.message.oneof_fields(index)
+                .filter(|sibling| sibling.number != field.number)
+                .try_for_each(|sibling| writeln!(f, "        delete message[\"{}\"];", sibling.key))?;
+        }
+        writeln!(f, "        break;")
+    }
+}
+
+impl<'a> fmt::Display for BinaryMessageFunctions<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (ts_type, fields) = (&self.message.ts_name, &self.message.fields);
+        let message = match fields.is_empty() {
+            true => "_message",
+            false => "message"
+        };
+        writeln!(f, "export function encode{}({}: {}): Uint8Array {{", self.prefix, message, ts_type)?;
+        writeln!(f, "  const writer = new Writer();")?;
+        for field in fields.iter() {
+            self.fmt_encode_field(f, field)?;
+        }
+        writeln!(f, "  return writer.finish();")?;
+        writeln!(f, "}}")?;
+        writeln!(f)?;
+        writeln!(f, "export function decode{}(bytes: Uint8Array): {} {{", self.prefix, ts_type)?;
+        writeln!(f, "  const reader = new Reader(bytes);")?;
+        writeln!(f, "  const message: Record<string, unknown> = {{}};")?;
+        if let Some(type_name) = &self.type_name {
+            writeln!(f, "  message[\"{}\"] = {};", TYPE_FIELD_KEY, type_name)?;
+        }
+        for field in fields.iter() {
+            if let Some(default) = json::field_default(field, self.types, self.resolver, self.options) {
+                writeln!(f, "  message[\"{}\"] = {};", field.key, default)?;
+            }
+        }
+        writeln!(f, "  while (reader.pos < reader.len) {{")?;
+        writeln!(f, "    const tag = reader.uint32();")?;
+        writeln!(f, "    switch (tag >>> 3) {{")?;
+        for field in fields.iter() {
+            self.fmt_decode_field(f, field)?;
+        }
+        writeln!(f, "      default:")?;
+        writeln!(f, "        reader.skip(tag & 7);")?;
+        writeln!(f, "    }}")?;
+        writeln!(f, "  }}")?;
+        writeln!(f, "  return message as unknown as {};", ts_type)?;
+        writeln!(f, "}}")
+    }
+}
+
+struct BinaryEnumFunctions<'a> {
+    prefix: String,
+    enum_type: &'a ir::Enum,
+    default: String,
+    style: EnumStyle,
+    is_open: bool
+}
+
+impl<'a> fmt::Display for BinaryEnumFunctions<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (ts_type, values) = (&self.enum_type.ts_name, &self.enum_type.values);
+        writeln!(f, "export function {}ToNumber(value: {}): number {{", self.prefix, ts_type)?;
+        match self.style {
+            EnumStyle::Union => {
+                writeln!(f, "  switch (value) {{")?;
+                for value in values.iter() {
+                    writeln!(f, "    case \"{}\":", value.ts_name)?;
+                    writeln!(f, "      return {};", value.number)?;
+                }
+                writeln!(f, "    default:")?;
+                writeln!(f, "      return 0;")?;
+                writeln!(f, "  }}")?;
+            },
+            EnumStyle::Enum | EnumStyle::Const => writeln!(f, "  return value;")?
+        }
+        writeln!(f, "}}")?;
+        writeln!(f)?;
+        writeln!(f, "export function {}FromNumber(value: number): {} {{", self.prefix, ts_type)?;
+        match self.style {
+            EnumStyle::Union => {
+                writeln!(f, "  switch (value) {{")?;
+                for value in values.iter() {
+                    writeln!(f, "    case {}:", value.number)?;
+                    writeln!(f, "      return \"{}\";", value.ts_name)?;
+                }
+                writeln!(f, "    default:")?;
+                match self.is_open {
+                    true => writeln!(f, "      return \"{}\";", UNRECOGNIZED)?,
+                    false => writeln!(f, "      return {};", self.default)?
+                }
+                writeln!(f, "  }}")?;
+            },
+            EnumStyle::Enum | EnumStyle::Const => writeln!(f, "  return value as {};", ts_type)?
+        }
+        writeln!(f, "}}")
+    }
+}
+
+const WIRE_HELPERS: &str = "\
+class Writer {
+  private readonly buf: number[] = [];
+
+  tag(fieldNumber: number, wireType: number): void {
+    this.uint32(fieldNumber * 8 + wireType);
+  }
+
+  uint32(value: number): void {
+    value >>>= 0;
+    while (value > 0x7f) {
+      this.buf.push((value & 0x7f) | 0x80);
+      value >>>= 7;
+    }
+    this.buf.push(value);
+  }
+
+  int32(value: number): void {
+    if (value < 0) {
+      this.uint64(BigInt(value));
+    } else {
+      this.uint32(value);
+    }
+  }
+
+  sint32(value: number): void {
+    this.uint32((value << 1) ^ (value >> 31));
+  }
+
+  uint64(value: bigint): void {
+    value = BigInt.asUintN(64, value);
+    while (value > BigInt(0x7f)) {
+      this.buf.push(Number(value & BigInt(0x7f)) | 0x80);
+      value >>= BigInt(7);
+    }
+    this.buf.push(Number(value));
+  }
+
+  int64(value: bigint): void {
+    this.uint64(value);
+  }
+
+  sint64(value: bigint): void {
+    this.uint64((value << BigInt(1)) ^ (value >> BigInt(63)));
+  }
+
+  bool(value: boolean): void {
+    this.buf.push(value ? 1 : 0);
+  }
+
+  fixed32(value: number): void {
+    this.fixed(4, (view) => view.setUint32(0, value, true));
+  }
+
+  sfixed32(value: number): void {
+    this.fixed(4, (view) => view.setInt32(0, value, true));
+  }
+
+  float(value: number): void {
+    this.fixed(4, (view) => view.setFloat32(0, value, true));
+  }
+
+  fixed64(value: bigint): void {
+    this.fixed(8, (view) => view.setBigUint64(0, BigInt.asUintN(64, value), true));
+  }
+
+  sfixed64(value: bigint): void {
+    this.fixed(8, (view) => view.setBigInt64(0, BigInt.asIntN(64, value), true));
+  }
+
+  double(value: number): void {
+    this.fixed(8, (view) => view.setFloat64(0, value, true));
+  }
+
+  string(value: string): void {
+    this.bytes(new TextEncoder().encode(value));
+  }
+
+  bytes(value: Uint8Array): void {
+    this.uint32(value.length);
+    this.raw(value);
+  }
+
+  raw(value: Uint8Array): void {
+    value.forEach((byte) => this.buf.push(byte));
+  }
+
+  finish(): Uint8Array {
+    return Uint8Array.from(this.buf);
+  }
+
+  private fixed(size: number, write: (view: DataView) => void): void {
+    const view = new DataView(new ArrayBuffer(size));
+    write(view);
+    new Uint8Array(view.buffer).forEach((byte) => this.buf.push(byte));
+  }
+}
+
+class Reader {
+  pos = 0;
+  readonly len: number;
+  private readonly view: DataView;
+
+  constructor(private readonly buf: Uint8Array) {
+    this.len = buf.length;
+    this.view = new DataView(buf.buffer, buf.byteOffset, buf.byteLength);
+  }
+
+  uint32(): number {
+    let value = 0;
+    for (let shift = 0; ; shift += 7) {
+      const byte = this.next();
+      if (shift < 32) value |= (byte & 0x7f) << shift;
+      if ((byte & 0x80) === 0) return value >>> 0;
+    }
+  }
+
+  int32(): number {
+    return this.uint32() | 0;
+  }
+
+  sint32(): number {
+    const value = this.uint32();
+    return (value >>> 1) ^ -(value & 1);
+  }
+
+  uint64(): bigint {
+    let value = BigInt(0);
+    for (let shift = BigInt(0); ; shift += BigInt(7)) {
+      const byte = this.next();
+      value |= BigInt(byte & 0x7f) << shift;
+      if ((byte & 0x80) === 0) return BigInt.asUintN(64, value);
+    }
+  }
+
+  int64(): bigint {
+    return BigInt.asIntN(64, this.uint64());
+  }
+
+  sint64(): bigint {
+    const value = this.uint64();
+    return (value >> BigInt(1)) ^ -(value & BigInt(1));
+  }
+
+  bool(): boolean {
+    return this.uint64() !== BigInt(0);
+  }
+
+  fixed32(): number {
+    return this.view.getUint32(this.advance(4), true);
+  }
+
+  sfixed32(): number {
+    return this.view.getInt32(this.advance(4), true);
+  }
+
+  float(): number {
+    return this.view.getFloat32(this.advance(4), true);
+  }
+
+  fixed64(): bigint {
+    return this.view.getBigUint64(this.advance(8), true);
+  }
+
+  sfixed64(): bigint {
+    return this.view.getBigInt64(this.advance(8), true);
+  }
+
+  double(): number {
+    return this.view.getFloat64(this.advance(8), true);
+  }
+
+  string(): string {
+    return new TextDecoder().decode(this.bytes());
+  }
+
+  bytes(): Uint8Array {
+    const length = this.uint32();
+    const start = this.advance(length);
+    return this.buf.subarray(start, start + length);
+  }
+
+  group(fieldNumber: number): Uint8Array {
+    const start = this.pos;
+    let end = start;
+    for (let tag = this.uint32(); tag !== fieldNumber * 8 + 4; tag = this.uint32()) {
+      this.skip(tag & 7);
+      end = this.pos;
+    }
+    return this.buf.subarray(start, end);
+  }
+
+  skip(wireType: number): void {
+    switch (wireType) {
+      case 0:
+        this.uint64();
+        break;
+      case 1:
+        this.advance(8);
+        break;
+      case 2:
+        this.advance(this.uint32());
+        break;
+      case 3:
+        for (let tag = this.uint32(); (tag & 7) !== 4; tag = this.uint32()) {
+          this.skip(tag & 7);
+        }
+        break;
+      case 5:
+        this.advance(4);
+        break;
+      default:
+        throw new Error(`invalid wire type ${wireType}`);
+    }
+  }
+
+  private next(): number {
+    return this.buf[this.advance(1)];
+  }
+
+  private advance(size: number): number {
+    const start = this.pos;
+    if (start + size > this.len) throw new RangeError(\"premature end of input\");
+    this.pos += size;
+    return start;
+  }
+}
+
+function encodeNested(write: (writer: Writer) => void): Uint8Array {
+  const writer = new Writer();
+  write(writer);
+  return writer.finish();
+}
+";
+
+const WRAPPER_HELPERS: &str = "\
+function wrap<T>(value: T | null, write: (writer: Writer, value: T) => void): Uint8Array {
+  return encodeNested((writer) => {
+    if (value !== null) write(writer, value);
+  });
+}
+
+function unwrap<T>(bytes: Uint8Array, read: (reader: Reader) => T, fallback: T): T {
+  const reader = new Reader(bytes);
+  let value = fallback;
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    if (tag >>> 3 === 1) {
+      value = read(reader);
+    } else {
+      reader.skip(tag & 7);
+    }
+  }
+  return value;
+}
+";
+
+const TIME_HELPERS: &str = "\
+function encodeTime(seconds: bigint, nanos: number): Uint8Array {
+  return encodeNested((writer) => {
+    if (seconds !== BigInt(0)) {
+      writer.tag(1, 0);
+      writer.int64(seconds);
+    }
+    if (nanos !== 0) {
+      writer.tag(2, 0);
+      writer.int32(nanos);
+    }
+  });
+}
+
+function decodeTime(bytes: Uint8Array): [bigint, number] {
+  const reader = new Reader(bytes);
+  let seconds = BigInt(0);
+  let nanos = 0;
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    switch (tag >>> 3) {
+      case 1:
+        seconds = reader.int64();
+        break;
+      case 2:
+        nanos = reader.int32();
+        break;
+      default:
+        reader.skip(tag & 7);
+    }
+  }
+  return [seconds, nanos];
+}
+
+function parseNanos(fraction: string | undefined): number {
+  return Number((fraction ?? \"\").padEnd(9, \"0\").slice(0, 9));
+}
+
+function formatNanos(nanos: number): string {
+  if (nanos === 0) return \"\";
+  const digits = nanos.toString().padStart(9, \"0\");
+  if (nanos % 1000000 === 0) return `.${digits.slice(0, 3)}`;
+  if (nanos % 1000 === 0) return `.${digits.slice(0, 6)}`;
+  return `.${digits}`;
+}
+
+function encodeTimestamp(value: string): Uint8Array {
+  const fraction = /\\.(\\d+)/.exec(value);
+  const seconds = Math.floor(Date.parse(value) / 1000);
+  return encodeTime(BigInt(seconds), parseNanos(fraction?.[1]));
+}
+
+function decodeTimestamp(bytes: Uint8Array): string {
+  const [seconds, nanos] = decodeTime(bytes);
+  const date = new Date(Number(seconds) * 1000).toISOString().replace(/\\.\\d+Z$/, \"\");
+  return `${date}${formatNanos(nanos)}Z`;
+}
+
+function encodeDuration(value: string): Uint8Array {
+  const match = /^(-)?(\\d+)(?:\\.(\\d+))?s$/.exec(value);
+  if (match === null) throw new RangeError(`invalid duration ${value}`);
+  const sign = match[1] === undefined ? 1 : -1;
+  return encodeTime(BigInt(match[2]) * BigInt(sign), parseNanos(match[3]) * sign);
+}
+
+function decodeDuration(bytes: Uint8Array): string {
+  const [seconds, nanos] = decodeTime(bytes);
+  const sign = seconds < BigInt(0) || nanos < 0 ? \"-\" : \"\";
+  const abs = seconds < BigInt(0) ? -seconds : seconds;
+  return `${sign}${abs}${formatNanos(Math.abs(nanos))}s`;
+}
+";
+
+const STRUCT_HELPERS: &str = "\
+function encodeValue(value: unknown): Uint8Array {
+  return encodeNested((writer) => {
+    if (typeof value === \"number\") {
+      writer.tag(2, 1);
+      writer.double(value);
+    } else if (typeof value === \"string\") {
+      writer.tag(3, 2);
+      writer.string(value);
+    } else if (typeof value === \"boolean\") {
+      writer.tag(4, 0);
+      writer.bool(value);
+    } else if (Array.isArray(value)) {
+      writer.tag(6, 2);
+      writer.bytes(encodeListValue(value));
+    } else if (typeof value === \"object\" && value !== null) {
+      writer.tag(5, 2);
+      writer.bytes(encodeStruct(value));
+    } else {
+      writer.tag(1, 0);
+      writer.int32(0);
+    }
+  });
+}
+
+function encodeStruct(value: unknown): Uint8Array {
+  return encodeNested((writer) => {
+    Object.entries(value as Record<string, unknown>).forEach(([key, field]) => {
+      writer.tag(1, 2);
+      writer.bytes(encodeNested((entry) => {
+        entry.tag(1, 2);
+        entry.string(key);
+        entry.tag(2, 2);
+        entry.bytes(encodeValue(field));
+      }));
+    });
+  });
+}
+
+function encodeListValue(value: unknown): Uint8Array {
+  return encodeNested((writer) => {
+    (value as unknown[]).forEach((element) => {
+      writer.tag(1, 2);
+      writer.bytes(encodeValue(element));
+    });
+  });
+}
+
+function decodeValue(bytes: Uint8Array): unknown {
+  const reader = new Reader(bytes);
+  let value: unknown = null;
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    switch (tag >>> 3) {
+      case 1:
+        reader.int32();
+        value = null;
+        break;
+      case 2:
+        value = reader.double();
+        break;
+      case 3:
+        value = reader.string();
+        break;
+      case 4:
+        value = reader.bool();
+        break;
+      case 5:
+        value = decodeStruct(reader.bytes());
+        break;
+      case 6:
+        value = decodeListValue(reader.bytes());
+        break;
+      default:
+        reader.skip(tag & 7);
+    }
+  }
+  return value;
+}
+
+function decodeStruct(bytes: Uint8Array): unknown {
+  const reader = new Reader(bytes);
+  const struct: Record<string, unknown> = {};
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    if (tag >>> 3 !== 1) {
+      reader.skip(tag & 7);
+      continue;
+    }
+    const entry = new Reader(reader.bytes());
+    let key = \"\";
+    let value: unknown = null;
+    while (entry.pos < entry.len) {
+      const entryTag = entry.uint32();
+      switch (entryTag >>> 3) {
+        case 1:
+          key = entry.string();
+          break;
+        case 2:
+          value = decodeValue(entry.bytes());
+          break;
+        default:
+          entry.skip(entryTag & 7);
+      }
+    }
+    struct[key] = value;
+  }
+  return struct;
+}
+
+function decodeListValue(bytes: Uint8Array): unknown {
+  const reader = new Reader(bytes);
+  const values: unknown[] = [];
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    if (tag >>> 3 === 1) {
+      values.push(decodeValue(reader.bytes()));
+    } else {
+      reader.skip(tag & 7);
+    }
+  }
+  return values;
+}
+";
+
+const ANY_HELPERS: &str = "\
+function encodeAny(value: Readonly<{ \"@type\": string } & Record<string, unknown>>): Uint8Array {
+  const payload = value[\"value\"];
+  return encodeNested((writer) => {
+    writer.tag(1, 2);
+    writer.string(value[\"@type\"]);
+    if (typeof payload === \"string\") {
+      writer.tag(2, 2);
+      writer.bytes(base64ToBytes(payload));
+    } else if (payload instanceof Uint8Array) {
+      writer.tag(2, 2);
+      writer.bytes(payload);
+    }
+  });
+}
+
+function decodeAny(bytes: Uint8Array): unknown {
+  const reader = new Reader(bytes);
+  let typeUrl = \"\";
+  let payload = new Uint8Array(0);
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    switch (tag >>> 3) {
+      case 1:
+        typeUrl = reader.string();
+        break;
+      case 2:
+        payload = reader.bytes();
+        break;
+      default:
+        reader.skip(tag & 7);
+    }
+  }
+  return { \"@type\": typeUrl, value: bytesToBase64(payload) };
+}
+";
+
+/// Every helper of binary codecs, for the shared runtime module.
+pub(crate) const HELPERS: [&str; 5] = [WIRE_HELPERS, WRAPPER_HELPERS, TIME_HELPERS, STRUCT_HELPERS, ANY_HELPERS];
+
+fn binary_file_stem(file_name: &str, resolver: &TypeResolver, options: &Options) -> String {
+    output_file_stem(file_name, "_binary", resolver, options)
+}
+
+/// Protobuf wire format encoders and decoders of the messages and enums of
+/// a file.
+pub(crate) struct Binary;
+
+impl Renderer for Binary {
+    fn render(
+        &self,
+        file: &ir::File,
+        types: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File> {
+        if file.messages.is_empty() && file.enums.is_empty() {
+            return vec![];
+        }
+        let stem = binary_file_stem(&file.name, resolver, options);
+        let field_references = file.messages
+            .iter()
+            .flat_map(|message| message.fields.iter())
+            .flat_map(|field| field.references())
+            .collect::<Vec<_>>();
+        let type_references = file.messages
+            .iter()
+            .map(|message| message.full_name.clone())
+            .chain(file.enums.iter().map(|enum_type| enum_type.full_name.clone()))
+            .chain(field_references.iter().filter(|type_name| !is_branded_int(type_name, options)).cloned())
+            .collect::<Vec<_>>();
+        let mut body = CodeWriter::for_file(file);
+        for enum_type in file.enums.iter() {
+            body.push('\n');
+            body.push(BinaryEnumFunctions{
+                prefix: resolver.function_prefix(&enum_type.full_name),
+                enum_type,
+                default: json::enum_default(&enum_type.full_name, types, options),
+                style: options.enum_style,
+                is_open: options.open_enums
+            });
+        }
+        for message in file.messages.iter() {
+            body.push('\n');
+            body.push(BinaryMessageFunctions{
+                prefix: resolver.function_prefix(&message.full_name),
+                type_name: type_field_value(&message.full_name, options),
+                message,
+                types,
+                resolver,
+                options
+            });
+        }
+        let mut content = CodeWriter::for_file(file);
+        content.push(gen_imports(&stem, type_references.iter(), resolver, options));
+        content.push(gen_function_imports(
+            &stem,
+            file,
+            &field_references,
+            "binary",
+            |prefix, is_enum| match is_enum {
+                true => vec![format!("{}FromNumber", prefix), format!("{}ToNumber", prefix)],
+                false => vec![format!("decode{}", prefix), format!("encode{}", prefix)]
+            },
+            resolver,
+            options
+        ));
+        let mut helpers = vec![WIRE_HELPERS];
+        if body.as_str().contains("wrap(") {
+            helpers.push(WRAPPER_HELPERS);
+        }
+        if ["codeTimestamp(", "codeDuration("].iter().any(|name| body.as_str().contains(name)) {
+            helpers.push(TIME_HELPERS);
+        }
+        if ["codeStruct(", "codeValue(", "codeListValue("].iter().any(|name| body.as_str().contains(name)) {
+            helpers.push(STRUCT_HELPERS);
+        }
+        if body.as_str().contains("codeAny(") {
+            helpers.push(ANY_HELPERS);
+        }
+        if ["codeAny(", "base64ToBytes(", "bytesToBase64("].iter().any(|name| body.as_str().contains(name)) {
+            helpers.push(BASE64_HELPERS);
+        }
+        content.push(runtime::gen_helpers(&stem, &helpers, options));
+        content.push(body.as_str());
+        vec![gen_resp_file(format!("{}.ts", stem), content.into_string())]
+    }
+}
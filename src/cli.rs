@@ -0,0 +1,274 @@
+use super::*;
+use protobuf::Message;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+pub(crate) const USAGE: &str = "\
+usage: protoc-gen-tst --descriptor_set <FILE> --out <DIR> [--parameter <PARAMETER>] [--file <PROTO>]... [--cache <FILE>]
+       protoc-gen-tst --request <FILE> --out <DIR> [--parameter <PARAMETER>] [--file <PROTO>]... [--cache <FILE>]
+       protoc-gen-tst --version
+       protoc-gen-tst --help
+Without arguments, reads a CodeGeneratorRequest from protoc on stdin.";
+
+/// The crate version and the parameters it supports.
+pub(crate) fn version() -> String {
+    let width = PARAMETERS.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+    let mut version = format!("{} {}\nparameters:\n", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+    for (key, values) in PARAMETERS {
+        version.push_str(&format!("  {:width$}  {}\n", key, values, width = width));
+    }
+    version
+}
+
+/// What the standalone mode reads in place of a request from protoc.
+enum Input {
+    DescriptorSet(String),
+    /// A `CodeGeneratorRequest` captured with `dump_request`.
+    Request(String)
+}
+
+/// Arguments of the standalone mode, which runs the generator against a
+/// serialized `FileDescriptorSet` or request instead of stdin.
+struct Args {
+    input: Input,
+    out: PathBuf,
+    parameter: Option<String>,
+    files: Vec<String>,
+    cache: Option<PathBuf>
+}
+
+impl Args {
+    fn parse(args: &[String]) -> Result<Args> {
+        let mut input = None;
+        let mut out = None;
+        let mut parameter = None;
+        let mut files = Vec::new();
+        let mut cache = None;
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (flag, value) = match arg.find('=') {
+                Some(index) => (&arg[..index], Some(arg[index + 1..].to_string())),
+                None => (arg.as_str(), None)
+            };
+            let mut value = || value.clone()
+                .or_else(|| args.next().cloned())
+                .ok_or_else(|| Error::Usage(format!("missing value for {}", flag)));
+            match flag {
+                "--descriptor_set" | "--request" if input.is_some() => {
+                    return Err(Error::Usage("--descriptor_set and --request are exclusive".to_string()))
+                },
+                "--descriptor_set" => input = Some(Input::DescriptorSet(value()?)),
+                "--request" => input = Some(Input::Request(value()?)),
+                "--out" => out = Some(PathBuf::from(value()?)),
+                "--parameter" => parameter = Some(value()?),
+                "--file" => files.push(value()?),
+                "--cache" => cache = Some(PathBuf::from(value()?)),
+                _ => return Err(Error::Usage(format!("unknown argument {}", arg)))
+            }
+        }
+        Ok(Args{
+            input: input.ok_or_else(|| Error::Usage("missing --descriptor_set or --request".to_string()))?,
+            out: out.ok_or_else(|| Error::Usage("missing --out".to_string()))?,
+            parameter,
+            files,
+            cache
+        })
+    }
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> Error {
+    Error::Io{ path: path.display().to_string(), error }
+}
+
+/// Builds the request protoc would send for `descriptor_set`. Without
+/// `--file`, every file in the set is generated.
+fn request(mut descriptor_set: FileDescriptorSet, args: &Args) -> CodeGeneratorRequest {
+    let mut req = CodeGeneratorRequest::new();
+    let files_to_generate = match args.files.is_empty() {
+        true => descriptor_set.get_file()
+            .iter()
+            .map(|proto_file| proto_file.get_name().to_string())
+            .collect(),
+        false => args.files.clone()
+    };
+    req.set_file_to_generate(files_to_generate.into());
+    req.set_parameter(args.parameter.clone().unwrap_or_default());
+    req.set_proto_file(descriptor_set.take_file());
+    req
+}
+
+/// Reads the request to generate from `args`. A captured request keeps its
+/// parameter and files unless `--parameter` or `--file` replace them.
+fn read_request(args: &Args) -> Result<CodeGeneratorRequest> {
+    match &args.input {
+        Input::DescriptorSet(path) => {
+            let path = Path::new(path);
+            let bytes = fs::read(path).map_err(|error| io_error(path, error))?;
+            let descriptor_set = protobuf::parse_from_bytes::<FileDescriptorSet>(&bytes).map_err(Error::DescriptorSet)?;
+            Ok(request(descriptor_set, args))
+        },
+        Input::Request(path) => {
+            let path = Path::new(path);
+            let bytes = fs::read(path).map_err(|error| io_error(path, error))?;
+            let mut req = protobuf::parse_from_bytes::<CodeGeneratorRequest>(&bytes).map_err(Error::Decode)?;
+            if let Some(parameter) = &args.parameter {
+                req.set_parameter(parameter.clone());
+            }
+            if !args.files.is_empty() {
+                req.set_file_to_generate(args.files.clone().into());
+            }
+            Ok(req)
+        }
+    }
+}
+
+/// Top-level names of the types `proto_file` declares.
+fn top_level_names(proto_file: &FileDescriptorProto) -> impl Iterator<Item = &str> {
+    proto_file.get_message_type()
+        .iter()
+        .map(|message_type| message_type.get_name())
+        .chain(proto_file.get_enum_type().iter().map(|enum_type| enum_type.get_name()))
+}
+
+/// Hash of everything the outputs of each file of `req` are generated from:
+/// the file and its transitive dependencies, the packages of every file of
+/// the request declaring their top-level names, the parameter and the
+/// configuration files and templates it names.
+fn input_hashes(req: &CodeGeneratorRequest, options: &Options) -> Result<BTreeMap<String, u64>> {
+    let mut settings = std::collections::hash_map::DefaultHasher::new();
+    env!("CARGO_PKG_VERSION").hash(&mut settings);
+    req.get_parameter().hash(&mut settings);
+    let configs = req.get_parameter()
+        .split(',')
+        .filter_map(|param| param.trim().strip_prefix("config="))
+        .map(str::to_string);
+    for path in configs.chain(options.templates().iter().cloned()) {
+        let path = Path::new(&path);
+        fs::read(path).map_err(|error| io_error(path, error))?.hash(&mut settings);
+    }
+    let proto_files = req.get_proto_file()
+        .iter()
+        .map(|proto_file| (proto_file.get_name(), proto_file))
+        .collect::<HashMap<_, _>>();
+    let mut packages_by_name = HashMap::<&str, BTreeSet<&str>>::new();
+    for proto_file in req.get_proto_file() {
+        for name in top_level_names(proto_file) {
+            packages_by_name.entry(name).or_default().insert(proto_file.get_package());
+        }
+    }
+    let mut hashes = BTreeMap::new();
+    for file_name in req.get_file_to_generate() {
+        let mut inputs = BTreeSet::new();
+        let mut pending = vec![file_name.as_str()];
+        while let Some(name) = pending.pop() {
+            if let Some(proto_file) = proto_files.get(name).filter(|_| inputs.insert(name)) {
+                pending.extend(proto_file.get_dependency().iter().map(String::as_str));
+            }
+        }
+        let mut hasher = settings.clone();
+        for name in inputs {
+            name.hash(&mut hasher);
+            let bytes = proto_files[name].write_to_bytes().map_err(Error::DescriptorSet)?;
+            bytes.hash(&mut hasher);
+            for name in top_level_names(proto_files[name]) {
+                packages_by_name[name].hash(&mut hasher);
+            }
+        }
+        hashes.insert(file_name.clone(), hasher.finish());
+    }
+    Ok(hashes)
+}
+
+/// What the last run generated into an output directory: per proto file,
+/// the hash of its inputs and the names of its outputs.
+type Cache = BTreeMap<String, (u64, Vec<String>)>;
+
+/// The cache of the last run, an `out <dir>` line followed by one
+/// `<hash> <file>` line per proto file with its outputs appended, separated
+/// by tabs. A missing or unreadable cache, or one of another output
+/// directory, is empty.
+fn read_cache(path: &Path, out: &Path) -> Cache {
+    let content = fs::read_to_string(path).unwrap_or_default();
+    let mut lines = content.lines();
+    if lines.next() != Some(format!("out {}", out.display()).as_str()) {
+        return Cache::new();
+    }
+    lines
+        .filter_map(|line| {
+            let (hash, rest) = line.split_once(' ')?;
+            let mut fields = rest.split('\t');
+            let file_name = fields.next()?.to_string();
+            Some((file_name, (u64::from_str_radix(hash, 16).ok()?, fields.map(str::to_string).collect())))
+        })
+        .collect()
+}
+
+fn write_cache(path: &Path, out: &Path, cache: &Cache) -> Result<()> {
+    let mut content = format!("out {}\n", out.display());
+    for (file_name, (hash, outputs)) in cache {
+        content.push_str(&format!("{:016x} {}", hash, file_name));
+        outputs.iter().for_each(|output| content.push_str(&format!("\t{}", output)));
+        content.push('\n');
+    }
+    fs::write(path, content).map_err(|error| io_error(path, error))
+}
+
+/// Writes `files` under `out`, returning their names.
+fn write_files(out: &Path, files: &[CodeGeneratorResponse_File]) -> Result<Vec<String>> {
+    for file in files {
+        let path = out.join(file.get_name());
+        // Rewriting identical files would trigger watchers for nothing.
+        if fs::read(&path).ok().as_deref() == Some(file.get_content().as_bytes()) {
+            continue;
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|error| io_error(dir, error))?;
+        }
+        fs::write(&path, file.get_content()).map_err(|error| io_error(&path, error))?;
+    }
+    Ok(files.iter().map(|file| file.get_name().to_string()).collect())
+}
+
+pub(crate) fn run(args: &[String]) -> Result<()> {
+    let args = Args::parse(args)?;
+    let req = read_request(&args)?;
+    let options = Options::parse(req.get_parameter())?;
+    if options.is_list_only() {
+        for file in generate(req, options)?.get_file() {
+            print!("{}", file.get_content());
+        }
+        return Ok(());
+    }
+    // With `--cache`, files whose inputs are unchanged since the last run
+    // and whose outputs are still there are left out, as long as no output
+    // spans several files. The others are generated one at a time to learn
+    // which outputs each has.
+    let path = match &args.cache {
+        Some(path) if options.is_incremental() => path,
+        _ => {
+            if !req.get_file_to_generate().is_empty() {
+                write_files(&args.out, generate(req, options)?.get_file())?;
+            }
+            return Ok(());
+        }
+    };
+    check_collisions(&req, Options::parse(req.get_parameter())?)?;
+    let hashes = input_hashes(&req, &options)?;
+    let mut cache = read_cache(path, &args.out);
+    for file_name in req.get_file_to_generate() {
+        let hash = hashes[file_name];
+        match cache.get(file_name) {
+            Some((cached, outputs)) if *cached == hash && outputs.iter().all(|output| args.out.join(output).exists()) => {},
+            _ => {
+                let mut single = req.clone();
+                single.set_file_to_generate(vec![file_name.clone()].into());
+                let outputs = write_files(&args.out, generate(single, Options::parse(req.get_parameter())?)?.get_file())?;
+                cache.insert(file_name.clone(), (hash, outputs));
+            }
+        }
+    }
+    cache.retain(|file_name, _| hashes.contains_key(file_name));
+    write_cache(path, &args.out, &cache)
+}
@@ -0,0 +1,325 @@
+use super::*;
+
+/// Something generating output files of its own: a proto file, or a
+/// declaration of one with `output_mode=per_message`.
+struct Owner<'a> {
+    file_name: &'a str,
+    declaration: Option<&'a str>,
+    location: String,
+    stem: String
+}
+
+/// Owners whose stems are equal after case folding, since case-insensitive
+/// file systems cannot tell them apart either.
+fn collisions<'a, 'b>(owners: &'b [Owner<'a>]) -> Vec<Vec<&'b Owner<'a>>> {
+    let mut by_stem = BTreeMap::<String, Vec<&Owner>>::new();
+    owners.iter().for_each(|owner| by_stem.entry(owner.stem.to_lowercase()).or_default().push(owner));
+    by_stem.into_values()
+        .filter(|owners| owners.len() > 1)
+        .collect()
+}
+
+/// Resolves the collisions of `owners` as `options.collisions` asks. The
+/// first owner of each collision keeps its stem, while the others are given
+/// the first free stem `rename` makes with a counter from 2, which returns
+/// the stem along with the value to record for the owner.
+fn resolve<'a>(
+    owners: &[Owner<'a>],
+    rename: impl Fn(&Owner, usize) -> (String, String),
+    options: &Options
+) -> Result<Vec<(&'a str, Option<&'a str>, String)>> {
+    let collisions = collisions(owners);
+    if collisions.is_empty() {
+        return Ok(vec![]);
+    }
+    if let CollisionStyle::Error = options.collisions {
+        return Err(Error::Collision(collisions.iter()
+            .map(|owners| format!(
+                "{} generate the same output {}",
+                owners.iter().map(|owner| owner.location.as_str()).collect::<Vec<_>>().join(" and "),
+                owners[0].stem
+            ))
+            .collect()
+        ));
+    }
+    let mut used = owners.iter().map(|owner| owner.stem.to_lowercase()).collect::<BTreeSet<_>>();
+    let mut renames = Vec::new();
+    for owner in collisions.iter().flat_map(|owners| owners.iter().skip(1)) {
+        let (stem, value) = (2..)
+            .map(|n| rename(owner, n))
+            .find(|(stem, _)| !used.contains(&stem.to_lowercase()))
+            .expect("only finitely many stems are in use");
+        used.insert(stem.to_lowercase());
+        renames.push((owner.file_name, owner.declaration, value));
+    }
+    Ok(renames)
+}
+
+/// Checks that no two proto files share an output path, which every kind of
+/// output would inherit. Renamed files are ordered by name, so the result
+/// does not depend on the order protoc lists them in.
+pub(crate) fn check_proto_files(
+    proto_files: &[&FileDescriptorProto],
+    resolver: &mut TypeResolver,
+    options: &Options
+) -> Result<()> {
+    let mut owners = proto_files.iter()
+        .map(|proto_file| Owner{
+            file_name: proto_file.get_name(),
+            declaration: None,
+            location: proto_file.get_name().to_string(),
+            stem: output_file_stem(proto_file.get_name(), "", resolver, options)
+        })
+        .collect::<Vec<_>>();
+    owners.sort_by_key(|owner| owner.file_name);
+    let renames = resolve(
+        &owners,
+        |owner, n| {
+            let path = format!("{}_{}", resolver.output_path(owner.file_name, options), n);
+            (output_path_stem(&path, "", options), path)
+        },
+        options
+    )?;
+    for (file_name, _, path) in renames {
+        resolver.output_paths.insert(file_name.to_string(), path);
+    }
+    Ok(())
+}
+
+/// Source location of the type or service `full_name` of `proto_file`.
+fn locate(proto_file: &FileDescriptorProto, full_name: &str) -> String {
+    let package = proto_file.get_package();
+    let path = proto_file.get_message_type()
+        .iter()
+        .enumerate()
+        .find_map(|(i, message_type)| message_path(
+            vec![FILE_MESSAGE_TYPE, i as i32],
+            &qualified_name(package, message_type.get_name()),
+            message_type,
+            full_name
+        ))
+        .or_else(|| proto_file.get_enum_type()
+            .iter()
+            .position(|enum_type| qualified_name(package, enum_type.get_name()) == full_name)
+            .map(|i| vec![FILE_ENUM_TYPE, i as i32])
+        )
+        .or_else(|| proto_file.get_service()
+            .iter()
+            .position(|service| qualified_name(package, service.get_name()) == full_name)
+            .map(|i| vec![FILE_SERVICE, i as i32])
+        );
+    locate_path(proto_file, path.as_deref(), full_name)
+}
+
+/// Source location of the element at `path` of `proto_file`, named
+/// `full_name`.
+fn locate_path(proto_file: &FileDescriptorProto, path: Option<&[i32]>, full_name: &str) -> String {
+    let span = path.and_then(|path| proto_file.get_source_code_info()
+        .get_location()
+        .iter()
+        .find(|location| location.get_path() == path)
+        .map(|location| location.get_span())
+    );
+    match span {
+        Some([line, column, ..]) => format!("{}:{}:{}: {}", proto_file.get_name(), line + 1, column + 1, full_name.trim_start_matches('.')),
+        _ => format!("{}: {}", proto_file.get_name(), full_name.trim_start_matches('.'))
+    }
+}
+
+fn message_path(path: Vec<i32>, name: &str, message_type: &DescriptorProto, full_name: &str) -> Option<Vec<i32>> {
+    if name == full_name {
+        return Some(path);
+    }
+    let nested_path = |kind: i32, i: usize| {
+        let mut path = path.clone();
+        path.extend(vec![kind, i as i32]);
+        path
+    };
+    message_type.get_nested_type()
+        .iter()
+        .enumerate()
+        .find_map(|(i, nested_type)| message_path(
+            nested_path(MESSAGE_NESTED_TYPE, i),
+            &format!("{}.{}", name, nested_type.get_name()),
+            nested_type,
+            full_name
+        ))
+        .or_else(|| message_type.get_enum_type()
+            .iter()
+            .position(|enum_type| format!("{}.{}", name, enum_type.get_name()) == full_name)
+            .map(|i| nested_path(MESSAGE_ENUM_TYPE, i))
+        )
+}
+
+/// Full name of what declares `name` in `proto_file`: the type it was
+/// flattened from, the outermost type of a namespace, or a service.
+fn declared_full_name(proto_file: &FileDescriptorProto, name: &str, resolver: &TypeResolver, options: &Options) -> Option<String> {
+    resolver.paths
+        .iter()
+        .filter(|(full_name, _)| resolver.file(full_name) == Some(proto_file.get_name()))
+        .filter(|(full_name, _)| resolver.import_name(full_name, options) == name)
+        .min_by_key(|(full_name, path)| (path.len(), full_name.as_str()))
+        .map(|(full_name, _)| full_name.clone())
+        .or_else(|| proto_file.get_service()
+            .iter()
+            .find(|service| format!("{}Client", service.get_name()) == name)
+            .map(|service| qualified_name(proto_file.get_package(), service.get_name()))
+        )
+}
+
+/// Checks that no two declarations share an output file with
+/// `output_mode=per_message`, where each gets a file named after it.
+/// Declarations of one name from a single proto file are the parts of a
+/// namespace, which share their file.
+pub(crate) fn check_declarations(
+    proto_files: &[&FileDescriptorProto],
+    outputs: &[(String, Vec<TsDeclaration>)],
+    resolver: &mut TypeResolver,
+    options: &Options
+) -> Result<()> {
+    let mut owners = Vec::<Owner>::new();
+    for declaration in outputs.iter().flat_map(|(_, declarations)| declarations) {
+        let file_name = match &declaration.source {
+            Some(file_name) => file_name.as_str(),
+            None => continue
+        };
+        if owners.iter().any(|owner| owner.file_name == file_name && owner.declaration == Some(declaration.name.as_str())) {
+            continue;
+        }
+        let location = proto_files.iter()
+            .find(|proto_file| proto_file.get_name() == file_name)
+            .and_then(|proto_file| declared_full_name(proto_file, &declaration.name, resolver, options)
+                .map(|full_name| locate(proto_file, &full_name))
+            )
+            .unwrap_or_else(|| format!("{}: {}", file_name, declaration.name));
+        owners.push(Owner{
+            file_name,
+            declaration: Some(declaration.name.as_str()),
+            location,
+            stem: declaration.name.clone()
+        });
+    }
+    owners.sort_by_key(|owner| (owner.file_name, owner.declaration));
+    let renames = resolve(
+        &owners,
+        |owner, n| {
+            let stem = format!("{}_{}", owner.stem, n);
+            (stem.clone(), stem)
+        },
+        options
+    )?;
+    for (file_name, declaration, stem) in renames {
+        resolver.declaration_stems.insert(
+            (file_name.to_string(), declaration.unwrap_or_default().to_string()),
+            stem
+        );
+    }
+    Ok(())
+}
+
+/// Checks that distinct elements of the proto files end up with distinct
+/// names in generated types: nested types flattened into the name of
+/// another type, and fields whose keys are equal after case conversion.
+pub(crate) fn check_names(proto_files: &[&FileDescriptorProto], resolver: &TypeResolver, options: &Options) -> Result<()> {
+    let mut collisions = Vec::new();
+    for proto_file in proto_files {
+        let mut by_name = BTreeMap::<String, BTreeSet<&str>>::new();
+        resolver.paths
+            .iter()
+            .filter(|(full_name, _)| resolver.file(full_name) == Some(proto_file.get_name()))
+            .for_each(|(full_name, path)| {
+                let name = match options.nested_style {
+                    NestedStyle::Flat => path.join("_"),
+                    NestedStyle::Namespace => path.join(".")
+                };
+                by_name.entry(name).or_default().insert(full_name);
+            });
+        for (name, full_names) in by_name.iter().filter(|(_, full_names)| full_names.len() > 1) {
+            collisions.push(format!(
+                "{} declare the same type {}",
+                full_names.iter().map(|full_name| locate(proto_file, full_name)).collect::<Vec<_>>().join(" and "),
+                name
+            ));
+        }
+        for (i, message_type) in proto_file.get_message_type().iter().enumerate() {
+            check_keys(
+                proto_file,
+                vec![FILE_MESSAGE_TYPE, i as i32],
+                &qualified_name(proto_file.get_package(), message_type.get_name()),
+                message_type,
+                options,
+                &mut collisions
+            );
+        }
+    }
+    match collisions.is_empty() {
+        true => Ok(()),
+        false => Err(Error::NameCollision(collisions))
+    }
+}
+
+/// Collects the fields of `message_type` and its nested types sharing a
+/// property key. Members of a discriminated oneof only share the object of
+/// their case, while the oneof takes a key of its own.
+fn check_keys(
+    proto_file: &FileDescriptorProto,
+    path: Vec<i32>,
+    full_name: &str,
+    message_type: &DescriptorProto,
+    options: &Options,
+    collisions: &mut Vec<String>
+) {
+    let located_field = |field: &FieldDescriptorProto| {
+        let i = message_type.get_field().iter().position(|other| other.get_name() == field.get_name()).unwrap_or_default();
+        let mut field_path = path.clone();
+        field_path.extend(vec![MESSAGE_FIELD, i as i32]);
+        locate_path(proto_file, Some(&field_path), &format!("{}.{}", full_name, field.get_name()))
+    };
+    let key = |field: &FieldDescriptorProto| match options.compat_style {
+        Some(CompatStyle::GrpcWebAsObject) => compat::descriptor_object_key(field, message_type),
+        None => field_key(field, options)
+    };
+    let mut report = |by_key: BTreeMap<String, Vec<String>>| {
+        for (key, fields) in by_key.iter().filter(|(_, fields)| fields.len() > 1) {
+            collisions.push(format!("{} declare the same key {}", fields.join(" and "), key));
+        }
+    };
+    let mut by_key = BTreeMap::<String, Vec<String>>::new();
+    for property in message_properties(message_type, options) {
+        match property {
+            MessageProperty::Field(field) => by_key.entry(key(field)).or_default().push(located_field(field)),
+            MessageProperty::Oneof{ name, key: oneof_key, fields } => {
+                let oneof_path = message_type.get_oneof_decl()
+                    .iter()
+                    .position(|oneof| oneof.get_name() == name)
+                    .map(|i| {
+                        let mut oneof_path = path.clone();
+                        oneof_path.extend(vec![MESSAGE_ONEOF_DECL, i as i32]);
+                        oneof_path
+                    });
+                by_key.entry(oneof_key)
+                    .or_default()
+                    .push(locate_path(proto_file, oneof_path.as_deref(), &format!("{}.{}", full_name, name)));
+                let mut by_member_key = BTreeMap::<String, Vec<String>>::new();
+                fields.iter().for_each(|field| by_member_key.entry(key(field)).or_default().push(located_field(field)));
+                report(by_member_key);
+            }
+        }
+    }
+    report(by_key);
+    for (i, nested_type) in message_type.get_nested_type().iter().enumerate() {
+        if nested_type.get_options().get_map_entry() {
+            continue;
+        }
+        let mut nested_path = path.clone();
+        nested_path.extend(vec![MESSAGE_NESTED_TYPE, i as i32]);
+        check_keys(
+            proto_file,
+            nested_path,
+            &format!("{}.{}", full_name, nested_type.get_name()),
+            nested_type,
+            options,
+            collisions
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use super::*;
+
+/// Words the JS generator of `protoc --js_out` prefixes with `pb_`.
+const JS_RESERVED_WORDS: &[&str] = &[
+    "abstract", "boolean", "break", "byte", "case", "catch", "char", "class", "const", "continue",
+    "debugger", "default", "delete", "do", "double", "else", "enum", "export", "extends", "false",
+    "final", "finally", "float", "for", "function", "goto", "if", "implements", "import", "in",
+    "instanceof", "int", "interface", "long", "native", "new", "null", "package", "private",
+    "protected", "public", "return", "short", "static", "super", "switch", "synchronized", "this",
+    "throw", "throws", "transient", "try", "typeof", "var", "void", "volatile", "while", "with"
+];
+
+/// Key of `field` in the `toObject()` output of google-protobuf classes: its
+/// lowercased name in lowerCamelCase, with a `List` or `Map` suffix when
+/// repeated.
+pub(crate) fn as_object_key(field: &ir::Field) -> String {
+    object_key(&field.name, match field.cardinality {
+        ir::Cardinality::Map{ .. } => "Map",
+        ir::Cardinality::Repeated => "List",
+        ir::Cardinality::Single => ""
+    })
+}
+
+/// `as_object_key` of a field of `message_type`, for the name checks running
+/// before the IR is built.
+pub(crate) fn descriptor_object_key(field: &FieldDescriptorProto, message_type: &DescriptorProto) -> String {
+    let suffix = match (map_entry_type(field, message_type), field.get_label()) {
+        (Some(_), _) => "Map",
+        (None, FieldDescriptorProto_Label::LABEL_REPEATED) => "List",
+        _ => ""
+    };
+    object_key(field.get_name(), suffix)
+}
+
+fn object_key(name: &str, suffix: &str) -> String {
+    let mut key = String::new();
+    for (i, word) in name.to_lowercase().split('_').enumerate() {
+        let mut chars = word.chars();
+        match (i, chars.next()) {
+            (0, _) => key.push_str(word),
+            (_, Some(first)) => {
+                key.extend(first.to_uppercase());
+                key.extend(chars);
+            },
+            (_, None) => {}
+        }
+    }
+    key.push_str(suffix);
+    match JS_RESERVED_WORDS.contains(&key.as_str()) {
+        true => format!("pb_{}", key),
+        false => key
+    }
+}
@@ -0,0 +1,311 @@
+use super::*;
+
+/// A value of a configuration file, in the shapes both JSON and YAML share.
+enum ConfigValue {
+    Scalar(String),
+    List(Vec<ConfigValue>),
+    Map(Vec<(String, ConfigValue)>)
+}
+
+struct JsonParser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>
+}
+
+impl<'a> JsonParser<'a> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+            self.chars.next();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> std::result::Result<(), String> {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(format!("expected '{}' but found '{}'", expected, c)),
+            None => Err(format!("expected '{}' but found the end", expected))
+        }
+    }
+
+    fn string(&mut self) -> std::result::Result<String, String> {
+        self.expect('"')?;
+        let mut value = String::new();
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(value),
+                Some('\\') => match self.chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some('r') => value.push('\r'),
+                    Some('u') => {
+                        let hex = (0..4).filter_map(|_| self.chars.next()).collect::<String>();
+                        let c = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32);
+                        value.push(c.ok_or_else(|| format!("invalid escape \\u{}", hex))?);
+                    },
+                    Some(c) => value.push(c),
+                    None => return Err("unterminated string".to_string())
+                },
+                Some(c) => value.push(c),
+                None => return Err("unterminated string".to_string())
+            }
+        }
+    }
+
+    fn value(&mut self) -> std::result::Result<ConfigValue, String> {
+        self.skip_whitespace();
+        match self.chars.peek() {
+            Some('{') => {
+                self.chars.next();
+                let mut entries = Vec::new();
+                self.skip_whitespace();
+                if self.chars.peek() == Some(&'}') {
+                    self.chars.next();
+                    return Ok(ConfigValue::Map(entries));
+                }
+                loop {
+                    let key = self.string()?;
+                    self.expect(':')?;
+                    entries.push((key, self.value()?));
+                    self.skip_whitespace();
+                    match self.chars.next() {
+                        Some(',') => self.skip_whitespace(),
+                        Some('}') => return Ok(ConfigValue::Map(entries)),
+                        _ => return Err("expected ',' or '}' in an object".to_string())
+                    }
+                }
+            },
+            Some('[') => {
+                self.chars.next();
+                let mut items = Vec::new();
+                self.skip_whitespace();
+                if self.chars.peek() == Some(&']') {
+                    self.chars.next();
+                    return Ok(ConfigValue::List(items));
+                }
+                loop {
+                    items.push(self.value()?);
+                    self.skip_whitespace();
+                    match self.chars.next() {
+                        Some(',') => {},
+                        Some(']') => return Ok(ConfigValue::List(items)),
+                        _ => return Err("expected ',' or ']' in an array".to_string())
+                    }
+                }
+            },
+            Some('"') => self.string().map(ConfigValue::Scalar),
+            Some(_) => {
+                let mut literal = String::new();
+                while let Some(&c) = self.chars.peek() {
+                    if c == ',' || c == '}' || c == ']' || c.is_whitespace() {
+                        break;
+                    }
+                    literal.push(c);
+                    self.chars.next();
+                }
+                match literal.as_str() {
+                    "true" | "false" | "null" => Ok(ConfigValue::Scalar(literal)),
+                    _ if literal.parse::<f64>().is_ok() => Ok(ConfigValue::Scalar(literal)),
+                    _ => Err(format!("unexpected '{}'", literal))
+                }
+            },
+            None => Err("unexpected end of input".to_string())
+        }
+    }
+}
+
+fn parse_json(text: &str) -> std::result::Result<ConfigValue, String> {
+    let mut parser = JsonParser{ chars: text.chars().peekable() };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    match parser.chars.next() {
+        Some(c) => Err(format!("unexpected '{}' after the value", c)),
+        None => Ok(value)
+    }
+}
+
+/// The content of a YAML line without its comment.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            (None, '#') if i == 0 || line[..i].ends_with(char::is_whitespace) => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn yaml_scalar(text: &str) -> std::result::Result<ConfigValue, String> {
+    let text = text.trim();
+    if text.starts_with('[') {
+        let inner = text.strip_prefix('[')
+            .and_then(|text| text.strip_suffix(']'))
+            .ok_or_else(|| format!("unterminated list {}", text))?;
+        return inner.split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(yaml_scalar)
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map(ConfigValue::List);
+    }
+    if text.len() >= 2 && text.starts_with('"') && text.ends_with('"') {
+        return match parse_json(text)? {
+            ConfigValue::Scalar(value) => Ok(ConfigValue::Scalar(value)),
+            _ => Err(format!("invalid string {}", text))
+        };
+    }
+    if text.len() >= 2 && text.starts_with('\'') && text.ends_with('\'') {
+        return Ok(ConfigValue::Scalar(text[1..text.len() - 1].replace("''", "'")));
+    }
+    Ok(ConfigValue::Scalar(text.to_string()))
+}
+
+/// Splits `key: value` at the colon ending the key, which may be quoted.
+fn yaml_key(text: &str) -> Option<(String, &str)> {
+    let (key, rest) = match text.chars().next()? {
+        quote @ ('"' | '\'') => {
+            let end = text[1..].find(quote)? + 1;
+            (text[1..end].to_string(), &text[end + 1..])
+        },
+        _ => {
+            let end = text.find(": ").or_else(|| text.strip_suffix(':').map(|key| key.len()))?;
+            (text[..end].trim().to_string(), &text[end..])
+        }
+    };
+    Some((key, rest.trim_start().strip_prefix(':')?))
+}
+
+/// Parses the block mappings and sequences, with scalar and flow list
+/// values, that configuration files need of YAML.
+struct YamlParser<'a> {
+    lines: Vec<(usize, usize, &'a str)>,
+    index: usize
+}
+
+impl<'a> YamlParser<'a> {
+    fn block(&mut self, indent: usize) -> std::result::Result<ConfigValue, String> {
+        let is_list = self.lines.get(self.index).is_some_and(|(_, _, text)| *text == "-" || text.starts_with("- "));
+        let mut items = Vec::new();
+        let mut entries = Vec::new();
+        while let Some(&(number, line_indent, text)) = self.lines.get(self.index) {
+            if line_indent < indent {
+                break;
+            }
+            if line_indent > indent {
+                return Err(format!("line {}: unexpected indentation", number));
+            }
+            if is_list != (text == "-" || text.starts_with("- ")) {
+                break;
+            }
+            self.index += 1;
+            if is_list {
+                let item = text.strip_prefix('-')
+                    .ok_or_else(|| format!("line {}: expected a list item", number))?;
+                items.push(yaml_scalar(item)?);
+                continue;
+            }
+            let (key, value) = yaml_key(text).ok_or_else(|| format!("line {}: expected key: value", number))?;
+            let value = match value.trim().is_empty() {
+                true => match self.lines.get(self.index) {
+                    Some(&(_, child_indent, child)) if child_indent > indent ||
+                        (child_indent == indent && (child == "-" || child.starts_with("- "))) => self.block(child_indent)?,
+                    _ => ConfigValue::Map(vec![])
+                },
+                false => yaml_scalar(value).map_err(|reason| format!("line {}: {}", number, reason))?
+            };
+            entries.push((key, value));
+        }
+        Ok(match is_list {
+            true => ConfigValue::List(items),
+            false => ConfigValue::Map(entries)
+        })
+    }
+}
+
+fn parse_yaml(text: &str) -> std::result::Result<ConfigValue, String> {
+    let lines = text.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, strip_comment(line).trim_end()))
+        .filter(|(_, line)| !line.trim().is_empty() && *line != "---")
+        .map(|(number, line)| (number, line.len() - line.trim_start().len(), line.trim_start()))
+        .collect::<Vec<_>>();
+    let mut parser = YamlParser{ lines, index: 0 };
+    parser.block(0)
+}
+
+fn scalars(key: &str, value: ConfigValue) -> std::result::Result<Vec<String>, String> {
+    match value {
+        ConfigValue::Scalar(value) => Ok(vec![value]),
+        ConfigValue::List(items) => items.into_iter()
+            .map(|item| match item {
+                ConfigValue::Scalar(value) => Ok(value),
+                _ => Err(format!("{}: expected a list of values", key))
+            })
+            .collect(),
+        ConfigValue::Map(_) => Err(format!("{}: expected a value or a list of values", key))
+    }
+}
+
+/// The `key=value` parameters a configuration file stands for. Lists repeat
+/// their key, while the `type_override` and `wkt` mappings expand to one
+/// parameter per entry.
+fn parameters(config: ConfigValue) -> std::result::Result<Vec<(String, String)>, String> {
+    let entries = match config {
+        ConfigValue::Map(entries) => entries,
+        _ => return Err("expected a mapping of options".to_string())
+    };
+    let mut parameters = Vec::new();
+    for (key, value) in entries {
+        match (key.as_str(), value) {
+            ("type_override", ConfigValue::Map(overrides)) => for (name, ts_type) in overrides {
+                for ts_type in scalars(&name, ts_type)? {
+                    parameters.push((key.clone(), format!("{}:{}", name, ts_type)));
+                }
+            },
+            ("wkt", ConfigValue::Map(overrides)) => for (name, ts_type) in overrides {
+                for ts_type in scalars(&name, ts_type)? {
+                    parameters.push((format!("wkt.{}", name), ts_type));
+                }
+            },
+            (_, value) => for value in scalars(&key, value)? {
+                if value != "null" {
+                    parameters.push((key.clone(), value));
+                }
+            }
+        }
+    }
+    Ok(parameters)
+}
+
+/// Reads the configuration file at `path`, JSON when named `*.json` and
+/// YAML otherwise.
+pub(crate) fn read_config(path: &str) -> Result<Vec<(String, String)>> {
+    let text = std::fs::read_to_string(path).map_err(|error| Error::Io{ path: path.to_string(), error })?;
+    let config = match path.ends_with(".json") {
+        true => parse_json(&text),
+        false => parse_yaml(&text)
+    };
+    config.and_then(parameters).map_err(|reason| Error::Config{ path: path.to_string(), reason })
+}
+
+/// Matches `path` against a glob where `*` matches within a directory and
+/// `**/` any number of directories.
+pub(crate) fn glob_match(pattern: &str, path: &str) -> bool {
+    if let Some(rest) = pattern.strip_prefix("**/") {
+        return glob_match(rest, path) ||
+            path.find('/').is_some_and(|index| glob_match(pattern, &path[index + 1..]));
+    }
+    if pattern == "**" {
+        return true;
+    }
+    match pattern.chars().next() {
+        Some('*') => (0..=path.len())
+            .filter(|&i| path.is_char_boundary(i) && !path[..i].contains('/'))
+            .any(|i| glob_match(&pattern[1..], &path[i..])),
+        Some('?') => path.chars().next().is_some_and(|c| c != '/' && glob_match(&pattern[1..], &path[c.len_utf8()..])),
+        Some(c) => path.starts_with(c) && glob_match(&pattern[c.len_utf8()..], &path[c.len_utf8()..]),
+        None => path.is_empty()
+    }
+}
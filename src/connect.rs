@@ -0,0 +1,168 @@
+use super::*;
+
+// `idempotency_level` of `google.protobuf.MethodOptions`, newer than the
+// descriptors compiled into the plugin.
+const METHOD_IDEMPOTENCY_LEVEL: u32 = 34;
+
+/// Stand-in for the message classes of `@bufbuild/protobuf`, encoding the
+/// plain generated types as JSON. Connect transports then have to use the
+/// JSON format.
+const CONNECT_JSON_MESSAGE_TYPE: &str = "\
+interface JsonMessageType<T> {
+  new (data?: Partial<T>): T & { toJsonString(): string; toBinary(): Uint8Array };
+  readonly typeName: string;
+  fromJsonString(json: string): T;
+  fromBinary(bytes: Uint8Array): T;
+}
+
+function jsonMessageType<T extends object>(typeName: string): JsonMessageType<T> {
+  return class {
+    static readonly typeName = typeName;
+
+    constructor(data?: Partial<T>) {
+      Object.assign(this, data);
+    }
+
+    toJsonString(): string {
+      return JSON.stringify(this);
+    }
+
+    toBinary(): Uint8Array {
+      throw new Error(`${typeName} supports only the JSON format`);
+    }
+
+    static fromJsonString(json: string): T {
+      return new this(JSON.parse(json)) as unknown as T;
+    }
+
+    static fromBinary(): T {
+      throw new Error(`${typeName} supports only the JSON format`);
+    }
+  } as unknown as JsonMessageType<T>;
+}
+";
+
+struct ConnectMethod {
+    name: String,
+    local_name: String,
+    input: String,
+    output: String,
+    kind: &'static str,
+    idempotency: Option<&'static str>
+}
+
+/// A service in the shape of `ServiceType` from `@connectrpc/connect`.
+struct ConnectService {
+    name: String,
+    type_name: String,
+    methods: Vec<ConnectMethod>
+}
+
+impl fmt::Display for ConnectService {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "export const {} = {{", self.name)?;
+        writeln!(f, "  typeName: \"{}\",", self.type_name)?;
+        match self.methods.is_empty() {
+            true => writeln!(f, "  methods: {{}}")?,
+            false => {
+                writeln!(f, "  methods: {{")?;
+                for method in self.methods.iter() {
+                    writeln!(f, "    {}: {{", method.local_name)?;
+                    writeln!(f, "      name: \"{}\",", method.name)?;
+                    writeln!(f, "      I: {},", method.input)?;
+                    writeln!(f, "      O: {},", method.output)?;
+                    match method.idempotency {
+                        Some(idempotency) => {
+                            writeln!(f, "      kind: MethodKind.{},", method.kind)?;
+                            writeln!(f, "      idempotency: MethodIdempotency.{}", idempotency)?;
+                        },
+                        None => writeln!(f, "      kind: MethodKind.{}", method.kind)?
+                    }
+                    writeln!(f, "    }},")?;
+                }
+                writeln!(f, "  }}")?;
+            }
+        }
+        writeln!(f, "}} as const;")
+    }
+}
+
+fn message_type_constant(type_name: &str, resolver: &TypeResolver) -> String {
+    format!("{}Type", resolver.function_prefix(type_name))
+}
+
+pub(crate) fn method_idempotency(method: &MethodDescriptorProto) -> Option<ir::Idempotency> {
+    let level = method.get_options().get_unknown_fields().get(METHOD_IDEMPOTENCY_LEVEL)?.varint.last()?;
+    match level {
+        1 => Some(ir::Idempotency::NoSideEffects),
+        2 => Some(ir::Idempotency::Idempotent),
+        _ => None
+    }
+}
+
+/// Connect service definitions of a file.
+pub(crate) struct Connect;
+
+impl Renderer for Connect {
+    fn render(
+        &self,
+        file: &ir::File,
+        _: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File> {
+        let methods = file.services.iter().flat_map(|service| service.methods.iter()).collect::<Vec<_>>();
+        let is_idempotency_used = methods.iter().any(|method| method.idempotency.is_some());
+        let header = match is_idempotency_used {
+            true => "import { MethodIdempotency, MethodKind } from \"@bufbuild/protobuf\";\n",
+            false => "import { MethodKind } from \"@bufbuild/protobuf\";\n"
+        };
+        let mut message_types = Vec::<&ir::Value>::new();
+        methods.iter()
+            .flat_map(|method| vec![&method.input, &method.output])
+            .for_each(|value| if !message_types.iter().any(|existing| existing.type_name == value.type_name) {
+                message_types.push(value);
+            });
+        let mut prelude = CONNECT_JSON_MESSAGE_TYPE.to_string();
+        prelude.push('\n');
+        for value in message_types {
+            prelude.push_str(&format!(
+                "const {} = jsonMessageType<{}>(\"{}\");\n",
+                message_type_constant(&value.type_name, resolver),
+                value.ts_type,
+                value.type_name.trim_start_matches('.')
+            ));
+        }
+        gen_service_file(
+            file,
+            "connect",
+            header,
+            &prelude,
+            |service| format!("{}", ConnectService{
+                name: service.name.clone(),
+                type_name: service.full_name.trim_start_matches('.').to_string(),
+                methods: service.methods
+                    .iter()
+                    .map(|method| ConnectMethod{
+                        name: method.name.clone(),
+                        local_name: lower_camel_case(&method.name),
+                        input: message_type_constant(&method.input.type_name, resolver),
+                        output: message_type_constant(&method.output.type_name, resolver),
+                        kind: match (method.client_streaming, method.server_streaming) {
+                            (false, false) => "Unary",
+                            (false, true) => "ServerStreaming",
+                            (true, false) => "ClientStreaming",
+                            (true, true) => "BiDiStreaming"
+                        },
+                        idempotency: method.idempotency.map(|idempotency| match idempotency {
+                            ir::Idempotency::NoSideEffects => "NoSideEffects",
+                            ir::Idempotency::Idempotent => "Idempotent"
+                        })
+                    })
+                    .collect()
+            }),
+            resolver,
+            options
+        )
+    }
+}
@@ -0,0 +1,114 @@
+use super::*;
+
+const MAP_KEY: i32 = 1;
+const MAP_VALUE: i32 = 2;
+
+/// Checks the invariants of descriptors protoc guarantees and the generators
+/// rely on, so that malformed or future descriptors are reported rather than
+/// crashing the plugin or silently producing wrong types.
+struct DescriptorChecker<'a> {
+    proto_file: &'a FileDescriptorProto
+}
+
+impl<'a> DescriptorChecker<'a> {
+    fn fail(&self, full_name: &str, reason: String) -> Result<()> {
+        Err(Error::descriptor(full_name, reason).in_file(self.proto_file.get_name()))
+    }
+
+    fn check_name(&self, scope: &str, kind: &str, name: &str) -> Result<()> {
+        match name.is_empty() {
+            true => self.fail(scope, format!("{} without a name", kind)),
+            false => Ok(())
+        }
+    }
+
+    fn check_field(&self, full_name: &str, field: &FieldDescriptorProto, message_type: &DescriptorProto) -> Result<()> {
+        if field.get_number() <= 0 {
+            return self.fail(full_name, format!("field number {} is not positive", field.get_number()));
+        }
+        let oneof_count = message_type.get_oneof_decl().len() as i32;
+        if field.has_oneof_index() && !(0..oneof_count).contains(&field.get_oneof_index()) {
+            return self.fail(full_name, format!("oneof_index {} is out of range", field.get_oneof_index()));
+        }
+        if is_proto3_optional(field) && !field.has_oneof_index() {
+            return self.fail(full_name, "proto3 optional field without a synthetic oneof".to_string());
+        }
+        match field.get_field_type() {
+            FieldDescriptorProto_Type::TYPE_MESSAGE |
+            FieldDescriptorProto_Type::TYPE_ENUM |
+            FieldDescriptorProto_Type::TYPE_GROUP if field.get_type_name().is_empty() =>
+                self.fail(full_name, format!("{:?} field without a type_name", field.get_field_type())),
+            _ => Ok(())
+        }
+    }
+
+    fn check_map_entry(&self, full_name: &str, entry: &DescriptorProto) -> Result<()> {
+        let numbers = entry.get_field().iter().map(|field| field.get_number()).collect::<Vec<_>>();
+        match numbers.as_slice() {
+            [MAP_KEY, MAP_VALUE] => Ok(()),
+            _ => self.fail(full_name, format!("map entry has fields {:?} instead of key = 1 and value = 2", numbers))
+        }
+    }
+
+    fn check_message(&self, full_name: &str, message_type: &DescriptorProto) -> Result<()> {
+        if message_type.get_options().get_map_entry() {
+            self.check_map_entry(full_name, message_type)?;
+        }
+        for field in message_type.get_field() {
+            self.check_name(full_name, "field", field.get_name())?;
+            self.check_field(&format!("{}.{}", full_name, field.get_name()), field, message_type)?;
+        }
+        for oneof in message_type.get_oneof_decl() {
+            self.check_name(full_name, "oneof", oneof.get_name())?;
+        }
+        for nested_type in message_type.get_nested_type() {
+            self.check_name(full_name, "nested message", nested_type.get_name())?;
+            self.check_message(&format!("{}.{}", full_name, nested_type.get_name()), nested_type)?;
+        }
+        for enum_type in message_type.get_enum_type() {
+            self.check_name(full_name, "nested enum", enum_type.get_name())?;
+            self.check_enum(&format!("{}.{}", full_name, enum_type.get_name()), enum_type)?;
+        }
+        Ok(())
+    }
+
+    fn check_enum(&self, full_name: &str, enum_type: &EnumDescriptorProto) -> Result<()> {
+        enum_type.get_value()
+            .iter()
+            .try_for_each(|value| self.check_name(full_name, "enum value", value.get_name()))
+    }
+
+    fn check_service(&self, full_name: &str, service: &ServiceDescriptorProto) -> Result<()> {
+        for method in service.get_method() {
+            self.check_name(full_name, "method", method.get_name())?;
+            let method_name = format!("{}.{}", full_name, method.get_name());
+            if method.get_input_type().is_empty() || method.get_output_type().is_empty() {
+                return self.fail(&method_name, "method without an input or output type".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks `proto_file` before anything is generated from it, reporting the
+/// first malformed descriptor as an `Error::Descriptor`.
+pub(crate) fn check_descriptor(proto_file: &FileDescriptorProto) -> Result<()> {
+    let checker = DescriptorChecker{ proto_file };
+    if proto_file.get_name().is_empty() {
+        return checker.fail(proto_file.get_package(), "file without a name".to_string());
+    }
+    let package = proto_file.get_package();
+    for message_type in proto_file.get_message_type() {
+        checker.check_name(package, "message", message_type.get_name())?;
+        checker.check_message(&qualified_name(package, message_type.get_name()), message_type)?;
+    }
+    for enum_type in proto_file.get_enum_type() {
+        checker.check_name(package, "enum", enum_type.get_name())?;
+        checker.check_enum(&qualified_name(package, enum_type.get_name()), enum_type)?;
+    }
+    for service in proto_file.get_service() {
+        checker.check_name(package, "service", service.get_name())?;
+        checker.check_service(&qualified_name(package, service.get_name()), service)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use super::*;
+
+// Fields of `google.protobuf.FeatureSet` and of the options carrying it,
+// newer than the descriptors the plugin is compiled against.
+const FILE_OPTIONS_FEATURES: u32 = 50;
+const MESSAGE_OPTIONS_FEATURES: u32 = 12;
+const FIELD_OPTIONS_FEATURES: u32 = 21;
+const FEATURE_FIELD_PRESENCE: u32 = 1;
+const FEATURE_REPEATED_FIELD_ENCODING: u32 = 3;
+const FEATURE_MESSAGE_ENCODING: u32 = 5;
+
+const PRESENCE_EXPLICIT: u64 = 1;
+const PRESENCE_LEGACY_REQUIRED: u64 = 3;
+const REPEATED_PACKED: u64 = 1;
+const MESSAGE_LENGTH_PREFIXED: u64 = 1;
+const MESSAGE_DELIMITED: u64 = 2;
+
+/// The features deciding how a field is typed and encoded, resolved from
+/// the edition defaults down to the field.
+#[derive(Clone, Copy)]
+struct Features {
+    field_presence: u64,
+    repeated_field_encoding: u64,
+    message_encoding: u64
+}
+
+impl Features {
+    /// Defaults of edition 2023, which edition 2024 keeps for these features.
+    const DEFAULTS: Features = Features{
+        field_presence: PRESENCE_EXPLICIT,
+        repeated_field_encoding: REPEATED_PACKED,
+        message_encoding: MESSAGE_LENGTH_PREFIXED
+    };
+
+    /// These features overridden by the `features` of an options message,
+    /// the field `number` of `option_fields`.
+    fn merged(self, option_fields: &protobuf::UnknownFields, number: u32) -> Features {
+        let features = match option_fields.get(number).and_then(|values| unknown_fields(&values.length_delimited.concat())) {
+            Some(features) => features,
+            None => return self
+        };
+        let feature = |number, inherited| features.get(number)
+            .and_then(|values| values.varint.last().copied())
+            .unwrap_or(inherited);
+        Features{
+            field_presence: feature(FEATURE_FIELD_PRESENCE, self.field_presence),
+            repeated_field_encoding: feature(FEATURE_REPEATED_FIELD_ENCODING, self.repeated_field_encoding),
+            message_encoding: feature(FEATURE_MESSAGE_ENCODING, self.message_encoding)
+        }
+    }
+}
+
+/// Spells the resolved features of `field` the way proto2 and proto3
+/// descriptors do: a `required` label, a proto3 `optional` marker, a group
+/// type and an explicit `packed` option.
+fn resolve_field(field: &mut FieldDescriptorProto, features: Features, is_map_entry: bool) {
+    let features = features.merged(field.get_options().get_unknown_fields(), FIELD_OPTIONS_FEATURES);
+    let is_message = matches!(
+        field.get_field_type(),
+        FieldDescriptorProto_Type::TYPE_MESSAGE | FieldDescriptorProto_Type::TYPE_GROUP
+    );
+    match field.get_label() {
+        FieldDescriptorProto_Label::LABEL_REPEATED => {
+            if !is_message && !field.get_options().has_packed() {
+                let is_packed = features.repeated_field_encoding == REPEATED_PACKED;
+                field.mut_options().set_packed(is_packed);
+            }
+        },
+        _ if is_map_entry || field.has_oneof_index() => {},
+        _ => match features.field_presence {
+            PRESENCE_LEGACY_REQUIRED => field.set_label(FieldDescriptorProto_Label::LABEL_REQUIRED),
+            PRESENCE_EXPLICIT if !is_message => field.mut_unknown_fields().add_varint(FIELD_PROTO3_OPTIONAL, 1),
+            _ => {}
+        }
+    }
+    if field.get_field_type() == FieldDescriptorProto_Type::TYPE_MESSAGE && features.message_encoding == MESSAGE_DELIMITED {
+        field.set_field_type(FieldDescriptorProto_Type::TYPE_GROUP);
+    }
+}
+
+fn resolve_message(message_type: &DescriptorProto, features: Features) -> DescriptorProto {
+    let features = features.merged(message_type.get_options().get_unknown_fields(), MESSAGE_OPTIONS_FEATURES);
+    let is_map_entry = message_type.get_options().get_map_entry();
+    let mut resolved = message_type.clone();
+    resolved.mut_field()
+        .iter_mut()
+        .for_each(|field| resolve_field(field, features, is_map_entry));
+    resolved.set_nested_type(message_type.get_nested_type()
+        .iter()
+        .map(|nested_type| resolve_message(nested_type, features))
+        .collect());
+    resolved
+}
+
+/// `proto_file`, declared with `edition = "..."`, with the presence and
+/// encoding its features give each field spelled out, so that it is
+/// generated like the equivalent proto2 or proto3 file.
+pub(crate) fn resolve_features(proto_file: &FileDescriptorProto) -> FileDescriptorProto {
+    let features = Features::DEFAULTS.merged(proto_file.get_options().get_unknown_fields(), FILE_OPTIONS_FEATURES);
+    let mut resolved = proto_file.clone();
+    resolved.set_message_type(proto_file.get_message_type()
+        .iter()
+        .map(|message_type| resolve_message(message_type, features))
+        .collect());
+    resolved
+}
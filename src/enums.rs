@@ -0,0 +1,79 @@
+use super::*;
+use super::json::{enum_default, JsonEnumFunctions};
+
+/// Frozen tables between the numbers and proto names of an enum. Aliases
+/// share a number, which maps to the first name like in protobuf runtimes.
+struct EnumTables<'a> {
+    prefix: String,
+    enum_type: &'a ir::Enum
+}
+
+impl<'a> fmt::Display for EnumTables<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values = &self.enum_type.values;
+        writeln!(
+            f,
+            "export const {}NameByNumber: Readonly<Record<number, string>> = Object.freeze({{",
+            self.prefix
+        )?;
+        for value in values.iter() {
+            writeln!(f, "  {}: \"{}\",", value.number, value.name)?;
+        }
+        writeln!(f, "}});")?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "export const {}NumberByName: Readonly<Record<string, number>> = Object.freeze({{",
+            self.prefix
+        )?;
+        for value in values.iter() {
+            for name in std::iter::once(&value.name).chain(value.aliases.iter()) {
+                writeln!(f, "  {}: {},", name, value.number)?;
+            }
+        }
+        writeln!(f, "}});")
+    }
+}
+
+fn enums_file_stem(file_name: &str, resolver: &TypeResolver, options: &Options) -> String {
+    output_file_stem(file_name, "_enums", resolver, options)
+}
+
+/// Number and name tables of the enums of a file, with their JSON
+/// conversions.
+pub(crate) struct Enums;
+
+impl Renderer for Enums {
+    fn render(
+        &self,
+        file: &ir::File,
+        types: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File> {
+        if file.enums.is_empty() {
+            return vec![];
+        }
+        let stem = enums_file_stem(&file.name, resolver, options);
+        let type_references = file.enums
+            .iter()
+            .map(|enum_type| enum_type.full_name.clone())
+            .collect::<Vec<_>>();
+        let mut content = CodeWriter::for_file(file);
+        content.push(gen_imports(&stem, type_references.iter(), resolver, options));
+        for enum_type in file.enums.iter() {
+            let prefix = resolver.function_prefix(&enum_type.full_name);
+            content.push('\n');
+            content.push(EnumTables{ prefix: prefix.clone(), enum_type });
+            content.push('\n');
+            content.push(JsonEnumFunctions{
+                prefix,
+                enum_type,
+                default: enum_default(&enum_type.full_name, types, options),
+                style: options.enum_style,
+                is_open: options.open_enums
+            });
+        }
+        vec![gen_resp_file(format!("{}.ts", stem), content.into_string())]
+    }
+}
@@ -0,0 +1,146 @@
+use super::*;
+
+const EXTENSIONS_SUFFIX: &str = "Extensions";
+
+pub(crate) const MESSAGE_OPTIONS: &str = ".google.protobuf.MessageOptions";
+pub(crate) const FIELD_OPTIONS: &str = ".google.protobuf.FieldOptions";
+
+fn add_message_extensions<'a>(
+    full_name: &str,
+    message_type: &'a DescriptorProto,
+    extensions: &mut Vec<(String, &'a FieldDescriptorProto)>
+) {
+    extensions.extend(message_type.get_extension()
+        .iter()
+        .map(|extension| (format!("{}.{}", full_name, extension.get_name()), extension)));
+    for nested_type in message_type.get_nested_type() {
+        add_message_extensions(&format!("{}.{}", full_name, nested_type.get_name()), nested_type, extensions);
+    }
+}
+
+/// Extensions `proto_file` declares at its top level and in its messages,
+/// by full name.
+pub(crate) fn file_extensions(proto_file: &FileDescriptorProto) -> Vec<(String, &FieldDescriptorProto)> {
+    let mut extensions = proto_file.get_extension()
+        .iter()
+        .map(|extension| (qualified_name(proto_file.get_package(), extension.get_name()), extension))
+        .collect::<Vec<_>>();
+    for message_type in proto_file.get_message_type() {
+        add_message_extensions(
+            &qualified_name(proto_file.get_package(), message_type.get_name()),
+            message_type,
+            &mut extensions
+        );
+    }
+    extensions
+}
+
+/// Key of an extension in proto3 JSON and in the extension bags.
+pub(crate) fn extension_key(full_name: &str) -> String {
+    format!("[{}]", full_name.trim_start_matches('.'))
+}
+
+/// A `<Message>Extensions` type per message `file` extends, with an
+/// optional property per extension it declares.
+pub(crate) fn gen_extension_declarations(
+    file: &ir::File,
+    resolver: &TypeResolver,
+    options: &Options
+) -> Vec<TsDeclaration> {
+    let mut extendees = BTreeMap::<&str, Vec<&ir::Extension>>::new();
+    for extension in file.extensions.iter() {
+        extendees.entry(extension.extendee.as_str()).or_default().push(extension);
+    }
+    extendees.into_iter()
+        .map(|(extendee, extensions)| {
+            let name = format!("{}{}", resolver.path(extendee).join("_"), EXTENSIONS_SUFFIX);
+            let ts_object_type = TsObjectType{
+                modifiers: TsModifiers::top_level(options),
+                name: name.clone(),
+                doc: Some(format!("Extensions of `{}` declared in `{}`.", extendee.trim_start_matches('.'), file.name)),
+                source: None,
+                is_readonly: options.readonly,
+                is_interface: matches!(options.declaration_style, DeclarationStyle::Interface),
+                empty: empty_message_type(&name, options),
+                fields: extensions.iter()
+                    .map(|extension| TsField{
+                        key: extension.field.key.clone(),
+                        ts_type: extension.field.ts_type.clone(),
+                        is_required: false,
+                        is_nullable: false,
+                        doc: extension.field.doc.clone(),
+                        source: resolver.source(&extension.full_name, options)
+                    })
+                    .collect(),
+                oneof_list: Vec::new(),
+                is_oneof_unset_variant: false
+            };
+            let references = extensions.iter()
+                .flat_map(|extension| extension.field.references())
+                .collect();
+            gen_declaration(&[name], ts_object_type.to_string(), references, options)
+        })
+        .collect()
+}
+
+/// `value`, a single varint, fixed or length-delimited record of `extension`,
+/// as a TypeScript literal. 64-bit integers are strings as in proto3 JSON.
+fn option_literal(extension: &FieldDescriptorProto, value: protobuf::UnknownValueRef, resolver: &TypeResolver) -> Option<String> {
+    use protobuf::UnknownValueRef::*;
+    let number = |value: f64| match value.is_finite() {
+        true => format!("{}", value),
+        false => format!("\"{}\"", value)
+    };
+    Some(match (extension.get_field_type(), value) {
+        (FieldDescriptorProto_Type::TYPE_BOOL, Varint(value)) => (value != 0).to_string(),
+        (FieldDescriptorProto_Type::TYPE_INT32, Varint(value)) => (value as i32).to_string(),
+        (FieldDescriptorProto_Type::TYPE_UINT32, Varint(value)) => (value as u32).to_string(),
+        (FieldDescriptorProto_Type::TYPE_SINT32, Varint(value)) =>
+            (((value >> 1) as i32) ^ -((value & 1) as i32)).to_string(),
+        (FieldDescriptorProto_Type::TYPE_INT64, Varint(value)) => format!("\"{}\"", value as i64),
+        (FieldDescriptorProto_Type::TYPE_UINT64, Varint(value)) => format!("\"{}\"", value),
+        (FieldDescriptorProto_Type::TYPE_SINT64, Varint(value)) =>
+            format!("\"{}\"", ((value >> 1) as i64) ^ -((value & 1) as i64)),
+        (FieldDescriptorProto_Type::TYPE_ENUM, Varint(value)) => {
+            let enum_value = resolver.enum_type(extension.get_type_name())
+                .and_then(|enum_type| enum_type.get_value().iter().find(|enum_value| enum_value.get_number() == value as i32));
+            match enum_value {
+                Some(enum_value) => format!("\"{}\"", enum_value.get_name()),
+                None => (value as i32).to_string()
+            }
+        },
+        (FieldDescriptorProto_Type::TYPE_FIXED32, Fixed32(value)) => value.to_string(),
+        (FieldDescriptorProto_Type::TYPE_SFIXED32, Fixed32(value)) => (value as i32).to_string(),
+        (FieldDescriptorProto_Type::TYPE_FLOAT, Fixed32(value)) => number(f32::from_bits(value) as f64),
+        (FieldDescriptorProto_Type::TYPE_FIXED64, Fixed64(value)) => format!("\"{}\"", value),
+        (FieldDescriptorProto_Type::TYPE_SFIXED64, Fixed64(value)) => format!("\"{}\"", value as i64),
+        (FieldDescriptorProto_Type::TYPE_DOUBLE, Fixed64(value)) => number(f64::from_bits(value)),
+        (FieldDescriptorProto_Type::TYPE_STRING, LengthDelimited(value)) =>
+            jsonschema::json_string(std::str::from_utf8(value).ok()?),
+        _ => return None
+    })
+}
+
+/// Values of the custom options set in `option_fields`, the unknown fields
+/// of a `google.protobuf.<extendee>` options message, for every extension
+/// of it in the request. Bytes and message options are left out.
+pub(crate) fn custom_options(
+    option_fields: &protobuf::UnknownFields,
+    extendee: &str,
+    resolver: &TypeResolver
+) -> Vec<(String, String)> {
+    resolver.extensions(extendee)
+        .iter()
+        .filter_map(|(full_name, extension)| {
+            let values = option_fields.get(extension.get_number() as u32)?
+                .iter()
+                .filter_map(|value| option_literal(extension, value, resolver))
+                .collect::<Vec<_>>();
+            let value = match extension.get_label() {
+                FieldDescriptorProto_Label::LABEL_REPEATED => format!("[{}]", values.join(", ")),
+                _ => values.last()?.clone()
+            };
+            Some((extension_key(full_name), value))
+        })
+        .collect()
+}
@@ -0,0 +1,175 @@
+use super::*;
+
+/// Expression turning a deep partial `value` into a complete one, or `None`
+/// when the partial value can be used as is.
+fn value_factory(value: &ir::Value, resolver: &TypeResolver) -> Option<String> {
+    match value.kind {
+        ir::ValueKind::Message => Some(format!("create{}($v)", resolver.function_prefix(&value.type_name))),
+        _ => None
+    }
+}
+
+fn field_factory(field: &ir::Field, resolver: &TypeResolver, options: &Options) -> Option<String> {
+    let value = value_factory(&field.value, resolver)?;
+    match field.cardinality {
+        ir::Cardinality::Map{ .. } => Some(match options.map_style {
+            MapStyle::Record => format!(
+                "Object.fromEntries(Object.entries($v).map(([k, x]) => [k, {}]))",
+                apply(&value, "x")
+            ),
+            MapStyle::Map => format!("new Map(Array.from($v, ([k, x]) => [k, {}]))", apply(&value, "x"))
+        }),
+        ir::Cardinality::Repeated => Some(format!("$v.map((x) => {})", apply(&value, "x"))),
+        ir::Cardinality::Single => Some(value)
+    }
+}
+
+struct MessageFactory<'a> {
+    prefix: String,
+    type_name: Option<String>,
+    message: &'a ir::Message,
+    types: &'a ir::Types<'a>,
+    resolver: &'a TypeResolver,
+    options: &'a Options
+}
+
+impl<'a> fmt::Display for MessageFactory<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ts_type = &self.message.ts_name;
+        let partial = match self.message.fields.is_empty() {
+            true => "_partial",
+            false => "partial"
+        };
+        writeln!(f, "export function create{}({}: DeepPartial<{}> = {{}}): {} {{", self.prefix, partial, ts_type, ts_type)?;
+        writeln!(f, "  const message: Record<string, unknown> = {{}};")?;
+        if let Some(type_name) = &self.type_name {
+            writeln!(f, "  message[\"{}\"] = {};", TYPE_FIELD_KEY, type_name)?;
+        }
+        for property in self.message.properties(self.options) {
+            let field = match property {
+                ir::Property::Field(field) => field,
+                ir::Property::Oneof{ index, oneof, fields } => {
+                    let local = format!("oneof{}", index);
+                    writeln!(f, "  const {} = partial[\"{}\"];", local, oneof.key)?;
+                    let mut branch = "if";
+                    for field in fields {
+                        let factory = match field_factory(field, self.resolver, self.options) {
+                            Some(factory) => factory,
+                            None => continue
+                        };
+                        writeln!(
+                            f,
+                            "  {0} ({1}?.$case === \"{3}\") message[\"{2}\"] = {{ $case: \"{3}\", {3}: {4} }};",
+                            branch,
+                            local,
+                            oneof.key,
+                            field.key,
+                            apply(&factory, &format!("{}[\"{}\"]", local, field.key))
+                        )?;
+                        branch = "else if";
+                    }
+                    writeln!(f, "  {} ({} !== undefined) message[\"{}\"] = {};", branch, local, oneof.key, local)?;
+                    continue;
+                }
+            };
+            let key = &field.key;
+            let value = format!("partial[\"{}\"]", key);
+            let factory = field_factory(field, self.resolver, self.options);
+            let default = json::field_default(field, self.types, self.resolver, self.options);
+            match (default, factory) {
+                (Some(default), Some(factory)) if field.is_optional => writeln!(
+                    f,
+                    "  message[\"{}\"] = {} == null ? {} : {};",
+                    key,
+                    value,
+                    default,
+                    apply(&factory, &value)
+                )?,
+                (Some(default), Some(factory)) => writeln!(
+                    f,
+                    "  message[\"{}\"] = {};",
+                    key,
+                    apply(&factory, &format!("({} ?? {})", value, default))
+                )?,
+                (Some(default), None) => writeln!(f, "  message[\"{}\"] = {} ?? {};", key, value, default)?,
+                (None, factory) => writeln!(
+                    f,
+                    "  if ({} !== undefined) message[\"{}\"] = {};",
+                    value,
+                    key,
+                    apply(&factory.unwrap_or_else(|| "$v".to_string()), &value)
+                )?
+            }
+        }
+        writeln!(f, "  return message as unknown as {};", ts_type)?;
+        writeln!(f, "}}")
+    }
+}
+
+pub(crate) const DEEP_PARTIAL: &str = "\
+export type DeepPartial<T> = T extends Uint8Array | Date
+  ? T
+  : T extends ReadonlyArray<infer U>
+    ? ReadonlyArray<DeepPartial<U>>
+    : T extends ReadonlyMap<infer K, infer V>
+      ? ReadonlyMap<K, DeepPartial<V>>
+      : T extends object
+        ? { readonly [P in keyof T]?: DeepPartial<T[P]> }
+        : T;
+";
+
+fn factories_file_stem(file_name: &str, resolver: &TypeResolver, options: &Options) -> String {
+    output_file_stem(file_name, "_factories", resolver, options)
+}
+
+/// `create<Message>` functions of a file, completing deep partial messages.
+pub(crate) struct Factories;
+
+impl Renderer for Factories {
+    fn render(
+        &self,
+        file: &ir::File,
+        types: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File> {
+        if file.messages.is_empty() {
+            return vec![];
+        }
+        let stem = factories_file_stem(&file.name, resolver, options);
+        let field_references = file.messages
+            .iter()
+            .flat_map(|message| message.fields.iter())
+            .flat_map(|field| field.references())
+            .filter(|type_name| types.enum_type(type_name).is_none())
+            .collect::<Vec<_>>();
+        let type_references = file.messages
+            .iter()
+            .map(|message| message.full_name.clone())
+            .collect::<Vec<_>>();
+        let mut content = CodeWriter::for_file(file);
+        content.push(gen_imports(&stem, type_references.iter(), resolver, options));
+        content.push(gen_function_imports(
+            &stem,
+            file,
+            &field_references,
+            "factories",
+            |prefix, _| vec![format!("create{}", prefix)],
+            resolver,
+            options
+        ));
+        content.push(runtime::gen_helpers(&stem, &[DEEP_PARTIAL], options));
+        for message in file.messages.iter() {
+            content.push('\n');
+            content.push(MessageFactory{
+                prefix: resolver.function_prefix(&message.full_name),
+                type_name: type_field_value(&message.full_name, options),
+                message,
+                types,
+                resolver,
+                options
+            });
+        }
+        vec![gen_resp_file(format!("{}.ts", stem), content.into_string())]
+    }
+}
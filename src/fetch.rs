@@ -0,0 +1,245 @@
+use super::*;
+use super::openapi::{field_path, path_template, query_fields, HttpBinding};
+
+/// Whether the `variable` of `template` matches several path segments, as
+/// in `{name=shelves/*/books/*}`, so its slashes are kept.
+fn is_multi_segment(template: &str, variable: &str) -> bool {
+    template.find(&format!("{{{}=", variable))
+        .and_then(|start| template[start..].split('}').next())
+        .is_some_and(|pattern| pattern.contains('/') || pattern.contains("**"))
+}
+
+/// Access to the request property selected by the dotted proto field `path`.
+fn request_access(path: &str, message: &ir::Message, types: &ir::Types) -> String {
+    let mut access = "request".to_string();
+    let mut current = Some(message);
+    for (i, name) in path.split('.').enumerate() {
+        let field = current.and_then(|message| field_path(name, message, types));
+        let key = field.map_or(name, |field| field.key.as_str());
+        access.push_str(&format!("{}[\"{}\"]", if i > 0 { "?." } else { "" }, key));
+        current = field.and_then(|field| types.message(&field.value.type_name));
+    }
+    access
+}
+
+struct FetchMethod {
+    name: String,
+    verb: String,
+    url: String,
+    query: Vec<(String, String)>,
+    body: Option<String>,
+    request: String,
+    response: String
+}
+
+impl fmt::Display for FetchMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "  {}(request: {}, init: RequestInit = {{}}): Promise<{}> {{",
+            self.name,
+            self.request,
+            self.response
+        )?;
+        let query = match self.query.is_empty() {
+            true => "undefined",
+            false => {
+                writeln!(f, "    const query = new URLSearchParams();")?;
+                for (key, access) in self.query.iter() {
+                    writeln!(f, "    appendQuery(query, \"{}\", {});", key, access)?;
+                }
+                "query"
+            }
+        };
+        writeln!(
+            f,
+            "    return this.call<{}>(\"{}\", `{}`, {}, {}, init);",
+            self.response,
+            self.verb.to_uppercase(),
+            self.url,
+            query,
+            self.body.as_deref().unwrap_or("undefined")
+        )?;
+        writeln!(f, "  }}")
+    }
+}
+
+struct FetchClient {
+    name: String,
+    methods: Vec<FetchMethod>
+}
+
+impl fmt::Display for FetchClient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "export class {} {{", self.name)?;
+        writeln!(f, "  private readonly fetch: Fetch;")?;
+        writeln!(f)?;
+        writeln!(f, "  constructor(")?;
+        writeln!(f, "    private readonly baseUrl: string,")?;
+        writeln!(f, "    private readonly options: FetchClientOptions = {{}}")?;
+        writeln!(f, "  ) {{")?;
+        writeln!(f, "    this.fetch = options.fetch ?? ((input, init) => globalThis.fetch(input, init));")?;
+        writeln!(f, "  }}")?;
+        writeln!(f)?;
+        writeln!(f, "  private async call<T>(")?;
+        writeln!(f, "    method: string,")?;
+        writeln!(f, "    path: string,")?;
+        writeln!(f, "    query: URLSearchParams | undefined,")?;
+        writeln!(f, "    body: unknown,")?;
+        writeln!(f, "    init: RequestInit")?;
+        writeln!(f, "  ): Promise<T> {{")?;
+        writeln!(f, "    const search = query === undefined || query.toString() === \"\" ? \"\" : `?${{query}}`;")?;
+        writeln!(f, "    const headers = new Headers(this.options.headers);")?;
+        writeln!(f, "    new Headers(init.headers).forEach((value, key) => headers.set(key, value));")?;
+        writeln!(f, "    if (body !== undefined) headers.set(\"Content-Type\", \"application/json\");")?;
+        writeln!(f, "    const response = await this.fetch(this.baseUrl + path + search, {{")?;
+        writeln!(f, "      ...init,")?;
+        writeln!(f, "      method,")?;
+        writeln!(f, "      headers,")?;
+        writeln!(f, "      body: body === undefined ? undefined : JSON.stringify(body)")?;
+        writeln!(f, "    }});")?;
+        writeln!(f, "    if (!response.ok) {{")?;
+        writeln!(f, "      throw new FetchError(response.status, await response.text());")?;
+        writeln!(f, "    }}")?;
+        writeln!(f, "    return (await response.json()) as T;")?;
+        writeln!(f, "  }}")?;
+        for method in self.methods.iter() {
+            writeln!(f)?;
+            write!(f, "{}", method)?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+const FETCH_PRELUDE: &str = "\
+export type Fetch = (input: string, init: RequestInit) => Promise<Response>;
+
+export interface FetchClientOptions {
+  readonly fetch?: Fetch;
+  readonly headers?: HeadersInit;
+}
+
+export class FetchError extends Error {
+  constructor(readonly status: number, readonly body: string) {
+    super(`HTTP ${status}: ${body}`);
+  }
+}
+
+function encodePath(value: unknown): string {
+  return String(value).split(\"/\").map(encodeURIComponent).join(\"/\");
+}
+
+function appendQuery(query: URLSearchParams, key: string, value: unknown): void {
+  if (value === undefined || value === null) return;
+  if (Array.isArray(value)) {
+    value.forEach((item) => appendQuery(query, key, item));
+    return;
+  }
+  query.append(key, String(value));
+}
+";
+
+fn fetch_method(method: &ir::Method, binding: &HttpBinding, index: usize, types: &ir::Types) -> FetchMethod {
+    let (path, variables) = path_template(&binding.path);
+    let input = types.message(&method.input.type_name);
+    let access = |path: &str| match input {
+        Some(message) => request_access(path, message, types),
+        None => format!("request[\"{}\"]", path)
+    };
+    let url = variables.iter().fold(path.replace('`', "\\`"), |url, variable| {
+        let encode = match is_multi_segment(&binding.path, variable) {
+            true => "encodePath",
+            false => "encodeURIComponent"
+        };
+        url.replace(
+            &format!("{{{}}}", variable),
+            &format!("${{{}(String({}))}}", encode, access(variable))
+        )
+    });
+    // A FieldMask is declared as a message, so it has no query string form.
+    let query = input.map_or_else(Vec::new, |message| query_fields(message, binding, &variables)
+        .into_iter()
+        .filter(|field| field.value.type_name != ".google.protobuf.FieldMask")
+        .map(|field| (field.json_name.clone(), access(&field.name)))
+        .collect()
+    );
+    let body = match binding.body.as_str() {
+        "" => None,
+        "*" => Some("request".to_string()),
+        field_name => Some(access(field_name))
+    };
+    let response = &method.output.ts_type;
+    let response = match binding.response_body.as_str() {
+        "" => response.clone(),
+        field_name => match types.message(&method.output.type_name).and_then(|message| field_path(field_name, message, types)) {
+            Some(field) => format!("{}[\"{}\"]", response, field.key),
+            None => response.clone()
+        }
+    };
+    FetchMethod{
+        name: match index {
+            0 => lower_camel_case(&method.name),
+            _ => format!("{}{}", lower_camel_case(&method.name), index)
+        },
+        verb: binding.verb.clone(),
+        url,
+        query,
+        body,
+        request: method.input.ts_type.clone(),
+        response
+    }
+}
+
+fn fetch_file_stem(file_name: &str, resolver: &TypeResolver, options: &Options) -> String {
+    output_file_stem(file_name, "_fetch", resolver, options)
+}
+
+/// Clients of the unary methods in a file bound to HTTP through
+/// `google.api.http`, sending JSON with a replaceable `fetch`.
+pub(crate) struct Fetch;
+
+impl Renderer for Fetch {
+    fn render(
+        &self,
+        file: &ir::File,
+        types: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File> {
+        let clients = file.services
+            .iter()
+            .map(|service| FetchClient{
+                name: format!("{}FetchClient", service.name),
+                methods: service.methods
+                    .iter()
+                    .filter(|method| method.is_unary())
+                    .flat_map(|method| method.http_bindings
+                        .iter()
+                        .enumerate()
+                        .map(|(i, binding)| fetch_method(method, binding, i, types))
+                        .collect::<Vec<_>>()
+                    )
+                    .collect()
+            })
+            .filter(|client| !client.methods.is_empty())
+            .collect::<Vec<_>>();
+        if clients.is_empty() {
+            return vec![];
+        }
+        let stem = fetch_file_stem(&file.name, resolver, options);
+        let references = file.services
+            .iter()
+            .flat_map(|service| service.methods.iter())
+            .flat_map(|method| vec![method.input.type_name.clone(), method.output.type_name.clone()])
+            .collect::<Vec<_>>();
+        let mut content = CodeWriter::for_file(file);
+        content.push(gen_imports(&stem, references.iter(), resolver, options));
+        content.push('\n');
+        content.push(FETCH_PRELUDE);
+        for client in clients.iter() {
+            content.push('\n');
+            content.push(client);
+        }
+        vec![gen_resp_file(format!("{}.ts", stem), content.into_string())]
+    }
+}
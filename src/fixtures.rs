@@ -0,0 +1,368 @@
+use super::*;
+
+const CONTEXT_CHECK: &str = "context.depth < context.maxDepth";
+
+/// Deterministic fake data: every `mock` call without a context starts a
+/// mulberry32 sequence from `seed`, and nested messages stop at `maxDepth`.
+const FIXTURE_PRELUDE: &str = "\
+export interface FixtureOptions {
+  readonly seed?: number;
+  readonly maxDepth?: number;
+}
+
+export interface FixtureContext {
+  readonly next: () => number;
+  readonly depth: number;
+  readonly maxDepth: number;
+}
+
+function fixtureContext(options: FixtureOptions | FixtureContext): FixtureContext {
+  if (\"next\" in options) return options;
+  let state = (options.seed ?? 1) >>> 0;
+  return {
+    next: () => {
+      state = (state + 0x6d2b79f5) >>> 0;
+      let t = Math.imul(state ^ (state >>> 15), state | 1);
+      t ^= t + Math.imul(t ^ (t >>> 7), t | 61);
+      return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+    },
+    depth: 0,
+    maxDepth: options.maxDepth ?? 3
+  };
+}
+";
+
+/// Helpers included when the generated functions call them, listed so that
+/// every helper precedes the helpers it calls.
+const FIXTURE_HELPERS: &[(&str, &str)] = &[
+    ("nested", "\
+function nested(context: FixtureContext): FixtureContext {
+  return { ...context, depth: context.depth + 1 };
+}
+"),
+    ("mockRepeated", "\
+function mockRepeated<T>(context: FixtureContext, value: () => T): T[] {
+  return Array.from({ length: 1 + mockInt(context, 3) }, value);
+}
+"),
+    ("mockPick", "\
+function mockPick<T>(context: FixtureContext, values: ReadonlyArray<T>): T {
+  return values[mockInt(context, values.length)];
+}
+"),
+    ("mockTimestamp", "\
+function mockTimestamp(context: FixtureContext): Date {
+  return new Date(Date.UTC(2020, 0, 1) + mockInt(context, 31536000) * 1000);
+}
+"),
+    ("mockString", "\
+function mockString(context: FixtureContext, name: string): string {
+  return `${name}-${mockInt(context, 1000)}`;
+}
+"),
+    ("mockBase64", "\
+function mockBase64(context: FixtureContext): string {
+  return btoa(String.fromCharCode(...mockBytes(context)));
+}
+"),
+    ("mockBytes", "\
+function mockBytes(context: FixtureContext): Uint8Array {
+  return Uint8Array.from({ length: 4 }, () => mockInt(context, 256));
+}
+"),
+    ("mockFloat", "\
+function mockFloat(context: FixtureContext): number {
+  return Math.round(context.next() * 100000) / 100;
+}
+"),
+    ("mockInt", "\
+function mockInt(context: FixtureContext, max: number): number {
+  return Math.floor(context.next() * max);
+}
+")
+];
+
+fn scalar_mock(field_type: FieldDescriptorProto_Type, name: &str, options: &Options) -> Option<String> {
+    if options.type_overrides.contains_key(scalar_name(field_type)) {
+        return None;
+    }
+    Some(match field_type {
+        FieldDescriptorProto_Type::TYPE_DOUBLE | FieldDescriptorProto_Type::TYPE_FLOAT =>
+            "mockFloat(context)".to_string(),
+        FieldDescriptorProto_Type::TYPE_INT64 |
+            FieldDescriptorProto_Type::TYPE_UINT64 |
+            FieldDescriptorProto_Type::TYPE_FIXED64 |
+            FieldDescriptorProto_Type::TYPE_SFIXED64 |
+            FieldDescriptorProto_Type::TYPE_SINT64 => match options.int64_style {
+                Int64Style::String => "String(mockInt(context, 1000))".to_string(),
+                Int64Style::BigInt => "BigInt(mockInt(context, 1000))".to_string(),
+                Int64Style::Number => "mockInt(context, 1000)".to_string()
+            },
+        FieldDescriptorProto_Type::TYPE_BOOL => "mockInt(context, 2) === 1".to_string(),
+        FieldDescriptorProto_Type::TYPE_STRING => format!("mockString(context, \"{}\")", name),
+        FieldDescriptorProto_Type::TYPE_BYTES => match options.bytes_style {
+            BytesStyle::Base64String => "mockBase64(context)".to_string(),
+            BytesStyle::Uint8Array => "mockBytes(context)".to_string()
+        },
+        FieldDescriptorProto_Type::TYPE_ENUM |
+            FieldDescriptorProto_Type::TYPE_MESSAGE |
+            FieldDescriptorProto_Type::TYPE_GROUP => return None,
+        _ => "mockInt(context, 1000)".to_string()
+    })
+}
+
+fn well_known_mock(type_name: &str, name: &str, options: &Options) -> Option<String> {
+    if let Some(type_override) = options.type_overrides.get(type_name) {
+        return match (type_name, type_override.ts_type.as_str()) {
+            (".google.protobuf.Timestamp", "Date") => Some("mockTimestamp(context)".to_string()),
+            _ => None
+        };
+    }
+    if let Some(field_type) = wrapped_type(type_name) {
+        return scalar_mock(field_type, name, options);
+    }
+    match type_name {
+        ".google.protobuf.Timestamp" => Some("mockTimestamp(context).toISOString()".to_string()),
+        ".google.protobuf.Duration" => Some("`${mockInt(context, 3600)}s`".to_string()),
+        ".google.protobuf.Struct" => Some("{}".to_string()),
+        ".google.protobuf.Value" | ".google.protobuf.NullValue" => Some("null".to_string()),
+        ".google.protobuf.ListValue" => Some("[]".to_string()),
+        ".google.protobuf.Any" => Some("{ \"@type\": \"type.googleapis.com/google.protobuf.Empty\" }".to_string()),
+        _ => None
+    }
+}
+
+/// Expression producing a fake `value` of the field `name`, or `None` for
+/// types whose shape is unknown to the generator.
+fn value_mock(
+    value: &ir::Value,
+    name: &str,
+    types: &ir::Types,
+    resolver: &TypeResolver,
+    options: &Options
+) -> Option<String> {
+    match value.kind {
+        ir::ValueKind::WellKnown => well_known_mock(&value.type_name, name, options),
+        ir::ValueKind::Enum => {
+            let enum_type = types.enum_type(&value.type_name)?;
+            let values = enum_type.values
+                .iter()
+                .map(|value| match options.enum_style {
+                    EnumStyle::Union => format!("\"{}\"", value.ts_name),
+                    _ => value.number.to_string()
+                })
+                .collect::<Vec<_>>();
+            match values.is_empty() {
+                true => None,
+                false => Some(format!("mockPick(context, [{}])", values.join(", ")))
+            }
+        },
+        ir::ValueKind::Message =>
+            Some(format!("mock{}({{}}, nested(context))", resolver.function_prefix(&value.type_name))),
+        ir::ValueKind::Scalar(field_type) => scalar_mock(field_type, name, options)
+    }
+}
+
+fn field_mock(field: &ir::Field, types: &ir::Types, resolver: &TypeResolver, options: &Options) -> Option<String> {
+    if field.type_option.is_some() {
+        return None;
+    }
+    if options.field_paths && field.field_mask.is_some() {
+        return Some("[]".to_string());
+    }
+    match &field.cardinality {
+        ir::Cardinality::Map{ key } => {
+            let key = scalar_mock(key.field_type, "key", options)?;
+            let value = value_mock(&field.value, "value", types, resolver, options)?;
+            Some(match options.map_style {
+                MapStyle::Record if key.starts_with("mockString(") => format!(
+                    "Object.fromEntries(mockRepeated(context, () => [{}, {}] as const))",
+                    key,
+                    value
+                ),
+                MapStyle::Record => format!(
+                    "Object.fromEntries(mockRepeated(context, () => [String({}), {}] as const))",
+                    key,
+                    value
+                ),
+                MapStyle::Map => format!("new Map(mockRepeated(context, () => [{}, {}] as const))", key, value)
+            })
+        },
+        cardinality => {
+            let value = value_mock(&field.value, &field.name, types, resolver, options)?;
+            match cardinality {
+                ir::Cardinality::Repeated => Some(format!("mockRepeated(context, () => {})", value)),
+                _ => Some(value)
+            }
+        }
+    }
+}
+
+struct MessageFixture<'a> {
+    prefix: String,
+    message: &'a ir::Message,
+    types: &'a ir::Types<'a>,
+    resolver: &'a TypeResolver,
+    options: &'a Options
+}
+
+impl<'a> MessageFixture<'a> {
+    fn statements(&self) -> Vec<String> {
+        let mut statements = Vec::new();
+        for property in self.message.properties(self.options) {
+            let (key, index, fields) = match property {
+                ir::Property::Field(field) => match field.oneof {
+                    // With `oneof=properties` the members are handled together at the first one.
+                    Some(index) => {
+                        let fields = self.message.oneof_fields(index).collect::<Vec<_>>();
+                        if fields[0].number != field.number {
+                            continue;
+                        }
+                        (None, index, fields)
+                    },
+                    None => {
+                        let mock = match field_mock(field, self.types, self.resolver, self.options) {
+                            Some(mock) => mock,
+                            None => continue
+                        };
+                        if !mock.contains("nested(context)") {
+                            statements.push(format!("message[\"{}\"] = {};", field.key, mock));
+                            continue;
+                        }
+                        statements.push(match json::field_default(field, self.types, self.resolver, self.options) {
+                            Some(default) => format!("message[\"{}\"] = {} ? {} : {};", field.key, CONTEXT_CHECK, mock, default),
+                            None => format!("if ({}) message[\"{}\"] = {};", CONTEXT_CHECK, field.key, mock)
+                        });
+                        continue;
+                    }
+                },
+                ir::Property::Oneof{ index, oneof, fields } => (Some(&oneof.key), index, fields)
+            };
+            let cases = fields.iter()
+                .filter_map(|field| field_mock(field, self.types, self.resolver, self.options).map(|mock| (field, mock)))
+                .collect::<Vec<_>>();
+            if cases.is_empty() {
+                continue;
+            }
+            let local = format!("oneof{}", index);
+            statements.push(format!("const {} = mockInt(context, {});", local, cases.len()));
+            for (i, (field, mock)) in cases.iter().enumerate() {
+                let condition = match mock.contains("nested(context)") {
+                    true => format!("{} === {} && {}", local, i, CONTEXT_CHECK),
+                    false => format!("{} === {}", local, i)
+                };
+                let branch = match i {
+                    0 => "if",
+                    _ => "else if"
+                };
+                statements.push(match key {
+                    Some(key) => format!(
+                        "{} ({}) message[\"{}\"] = {{ $case: \"{3}\", {3}: {4} }};",
+                        branch,
+                        condition,
+                        key,
+                        field.key,
+                        mock
+                    ),
+                    None => format!("{} ({}) message[\"{}\"] = {};", branch, condition, field.key, mock)
+                });
+            }
+        }
+        statements
+    }
+}
+
+impl<'a> fmt::Display for MessageFixture<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ts_type = &self.message.ts_name;
+        let statements = self.statements();
+        let options = match statements.is_empty() {
+            true => "_options",
+            false => "options"
+        };
+        writeln!(
+            f,
+            "export function mock{}(overrides: Partial<{}> = {{}}, {}: FixtureOptions | FixtureContext = {{}}): {} {{",
+            self.prefix,
+            ts_type,
+            options,
+            ts_type
+        )?;
+        if !statements.is_empty() {
+            writeln!(f, "  const context = fixtureContext(options);")?;
+        }
+        writeln!(f, "  const message: Record<string, unknown> = {{}};")?;
+        for statement in statements.iter() {
+            writeln!(f, "  {}", statement)?;
+        }
+        writeln!(f, "  return {{ ...message, ...overrides }} as unknown as {};", ts_type)?;
+        writeln!(f, "}}")
+    }
+}
+
+fn fixtures_file_stem(file_name: &str, resolver: &TypeResolver, options: &Options) -> String {
+    output_file_stem(file_name, "_fixtures", resolver, options)
+}
+
+/// Deterministic `mock<Message>` functions of the messages of a file.
+pub(crate) struct Fixtures;
+
+impl Renderer for Fixtures {
+    fn render(
+        &self,
+        file: &ir::File,
+        types: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File> {
+        if file.messages.is_empty() {
+            return vec![];
+        }
+        let stem = fixtures_file_stem(&file.name, resolver, options);
+        let field_references = file.messages
+            .iter()
+            .flat_map(|message| message.fields.iter())
+            .filter(|field| field.type_option.is_none())
+            .flat_map(|field| field.references())
+            .filter(|type_name| types.enum_type(type_name).is_none())
+            .collect::<Vec<_>>();
+        let type_references = file.messages
+            .iter()
+            .map(|message| message.full_name.clone())
+            .collect::<Vec<_>>();
+        let body = file.messages
+            .iter()
+            .map(|message| format!("{}", MessageFixture{
+                prefix: resolver.function_prefix(&message.full_name),
+                message,
+                types,
+                resolver,
+                options
+            }))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut content = gen_imports(&stem, type_references.iter(), resolver, options);
+        content.push_str(&gen_function_imports(
+            &stem,
+            file,
+            &field_references,
+            "fixtures",
+            |prefix, _| vec![format!("mock{}", prefix)],
+            resolver,
+            options
+        ));
+        content.push('\n');
+        content.push_str(FIXTURE_PRELUDE);
+        let mut used = body.clone();
+        for (name, helper) in FIXTURE_HELPERS.iter() {
+            if used.contains(&format!("{}(", name)) {
+                used.push_str(helper);
+                content.push('\n');
+                content.push_str(helper);
+            }
+        }
+        content.push('\n');
+        content.push_str(&body);
+        vec![gen_resp_file(format!("{}.ts", stem), content)]
+    }
+}
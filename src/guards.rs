@@ -0,0 +1,222 @@
+use super::*;
+
+fn ts_type_check(ts_type: &TsType) -> String {
+    match ts_type {
+        TsType::Boolean => "typeof $v === \"boolean\"".to_string(),
+        TsType::Number => "typeof $v === \"number\"".to_string(),
+        TsType::BigInt => "typeof $v === \"bigint\"".to_string(),
+        TsType::String => "typeof $v === \"string\"".to_string(),
+        TsType::Uint8Array => "$v instanceof Uint8Array".to_string(),
+        TsType::Never => "false".to_string(),
+        TsType::Object(_) => "$v !== undefined".to_string(),
+        TsType::Nullable(inner) => format!("($v === null || {})", ts_type_check(inner))
+    }
+}
+
+fn well_known_check(type_name: &str, options: &Options) -> Option<String> {
+    let ts_type = well_known_type_to_ts_type(type_name, options)?;
+    if let Some(override_type) = options.type_overrides.get(type_name).map(|type_override| type_override.ts_type.as_str()) {
+        return Some(match (type_name, override_type) {
+            (".google.protobuf.Timestamp", "Date") => "$v instanceof Date".to_string(),
+            _ => ts_type_check(&ts_type)
+        });
+    }
+    Some(match type_name {
+        ".google.protobuf.Struct" => "(typeof $v === \"object\" && $v !== null && !Array.isArray($v))".to_string(),
+        ".google.protobuf.ListValue" => "Array.isArray($v)".to_string(),
+        ".google.protobuf.NullValue" => "$v === null".to_string(),
+        ".google.protobuf.Any" =>
+            "(typeof $v === \"object\" && $v !== null && typeof ($v as Record<string, unknown>)[\"@type\"] === \"string\")"
+                .to_string(),
+        _ => ts_type_check(&ts_type)
+    })
+}
+
+fn value_check(value: &ir::Value, resolver: &TypeResolver, options: &Options) -> String {
+    match value.kind {
+        ir::ValueKind::Scalar(field_type) => ts_type_check(&scalar_to_ts_type(field_type, options)),
+        ir::ValueKind::WellKnown => well_known_check(&value.type_name, options).unwrap_or_else(|| "true".to_string()),
+        ir::ValueKind::Enum | ir::ValueKind::Message => format!("is{}($v)", resolver.function_prefix(&value.type_name))
+    }
+}
+
+fn field_check(field: &ir::Field, resolver: &TypeResolver, options: &Options) -> String {
+    let value = value_check(&field.value, resolver, options);
+    match (&field.cardinality, options.map_style) {
+        (ir::Cardinality::Map{ .. }, MapStyle::Record) => format!(
+            "(typeof $v === \"object\" && $v !== null && Object.values($v).every((x) => {}))",
+            apply(&value, "x")
+        ),
+        (ir::Cardinality::Map{ .. }, MapStyle::Map) => format!(
+            "($v instanceof Map && Array.from($v.values()).every((x) => {}))",
+            apply(&value, "x")
+        ),
+        (ir::Cardinality::Repeated, _) => format!("(Array.isArray($v) && $v.every((x) => {}))", apply(&value, "x")),
+        (ir::Cardinality::Single, _) => value
+    }
+}
+
+struct MessageGuard<'a> {
+    prefix: String,
+    message: &'a ir::Message,
+    resolver: &'a TypeResolver,
+    options: &'a Options
+}
+
+impl<'a> fmt::Display for MessageGuard<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "export function is{}(value: unknown): value is {} {{", self.prefix, self.message.ts_name)?;
+        if self.message.fields.is_empty() {
+            writeln!(f, "  return typeof value === \"object\" && value !== null;")?;
+            return writeln!(f, "}}");
+        }
+        writeln!(f, "  if (typeof value !== \"object\" || value === null) return false;")?;
+        writeln!(f, "  const message = value as Record<string, unknown>;")?;
+        let check = |field, value: &str| apply(&field_check(field, self.resolver, self.options), value);
+        let mut conditions = Vec::new();
+        for property in self.message.properties(self.options) {
+            match property {
+                ir::Property::Field(field) => {
+                    let value = format!("message[\"{}\"]", field.key);
+                    conditions.push(match (field.is_optional, field.oneof.is_some()) {
+                        (true, _) => format!("({} || {})", absent_check(&value, self.options), check(field, &value)),
+                        (false, true) => format!("({} === undefined || {})", value, check(field, &value)),
+                        (false, false) => check(field, &value)
+                    });
+                },
+                ir::Property::Oneof{ index, oneof, fields } => {
+                    let local = format!("oneof{}", index);
+                    writeln!(
+                        f,
+                        "  const {} = message[\"{}\"] as Record<string, unknown> | null | undefined;",
+                        local,
+                        oneof.key
+                    )?;
+                    let cases = fields.iter()
+                        .map(|field| format!(
+                            "({0}[\"$case\"] === \"{1}\" && {2})",
+                            local,
+                            field.key,
+                            check(field, &format!("{}[\"{}\"]", local, field.key))
+                        ))
+                        .collect::<Vec<_>>();
+                    conditions.push(format!(
+                        "({0} === undefined || (typeof {0} === \"object\" && {0} !== null && ({1})))",
+                        local,
+                        cases.join(" || ")
+                    ));
+                }
+            }
+        }
+        if let OneofStyle::Properties = self.options.oneof_style {
+            for index in 0..self.message.oneofs.len() {
+                let members = self.message.oneof_fields(index)
+                    .map(|field| format!("message[\"{}\"]", field.key))
+                    .collect::<Vec<_>>();
+                if members.len() > 1 {
+                    conditions.push(format!(
+                        "[{}].filter((x) => x !== undefined).length <= 1",
+                        members.join(", ")
+                    ));
+                }
+            }
+        }
+        writeln!(f, "  return (")?;
+        writeln!(f, "    {}", conditions.join(" &&\n    "))?;
+        writeln!(f, "  );")?;
+        writeln!(f, "}}")
+    }
+}
+
+struct EnumGuard<'a> {
+    prefix: String,
+    enum_type: &'a ir::Enum,
+    style: EnumStyle,
+    is_open: bool
+}
+
+impl<'a> fmt::Display for EnumGuard<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values = self.enum_type.values
+            .iter()
+            .map(|value| match self.style {
+                EnumStyle::Union => format!("value === \"{}\"", value.ts_name),
+                EnumStyle::Enum | EnumStyle::Const => format!("value === {}", value.number)
+            })
+            .chain(match (self.is_open, self.style) {
+                (false, _) => None,
+                (true, EnumStyle::Union) => Some(format!("value === \"{}\"", UNRECOGNIZED)),
+                (true, _) => Some(format!("value === {}", UNRECOGNIZED_NUMBER))
+            })
+            .collect::<Vec<_>>();
+        writeln!(f, "export function is{}(value: unknown): value is {} {{", self.prefix, self.enum_type.ts_name)?;
+        match values.is_empty() {
+            true => writeln!(f, "  return false;")?,
+            false => writeln!(f, "  return {};", values.join(" || "))?
+        }
+        writeln!(f, "}}")
+    }
+}
+
+fn guards_file_stem(file_name: &str, resolver: &TypeResolver, options: &Options) -> String {
+    output_file_stem(file_name, "_guards", resolver, options)
+}
+
+/// Type guards of the messages and enums of a file, rendered from the IR.
+pub(crate) struct Guards;
+
+impl Renderer for Guards {
+    fn render(
+        &self,
+        file: &ir::File,
+        _: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File> {
+        if file.messages.is_empty() && file.enums.is_empty() {
+            return vec![];
+        }
+        let stem = guards_file_stem(&file.name, resolver, options);
+        let field_references = file.messages
+            .iter()
+            .flat_map(|message| message.fields.iter())
+            .map(|field| field.value.type_name.clone())
+            .filter(|type_name| !type_name.is_empty())
+            .collect::<Vec<_>>();
+        let type_references = file.messages
+            .iter()
+            .map(|message| message.full_name.clone())
+            .chain(file.enums.iter().map(|enum_type| enum_type.full_name.clone()))
+            .collect::<Vec<_>>();
+        let mut content = CodeWriter::for_file(file);
+        content.push(gen_imports(&stem, type_references.iter(), resolver, options));
+        content.push(gen_function_imports(
+            &stem,
+            file,
+            &field_references,
+            "guards",
+            |prefix, _| vec![format!("is{}", prefix)],
+            resolver,
+            options
+        ));
+        for enum_type in file.enums.iter() {
+            content.push('\n');
+            content.push(EnumGuard{
+                prefix: resolver.function_prefix(&enum_type.full_name),
+                enum_type,
+                style: options.enum_style,
+                is_open: options.open_enums
+            });
+        }
+        for message in file.messages.iter() {
+            content.push('\n');
+            content.push(MessageGuard{
+                prefix: resolver.function_prefix(&message.full_name),
+                message,
+                resolver,
+                options
+            });
+        }
+        vec![gen_resp_file(format!("{}.ts", stem), content.into_string())]
+    }
+}
@@ -0,0 +1,414 @@
+use super::*;
+
+fn apply_pair(template: &str, a: &str, b: &str) -> String {
+    template.replace("$a", a).replace("$b", b)
+}
+
+const ARRAY_EQUAL: &str = "\
+function arrayEqual<T>(a: ReadonlyArray<T>, b: ReadonlyArray<T>, equal: (x: T, y: T) => boolean = (x, y) => x === y): boolean {
+  return a.length === b.length && a.every((x, i) => equal(x, b[i] as T));
+}
+";
+
+const RECORD_EQUAL: &str = "\
+function recordEqual<T>(
+  a: { readonly [key: string]: T },
+  b: { readonly [key: string]: T },
+  equal: (x: T, y: T) => boolean = (x, y) => x === y
+): boolean {
+  const keys = Object.keys(a);
+  return keys.length === Object.keys(b).length &&
+    keys.every((k) => Object.prototype.hasOwnProperty.call(b, k) && equal(a[k] as T, b[k] as T));
+}
+";
+
+const MAP_EQUAL: &str = "\
+function mapEqual<K, T>(a: ReadonlyMap<K, T>, b: ReadonlyMap<K, T>, equal: (x: T, y: T) => boolean = (x, y) => x === y): boolean {
+  if (a.size !== b.size) return false;
+  for (const [k, x] of a) {
+    if (!b.has(k) || !equal(x, b.get(k) as T)) return false;
+  }
+  return true;
+}
+";
+
+const BYTES_EQUAL: &str = "\
+function bytesEqual(a: Uint8Array | null | undefined, b: Uint8Array | null | undefined): boolean {
+  if (a === b) return true;
+  if (a == null || b == null || a.length !== b.length) return false;
+  return a.every((x, i) => x === b[i]);
+}
+";
+
+const DEEP_EQUAL: &str = "\
+function deepEqual(a: unknown, b: unknown): boolean {
+  if (a === b) return true;
+  if (typeof a !== \"object\" || typeof b !== \"object\" || a === null || b === null) return false;
+  if (a instanceof Uint8Array || b instanceof Uint8Array) {
+    return a instanceof Uint8Array && b instanceof Uint8Array && bytesEqual(a, b);
+  }
+  if (a instanceof Date || b instanceof Date) {
+    return a instanceof Date && b instanceof Date && a.getTime() === b.getTime();
+  }
+  if (Array.isArray(a) || Array.isArray(b)) {
+    return Array.isArray(a) && Array.isArray(b) && arrayEqual(a, b, deepEqual);
+  }
+  if (a instanceof Map || b instanceof Map) {
+    return a instanceof Map && b instanceof Map && mapEqual(a, b, deepEqual);
+  }
+  return recordEqual(a as Record<string, unknown>, b as Record<string, unknown>, deepEqual);
+}
+";
+
+const DEEP_CLONE: &str = "\
+function deepClone<T>(value: T): T {
+  if (typeof value !== \"object\" || value === null) return value;
+  if (value instanceof Uint8Array) return value.slice() as T;
+  if (value instanceof Date) return new Date(value.getTime()) as T;
+  if (Array.isArray(value)) return value.map((x) => deepClone(x)) as T;
+  if (value instanceof Map) return new Map(Array.from(value, ([k, x]) => [k, deepClone(x)] as const)) as T;
+  return Object.fromEntries(Object.entries(value).map(([k, x]) => [k, deepClone(x)] as const)) as T;
+}
+";
+
+/// Whether values of `ts_type` are compared and copied as they are.
+fn is_primitive(ts_type: &TsType) -> bool {
+    match ts_type {
+        TsType::Boolean | TsType::Number | TsType::BigInt | TsType::String | TsType::Never => true,
+        TsType::Nullable(inner) => is_primitive(inner),
+        TsType::Uint8Array | TsType::Object(_) => false
+    }
+}
+
+/// Expression comparing the values `$a` and `$b`, or `None` when `===` does.
+fn value_equality(value: &ir::Value, resolver: &TypeResolver, options: &Options) -> Option<String> {
+    match value.kind {
+        ir::ValueKind::Scalar(field_type) => match scalar_to_ts_type(field_type, options) {
+            TsType::Uint8Array => Some("bytesEqual($a, $b)".to_string()),
+            _ => None
+        },
+        ir::ValueKind::Enum => None,
+        ir::ValueKind::Message => Some(format!("equals{}($a, $b)", resolver.function_prefix(&value.type_name))),
+        ir::ValueKind::WellKnown => match well_known_type_to_ts_type(&value.type_name, options) {
+            Some(ts_type) if is_primitive(&ts_type) => None,
+            _ => Some("deepEqual($a, $b)".to_string())
+        }
+    }
+}
+
+/// Expression copying the value `$v`, or `None` when it is immutable.
+fn value_clone(value: &ir::Value, resolver: &TypeResolver, options: &Options) -> Option<String> {
+    match value.kind {
+        ir::ValueKind::Scalar(field_type) => match scalar_to_ts_type(field_type, options) {
+            TsType::Uint8Array => Some("$v.slice()".to_string()),
+            _ => None
+        },
+        ir::ValueKind::Enum => None,
+        ir::ValueKind::Message => Some(format!("clone{}($v)", resolver.function_prefix(&value.type_name))),
+        ir::ValueKind::WellKnown => match well_known_type_to_ts_type(&value.type_name, options) {
+            Some(ts_type) if is_primitive(&ts_type) => None,
+            _ => Some("deepClone($v)".to_string())
+        }
+    }
+}
+
+/// Condition under which the values `$a` and `$b` of `field` are equal.
+fn field_equality(field: &ir::Field, may_be_absent: bool, resolver: &TypeResolver, options: &Options) -> String {
+    let value = value_equality(&field.value, resolver, options);
+    let callback = value.as_ref()
+        .map(|value| format!(", (x, y) => {}", apply_pair(value, "x", "y")))
+        .unwrap_or_default();
+    match (&field.cardinality, options.map_style) {
+        (ir::Cardinality::Repeated, _) => format!("arrayEqual($a, $b{})", callback),
+        (ir::Cardinality::Map{ .. }, MapStyle::Record) => format!("recordEqual($a, $b{})", callback),
+        (ir::Cardinality::Map{ .. }, MapStyle::Map) => format!("mapEqual($a, $b{})", callback),
+        (ir::Cardinality::Single, _) => match value {
+            Some(value) if may_be_absent && field.value.kind == ir::ValueKind::Message =>
+                format!("($a === $b || ($a != null && $b != null && {}))", value),
+            Some(value) => value,
+            None => "$a === $b".to_string()
+        }
+    }
+}
+
+/// Expression copying the present value `$v` of `field`, or `None` when
+/// sharing it is safe.
+fn field_clone(field: &ir::Field, resolver: &TypeResolver, options: &Options) -> Option<String> {
+    let value = value_clone(&field.value, resolver, options);
+    match (&field.cardinality, options.map_style, value) {
+        (ir::Cardinality::Repeated, _, Some(value)) => Some(format!("$v.map((x) => {})", apply(&value, "x"))),
+        (ir::Cardinality::Repeated, _, None) => Some("$v.slice()".to_string()),
+        (ir::Cardinality::Map{ .. }, MapStyle::Record, Some(value)) => Some(format!(
+            "Object.fromEntries(Object.entries($v).map(([k, x]) => [k, {}] as const))",
+            apply(&value, "x")
+        )),
+        (ir::Cardinality::Map{ .. }, MapStyle::Record, None) => Some("{ ...$v }".to_string()),
+        (ir::Cardinality::Map{ .. }, MapStyle::Map, Some(value)) => Some(format!(
+            "new Map(Array.from($v, ([k, x]) => [k, {}] as const))",
+            apply(&value, "x")
+        )),
+        (ir::Cardinality::Map{ .. }, MapStyle::Map, None) => Some("new Map($v)".to_string()),
+        (ir::Cardinality::Single, _, value) => value
+    }
+}
+
+struct MessageEquality<'a> {
+    prefix: String,
+    message: &'a ir::Message,
+    resolver: &'a TypeResolver,
+    options: &'a Options
+}
+
+impl<'a> fmt::Display for MessageEquality<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ts_type = &self.message.ts_name;
+        if self.message.fields.is_empty() {
+            writeln!(f, "export function equals{}(_a: {1}, _b: {1}): boolean {{", self.prefix, ts_type)?;
+            writeln!(f, "  return true;")?;
+            return writeln!(f, "}}");
+        }
+        writeln!(f, "export function equals{}(a: {1}, b: {1}): boolean {{", self.prefix, ts_type)?;
+        writeln!(f, "  if (a === b) return true;")?;
+        for property in self.message.properties(self.options) {
+            match property {
+                ir::Property::Field(field) => {
+                    let may_be_absent = field.is_optional || field.oneof.is_some();
+                    let equality = field_equality(field, may_be_absent, self.resolver, self.options);
+                    let (a, b) = (format!("a[\"{}\"]", field.key), format!("b[\"{}\"]", field.key));
+                    match equality.as_str() {
+                        "$a === $b" => writeln!(f, "  if ({} !== {}) return false;", a, b)?,
+                        equality => writeln!(f, "  if (!{}) return false;", apply_pair(equality, &a, &b))?
+                    }
+                },
+                ir::Property::Oneof{ index, oneof, fields } => {
+                    let (x, y) = (format!("oneof{}a", index), format!("oneof{}b", index));
+                    writeln!(f, "  const {} = a[\"{}\"], {} = b[\"{}\"];", x, oneof.key, y, oneof.key)?;
+                    writeln!(f, "  if ({} !== {}) {{", x, y)?;
+                    writeln!(f, "    if ({0} == null || {1} == null || {0}.$case !== {1}.$case) return false;", x, y)?;
+                    writeln!(f, "    switch ({}.$case) {{", x)?;
+                    for field in fields {
+                        let equality = field_equality(field, false, self.resolver, self.options);
+                        let a = format!("{}[\"{}\"]", x, field.key);
+                        let b = format!("({} as typeof {})[\"{}\"]", y, x, field.key);
+                        writeln!(f, "      case \"{}\":", field.key)?;
+                        match equality.as_str() {
+                            "$a === $b" => writeln!(f, "        if ({} !== {}) return false;", a, b)?,
+                            equality => writeln!(f, "        if (!{}) return false;", apply_pair(equality, &a, &b))?
+                        }
+                        writeln!(f, "        break;")?;
+                    }
+                    writeln!(f, "    }}")?;
+                    writeln!(f, "  }}")?;
+                }
+            }
+        }
+        writeln!(f, "  return true;")?;
+        writeln!(f, "}}")
+    }
+}
+
+struct MessageClone<'a> {
+    prefix: String,
+    message: &'a ir::Message,
+    resolver: &'a TypeResolver,
+    options: &'a Options
+}
+
+impl<'a> fmt::Display for MessageClone<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ts_type = &self.message.ts_name;
+        writeln!(f, "export function clone{}(message: {1}): {1} {{", self.prefix, ts_type)?;
+        writeln!(f, "  const clone: Record<string, unknown> = {{ ...message }};")?;
+        for property in self.message.properties(self.options) {
+            match property {
+                ir::Property::Field(field) => {
+                    let value = format!("message[\"{}\"]", field.key);
+                    let clone = match field_clone(field, self.resolver, self.options) {
+                        Some(clone) => apply(&clone, &value),
+                        None => continue
+                    };
+                    match field.is_optional || field.oneof.is_some() {
+                        true => writeln!(f, "  if ({} != null) clone[\"{}\"] = {};", value, field.key, clone)?,
+                        false => writeln!(f, "  clone[\"{}\"] = {};", field.key, clone)?
+                    }
+                },
+                ir::Property::Oneof{ index, oneof, fields } => {
+                    let local = format!("oneof{}", index);
+                    writeln!(f, "  const {} = message[\"{}\"];", local, oneof.key)?;
+                    let mut branch = "if";
+                    for field in fields {
+                        let clone = match field_clone(field, self.resolver, self.options) {
+                            Some(clone) => clone,
+                            None => continue
+                        };
+                        writeln!(
+                            f,
+                            "  {0} ({1}?.$case === \"{3}\") clone[\"{2}\"] = {{ $case: \"{3}\", \"{3}\": {4} }};",
+                            branch,
+                            local,
+                            oneof.key,
+                            field.key,
+                            apply(&clone, &format!("{}[\"{}\"]", local, field.key))
+                        )?;
+                        branch = "else if";
+                    }
+                    writeln!(f, "  {} ({} != null) clone[\"{}\"] = {{ ...{} }};", branch, local, oneof.key, local)?;
+                }
+            }
+        }
+        writeln!(f, "  return clone as unknown as {};", ts_type)?;
+        writeln!(f, "}}")
+    }
+}
+
+/// `match<Message><Oneof>`, calling the case of the member set in a oneof,
+/// or `none`. Its cases must cover every member.
+struct OneofMatch<'a> {
+    prefix: String,
+    message: &'a ir::Message,
+    index: usize,
+    options: &'a Options
+}
+
+impl<'a> fmt::Display for OneofMatch<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let key = &self.message.oneofs[self.index].key;
+        let fields = self.message.oneof_fields(self.index).collect::<Vec<_>>();
+        let mut chars = key.chars();
+        let oneof = chars.next()
+            .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+            .unwrap_or_default();
+        writeln!(f, "export function match{}{}<R>(", self.prefix, oneof)?;
+        writeln!(f, "  message: {},", self.message.ts_name)?;
+        writeln!(f, "  cases: {{")?;
+        for field in fields.iter() {
+            writeln!(f, "    readonly {}: (value: {}) => R;", property_key(&field.key), field.value.ts_type)?;
+        }
+        writeln!(f, "    readonly none: () => R;")?;
+        writeln!(f, "  }}")?;
+        writeln!(f, "): R {{")?;
+        match self.options.oneof_style {
+            OneofStyle::Properties => for field in fields.iter() {
+                let value = format!("message[\"{}\"]", field.key);
+                writeln!(f, "  if ({} !== undefined) return cases[\"{}\"]({});", value, field.key, value)?;
+            },
+            OneofStyle::Discriminated => {
+                writeln!(f, "  const oneof = message[\"{}\"];", key)?;
+                writeln!(f, "  if (oneof != null) {{")?;
+                writeln!(f, "    switch (oneof.$case) {{")?;
+                for field in fields.iter() {
+                    writeln!(
+                        f,
+                        "      case \"{0}\": return cases[\"{0}\"](oneof[\"{0}\"]);",
+                        field.key
+                    )?;
+                }
+                writeln!(f, "    }}")?;
+                writeln!(f, "  }}")?;
+            }
+        }
+        writeln!(f, "  return cases.none();")?;
+        writeln!(f, "}}")
+    }
+}
+
+fn helpers_file_stem(file_name: &str, resolver: &TypeResolver, options: &Options) -> String {
+    output_file_stem(file_name, "_helpers", resolver, options)
+}
+
+/// Structural equality and deep copy functions of the messages of a file,
+/// rendered from the IR.
+pub(crate) struct Helpers {
+    pub(crate) equality: bool,
+    pub(crate) clone: bool,
+    pub(crate) matching: bool
+}
+
+impl Renderer for Helpers {
+    fn render(
+        &self,
+        file: &ir::File,
+        _: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File> {
+        if file.messages.is_empty() {
+            return vec![];
+        }
+        let stem = helpers_file_stem(&file.name, resolver, options);
+        let field_references = file.messages
+            .iter()
+            .flat_map(|message| message.fields.iter())
+            .filter(|field| field.value.kind == ir::ValueKind::Message)
+            .map(|field| field.value.type_name.clone())
+            .collect::<Vec<_>>();
+        let member_types = file.messages
+            .iter()
+            .flat_map(|message| message.fields.iter())
+            .filter(|field| self.matching && field.oneof.is_some() && !field.value.type_name.is_empty())
+            .map(|field| field.value.type_name.clone());
+        let type_references = file.messages
+            .iter()
+            .map(|message| message.full_name.clone())
+            .chain(member_types)
+            .collect::<Vec<_>>();
+        let mut body = CodeWriter::for_file(file);
+        for message in file.messages.iter() {
+            let prefix = resolver.function_prefix(&message.full_name);
+            if self.equality {
+                body.push('\n');
+                body.push(MessageEquality{ prefix: prefix.clone(), message, resolver, options });
+            }
+            if self.clone {
+                body.push('\n');
+                body.push(MessageClone{ prefix: prefix.clone(), message, resolver, options });
+            }
+            if self.matching {
+                for index in (0..message.oneofs.len()).filter(|&index| message.oneof_fields(index).next().is_some()) {
+                    body.push('\n');
+                    body.push(OneofMatch{ prefix: prefix.clone(), message, index, options });
+                }
+            }
+        }
+        // Only oneof matchers may be asked for, and the file may have none.
+        if body.as_str().is_empty() {
+            return vec![];
+        }
+        let mut content = CodeWriter::with_capacity(body.as_str().len() + 2048);
+        content.push(gen_imports(&stem, type_references.iter(), resolver, options));
+        content.push(gen_function_imports(
+            &stem,
+            file,
+            &field_references,
+            "helpers",
+            |prefix, _| {
+                let mut names = Vec::new();
+                if self.equality {
+                    names.push(format!("equals{}", prefix));
+                }
+                if self.clone {
+                    names.push(format!("clone{}", prefix));
+                }
+                names
+            },
+            resolver,
+            options
+        ));
+        let body = body.into_string();
+        let uses_deep_equal = body.contains("deepEqual(");
+        let helpers = [
+            (ARRAY_EQUAL, uses_deep_equal || body.contains("arrayEqual(")),
+            (RECORD_EQUAL, uses_deep_equal || body.contains("recordEqual(")),
+            (MAP_EQUAL, uses_deep_equal || body.contains("mapEqual(")),
+            (BYTES_EQUAL, uses_deep_equal || body.contains("bytesEqual(")),
+            (DEEP_EQUAL, uses_deep_equal),
+            (DEEP_CLONE, body.contains("deepClone("))
+        ];
+        for (helper, _) in helpers.iter().filter(|(_, is_used)| *is_used) {
+            content.push('\n');
+            content.push(helper);
+        }
+        content.push(body);
+        vec![gen_resp_file(format!("{}.ts", stem), content.into_string())]
+    }
+}
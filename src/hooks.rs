@@ -0,0 +1,122 @@
+use super::*;
+
+const REACT_QUERY_PRELUDE: &str = "\
+export interface RpcTransport {
+  unary<I, O>(path: string, request: I, signal?: AbortSignal): Promise<O>;
+}
+";
+
+struct HookMethod {
+    name: String,
+    full_name: String,
+    path: String,
+    request: String,
+    response: String
+}
+
+/// Query and mutation hooks of the unary methods of a service. Query keys
+/// start with the full method name, so `queryClient.invalidateQueries`
+/// can match every request of a method.
+struct ReactQueryHooks {
+    methods: Vec<HookMethod>
+}
+
+impl fmt::Display for ReactQueryHooks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, method) in self.methods.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let key_type = format!("{}QueryKey", method.name);
+            writeln!(f, "export type {} = readonly [\"{}\", {}];", key_type, method.full_name, method.request)?;
+            writeln!(f)?;
+            writeln!(f, "export function {}QueryKey(request: {}): {} {{", lower_camel_case(&method.name), method.request, key_type)?;
+            writeln!(f, "  return [\"{}\", request];", method.full_name)?;
+            writeln!(f, "}}")?;
+            writeln!(f)?;
+            writeln!(f, "export function use{}Query(", method.name)?;
+            writeln!(f, "  transport: RpcTransport,")?;
+            writeln!(f, "  request: {},", method.request)?;
+            writeln!(
+                f,
+                "  options: Omit<UseQueryOptions<{0}, Error, {0}, {1}>, \"queryKey\" | \"queryFn\"> = {{}}",
+                method.response,
+                key_type
+            )?;
+            writeln!(f, "): UseQueryResult<{}, Error> {{", method.response)?;
+            writeln!(f, "  return useQuery({{")?;
+            writeln!(f, "    ...options,")?;
+            writeln!(f, "    queryKey: {}QueryKey(request),", lower_camel_case(&method.name))?;
+            writeln!(
+                f,
+                "    queryFn: ({{ signal }}) => transport.unary<{}, {}>(\"{}\", request, signal)",
+                method.request,
+                method.response,
+                method.path
+            )?;
+            writeln!(f, "  }});")?;
+            writeln!(f, "}}")?;
+            writeln!(f)?;
+            writeln!(f, "export function use{}Mutation(", method.name)?;
+            writeln!(f, "  transport: RpcTransport,")?;
+            writeln!(
+                f,
+                "  options: Omit<UseMutationOptions<{}, Error, {}>, \"mutationFn\"> = {{}}",
+                method.response,
+                method.request
+            )?;
+            writeln!(f, "): UseMutationResult<{}, Error, {}> {{", method.response, method.request)?;
+            writeln!(f, "  return useMutation({{")?;
+            writeln!(f, "    ...options,")?;
+            writeln!(
+                f,
+                "    mutationFn: (request) => transport.unary<{}, {}>(\"{}\", request)",
+                method.request,
+                method.response,
+                method.path
+            )?;
+            writeln!(f, "  }});")?;
+            writeln!(f, "}}")?;
+        }
+        Ok(())
+    }
+}
+
+/// React Query hooks of the unary methods of a file.
+pub(crate) struct ReactQuery;
+
+impl Renderer for ReactQuery {
+    fn render(
+        &self,
+        file: &ir::File,
+        _: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File> {
+        gen_service_file(
+            file,
+            "hooks",
+            &options.ts_syntax.imports(
+                "@tanstack/react-query",
+                &["useMutation", "useQuery"],
+                &["UseMutationOptions", "UseMutationResult", "UseQueryOptions", "UseQueryResult"]
+            ),
+            REACT_QUERY_PRELUDE,
+            |service| format!("{}", ReactQueryHooks{
+                methods: service.methods
+                    .iter()
+                    .filter(|method| method.is_unary())
+                    .map(|method| HookMethod{
+                        name: method.name.clone(),
+                        full_name: format!("{}.{}", service.full_name.trim_start_matches('.'), method.name),
+                        path: method.path.clone(),
+                        request: method.input.ts_type.clone(),
+                        response: method.output.ts_type.clone()
+                    })
+                    .collect()
+            }),
+            resolver,
+            options
+        )
+    }
+}
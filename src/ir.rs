@@ -0,0 +1,529 @@
+use super::*;
+use super::extensions::{FIELD_OPTIONS, MESSAGE_OPTIONS};
+
+use protobuf::Message as _;
+
+/// What the values of a field are.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ValueKind {
+    Scalar(FieldDescriptorProto_Type),
+    Enum,
+    Message,
+    /// A well-known or overridden type, represented by a TypeScript type
+    /// rather than a generated one.
+    WellKnown
+}
+
+/// A single value of a field, or the key or value of a map.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Value {
+    pub kind: ValueKind,
+    pub field_type: FieldDescriptorProto_Type,
+    /// Fully-qualified name of the type, empty for scalars.
+    pub type_name: String,
+    pub ts_type: String
+}
+
+impl Value {
+    /// Name the value is imported by: scalars by their name, so that
+    /// overrides of them can be imported.
+    pub fn reference(&self) -> String {
+        match self.type_name.as_str() {
+            "" => scalar_name(self.field_type).to_string(),
+            type_name => type_name.to_string()
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Cardinality {
+    Single,
+    Repeated,
+    Map {
+        key: Value
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    pub name: String,
+    /// Property name in generated types.
+    pub key: String,
+    pub json_name: String,
+    pub number: i32,
+    /// Type of the property, i.e. the value with its cardinality applied.
+    pub ts_type: String,
+    pub value: Value,
+    pub cardinality: Cardinality,
+    /// Whether the field tracks presence, so that absence is representable.
+    pub is_optional: bool,
+    /// Index of the oneof the field is a member of. Synthetic oneofs of
+    /// proto3 `optional` fields are not counted as membership.
+    pub oneof: Option<usize>,
+    /// Whether the field is proto2 `required`.
+    pub is_required: bool,
+    /// Whether the values of a repeated field are written packed.
+    pub is_packed: bool,
+    /// The `(tst.ts_type)` of the field, replacing the value type of maps.
+    pub type_option: Option<String>,
+    /// The message whose paths a `google.protobuf.FieldMask` field holds,
+    /// per `(tst.field_mask)`.
+    pub field_mask: Option<String>,
+    pub(crate) constraints: Option<validate::FieldConstraints>,
+    /// Custom `FieldOptions` set on the field, as TypeScript literals by
+    /// extension key.
+    pub custom_options: Vec<(String, String)>,
+    pub doc: Option<String>
+}
+
+impl Field {
+    /// Whether absence is representable, as for optional fields and oneof
+    /// members.
+    pub fn has_presence(&self) -> bool {
+        self.is_optional || self.oneof.is_some()
+    }
+
+    /// Types the field refers to, with `(tst.ts_type)` by its value.
+    pub fn references(&self) -> Vec<String> {
+        let value = self.type_option.clone().unwrap_or_else(|| self.value.reference());
+        match &self.cardinality {
+            Cardinality::Map{ key } => vec![key.reference(), value],
+            _ => vec![value]
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Oneof {
+    pub name: String,
+    pub key: String,
+    pub doc: Option<String>
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Message {
+    pub full_name: String,
+    pub ts_name: String,
+    pub doc: Option<String>,
+    pub fields: Vec<Field>,
+    /// Every oneof declared, indexed like `Field::oneof`.
+    pub oneofs: Vec<Oneof>,
+    /// Whether the type is branded, with `nominal=true` or `(tst.nominal)`.
+    pub is_nominal: bool,
+    /// Custom `MessageOptions` set on the message, as TypeScript literals by
+    /// extension key.
+    pub custom_options: Vec<(String, String)>
+}
+
+/// A property of a generated message type, like `MessageProperty`.
+pub(crate) enum Property<'a> {
+    Field(&'a Field),
+    Oneof {
+        index: usize,
+        oneof: &'a Oneof,
+        fields: Vec<&'a Field>
+    }
+}
+
+impl Message {
+    /// Members of the oneof at `index`, in declaration order.
+    pub fn oneof_fields(&self, index: usize) -> impl Iterator<Item = &Field> {
+        self.fields.iter().filter(move |field| field.oneof == Some(index))
+    }
+
+    pub(crate) fn properties(&self, options: &Options) -> Vec<Property<'_>> {
+        let mut properties = Vec::<Property>::new();
+        for field in self.fields.iter() {
+            let index = match (options.oneof_style, field.oneof) {
+                (OneofStyle::Discriminated, Some(index)) => index,
+                _ => {
+                    properties.push(Property::Field(field));
+                    continue;
+                }
+            };
+            let grouped = properties.iter_mut().find_map(|property| match property {
+                Property::Oneof{ index: grouped, fields, .. } if *grouped == index => Some(fields),
+                _ => None
+            });
+            match grouped {
+                Some(fields) => fields.push(field),
+                None => properties.push(Property::Oneof{ index, oneof: &self.oneofs[index], fields: vec![field] })
+            }
+        }
+        properties
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumValue {
+    pub name: String,
+    /// Name in generated types, after `enum_strip_prefix` and
+    /// `enum_value_case`.
+    pub ts_name: String,
+    pub number: i32,
+    /// Other names `allow_alias` gives `number`, which have no value of
+    /// their own.
+    pub aliases: Vec<String>,
+    pub doc: Option<String>
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Enum {
+    pub full_name: String,
+    pub ts_name: String,
+    /// With `enum_style=union`, which has no room for docs of values, the
+    /// doc also names the aliases of values.
+    pub doc: Option<String>,
+    pub values: Vec<EnumValue>
+}
+
+/// `idempotency_level` of a method, when it is not the default.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Idempotency {
+    NoSideEffects,
+    Idempotent
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Method {
+    pub name: String,
+    /// HTTP/2 path of the method, `/package.Service/Method`.
+    pub path: String,
+    pub input: Value,
+    pub output: Value,
+    pub client_streaming: bool,
+    pub server_streaming: bool,
+    pub idempotency: Option<Idempotency>,
+    /// Bindings of the method from `google.api.http`, in declaration order.
+    pub(crate) http_bindings: Vec<openapi::HttpBinding>,
+    pub doc: Option<String>
+}
+
+impl Method {
+    pub fn is_unary(&self) -> bool {
+        !self.client_streaming && !self.server_streaming
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Service {
+    pub full_name: String,
+    pub name: String,
+    pub doc: Option<String>,
+    pub methods: Vec<Method>
+}
+
+/// An extension declared at the top level of a file or in a message.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Extension {
+    pub full_name: String,
+    /// Fully-qualified name of the message extended.
+    pub extendee: String,
+    /// The extension as a property of the extension bag, keyed
+    /// `[package.name]` as in proto3 JSON.
+    pub field: Field
+}
+
+/// A proto file with its messages and enums flattened in declaration order.
+/// Synthetic map entries and `(tst.skip)` types are left out.
+#[derive(Clone, Debug, PartialEq)]
+pub struct File {
+    pub name: String,
+    pub package: String,
+    pub messages: Vec<Message>,
+    pub enums: Vec<Enum>,
+    pub extensions: Vec<Extension>,
+    pub services: Vec<Service>
+}
+
+impl File {
+    /// Enums declared directly in the message `scope`, or at the top level
+    /// of the file without one.
+    pub fn enums_in<'a>(&'a self, scope: Option<&'a str>) -> impl Iterator<Item = &'a Enum> + 'a {
+        let top_level = match self.package.as_str() {
+            "" => String::new(),
+            package => format!(".{}", package)
+        };
+        self.enums.iter().filter(move |enum_type| {
+            let parent = enum_type.full_name.rsplit_once('.').map_or("", |(parent, _)| parent);
+            parent == scope.unwrap_or(&top_level)
+        })
+    }
+}
+
+/// The messages and enums of every file of a request by full name, for
+/// renderers following references out of the file they render.
+pub struct Types<'a> {
+    messages: HashMap<&'a str, &'a Message>,
+    enums: HashMap<&'a str, &'a Enum>
+}
+
+impl<'a> Types<'a> {
+    pub fn new(files: &'a [File]) -> Types<'a> {
+        Types{
+            messages: files.iter()
+                .flat_map(|file| file.messages.iter())
+                .map(|message| (message.full_name.as_str(), message))
+                .collect(),
+            enums: files.iter()
+                .flat_map(|file| file.enums.iter())
+                .map(|enum_type| (enum_type.full_name.as_str(), enum_type))
+                .collect()
+        }
+    }
+
+    pub fn message(&self, type_name: &str) -> Option<&'a Message> {
+        self.messages.get(type_name).copied()
+    }
+
+    pub fn enum_type(&self, type_name: &str) -> Option<&'a Enum> {
+        self.enums.get(type_name).copied()
+    }
+}
+
+fn type_value(
+    field_type: FieldDescriptorProto_Type,
+    type_name: &str,
+    resolver: &TypeResolver,
+    options: &Options
+) -> Value {
+    let kind = match well_known_type_to_ts_type(type_name, options) {
+        Some(_) => ValueKind::WellKnown,
+        None if resolver.enum_type(type_name).is_some() => ValueKind::Enum,
+        None => ValueKind::Message
+    };
+    Value{
+        kind,
+        field_type,
+        type_name: type_name.to_string(),
+        ts_type: type_name_to_ts_type(type_name, resolver, options).to_string()
+    }
+}
+
+fn field_value(field: &FieldDescriptorProto, resolver: &TypeResolver, options: &Options) -> Value {
+    let mut value = match field.get_field_type() {
+        FieldDescriptorProto_Type::TYPE_ENUM |
+            FieldDescriptorProto_Type::TYPE_MESSAGE |
+            FieldDescriptorProto_Type::TYPE_GROUP => type_value(field.get_field_type(), field.get_type_name(), resolver, options),
+        field_type => Value{
+            kind: ValueKind::Scalar(field_type),
+            field_type,
+            type_name: String::new(),
+            ts_type: String::new()
+        }
+    };
+    value.ts_type = field_type_to_ts_type(field, resolver, options).to_string();
+    value
+}
+
+/// Whether the values of `field`, if repeated, are written packed: proto3
+/// packs them unless `[packed = false]`, proto2 only with `[packed = true]`.
+fn is_packed(field: &FieldDescriptorProto, syntax: &str) -> bool {
+    match field.get_options().has_packed() {
+        true => field.get_options().get_packed(),
+        false => syntax == "proto3"
+    }
+}
+
+fn build_field(
+    full_name: &str,
+    field: &FieldDescriptorProto,
+    message_type: &DescriptorProto,
+    syntax: &str,
+    resolver: &TypeResolver,
+    options: &Options
+) -> Field {
+    let entry = map_entry_type(field, message_type);
+    let entry_field = |number: i32| entry?.get_field()
+        .iter()
+        .find(|entry_field| entry_field.get_number() == number)
+        .map(|entry_field| field_value(entry_field, resolver, options));
+    let (value, cardinality) = match (entry_field(1), entry_field(2)) {
+        (Some(key), Some(value)) => (value, Cardinality::Map{ key }),
+        _ => (field_value(field, resolver, options), match field.get_label() {
+            FieldDescriptorProto_Label::LABEL_REPEATED => Cardinality::Repeated,
+            _ => Cardinality::Single
+        })
+    };
+    Field{
+        name: field.get_name().to_string(),
+        key: field_key(field, options),
+        json_name: field.get_json_name().to_string(),
+        number: field.get_number(),
+        ts_type: field_to_ts_field_type(field, message_type, resolver, options)
+            .display(options.readonly)
+            .to_string(),
+        value,
+        cardinality,
+        is_optional: is_optional_field(field, options),
+        oneof: Some(field.get_oneof_index() as usize)
+            .filter(|_| field.has_oneof_index() && !is_proto3_optional(field)),
+        is_required: field.get_label() == FieldDescriptorProto_Label::LABEL_REQUIRED,
+        is_packed: is_packed(field, syntax),
+        type_option: field_ts_type_option(field),
+        field_mask: field_mask_target(field, resolver),
+        constraints: validate::field_constraints(field, message_type),
+        custom_options: extensions::custom_options(field.get_options().get_unknown_fields(), FIELD_OPTIONS, resolver),
+        doc: field_doc(full_name, field, message_type, resolver)
+    }
+}
+
+fn build_message(
+    full_name: &str,
+    message_type: &DescriptorProto,
+    syntax: &str,
+    resolver: &TypeResolver,
+    options: &Options
+) -> Message {
+    Message{
+        full_name: full_name.to_string(),
+        ts_name: resolver.resolve(full_name, options),
+        doc: ts_doc(
+            resolver.comment(full_name),
+            message_type.get_options().get_deprecated(),
+            message_type.get_options().get_unknown_fields()
+        ),
+        fields: message_type.get_field()
+            .iter()
+            .map(|field| build_field(full_name, field, message_type, syntax, resolver, options))
+            .collect(),
+        oneofs: message_type.get_oneof_decl()
+            .iter()
+            .map(|oneof| Oneof{
+                name: oneof.get_name().to_string(),
+                key: oneof_key(oneof, options),
+                doc: resolver.comment(&format!("{}.{}", full_name, oneof.get_name())).map(str::to_string)
+            })
+            .collect(),
+        is_nominal: options.nominal || bool_option(message_type.get_options().get_unknown_fields(), OPTION_NOMINAL),
+        custom_options: extensions::custom_options(
+            message_type.get_options().get_unknown_fields(),
+            MESSAGE_OPTIONS,
+            resolver
+        )
+    }
+}
+
+fn build_enum(full_name: &str, enum_type: &EnumDescriptorProto, resolver: &TypeResolver, options: &Options) -> Enum {
+    let union_aliases = match options.enum_style {
+        EnumStyle::Union => without_aliases(enum_type, enum_type.get_value())
+            .flat_map(|value| enum_aliases(enum_type, value)
+                .into_iter()
+                .map(move |alias| format!("`{}` is an alias of `{}`.", alias, value.get_name())))
+            .collect(),
+        _ => Vec::new()
+    };
+    let comment = match (resolver.comment(full_name), union_aliases.is_empty()) {
+        (comment, true) => comment.map(str::to_string),
+        (Some(comment), false) => Some(format!("{}\n\n{}", comment, union_aliases.join("\n"))),
+        (None, false) => Some(union_aliases.join("\n"))
+    };
+    Enum{
+        full_name: full_name.to_string(),
+        ts_name: resolver.resolve(full_name, options),
+        doc: ts_doc(
+            comment.as_deref(),
+            enum_type.get_options().get_deprecated(),
+            enum_type.get_options().get_unknown_fields()
+        ),
+        values: without_aliases(enum_type, enum_type.get_value().iter().zip(enum_value_names(enum_type, options)))
+            .map(|(value, ts_name)| EnumValue{
+                name: value.get_name().to_string(),
+                ts_name,
+                number: value.get_number(),
+                aliases: enum_aliases(enum_type, value).into_iter().map(str::to_string).collect(),
+                doc: enum_value_doc(
+                    resolver.comment(&format!("{}.{}", full_name, value.get_name())),
+                    &enum_aliases(enum_type, value),
+                    value
+                )
+            })
+            .collect()
+    }
+}
+
+fn build_extension(
+    full_name: String,
+    extension: &FieldDescriptorProto,
+    syntax: &str,
+    resolver: &TypeResolver,
+    options: &Options
+) -> Extension {
+    let scope = full_name.rsplit_once('.').map_or("", |(scope, _)| scope);
+    let field = build_field(scope, extension, &DescriptorProto::new(), syntax, resolver, options);
+    Extension{
+        extendee: extension.get_extendee().to_string(),
+        field: Field{
+            key: extensions::extension_key(&full_name),
+            doc: ts_doc(
+                resolver.comment(&full_name),
+                extension.get_options().get_deprecated(),
+                extension.get_options().get_unknown_fields()
+            ),
+            ..field
+        },
+        full_name
+    }
+}
+
+fn build_service(
+    proto_file: &FileDescriptorProto,
+    service: &ServiceDescriptorProto,
+    resolver: &TypeResolver,
+    options: &Options
+) -> Service {
+    let full_name = qualified_name(proto_file.get_package(), service.get_name());
+    Service{
+        name: service.get_name().to_string(),
+        doc: ts_doc(
+            resolver.comment(&full_name),
+            service.get_options().get_deprecated(),
+            service.get_options().get_unknown_fields()
+        ),
+        methods: service.get_method()
+            .iter()
+            .map(|method| Method{
+                name: method.get_name().to_string(),
+                path: method_path(proto_file, service, method),
+                input: type_value(FieldDescriptorProto_Type::TYPE_MESSAGE, method.get_input_type(), resolver, options),
+                output: type_value(FieldDescriptorProto_Type::TYPE_MESSAGE, method.get_output_type(), resolver, options),
+                client_streaming: method.get_client_streaming(),
+                server_streaming: method.get_server_streaming(),
+                idempotency: connect::method_idempotency(method),
+                http_bindings: openapi::http_bindings(method),
+                doc: ts_doc(
+                    resolver.comment(&format!("{}.{}", full_name, method.get_name())),
+                    method.get_options().get_deprecated(),
+                    method.get_options().get_unknown_fields()
+                )
+            })
+            .collect(),
+        full_name
+    }
+}
+
+/// Resolves `proto_file` against the request once, so that declarations and
+/// renderers read names, TypeScript types, presence and oneofs instead of
+/// interpreting descriptors again.
+pub(crate) fn build(proto_file: &FileDescriptorProto, resolver: &TypeResolver, options: &Options) -> File {
+    File{
+        name: proto_file.get_name().to_string(),
+        package: proto_file.get_package().to_string(),
+        messages: file_messages(proto_file)
+            .into_iter()
+            .map(|(full_name, message_type)| build_message(&full_name, message_type, proto_file.get_syntax(), resolver, options))
+            .collect(),
+        enums: file_enums(proto_file)
+            .into_iter()
+            .map(|(full_name, enum_type)| build_enum(&full_name, enum_type, resolver, options))
+            .collect(),
+        extensions: extensions::file_extensions(proto_file)
+            .into_iter()
+            .map(|(full_name, extension)| build_extension(full_name, extension, proto_file.get_syntax(), resolver, options))
+            .collect(),
+        services: proto_file.get_service()
+            .iter()
+            .map(|service| build_service(proto_file, service, resolver, options))
+            .collect()
+    }
+}
@@ -0,0 +1,514 @@
+use super::*;
+
+/// TypeScript expressions converting a single field value to and from its
+/// proto3 JSON form. `$v` stands for the value being converted.
+struct ValueCodec {
+    to_json: String,
+    from_json: String,
+    default: Option<String>,
+    is_set: String
+}
+
+impl ValueCodec {
+    fn new(to_json: &str, from_json: &str, default: Option<&str>, is_set: &str) -> ValueCodec {
+        ValueCodec{
+            to_json: to_json.to_string(),
+            from_json: from_json.to_string(),
+            default: default.map(|default| default.to_string()),
+            is_set: is_set.to_string()
+        }
+    }
+
+    fn identity(from_json: &str, default: &str) -> ValueCodec {
+        ValueCodec::new("$v", from_json, Some(default), &format!("$v !== {}", default))
+    }
+}
+
+fn is_identity(template: &str) -> bool {
+    template == "$v"
+}
+
+pub(crate) fn enum_default(type_name: &str, types: &ir::Types, options: &Options) -> String {
+    let values = match types.enum_type(type_name) {
+        Some(enum_type) => &enum_type.values,
+        None => return "undefined as never".to_string()
+    };
+    let default = values.iter().find(|value| value.number == 0).or_else(|| values.first());
+    match (default, options.enum_style) {
+        (None, _) => "undefined as never".to_string(),
+        (Some(value), EnumStyle::Union) => format!("\"{}\"", value.ts_name),
+        (Some(value), _) => value.number.to_string()
+    }
+}
+
+fn scalar_codec(field_type: FieldDescriptorProto_Type, options: &Options) -> ValueCodec {
+    match field_type {
+        FieldDescriptorProto_Type::TYPE_INT64 |
+            FieldDescriptorProto_Type::TYPE_UINT64 |
+            FieldDescriptorProto_Type::TYPE_FIXED64 |
+            FieldDescriptorProto_Type::TYPE_SFIXED64 |
+            FieldDescriptorProto_Type::TYPE_SINT64 => match options.int64_style {
+                Int64Style::String => ValueCodec::identity("String($v)", "\"0\""),
+                Int64Style::BigInt => ValueCodec::new(
+                    "$v.toString()",
+                    "BigInt($v as string | number)",
+                    Some("BigInt(0)"),
+                    "$v !== BigInt(0)"
+                ),
+                Int64Style::Number => ValueCodec::new("String($v)", "Number($v)", Some("0"), "$v !== 0")
+            },
+        FieldDescriptorProto_Type::TYPE_BOOL => ValueCodec::identity("$v === true", "false"),
+        FieldDescriptorProto_Type::TYPE_STRING => ValueCodec::identity("String($v)", "\"\""),
+        FieldDescriptorProto_Type::TYPE_BYTES => match options.bytes_style {
+            BytesStyle::Base64String => ValueCodec::identity("String($v)", "\"\""),
+            BytesStyle::Uint8Array => ValueCodec::new(
+                "bytesToBase64($v)",
+                "base64ToBytes(String($v))",
+                Some("new Uint8Array(0)"),
+                "$v.length > 0"
+            )
+        },
+        _ => ValueCodec::identity("Number($v)", "0")
+    }
+}
+
+fn well_known_codec(type_name: &str, options: &Options) -> Option<ValueCodec> {
+    let ts_type = well_known_type_to_ts_type(type_name, options)?;
+    if let Some(override_type) = options.type_overrides.get(type_name).map(|type_override| type_override.ts_type.as_str()) {
+        return Some(match (type_name, override_type) {
+            (".google.protobuf.Timestamp", "Date") => ValueCodec::new(
+                "$v.toISOString()",
+                "new Date(String($v))",
+                None,
+                "$v !== undefined"
+            ),
+            _ => ValueCodec::new("$v", &format!("$v as {}", override_type), None, "$v !== undefined")
+        });
+    }
+    Some(match ts_type {
+        TsType::Nullable(_) => {
+            let inner = scalar_codec(
+                wrapped_type(type_name).unwrap_or(FieldDescriptorProto_Type::TYPE_DOUBLE),
+                options
+            );
+            ValueCodec::new(
+                &match is_identity(&inner.to_json) {
+                    true => "$v".to_string(),
+                    false => format!("$v === null ? null : {}", inner.to_json)
+                },
+                &format!("$v === null ? null : {}", inner.from_json),
+                Some("null"),
+                "$v !== null"
+            )
+        },
+        TsType::String => ValueCodec::new("$v", "String($v)", None, "$v !== undefined"),
+        TsType::Object(ref name) if name == "null" => ValueCodec::new("null", "null", Some("null"), "false"),
+        ts_type => ValueCodec::new("$v", &format!("$v as {}", ts_type), None, "$v !== undefined")
+    })
+}
+
+fn value_codec(
+    value: &ir::Value,
+    types: &ir::Types,
+    resolver: &TypeResolver,
+    options: &Options
+) -> ValueCodec {
+    if let ir::ValueKind::Scalar(field_type) = value.kind {
+        return scalar_codec(field_type, options);
+    }
+    if let Some(codec) = well_known_codec(&value.type_name, options) {
+        return codec;
+    }
+    let prefix = resolver.function_prefix(&value.type_name);
+    match value.kind {
+        ir::ValueKind::Enum => {
+            let default = enum_default(&value.type_name, types, options);
+            ValueCodec::new(
+                &format!("{}ToJSON($v)", prefix),
+                &format!("{}FromJSON($v)", prefix),
+                Some(&default),
+                &format!("$v !== {}", default)
+            )
+        },
+        _ => ValueCodec::new(
+            &format!("{}ToJSON($v)", prefix),
+            &format!("{}FromJSON($v)", prefix),
+            None,
+            "$v !== undefined"
+        )
+    }
+}
+
+fn map_key_from_json(field_type: FieldDescriptorProto_Type, options: &Options) -> &'static str {
+    match (field_type, options.map_style) {
+        (_, MapStyle::Record) => "k",
+        (FieldDescriptorProto_Type::TYPE_BOOL, _) => "k === \"true\"",
+        (FieldDescriptorProto_Type::TYPE_STRING, _) => "k",
+        (_, _) => match scalar_to_ts_type(field_type, options) {
+            TsType::String => "k",
+            TsType::BigInt => "BigInt(k)",
+            _ => "Number(k)"
+        }
+    }
+}
+
+fn field_codec(
+    field: &ir::Field,
+    types: &ir::Types,
+    resolver: &TypeResolver,
+    options: &Options
+) -> ValueCodec {
+    let value = value_codec(&field.value, types, resolver, options);
+    match &field.cardinality {
+        ir::Cardinality::Map{ key } => {
+            let entries_from_json = format!(
+                "Object.entries($v as Record<string, unknown>).map(([k, x]) => [{}, {}] as const)",
+                map_key_from_json(key.field_type, options),
+                apply(&value.from_json, "x")
+            );
+            match options.map_style {
+                MapStyle::Record => ValueCodec::new(
+                    &match is_identity(&value.to_json) {
+                        true => "$v".to_string(),
+                        false => format!(
+                            "Object.fromEntries(Object.entries($v).map(([k, x]) => [k, {}]))",
+                            apply(&value.to_json, "x")
+                        )
+                    },
+                    &format!("Object.fromEntries({})", entries_from_json),
+                    Some("{}"),
+                    "Object.keys($v).length > 0"
+                ),
+                MapStyle::Map => ValueCodec::new(
+                    &format!(
+                        "Object.fromEntries(Array.from($v.entries()).map(([k, x]) => [String(k), {}]))",
+                        apply(&value.to_json, "x")
+                    ),
+                    &format!("new Map({})", entries_from_json),
+                    Some("new Map()"),
+                    "$v.size > 0"
+                )
+            }
+        },
+        ir::Cardinality::Repeated => ValueCodec::new(
+            &match is_identity(&value.to_json) {
+                true => "$v".to_string(),
+                false => format!("$v.map((x) => {})", apply(&value.to_json, "x"))
+            },
+            &format!(
+                "Array.isArray($v) ? $v.map((x: unknown) => {}) : []",
+                apply(&value.from_json, "x")
+            ),
+            Some("[]"),
+            "$v.length > 0"
+        ),
+        ir::Cardinality::Single => value
+    }
+}
+
+/// Default TypeScript value of `field` when it is absent, if it has one.
+pub(crate) fn field_default(
+    field: &ir::Field,
+    types: &ir::Types,
+    resolver: &TypeResolver,
+    options: &Options
+) -> Option<String> {
+    if field.is_optional {
+        return match options.optional_style {
+            OptionalStyle::Null => Some("null".to_string()),
+            OptionalStyle::Undefined | OptionalStyle::Both => None
+        };
+    }
+    match field.has_presence() {
+        true => None,
+        false => field_codec(field, types, resolver, options).default
+    }
+}
+
+/// Default TypeScript value of the key or value of a map entry before it is
+/// read. Message values are optional like singular message fields.
+pub(crate) fn entry_default(
+    value: &ir::Value,
+    types: &ir::Types,
+    resolver: &TypeResolver,
+    options: &Options
+) -> Option<String> {
+    let is_optional = !options.force_message_required &&
+        matches!(value.field_type, FieldDescriptorProto_Type::TYPE_MESSAGE | FieldDescriptorProto_Type::TYPE_GROUP) &&
+        !matches!(well_known_type_to_ts_type(&value.type_name, options), Some(TsType::Nullable(_)));
+    match (is_optional, options.optional_style) {
+        (true, OptionalStyle::Null) => Some("null".to_string()),
+        (true, OptionalStyle::Undefined | OptionalStyle::Both) => None,
+        (false, _) => value_codec(value, types, resolver, options).default
+    }
+}
+
+/// Condition under which `value` differs from the default of `field` and
+/// therefore has to be serialized. Proto2 `required` fields always are.
+pub(crate) fn field_is_set(
+    field: &ir::Field,
+    value: &str,
+    types: &ir::Types,
+    resolver: &TypeResolver,
+    options: &Options
+) -> String {
+    if field.is_optional {
+        return present_check(value, options);
+    }
+    let codec = field_codec(field, types, resolver, options);
+    match field.has_presence() || field.is_required || codec.default.is_none() {
+        true => format!("{} !== undefined", value),
+        false => apply(&codec.is_set, value)
+    }
+}
+
+/// Default TypeScript value of a scalar of `field_type`.
+pub(crate) fn scalar_default(field_type: FieldDescriptorProto_Type, options: &Options) -> String {
+    scalar_codec(field_type, options).default.unwrap_or_else(|| "undefined".to_string())
+}
+
+/// Key of `field` in serialized JSON: the `json_name` of the proto3 JSON
+/// mapping, or the proto field name when `field_name=original`.
+fn json_key<'a>(field: &'a ir::Field, options: &Options) -> &'a str {
+    match options.field_name_style {
+        FieldNameStyle::Original => &field.name,
+        FieldNameStyle::Json | FieldNameStyle::Camel => &field.json_name
+    }
+}
+
+struct JsonMessageFunctions<'a> {
+    prefix: String,
+    type_name: Option<String>,
+    message: &'a ir::Message,
+    types: &'a ir::Types<'a>,
+    resolver: &'a TypeResolver,
+    options: &'a Options
+}
+
+impl<'a> fmt::Display for JsonMessageFunctions<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ts_type = &self.message.ts_name;
+        let fields = self.message.fields
+            .iter()
+            .map(|field| (field, field_codec(field, self.types, self.resolver, self.options)))
+            .collect::<Vec<_>>();
+        let message = match fields.is_empty() {
+            true => "_message",
+            false => "message"
+        };
+        writeln!(f, "export function {}ToJSON({}: {}): unknown {{", self.prefix, message, ts_type)?;
+        writeln!(f, "  const json: Record<string, unknown> = {{}};")?;
+        for (field, codec) in fields.iter() {
+            let (condition, value) = discriminated_oneof_access("message", field, &self.message.oneofs, self.options)
+                .unwrap_or_else(|| {
+                    let value = format!("message[\"{}\"]", field.key);
+                    (field_is_set(field, &value, self.types, self.resolver, self.options), value)
+                });
+            writeln!(
+                f,
+                "  if ({}) json[\"{}\"] = {};",
+                condition,
+                json_key(field, self.options),
+                apply(&codec.to_json, &value)
+            )?;
+        }
+        writeln!(f, "  return json;")?;
+        writeln!(f, "}}")?;
+        writeln!(f)?;
+        let value = match fields.is_empty() {
+            true => "_value",
+            false => "value"
+        };
+        writeln!(f, "export function {}FromJSON({}: unknown): {} {{", self.prefix, value, ts_type)?;
+        if !fields.is_empty() {
+            writeln!(
+                f,
+                "  const json = (typeof value === \"object\" && value !== null ? value : {{}}) as Record<string, unknown>;"
+            )?;
+        }
+        writeln!(f, "  const message: Record<string, unknown> = {{}};")?;
+        if let Some(type_name) = &self.type_name {
+            writeln!(f, "  message[\"{}\"] = {};", TYPE_FIELD_KEY, type_name)?;
+        }
+        for (field, codec) in fields.iter() {
+            let local = format!("v{}", field.number);
+            writeln!(f, "  const {} = pick(json, \"{}\", \"{}\");", local, field.json_name, field.name)?;
+            match field_default(field, self.types, self.resolver, self.options) {
+                Some(default) => writeln!(
+                    f,
+                    "  message[\"{}\"] = {} == null ? {} : {};",
+                    field.key,
+                    local,
+                    default,
+                    apply(&codec.from_json, &local)
+                )?,
+                None => {
+                    let value = apply(&codec.from_json, &local);
+                    let assign = discriminated_oneof_assign("message", field, &self.message.oneofs, &value, self.options)
+                        .unwrap_or_else(|| format!("message[\"{}\"] = {}", field.key, value));
+                    writeln!(f, "  if ({} != null) {};", local, assign)?
+                }
+            }
+        }
+        writeln!(f, "  return message as unknown as {};", ts_type)?;
+        writeln!(f, "}}")
+    }
+}
+
+pub(crate) struct JsonEnumFunctions<'a> {
+    pub(crate) prefix: String,
+    pub(crate) enum_type: &'a ir::Enum,
+    pub(crate) default: String,
+    pub(crate) style: EnumStyle,
+    pub(crate) is_open: bool
+}
+
+impl<'a> fmt::Display for JsonEnumFunctions<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (ts_type, values) = (&self.enum_type.ts_name, &self.enum_type.values);
+        let is_renamed = values.iter().any(|value| value.name != value.ts_name);
+        match self.style {
+            EnumStyle::Union if !is_renamed => {
+                writeln!(f, "export function {}ToJSON(value: {}): string {{", self.prefix, ts_type)?;
+                writeln!(f, "  return value;")?;
+                writeln!(f, "}}")?;
+            },
+            EnumStyle::Union => {
+                writeln!(f, "export function {}ToJSON(value: {}): string {{", self.prefix, ts_type)?;
+                writeln!(f, "  switch (value) {{")?;
+                for value in values.iter() {
+                    writeln!(f, "    case \"{}\":", value.ts_name)?;
+                    writeln!(f, "      return \"{}\";", value.name)?;
+                }
+                writeln!(f, "    default:")?;
+                writeln!(f, "      return value;")?;
+                writeln!(f, "  }}")?;
+                writeln!(f, "}}")?;
+            },
+            EnumStyle::Enum | EnumStyle::Const => {
+                writeln!(f, "export function {}ToJSON(value: {}): string | number {{", self.prefix, ts_type)?;
+                writeln!(f, "  switch (value) {{")?;
+                for value in values.iter() {
+                    writeln!(f, "    case {}:", value.number)?;
+                    writeln!(f, "      return \"{}\";", value.name)?;
+                }
+                writeln!(f, "    default:")?;
+                writeln!(f, "      return value;")?;
+                writeln!(f, "  }}")?;
+                writeln!(f, "}}")?;
+            }
+        }
+        writeln!(f)?;
+        writeln!(f, "export function {}FromJSON(value: unknown): {} {{", self.prefix, ts_type)?;
+        writeln!(f, "  switch (value) {{")?;
+        // Parsers accept every name of a number, so aliases decode to the
+        // value they alias.
+        for value in values.iter() {
+            writeln!(f, "    case \"{}\":", value.name)?;
+            for alias in value.aliases.iter() {
+                writeln!(f, "    case \"{}\":", alias)?;
+            }
+            writeln!(f, "    case {}:", value.number)?;
+            match self.style {
+                EnumStyle::Union => writeln!(f, "      return \"{}\";", value.ts_name)?,
+                _ => writeln!(f, "      return {};", value.number)?
+            }
+        }
+        writeln!(f, "    default:")?;
+        match (self.style, self.is_open) {
+            (EnumStyle::Union, false) => writeln!(f, "      return {};", self.default)?,
+            (EnumStyle::Union, true) => writeln!(f, "      return \"{}\";", UNRECOGNIZED)?,
+            (_, false) => writeln!(
+                f,
+                "      return (typeof value === \"number\" ? value : {}) as {};",
+                self.default,
+                ts_type
+            )?,
+            (_, true) => writeln!(
+                f,
+                "      return (typeof value === \"number\" ? value : {}) as {};",
+                UNRECOGNIZED_NUMBER,
+                ts_type
+            )?
+        }
+        writeln!(f, "  }}")?;
+        writeln!(f, "}}")
+    }
+}
+
+pub(crate) const JSON_HELPERS: &str = "\
+function pick(json: Record<string, unknown>, jsonName: string, protoName: string): unknown {
+  return json[jsonName] ?? json[protoName];
+}
+";
+
+fn json_file_stem(file_name: &str, resolver: &TypeResolver, options: &Options) -> String {
+    output_file_stem(file_name, "_json", resolver, options)
+}
+
+/// Proto3 JSON conversions of the messages and enums of a file.
+pub(crate) struct Json;
+
+impl Renderer for Json {
+    fn render(
+        &self,
+        file: &ir::File,
+        types: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File> {
+        if file.messages.is_empty() && file.enums.is_empty() {
+            return vec![];
+        }
+        let stem = json_file_stem(&file.name, resolver, options);
+        let field_references = file.messages
+            .iter()
+            .flat_map(|message| message.fields.iter())
+            .flat_map(|field| field.references())
+            .collect::<Vec<_>>();
+        let type_references = file.messages
+            .iter()
+            .map(|message| message.full_name.clone())
+            .chain(file.enums.iter().map(|enum_type| enum_type.full_name.clone()))
+            .chain(field_references.iter().filter(|type_name| !is_branded_int(type_name, options)).cloned())
+            .collect::<Vec<_>>();
+        let mut content = CodeWriter::for_file(file);
+        content.push(gen_imports(&stem, type_references.iter(), resolver, options));
+        content.push(gen_function_imports(
+            &stem,
+            file,
+            &field_references,
+            "json",
+            |prefix, _| vec![format!("{}FromJSON", prefix), format!("{}ToJSON", prefix)],
+            resolver,
+            options
+        ));
+        let helpers = match options.bytes_style {
+            BytesStyle::Uint8Array => vec![JSON_HELPERS, BASE64_HELPERS],
+            BytesStyle::Base64String => vec![JSON_HELPERS]
+        };
+        content.push(runtime::gen_helpers(&stem, &helpers, options));
+        for enum_type in file.enums.iter() {
+            content.push('\n');
+            content.push(JsonEnumFunctions{
+                prefix: resolver.function_prefix(&enum_type.full_name),
+                enum_type,
+                default: enum_default(&enum_type.full_name, types, options),
+                style: options.enum_style,
+                is_open: options.open_enums
+            });
+        }
+        for message in file.messages.iter() {
+            content.push('\n');
+            content.push(JsonMessageFunctions{
+                prefix: resolver.function_prefix(&message.full_name),
+                type_name: type_field_value(&message.full_name, options),
+                message,
+                types,
+                resolver,
+                options
+            });
+        }
+        vec![gen_resp_file(format!("{}.ts", stem), content.into_string())]
+    }
+}
@@ -0,0 +1,395 @@
+use super::*;
+use super::validate::ValueConstraints;
+
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// JSON value of a schema document, printed with two-space indentation and
+/// arrays of scalars kept on one line.
+pub(crate) enum JsonNode {
+    String(String),
+    Number(i64),
+    Float(f64),
+    Bool(bool),
+    Array(Vec<JsonNode>),
+    Object(Vec<(String, JsonNode)>)
+}
+
+impl JsonNode {
+    pub(crate) fn string(value: &str) -> JsonNode {
+        JsonNode::String(value.to_string())
+    }
+
+    pub(crate) fn object(entries: Vec<(&str, JsonNode)>) -> JsonNode {
+        JsonNode::Object(entries.into_iter().map(|(key, value)| (key.to_string(), value)).collect())
+    }
+
+    pub(crate) fn strings<'a>(values: impl Iterator<Item = &'a str>) -> JsonNode {
+        JsonNode::Array(values.map(JsonNode::string).collect())
+    }
+
+    fn is_scalar(&self) -> bool {
+        !matches!(self, JsonNode::Array(_) | JsonNode::Object(_))
+    }
+
+    fn write(&self, f: &mut fmt::Formatter, indent: usize) -> fmt::Result {
+        match self {
+            JsonNode::String(value) => write!(f, "{}", json_string(value)),
+            JsonNode::Number(value) => write!(f, "{}", value),
+            JsonNode::Float(value) => write!(f, "{}", value),
+            JsonNode::Bool(value) => write!(f, "{}", value),
+            JsonNode::Array(values) if values.iter().all(JsonNode::is_scalar) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    value.write(f, indent)?;
+                }
+                write!(f, "]")
+            },
+            JsonNode::Array(values) => {
+                writeln!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    write!(f, "{:1$}", "", indent + 2)?;
+                    value.write(f, indent + 2)?;
+                    writeln!(f, "{}", if i + 1 < values.len() { "," } else { "" })?;
+                }
+                write!(f, "{:1$}]", "", indent)
+            },
+            JsonNode::Object(entries) if entries.is_empty() => write!(f, "{{}}"),
+            JsonNode::Object(entries) => {
+                writeln!(f, "{{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    write!(f, "{:1$}{2}: ", "", indent + 2, json_string(key))?;
+                    value.write(f, indent + 2)?;
+                    writeln!(f, "{}", if i + 1 < entries.len() { "," } else { "" })?;
+                }
+                write!(f, "{:1$}}}", "", indent)
+            }
+        }
+    }
+}
+
+impl fmt::Display for JsonNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write(f, 0)?;
+        writeln!(f)
+    }
+}
+
+pub(crate) fn json_string(value: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c)
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn integer_schema(minimum: i64, maximum: i64) -> JsonNode {
+    JsonNode::object(vec![
+        ("type", JsonNode::string("integer")),
+        ("minimum", JsonNode::Number(minimum)),
+        ("maximum", JsonNode::Number(maximum))
+    ])
+}
+
+/// 64-bit integers are strings in the JSON mapping, though parsers accept
+/// numbers as well.
+fn int64_schema(is_signed: bool) -> JsonNode {
+    JsonNode::object(vec![
+        ("type", JsonNode::strings(vec!["string", "integer"].into_iter())),
+        ("pattern", JsonNode::string(if is_signed { "^-?[0-9]+$" } else { "^[0-9]+$" }))
+    ])
+}
+
+fn scalar_schema(field_type: FieldDescriptorProto_Type) -> JsonNode {
+    match field_type {
+        FieldDescriptorProto_Type::TYPE_DOUBLE | FieldDescriptorProto_Type::TYPE_FLOAT => JsonNode::object(vec![
+            ("anyOf", JsonNode::Array(vec![
+                JsonNode::object(vec![("type", JsonNode::string("number"))]),
+                JsonNode::object(vec![("enum", JsonNode::strings(vec!["NaN", "Infinity", "-Infinity"].into_iter()))])
+            ]))
+        ]),
+        FieldDescriptorProto_Type::TYPE_INT32 |
+            FieldDescriptorProto_Type::TYPE_SINT32 |
+            FieldDescriptorProto_Type::TYPE_SFIXED32 => integer_schema(i32::MIN as i64, i32::MAX as i64),
+        FieldDescriptorProto_Type::TYPE_UINT32 |
+            FieldDescriptorProto_Type::TYPE_FIXED32 => integer_schema(0, u32::MAX as i64),
+        FieldDescriptorProto_Type::TYPE_INT64 |
+            FieldDescriptorProto_Type::TYPE_SINT64 |
+            FieldDescriptorProto_Type::TYPE_SFIXED64 => int64_schema(true),
+        FieldDescriptorProto_Type::TYPE_UINT64 |
+            FieldDescriptorProto_Type::TYPE_FIXED64 => int64_schema(false),
+        FieldDescriptorProto_Type::TYPE_BOOL => JsonNode::object(vec![("type", JsonNode::string("boolean"))]),
+        FieldDescriptorProto_Type::TYPE_STRING => JsonNode::object(vec![("type", JsonNode::string("string"))]),
+        FieldDescriptorProto_Type::TYPE_BYTES => JsonNode::object(vec![
+            ("type", JsonNode::string("string")),
+            ("contentEncoding", JsonNode::string("base64"))
+        ]),
+        FieldDescriptorProto_Type::TYPE_ENUM |
+            FieldDescriptorProto_Type::TYPE_MESSAGE |
+            FieldDescriptorProto_Type::TYPE_GROUP => JsonNode::object(vec![])
+    }
+}
+
+fn well_known_schema(type_name: &str) -> Option<JsonNode> {
+    if let Some(field_type) = wrapped_type(type_name) {
+        return Some(JsonNode::object(vec![
+            ("anyOf", JsonNode::Array(vec![
+                scalar_schema(field_type),
+                JsonNode::object(vec![("type", JsonNode::string("null"))])
+            ]))
+        ]));
+    }
+    Some(match type_name {
+        ".google.protobuf.Timestamp" => JsonNode::object(vec![
+            ("type", JsonNode::string("string")),
+            ("format", JsonNode::string("date-time"))
+        ]),
+        ".google.protobuf.Duration" => JsonNode::object(vec![
+            ("type", JsonNode::string("string")),
+            ("pattern", JsonNode::string("^-?[0-9]+(\\.[0-9]{1,9})?s$"))
+        ]),
+        ".google.protobuf.FieldMask" => JsonNode::object(vec![("type", JsonNode::string("string"))]),
+        ".google.protobuf.Struct" => JsonNode::object(vec![("type", JsonNode::string("object"))]),
+        ".google.protobuf.Value" => JsonNode::object(vec![]),
+        ".google.protobuf.ListValue" => JsonNode::object(vec![("type", JsonNode::string("array"))]),
+        ".google.protobuf.NullValue" => JsonNode::object(vec![("type", JsonNode::string("null"))]),
+        ".google.protobuf.Any" => JsonNode::object(vec![
+            ("type", JsonNode::string("object")),
+            ("properties", JsonNode::object(vec![("@type", JsonNode::object(vec![("type", JsonNode::string("string"))]))])),
+            ("required", JsonNode::strings(vec!["@type"].into_iter()))
+        ]),
+        _ => return None
+    })
+}
+
+/// Constraint on the object keys of a map, which are strings in JSON.
+fn map_key_schema(field_type: FieldDescriptorProto_Type) -> Option<JsonNode> {
+    match field_type {
+        FieldDescriptorProto_Type::TYPE_STRING => None,
+        FieldDescriptorProto_Type::TYPE_BOOL =>
+            Some(JsonNode::object(vec![("enum", JsonNode::strings(vec!["true", "false"].into_iter()))])),
+        FieldDescriptorProto_Type::TYPE_UINT32 |
+            FieldDescriptorProto_Type::TYPE_FIXED32 |
+            FieldDescriptorProto_Type::TYPE_UINT64 |
+            FieldDescriptorProto_Type::TYPE_FIXED64 =>
+            Some(JsonNode::object(vec![("pattern", JsonNode::string("^[0-9]+$"))])),
+        _ => Some(JsonNode::object(vec![("pattern", JsonNode::string("^-?[0-9]+$"))]))
+    }
+}
+
+/// Adds the keywords for validation rules of a value to its `schema`,
+/// replacing the range of its type.
+fn constrain(mut schema: JsonNode, constraints: &ValueConstraints) -> JsonNode {
+    if let JsonNode::Object(keywords) = &mut schema {
+        let mut push = |keyword: &str, value| match keywords.iter_mut().find(|(key, _)| key == keyword) {
+            Some(entry) => entry.1 = value,
+            None => keywords.push((keyword.to_string(), value))
+        };
+        if let Some(n) = constraints.min_len {
+            push("minLength", JsonNode::Number(n as i64));
+        }
+        if let Some(n) = constraints.max_len {
+            push("maxLength", JsonNode::Number(n as i64));
+        }
+        if let Some(pattern) = &constraints.pattern {
+            push("pattern", JsonNode::string(pattern));
+        }
+        if let Some(format) = constraints.format {
+            push("format", JsonNode::string(format));
+        }
+        if let Some(bound) = constraints.minimum {
+            push(if bound.is_exclusive { "exclusiveMinimum" } else { "minimum" }, JsonNode::Float(bound.value));
+        }
+        if let Some(bound) = constraints.maximum {
+            push(if bound.is_exclusive { "exclusiveMaximum" } else { "maximum" }, JsonNode::Float(bound.value));
+        }
+    }
+    schema
+}
+
+/// Builds the schemas of one document, collecting the messages it refers to
+/// so they can be added as definitions under `ref_prefix`. References to
+/// `root` point at the document itself.
+pub(crate) struct SchemaBuilder<'a> {
+    pub(crate) root: Option<&'a str>,
+    pub(crate) ref_prefix: &'a str,
+    pub(crate) types: &'a ir::Types<'a>,
+    pub(crate) resolver: &'a TypeResolver,
+    pub(crate) references: BTreeSet<String>
+}
+
+impl<'a> SchemaBuilder<'a> {
+    pub(crate) fn value_schema(&mut self, value: &ir::Value) -> JsonNode {
+        let type_name = value.type_name.as_str();
+        match value.field_type {
+            FieldDescriptorProto_Type::TYPE_ENUM => match (well_known_schema(type_name), self.types.enum_type(type_name)) {
+                (Some(schema), _) => schema,
+                (None, Some(enum_type)) => JsonNode::object(vec![
+                    ("type", JsonNode::string("string")),
+                    ("enum", JsonNode::strings(enum_type.values
+                        .iter()
+                        .flat_map(|value| std::iter::once(&value.name).chain(value.aliases.iter()))
+                        .map(String::as_str)
+                    ))
+                ]),
+                (None, None) => JsonNode::object(vec![("type", JsonNode::string("string"))])
+            },
+            FieldDescriptorProto_Type::TYPE_MESSAGE | FieldDescriptorProto_Type::TYPE_GROUP => {
+                if let Some(schema) = well_known_schema(type_name) {
+                    return schema;
+                }
+                if self.types.message(type_name).is_none() {
+                    return JsonNode::object(vec![("type", JsonNode::string("object"))]);
+                }
+                if self.root == Some(type_name) {
+                    return JsonNode::object(vec![("$ref", JsonNode::string("#"))]);
+                }
+                self.references.insert(type_name.to_string());
+                JsonNode::object(vec![
+                    ("$ref", JsonNode::String(format!("{}{}", self.ref_prefix, type_name.trim_start_matches('.'))))
+                ])
+            },
+            field_type => scalar_schema(field_type)
+        }
+    }
+
+    pub(crate) fn field_schema(&mut self, field: &ir::Field) -> JsonNode {
+        let constraints = field.constraints.clone().unwrap_or_default();
+        let value = constrain(self.value_schema(&field.value), &constraints.value);
+        match &field.cardinality {
+            ir::Cardinality::Map{ key } => {
+                let mut keywords = vec![("type", JsonNode::string("object"))];
+                keywords.extend(map_key_schema(key.field_type).map(|schema| ("propertyNames", schema)));
+                keywords.push(("additionalProperties", value));
+                keywords.extend(constraints.min_items.map(|n| ("minProperties", JsonNode::Number(n as i64))));
+                keywords.extend(constraints.max_items.map(|n| ("maxProperties", JsonNode::Number(n as i64))));
+                JsonNode::object(keywords)
+            },
+            ir::Cardinality::Repeated => {
+                let mut keywords = vec![
+                    ("type", JsonNode::string("array")),
+                    ("items", value)
+                ];
+                keywords.extend(constraints.min_items.map(|n| ("minItems", JsonNode::Number(n as i64))));
+                keywords.extend(constraints.max_items.map(|n| ("maxItems", JsonNode::Number(n as i64))));
+                if constraints.unique_items {
+                    keywords.push(("uniqueItems", JsonNode::Bool(true)));
+                }
+                JsonNode::object(keywords)
+            },
+            ir::Cardinality::Single => value
+        }
+    }
+
+    /// Keywords describing `message` as a JSON object keyed by the JSON
+    /// names of its fields.
+    pub(crate) fn message_schema(&mut self, message: &ir::Message) -> Vec<(String, JsonNode)> {
+        let mut entries = vec![("type".to_string(), JsonNode::string("object"))];
+        if let Some(comment) = self.resolver.comment(&message.full_name) {
+            entries.push(("description".to_string(), JsonNode::string(comment)));
+        }
+        let properties = message.fields
+            .iter()
+            .map(|field| {
+                let mut schema = self.field_schema(field);
+                let comment = self.resolver.comment(&format!("{}.{}", message.full_name, field.name));
+                if let (JsonNode::Object(keywords), Some(comment)) = (&mut schema, comment) {
+                    keywords.push(("description".to_string(), JsonNode::string(comment)));
+                }
+                (field.json_name.clone(), schema)
+            })
+            .collect::<Vec<_>>();
+        entries.push(("properties".to_string(), JsonNode::Object(properties)));
+        let required = message.fields
+            .iter()
+            .filter(|field| field.is_required || field.constraints.as_ref().is_some_and(|constraints| constraints.required))
+            .map(|field| field.json_name.as_str())
+            .collect::<Vec<_>>();
+        if !required.is_empty() {
+            entries.push(("required".to_string(), JsonNode::strings(required.into_iter())));
+        }
+        entries.push(("additionalProperties".to_string(), JsonNode::Bool(false)));
+        entries
+    }
+
+    /// Schemas of the referenced messages, including those only referred to
+    /// by other definitions, keyed by their full names.
+    pub(crate) fn definitions(&mut self) -> Vec<(String, JsonNode)> {
+        let mut definitions = BTreeMap::<String, JsonNode>::new();
+        while let Some(type_name) = self.references.iter().find(|type_name| !definitions.contains_key(*type_name)).cloned() {
+            let schema = match self.types.message(&type_name) {
+                Some(referenced) => JsonNode::Object(self.message_schema(referenced)),
+                None => JsonNode::object(vec![])
+            };
+            definitions.insert(type_name, schema);
+        }
+        definitions.into_iter()
+            .map(|(type_name, schema)| (type_name.trim_start_matches('.').to_string(), schema))
+            .collect()
+    }
+}
+
+/// A self-contained draft 2020-12 document for `message`, with the messages
+/// it refers to transitively under `$defs`.
+fn message_document(file_name: &str, message: &ir::Message, types: &ir::Types, resolver: &TypeResolver) -> JsonNode {
+    let mut builder = SchemaBuilder{
+        root: Some(&message.full_name),
+        ref_prefix: "#/$defs/",
+        types,
+        resolver,
+        references: BTreeSet::new()
+    };
+    let mut entries = vec![
+        ("$schema".to_string(), JsonNode::string(JSON_SCHEMA_DIALECT)),
+        ("$id".to_string(), JsonNode::string(file_name)),
+        ("title".to_string(), JsonNode::string(message.full_name.trim_start_matches('.')))
+    ];
+    entries.extend(builder.message_schema(message));
+    let definitions = builder.definitions();
+    if !definitions.is_empty() {
+        entries.push(("$defs".to_string(), JsonNode::Object(definitions)));
+    }
+    JsonNode::Object(entries)
+}
+
+fn json_schema_file_name(full_name: &str, file_name: &str, resolver: &TypeResolver, options: &Options) -> String {
+    let path = resolver.output_path(file_name, options);
+    let name = format!("{}.schema.json", full_name.trim_start_matches('.'));
+    match path.rsplit_once('/') {
+        Some((dir, _)) => format!("{}/{}", dir, name),
+        None => name
+    }
+}
+
+/// A JSON Schema document per message, with `target=jsonschema`.
+pub(crate) struct JsonSchema;
+
+impl Renderer for JsonSchema {
+    fn render(
+        &self,
+        file: &ir::File,
+        types: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File> {
+        file.messages
+            .iter()
+            .map(|message| {
+                let name = json_schema_file_name(&message.full_name, &file.name, resolver, options);
+                let document = message_document(&name, message, types, resolver);
+                gen_resp_file(name, format!("{}", document))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,4347 @@
+extern crate protobuf;
+
+mod binary;
+mod collisions;
+mod compat;
+mod config;
+mod connect;
+mod descriptors;
+mod editions;
+mod extensions;
+mod enums;
+mod factories;
+mod fetch;
+mod fixtures;
+mod guards;
+mod helpers;
+mod hooks;
+pub mod ir;
+mod json;
+mod jsonschema;
+mod log;
+mod masks;
+mod metadata;
+mod msw;
+mod openapi;
+mod parallel;
+mod registry;
+mod runtime;
+mod strict;
+mod template;
+mod validate;
+mod writer;
+mod zod;
+
+use protobuf::plugin::*;
+use protobuf::descriptor::*;
+use protobuf::error::ProtobufError;
+use protobuf::Message;
+use std::fmt;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use writer::CodeWriter;
+
+#[derive(Debug)]
+pub enum Error {
+    Parameter(String),
+    Descriptor {
+        file: String,
+        name: String,
+        reason: String
+    },
+    Decode(ProtobufError),
+    Encode(ProtobufError),
+    Usage(String),
+    Io {
+        path: String,
+        error: std::io::Error
+    },
+    DescriptorSet(ProtobufError),
+    Unsupported(Vec<String>),
+    Diagnostics(Vec<String>),
+    Collision(Vec<String>),
+    NameCollision(Vec<String>),
+    Config {
+        path: String,
+        reason: String
+    },
+    Template {
+        path: String,
+        reason: String
+    }
+}
+
+impl Error {
+    fn descriptor(name: &str, reason: String) -> Error {
+        Error::Descriptor{
+            file: String::new(),
+            name: name.trim_start_matches('.').to_string(),
+            reason
+        }
+    }
+
+    fn in_file(self, file_name: &str) -> Error {
+        match self {
+            Error::Descriptor{ name, reason, .. } => Error::Descriptor{
+                file: file_name.to_string(),
+                name,
+                reason
+            },
+            error => error
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parameter(reason) => write!(f, "invalid parameter: {}", reason),
+            Error::Descriptor{ file, name, reason } => write!(f, "{}: {}: {}", file, name, reason),
+            Error::Decode(error) => write!(f, "failed to decode CodeGeneratorRequest: {}", error),
+            Error::Encode(error) => write!(f, "failed to write CodeGeneratorResponse: {}", error),
+            Error::Usage(reason) => write!(f, "{}", reason),
+            Error::Io{ path, error } => write!(f, "{}: {}", path, error),
+            Error::DescriptorSet(error) => write!(f, "failed to decode FileDescriptorSet: {}", error),
+            Error::Unsupported(problems) => write!(f, "unsupported constructs:\n{}", problems.join("\n")),
+            Error::Diagnostics(diagnostics) => write!(f, "{}", diagnostics.join("\n")),
+            Error::Collision(collisions) => write!(f, "output file collisions:\n{}", collisions.join("\n")),
+            Error::NameCollision(collisions) => write!(f, "name collisions:\n{}", collisions.join("\n")),
+            Error::Config{ path, reason } => write!(f, "{}: {}", path, reason),
+            Error::Template{ path, reason } => write!(f, "{}: {}", path, reason)
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Clone, Copy)]
+enum EnumStyle {
+    Union,
+    Enum,
+    Const
+}
+
+#[derive(Clone, Copy)]
+enum NestedStyle {
+    Flat,
+    Namespace
+}
+
+#[derive(Clone, Copy)]
+enum MapStyle {
+    Record,
+    Map
+}
+
+#[derive(Clone, Copy)]
+enum Int64Style {
+    String,
+    BigInt,
+    Number
+}
+
+#[derive(Clone, Copy)]
+enum BytesStyle {
+    Base64String,
+    Uint8Array
+}
+
+#[derive(Clone, Copy)]
+enum OutputMode {
+    PerFile,
+    PerMessage,
+    SingleFile
+}
+
+#[derive(Clone, Copy)]
+enum ModuleStyle {
+    Esm,
+    Global,
+    Module
+}
+
+#[derive(Clone, Copy)]
+enum ImportExtension {
+    None,
+    Js,
+    /// The extension of the file imported, for `allowImportingTsExtensions`.
+    Ts
+}
+
+/// The TypeScript version generated code has to compile with.
+#[derive(Clone, Copy)]
+enum TsSyntax {
+    /// TypeScript 3.8 and later, with `import type` and `export type { .. }`.
+    Modern,
+    /// TypeScript before 3.8, which has no type-only imports and exports.
+    Legacy
+}
+
+impl TsSyntax {
+    /// The modifier of imports and exports of types only. Plain imports of
+    /// types are elided all the same, but not under `verbatimModuleSyntax`.
+    fn type_only(self) -> &'static str {
+        match self {
+            TsSyntax::Modern => "type ",
+            TsSyntax::Legacy => ""
+        }
+    }
+
+    /// Imports of the `values` and `types` of `module`. Types are imported by
+    /// a statement of their own, as inline `type` specifiers need TypeScript
+    /// 4.5.
+    fn imports(self, module: &str, values: &[&str], types: &[&str]) -> String {
+        match self {
+            TsSyntax::Modern => format!(
+                "import {{ {} }} from \"{module}\";\nimport type {{ {} }} from \"{module}\";\n",
+                values.join(", "),
+                types.join(", "),
+                module = module
+            ),
+            TsSyntax::Legacy => format!("import {{ {} }} from \"{}\";\n", [values, types].concat().join(", "), module)
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ClientStyle {
+    GrpcWeb,
+    GrpcJs,
+    Fetch,
+    Connect
+}
+
+#[derive(Clone, Copy)]
+enum FieldNameStyle {
+    Json,
+    Original,
+    Camel
+}
+
+#[derive(Clone, Copy)]
+enum DeclarationStyle {
+    Type,
+    /// Interfaces, except for messages with `oneof=properties` oneofs: an
+    /// interface cannot extend their exclusive unions, so those messages
+    /// stay type aliases.
+    Interface
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum OptionalStyle {
+    Undefined,
+    Null,
+    Both
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum EmptyStyle {
+    Record,
+    Object,
+    Branded
+}
+
+#[derive(Clone, Copy)]
+enum OneofStyle {
+    Properties,
+    Discriminated
+}
+
+#[derive(Clone, Copy)]
+enum PathStyle {
+    SourceRelative,
+    Package
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum EnumValueCase {
+    Screaming,
+    Camel,
+    Pascal,
+    Lower
+}
+
+#[derive(Clone, Copy)]
+enum FileCase {
+    Preserve,
+    Snake,
+    Kebab,
+    Pascal
+}
+
+#[derive(Clone, Copy)]
+enum SchemaStyle {
+    Zod
+}
+
+#[derive(Clone, Copy)]
+enum HookStyle {
+    ReactQuery
+}
+
+#[derive(Clone, Copy)]
+enum MockStyle {
+    Msw
+}
+
+#[derive(Clone, Copy)]
+enum Target {
+    TypeScript,
+    JsonSchema,
+    OpenApi
+}
+
+/// How strict mode reports what it rejects: under a summary line, or as
+/// bare `file.proto:line:col: message` lines for editors and CI annotations.
+#[derive(Clone, Copy, PartialEq)]
+enum ErrorFormat {
+    Text,
+    Machine
+}
+
+/// Where runtime files get their helpers from instead of declaring them:
+/// a `runtime.ts` generated along with them, or a package.
+enum RuntimeModule {
+    Shared,
+    Package(String)
+}
+
+#[derive(Clone, Copy)]
+enum CollisionStyle {
+    Error,
+    Rename
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum PartialStyle {
+    Patch,
+    Exact
+}
+
+#[derive(Clone, Copy)]
+enum CompatStyle {
+    GrpcWebAsObject
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum MetadataStyle {
+    Fields,
+    Descriptors
+}
+
+/// TypeScript type replacing a proto type or scalar, imported from `module`
+/// when given.
+struct TypeOverride {
+    ts_type: String,
+    module: Option<String>
+}
+
+impl TypeOverride {
+    /// Parses `Type` or `Type from "module"`.
+    fn parse(value: &str) -> TypeOverride {
+        match value.split_once(" from ") {
+            Some((ts_type, module)) => TypeOverride{
+                ts_type: ts_type.trim().to_string(),
+                module: Some(module.trim().trim_matches(|c| c == '"' || c == '\'').to_string())
+            },
+            None => TypeOverride{ ts_type: value.trim().to_string(), module: None }
+        }
+    }
+
+    /// The identifier to import, i.e. `Long` of `Long.Long` or `Money<T>`.
+    fn import_name(&self) -> String {
+        self.ts_type.chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == '$')
+            .collect()
+    }
+}
+
+const SCALAR_NAMES: &[&str] = &[
+    "double", "float", "int32", "int64", "uint32", "uint64", "sint32", "sint64",
+    "fixed32", "fixed64", "sfixed32", "sfixed64", "bool", "string", "bytes"
+];
+
+/// Keys of the plugin parameter and the values each accepts, as printed by
+/// `--version`.
+pub const PARAMETERS: &[(&str, &str)] = &[
+    ("target", "typescript|jsonschema|openapi"),
+    ("enum", "union|enum|const"),
+    ("enum_strip_prefix", "true|false"),
+    ("enum_value_case", "screaming|camel|pascal|lower"),
+    ("open_enums", "true|false"),
+    ("nested", "flat|namespace"),
+    ("map", "record|map"),
+    ("int64", "string|bigint|number"),
+    ("bytes", "base64string|uint8array"),
+    ("output_mode", "per_file|per_message|single_file"),
+    ("module", "esm|global|module"),
+    ("namespaces", "true|false"),
+    ("ts_syntax", "modern|legacy"),
+    ("import_extension", "none|js|ts"),
+    ("declaration", "type|interface"),
+    ("field_name", "json|original|camel"),
+    ("oneof", "properties|discriminated"),
+    ("oneof_allow_unset", "true|false"),
+    ("compat", "none|grpc-web-asobject|ts-proto"),
+    ("force_message_required", "true|false"),
+    ("optional_style", "undefined|null|both"),
+    ("empty", "record|object|branded"),
+    ("nominal", "true|false"),
+    ("type_field", "true|false"),
+    ("readonly", "true|false"),
+    ("mutable_types", "true|false"),
+    ("partials", "false|true|exact"),
+    ("verbose", "true|false"),
+    ("strict", "true|false"),
+    ("error_format", "text|machine"),
+    ("collisions", "error|rename"),
+    ("index", "true|false"),
+    ("banner", "none|<text>"),
+    ("paths", "source_relative|package"),
+    ("file_case", "preserve|snake|kebab|pascal"),
+    ("client", "none|grpc-web|grpc-js|fetch|connect"),
+    ("runtime", "json|binary"),
+    ("runtime_module", "inline|shared|<module>"),
+    ("schema", "none|zod"),
+    ("hooks", "none|react-query"),
+    ("mocks", "none|msw"),
+    ("guards", "true|false"),
+    ("factories", "true|false"),
+    ("fixtures", "true|false"),
+    ("enum_functions", "true|false"),
+    ("metadata", "none|fields|descriptors"),
+    ("field_paths", "true|false"),
+    ("field_masks", "true|false"),
+    ("service_paths", "true|false"),
+    ("handlers", "true|false"),
+    ("branded_ints", "true|false"),
+    ("list_only", "true|false"),
+    ("source_info", "true|false"),
+    ("any_registry", "true|false"),
+    ("jobs", "auto|<count>"),
+    ("log", "off|warn|info|debug"),
+    ("exclude", "<glob>"),
+    ("config", "<file>"),
+    ("file_suffix", "<suffix>.ts"),
+    ("alias_prefix", "<prefix>"),
+    ("field_path_depth", "<depth>"),
+    ("type_override", "<type>:<ts type>"),
+    ("import_map", "<glob>:<module>"),
+    ("helpers", "none|equality|clone|match"),
+    ("template", "<file>"),
+    ("dump_request", "<file>"),
+    ("wkt.<Name>", "<ts type>")
+];
+
+/// Generator options, parsed from the comma-separated `key=value` plugin
+/// parameter.
+pub struct Options {
+    target: Target,
+    enum_style: EnumStyle,
+    enum_strip_prefix: bool,
+    enum_value_case: EnumValueCase,
+    open_enums: bool,
+    nested_style: NestedStyle,
+    map_style: MapStyle,
+    int64_style: Int64Style,
+    bytes_style: BytesStyle,
+    output_mode: OutputMode,
+    module_style: ModuleStyle,
+    /// Wraps the types of each proto package in a namespace of that name.
+    namespaces: bool,
+    ts_syntax: TsSyntax,
+    import_extension: ImportExtension,
+    /// Replaces relative specifiers of generated files, as a tsconfig
+    /// `paths` alias of the output directory.
+    alias_prefix: Option<String>,
+    declaration_style: DeclarationStyle,
+    field_name_style: FieldNameStyle,
+    oneof_style: OneofStyle,
+    oneof_allow_unset: bool,
+    compat_style: Option<CompatStyle>,
+    force_message_required: bool,
+    optional_style: OptionalStyle,
+    empty_style: EmptyStyle,
+    nominal: bool,
+    type_field: bool,
+    readonly: bool,
+    mutable_types: bool,
+    partial_style: Option<PartialStyle>,
+    log_level: log::LogLevel,
+    strict: bool,
+    error_format: ErrorFormat,
+    collisions: CollisionStyle,
+    excludes: Vec<String>,
+    index: bool,
+    banner: Option<String>,
+    path_style: PathStyle,
+    file_suffix: String,
+    file_case: FileCase,
+    client_style: Option<ClientStyle>,
+    runtime_json: bool,
+    runtime_binary: bool,
+    schema_style: Option<SchemaStyle>,
+    hook_style: Option<HookStyle>,
+    mock_style: Option<MockStyle>,
+    guards: bool,
+    factories: bool,
+    fixtures: bool,
+    enum_functions: bool,
+    metadata_style: Option<MetadataStyle>,
+    field_paths: bool,
+    field_path_depth: usize,
+    field_masks: bool,
+    type_overrides: HashMap<String, TypeOverride>,
+    /// Globs of proto files generated elsewhere, with the module their types
+    /// are imported from.
+    import_maps: Vec<(String, String)>,
+    /// Paths of the Handlebars templates rendered for each proto file.
+    templates: Vec<String>,
+    /// Threads generating proto files, or 0 for one per CPU.
+    jobs: usize,
+    any_registry: bool,
+    source_info: bool,
+    equality_helpers: bool,
+    clone_helpers: bool,
+    match_helpers: bool,
+    service_paths: bool,
+    handlers: bool,
+    runtime_module: Option<RuntimeModule>,
+    branded_ints: bool,
+    list_only: bool,
+    dump_request: Option<String>
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options::parse("").unwrap_or_else(|_| unreachable!())
+    }
+}
+
+impl Options {
+    pub fn parse(parameter: &str) -> Result<Options> {
+        let mut options = Options{
+            target: Target::TypeScript,
+            enum_style: EnumStyle::Union,
+            enum_strip_prefix: false,
+            enum_value_case: EnumValueCase::Screaming,
+            open_enums: false,
+            nested_style: NestedStyle::Flat,
+            map_style: MapStyle::Record,
+            int64_style: Int64Style::String,
+            bytes_style: BytesStyle::Base64String,
+            output_mode: OutputMode::PerFile,
+            module_style: ModuleStyle::Esm,
+            namespaces: false,
+            ts_syntax: TsSyntax::Modern,
+            import_extension: ImportExtension::None,
+            alias_prefix: None,
+            declaration_style: DeclarationStyle::Type,
+            field_name_style: FieldNameStyle::Json,
+            oneof_style: OneofStyle::Properties,
+            oneof_allow_unset: false,
+            compat_style: None,
+            force_message_required: false,
+            optional_style: OptionalStyle::Undefined,
+            empty_style: EmptyStyle::Record,
+            nominal: false,
+            type_field: false,
+            readonly: true,
+            mutable_types: false,
+            partial_style: None,
+            log_level: log::LogLevel::Off,
+            strict: false,
+            error_format: ErrorFormat::Text,
+            collisions: CollisionStyle::Error,
+            excludes: Vec::new(),
+            index: false,
+            banner: Some(format!("Code generated by {} {}. DO NOT EDIT.", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))),
+            path_style: PathStyle::SourceRelative,
+            file_suffix: "_pb.d.ts".to_string(),
+            file_case: FileCase::Preserve,
+            client_style: None,
+            runtime_json: false,
+            runtime_binary: false,
+            schema_style: None,
+            hook_style: None,
+            mock_style: None,
+            guards: false,
+            factories: false,
+            fixtures: false,
+            enum_functions: false,
+            metadata_style: None,
+            field_paths: false,
+            field_path_depth: 3,
+            field_masks: false,
+            type_overrides: HashMap::new(),
+            import_maps: Vec::new(),
+            templates: Vec::new(),
+            jobs: 0,
+            any_registry: false,
+            source_info: false,
+            equality_helpers: false,
+            clone_helpers: false,
+            match_helpers: false,
+            service_paths: false,
+            handlers: false,
+            runtime_module: None,
+            branded_ints: false,
+            list_only: false,
+            dump_request: None
+        };
+        // A token without `=` continues the list value of the parameter
+        // before it, as in `helpers=equality,clone`.
+        let mut parameters = Vec::<(String, String)>::new();
+        for param in parameter.split(',').filter(|param| !param.is_empty()) {
+            match (param.split_once('='), parameters.last_mut()) {
+                (Some((key, value)), _) => parameters.push((key.trim().to_string(), value.trim().to_string())),
+                (None, Some((_, value))) => {
+                    value.push(',');
+                    value.push_str(param.trim());
+                },
+                (None, None) => parameters.push((param.trim().to_string(), String::new()))
+            }
+        }
+        // The last value given for each option, parameter or configuration.
+        let mut given = HashMap::<String, String>::new();
+        for (_, path) in parameters.iter().filter(|(key, _)| key == "config") {
+            for (key, value) in config::read_config(path)? {
+                given.insert(key.clone(), value.clone());
+                let result = match key.as_str() {
+                    "config" => Err(Error::Parameter(format!("{}={}", key, value))),
+                    _ => options.set(&key, &value)
+                };
+                result.map_err(|error| match error {
+                    Error::Parameter(reason) => Error::Config{ path: path.clone(), reason: format!("invalid option {}", reason) },
+                    error => error
+                })?;
+            }
+        }
+        for (key, value) in parameters.iter() {
+            options.set(key, value)?;
+            given.insert(key.clone(), value.clone());
+        }
+        // Namespaces are global, so every type goes into one global file.
+        if options.namespaces {
+            for (key, value) in [("output_mode", "single_file"), ("module", "global")] {
+                if let Some(given) = given.get(key).filter(|given| *given != value) {
+                    return Err(Error::Parameter(format!("{}={} conflicts with namespaces=true", key, given)));
+                }
+            }
+            options.module_style = ModuleStyle::Global;
+            options.output_mode = OutputMode::SingleFile;
+        }
+        Ok(options)
+    }
+
+    /// Sets the option `key`. Parameters and configuration files set the
+    /// same options, parameters after the configuration files they name.
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match (key, value) {
+            ("target", "typescript") => self.target = Target::TypeScript,
+            ("target", "jsonschema") => self.target = Target::JsonSchema,
+            ("target", "openapi") => self.target = Target::OpenApi,
+            ("enum", "union") => self.enum_style = EnumStyle::Union,
+            ("enum", "enum") => self.enum_style = EnumStyle::Enum,
+            ("enum", "const") => self.enum_style = EnumStyle::Const,
+            ("enum_strip_prefix", "true") => self.enum_strip_prefix = true,
+            ("enum_strip_prefix", "false") => self.enum_strip_prefix = false,
+            ("enum_value_case", "screaming") => self.enum_value_case = EnumValueCase::Screaming,
+            ("enum_value_case", "camel") => self.enum_value_case = EnumValueCase::Camel,
+            ("enum_value_case", "pascal") => self.enum_value_case = EnumValueCase::Pascal,
+            ("enum_value_case", "lower") => self.enum_value_case = EnumValueCase::Lower,
+            ("open_enums", "true") => self.open_enums = true,
+            ("open_enums", "false") => self.open_enums = false,
+            ("nested", "flat") => self.nested_style = NestedStyle::Flat,
+            ("nested", "namespace") => self.nested_style = NestedStyle::Namespace,
+            ("map", "record") => self.map_style = MapStyle::Record,
+            ("map", "map") => self.map_style = MapStyle::Map,
+            ("int64", "string") => self.int64_style = Int64Style::String,
+            ("int64", "bigint") => self.int64_style = Int64Style::BigInt,
+            ("int64", "number") => self.int64_style = Int64Style::Number,
+            ("bytes", "base64string") => self.bytes_style = BytesStyle::Base64String,
+            ("bytes", "uint8array") => self.bytes_style = BytesStyle::Uint8Array,
+            ("output_mode", "per_file") => self.output_mode = OutputMode::PerFile,
+            ("output_mode", "per_message") => self.output_mode = OutputMode::PerMessage,
+            ("output_mode", "single_file") => self.output_mode = OutputMode::SingleFile,
+            ("module", "esm") => self.module_style = ModuleStyle::Esm,
+            ("module", "global") => self.module_style = ModuleStyle::Global,
+            ("module", "module") => self.module_style = ModuleStyle::Module,
+            ("namespaces", "true") => self.namespaces = true,
+            ("namespaces", "false") => self.namespaces = false,
+            ("ts_syntax", "modern") => self.ts_syntax = TsSyntax::Modern,
+            ("ts_syntax", "legacy") => self.ts_syntax = TsSyntax::Legacy,
+            ("import_extension", "none") => self.import_extension = ImportExtension::None,
+            ("import_extension", "js") => self.import_extension = ImportExtension::Js,
+            ("import_extension", "ts") => self.import_extension = ImportExtension::Ts,
+            ("declaration", "type") => self.declaration_style = DeclarationStyle::Type,
+            ("declaration", "interface") => self.declaration_style = DeclarationStyle::Interface,
+            ("field_name", "json") => self.field_name_style = FieldNameStyle::Json,
+            ("field_name", "original") => self.field_name_style = FieldNameStyle::Original,
+            ("field_name", "camel") => self.field_name_style = FieldNameStyle::Camel,
+            ("oneof", "properties") => self.oneof_style = OneofStyle::Properties,
+            ("oneof", "discriminated") => self.oneof_style = OneofStyle::Discriminated,
+            ("oneof_allow_unset", "true") => self.oneof_allow_unset = true,
+            ("oneof_allow_unset", "false") => self.oneof_allow_unset = false,
+            ("compat", "none") => self.compat_style = None,
+            // The styles `toObject()` produces, which later options may
+            // still override.
+            ("compat", "grpc-web-asobject") => {
+                self.compat_style = Some(CompatStyle::GrpcWebAsObject);
+                self.enum_style = EnumStyle::Enum;
+                self.int64_style = Int64Style::Number;
+                self.bytes_style = BytesStyle::Base64String;
+                self.oneof_style = OneofStyle::Properties;
+                self.optional_style = OptionalStyle::Undefined;
+            },
+            // ts-proto's default shapes are all expressible through other
+            // options, so this only presets them.
+            ("compat", "ts-proto") => {
+                self.compat_style = None;
+                self.field_name_style = FieldNameStyle::Camel;
+                self.oneof_style = OneofStyle::Discriminated;
+                self.enum_style = EnumStyle::Enum;
+                self.int64_style = Int64Style::String;
+                self.bytes_style = BytesStyle::Uint8Array;
+                self.optional_style = OptionalStyle::Undefined;
+                self.readonly = false;
+                let wrappers = [
+                    ("DoubleValue", "number"), ("FloatValue", "number"), ("Int64Value", "string"),
+                    ("UInt64Value", "string"), ("Int32Value", "number"), ("UInt32Value", "number"),
+                    ("BoolValue", "boolean"), ("StringValue", "string"), ("BytesValue", "Uint8Array")
+                ];
+                for (name, ts_type) in std::iter::once(("Timestamp", "Date")).chain(wrappers) {
+                    self.type_overrides
+                        .entry(format!(".google.protobuf.{}", name))
+                        .or_insert_with(|| TypeOverride::parse(ts_type));
+                }
+            },
+            ("force_message_required", "true") => self.force_message_required = true,
+            ("force_message_required", "false") => self.force_message_required = false,
+            ("optional_style", "undefined") => self.optional_style = OptionalStyle::Undefined,
+            ("optional_style", "null") => self.optional_style = OptionalStyle::Null,
+            ("optional_style", "both") => self.optional_style = OptionalStyle::Both,
+            ("empty", "record") => self.empty_style = EmptyStyle::Record,
+            ("empty", "object") => self.empty_style = EmptyStyle::Object,
+            ("empty", "branded") => self.empty_style = EmptyStyle::Branded,
+            ("nominal", "true") => self.nominal = true,
+            ("nominal", "false") => self.nominal = false,
+            ("type_field", "true") => self.type_field = true,
+            ("type_field", "false") => self.type_field = false,
+            ("readonly", "true") => self.readonly = true,
+            ("readonly", "false") => self.readonly = false,
+            ("mutable_types", "true") => self.mutable_types = true,
+            ("mutable_types", "false") => self.mutable_types = false,
+            ("partials", "false") => self.partial_style = None,
+            ("partials", "true") => self.partial_style = Some(PartialStyle::Patch),
+            ("partials", "exact") => self.partial_style = Some(PartialStyle::Exact),
+            ("verbose", "true") => self.log_level = log::LogLevel::Info,
+            ("verbose", "false") => self.log_level = log::LogLevel::Off,
+            ("log", value) => match log::LogLevel::parse(value) {
+                Some(level) => self.log_level = level,
+                None => return Err(Error::Parameter(format!("log={}", value)))
+            },
+            ("strict", "true") => self.strict = true,
+            ("strict", "false") => self.strict = false,
+            ("error_format", "text") => self.error_format = ErrorFormat::Text,
+            ("error_format", "machine") => self.error_format = ErrorFormat::Machine,
+            ("exclude", value) if !value.is_empty() => self.excludes.push(value.to_string()),
+            ("config", value) if !value.is_empty() => {},
+            ("collisions", "error") => self.collisions = CollisionStyle::Error,
+            ("collisions", "rename") => self.collisions = CollisionStyle::Rename,
+            ("index", "true") => self.index = true,
+            ("index", "false") => self.index = false,
+            ("banner", "none") => self.banner = None,
+            ("banner", value) if !value.is_empty() => self.banner = Some(value.to_string()),
+            ("paths", "source_relative") => self.path_style = PathStyle::SourceRelative,
+            ("paths", "package") => self.path_style = PathStyle::Package,
+            ("file_suffix", value) if value.ends_with(".ts") => self.file_suffix = value.to_string(),
+            ("file_case", "preserve") => self.file_case = FileCase::Preserve,
+            ("file_case", "snake") => self.file_case = FileCase::Snake,
+            ("file_case", "kebab") => self.file_case = FileCase::Kebab,
+            ("file_case", "pascal") => self.file_case = FileCase::Pascal,
+            ("client", "none") => self.client_style = None,
+            ("client", "grpc-web") => self.client_style = Some(ClientStyle::GrpcWeb),
+            ("client", "grpc-js") => self.client_style = Some(ClientStyle::GrpcJs),
+            ("client", "fetch") => self.client_style = Some(ClientStyle::Fetch),
+            ("client", "connect") => self.client_style = Some(ClientStyle::Connect),
+            ("runtime", "json") => self.runtime_json = true,
+            ("runtime", "binary") => self.runtime_binary = true,
+            ("runtime_module", "inline") => self.runtime_module = None,
+            ("runtime_module", "shared") => self.runtime_module = Some(RuntimeModule::Shared),
+            ("runtime_module", value) if !value.is_empty() => {
+                self.runtime_module = Some(RuntimeModule::Package(value.to_string()));
+            },
+            ("schema", "none") => self.schema_style = None,
+            ("schema", "zod") => self.schema_style = Some(SchemaStyle::Zod),
+            ("hooks", "none") => self.hook_style = None,
+            ("hooks", "react-query") => self.hook_style = Some(HookStyle::ReactQuery),
+            ("mocks", "none") => self.mock_style = None,
+            ("mocks", "msw") => self.mock_style = Some(MockStyle::Msw),
+            ("guards", "true") => self.guards = true,
+            ("guards", "false") => self.guards = false,
+            ("factories", "true") => self.factories = true,
+            ("factories", "false") => self.factories = false,
+            ("fixtures", "true") => self.fixtures = true,
+            ("fixtures", "false") => self.fixtures = false,
+            ("enum_functions", "true") => self.enum_functions = true,
+            ("enum_functions", "false") => self.enum_functions = false,
+            ("metadata", "none") => self.metadata_style = None,
+            ("metadata", "fields") => self.metadata_style = Some(MetadataStyle::Fields),
+            ("metadata", "descriptors") => self.metadata_style = Some(MetadataStyle::Descriptors),
+            ("field_paths", "true") => self.field_paths = true,
+            ("field_paths", "false") => self.field_paths = false,
+            ("field_masks", "true") => self.field_masks = true,
+            ("field_masks", "false") => self.field_masks = false,
+            ("field_path_depth", value) => match value.parse::<usize>() {
+                Ok(depth) if depth > 0 => self.field_path_depth = depth,
+                _ => return Err(Error::Parameter(format!("field_path_depth={}", value)))
+            },
+            ("type_override", value) => match value.split_once(':') {
+                Some((name, ts_type)) if (name.starts_with('.') || SCALAR_NAMES.contains(&name)) &&
+                    !ts_type.trim().is_empty() => {
+                    self.type_overrides.insert(name.to_string(), TypeOverride::parse(ts_type));
+                },
+                _ => return Err(Error::Parameter(format!("type_override={}", value)))
+            },
+            ("import_map", value) => match value.split_once(':') {
+                Some((pattern, module)) if !pattern.trim().is_empty() && !module.trim().is_empty() => {
+                    self.import_maps.push((pattern.trim().to_string(), module.trim().to_string()));
+                },
+                _ => return Err(Error::Parameter(format!("import_map={}", value)))
+            },
+            ("helpers", value) => for helper in value.split(',').map(str::trim) {
+                match helper {
+                    "none" => {
+                        self.equality_helpers = false;
+                        self.clone_helpers = false;
+                        self.match_helpers = false;
+                    },
+                    "equality" => self.equality_helpers = true,
+                    "clone" => self.clone_helpers = true,
+                    "match" => self.match_helpers = true,
+                    _ => return Err(Error::Parameter(format!("helpers={}", value)))
+                }
+            },
+            ("service_paths", "true") => self.service_paths = true,
+            ("service_paths", "false") => self.service_paths = false,
+            ("handlers", "true") => self.handlers = true,
+            ("handlers", "false") => self.handlers = false,
+            ("branded_ints", "true") => self.branded_ints = true,
+            ("branded_ints", "false") => self.branded_ints = false,
+            ("list_only", "true") => self.list_only = true,
+            ("list_only", "false") => self.list_only = false,
+            ("source_info", "true") => self.source_info = true,
+            ("source_info", "false") => self.source_info = false,
+            ("any_registry", "true") => self.any_registry = true,
+            ("any_registry", "false") => self.any_registry = false,
+            ("jobs", "auto") => self.jobs = 0,
+            ("jobs", value) => match value.parse::<usize>() {
+                Ok(jobs) if jobs > 0 => self.jobs = jobs,
+                _ => return Err(Error::Parameter(format!("jobs={}", value)))
+            },
+            ("template", value) if !value.is_empty() => self.templates.push(value.to_string()),
+            ("dump_request", value) if !value.is_empty() => self.dump_request = Some(value.to_string()),
+            ("alias_prefix", value) if !value.trim_end_matches('/').is_empty() => {
+                self.alias_prefix = Some(value.trim_end_matches('/').to_string());
+            },
+            (key, value) if key.starts_with("wkt.") && !value.is_empty() => {
+                self.type_overrides.insert(
+                    format!(".google.protobuf.{}", &key["wkt.".len()..]),
+                    TypeOverride::parse(value)
+                );
+            },
+            (key, value) => return Err(Error::Parameter(format!("{}={}", key, value)))
+        }
+        Ok(())
+    }
+
+    /// Whether the outputs of a proto file depend on nothing but the file,
+    /// its dependencies, these options and the packages of the other files
+    /// of the request declaring its top-level names, which decide whether
+    /// they are prefixed, so that unchanged files need not be generated
+    /// again. Indexes, registries, single and per-message files and renamed
+    /// collisions span every file generated.
+    pub fn is_incremental(&self) -> bool {
+        matches!(self.output_mode, OutputMode::PerFile) &&
+            matches!(self.collisions, CollisionStyle::Error) &&
+            !self.index &&
+            !self.any_registry
+    }
+
+    /// Whether only the list of the files to generate is asked for.
+    pub fn is_list_only(&self) -> bool {
+        self.list_only
+    }
+
+    /// Paths of the templates rendered for each proto file.
+    pub fn templates(&self) -> &[String] {
+        &self.templates
+    }
+
+    /// The module that generates `file_name` according to `import_map`.
+    fn mapped_module(&self, file_name: &str) -> Option<&str> {
+        self.import_maps
+            .iter()
+            .find(|(pattern, _)| config::glob_match(pattern, file_name))
+            .map(|(_, module)| module.as_str())
+    }
+
+    /// Overrides the types of the proto files mapped by `import_map` with
+    /// imports from their modules, named the way protobuf-es and ts-proto
+    /// flatten nested types. Explicit overrides are kept.
+    fn map_imports(&mut self, proto_files: &[FileDescriptorProto]) {
+        fn add_message(scope: &str, message_type: &DescriptorProto, types: &mut Vec<String>) {
+            let full_name = format!("{}.{}", scope, message_type.get_name());
+            message_type.get_nested_type()
+                .iter()
+                .filter(|nested_type| !nested_type.get_options().get_map_entry())
+                .for_each(|nested_type| add_message(&full_name, nested_type, types));
+            message_type.get_enum_type()
+                .iter()
+                .for_each(|enum_type| types.push(format!("{}.{}", full_name, enum_type.get_name())));
+            types.push(full_name);
+        }
+        for proto_file in proto_files {
+            let module = match self.mapped_module(proto_file.get_name()) {
+                Some(module) => module.to_string(),
+                None => continue
+            };
+            let package = qualified_name(proto_file.get_package(), "");
+            let package = package.trim_end_matches('.');
+            let mut types = Vec::new();
+            proto_file.get_message_type().iter().for_each(|message_type| add_message(package, message_type, &mut types));
+            proto_file.get_enum_type()
+                .iter()
+                .for_each(|enum_type| types.push(format!("{}.{}", package, enum_type.get_name())));
+            for full_name in types {
+                let ts_type = full_name[package.len() + 1..].replace('.', "_");
+                self.type_overrides.entry(full_name).or_insert(TypeOverride{ ts_type, module: Some(module.clone()) });
+            }
+        }
+    }
+}
+
+fn qualified_name(scope: &str, name: &str) -> String {
+    match scope {
+        "" => format!(".{}", name),
+        _ => format!(".{}.{}", scope, name)
+    }
+}
+
+const FILE_MESSAGE_TYPE: i32 = 4;
+const FILE_ENUM_TYPE: i32 = 5;
+const FILE_SERVICE: i32 = 6;
+const FILE_EXTENSION: i32 = 7;
+const MESSAGE_FIELD: i32 = 2;
+const MESSAGE_NESTED_TYPE: i32 = 3;
+const MESSAGE_ENUM_TYPE: i32 = 4;
+const MESSAGE_EXTENSION: i32 = 6;
+const MESSAGE_ONEOF_DECL: i32 = 8;
+const ENUM_VALUE: i32 = 2;
+const SERVICE_METHOD: i32 = 2;
+
+/// Leading and trailing comments of a location, with the single space that
+/// usually follows `//` removed from every line.
+fn location_comment(location: &SourceCodeInfo_Location) -> Option<String> {
+    let comment = [location.get_leading_comments(), location.get_trailing_comments()]
+        .iter()
+        .filter(|comment| !comment.trim().is_empty())
+        .map(|comment| comment.trim_matches('\n')
+            .lines()
+            .map(|line| line.strip_prefix(' ').unwrap_or(line).trim_end())
+            .collect::<Vec<_>>()
+            .join("\n")
+        )
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    match comment.is_empty() {
+        true => None,
+        false => Some(comment)
+    }
+}
+
+struct CommentCollector<'a> {
+    file_name: &'a str,
+    locations: HashMap<&'a [i32], &'a SourceCodeInfo_Location>,
+    comments: HashMap<String, String>,
+    sources: HashMap<String, String>
+}
+
+impl<'a> CommentCollector<'a> {
+    fn add(&mut self, full_name: String, path: &[i32]) {
+        let location = match self.locations.get(path) {
+            Some(location) => location,
+            None => return
+        };
+        if let Some(line) = location.get_span().first() {
+            self.sources.insert(full_name.clone(), format!("{}:{}", self.file_name, line + 1));
+        }
+        if let Some(comment) = location_comment(location) {
+            self.comments.insert(full_name, comment);
+        }
+    }
+
+    fn add_enum(&mut self, full_name: String, path: Vec<i32>, enum_type: &EnumDescriptorProto) {
+        enum_type.get_value().iter().enumerate().for_each(|(i, value)| {
+            self.add(
+                format!("{}.{}", full_name, value.get_name()),
+                &[&path[..], &[ENUM_VALUE, i as i32]].concat()
+            );
+        });
+        self.add(full_name, &path);
+    }
+
+    fn add_message(&mut self, full_name: String, path: Vec<i32>, message_type: &DescriptorProto) {
+        message_type.get_field().iter().enumerate().for_each(|(i, field)| {
+            self.add(
+                format!("{}.{}", full_name, field.get_name()),
+                &[&path[..], &[MESSAGE_FIELD, i as i32]].concat()
+            );
+        });
+        message_type.get_oneof_decl().iter().enumerate().for_each(|(i, oneof)| {
+            self.add(
+                format!("{}.{}", full_name, oneof.get_name()),
+                &[&path[..], &[MESSAGE_ONEOF_DECL, i as i32]].concat()
+            );
+        });
+        message_type.get_extension().iter().enumerate().for_each(|(i, extension)| {
+            self.add(
+                format!("{}.{}", full_name, extension.get_name()),
+                &[&path[..], &[MESSAGE_EXTENSION, i as i32]].concat()
+            );
+        });
+        message_type.get_nested_type().iter().enumerate().for_each(|(i, nested_type)| {
+            self.add_message(
+                format!("{}.{}", full_name, nested_type.get_name()),
+                [&path[..], &[MESSAGE_NESTED_TYPE, i as i32]].concat(),
+                nested_type
+            );
+        });
+        message_type.get_enum_type().iter().enumerate().for_each(|(i, enum_type)| {
+            self.add_enum(
+                format!("{}.{}", full_name, enum_type.get_name()),
+                [&path[..], &[MESSAGE_ENUM_TYPE, i as i32]].concat(),
+                enum_type
+            );
+        });
+        self.add(full_name, &path);
+    }
+}
+
+/// Comments of the elements of `proto_file`, and the `file:line` each is
+/// declared at, by full name.
+fn file_comments(proto_file: &FileDescriptorProto) -> (HashMap<String, String>, HashMap<String, String>) {
+    let package = proto_file.get_package();
+    let mut collector = CommentCollector{
+        file_name: proto_file.get_name(),
+        locations: proto_file.get_source_code_info()
+            .get_location()
+            .iter()
+            .map(|location| (location.get_path(), location))
+            .collect(),
+        comments: HashMap::new(),
+        sources: HashMap::new()
+    };
+    proto_file.get_message_type().iter().enumerate().for_each(|(i, message_type)| {
+        collector.add_message(
+            qualified_name(package, message_type.get_name()),
+            vec![FILE_MESSAGE_TYPE, i as i32],
+            message_type
+        );
+    });
+    proto_file.get_enum_type().iter().enumerate().for_each(|(i, enum_type)| {
+        collector.add_enum(
+            qualified_name(package, enum_type.get_name()),
+            vec![FILE_ENUM_TYPE, i as i32],
+            enum_type
+        );
+    });
+    proto_file.get_extension().iter().enumerate().for_each(|(i, extension)| {
+        collector.add(qualified_name(package, extension.get_name()), &[FILE_EXTENSION, i as i32]);
+    });
+    proto_file.get_service().iter().enumerate().for_each(|(i, service)| {
+        let full_name = qualified_name(package, service.get_name());
+        service.get_method().iter().enumerate().for_each(|(j, method)| {
+            collector.add(
+                format!("{}.{}", full_name, method.get_name()),
+                &[FILE_SERVICE, i as i32, SERVICE_METHOD, j as i32]
+            );
+        });
+        collector.add(full_name, &[FILE_SERVICE, i as i32]);
+    });
+    (collector.comments, collector.sources)
+}
+
+// Extensions of `proto/tst/options.proto`, read from the unknown fields of
+// the options since they are not compiled into the plugin.
+const OPTION_TS_TYPE: u32 = 52713;
+const OPTION_SKIP: u32 = 52714;
+const OPTION_NAME: u32 = 52715;
+const OPTION_FIELD_MASK: u32 = 52716;
+const OPTION_EXAMPLE: u32 = 52717;
+const OPTION_NOMINAL: u32 = 52718;
+
+fn string_option(unknown_fields: &protobuf::UnknownFields, number: u32) -> Option<String> {
+    unknown_fields.get(number)
+        .and_then(|values| values.length_delimited.last())
+        .and_then(|value| String::from_utf8(value.clone()).ok())
+        .filter(|value| !value.trim().is_empty())
+}
+
+/// Every value of a repeated string option, in declaration order.
+fn string_options(unknown_fields: &protobuf::UnknownFields, number: u32) -> Vec<String> {
+    unknown_fields.get(number)
+        .map(|values| values.length_delimited
+            .iter()
+            .filter_map(|value| String::from_utf8(value.clone()).ok())
+            .filter(|value| !value.trim().is_empty())
+            .collect()
+        )
+        .unwrap_or_default()
+}
+
+/// Fields of a serialized option message, none of which the plugin knows.
+fn unknown_fields(bytes: &[u8]) -> Option<protobuf::UnknownFields> {
+    protobuf::parse_from_bytes::<protobuf::well_known_types::Empty>(bytes)
+        .ok()
+        .map(|mut message| std::mem::take(message.mut_unknown_fields()))
+}
+
+fn bool_option(unknown_fields: &protobuf::UnknownFields, number: u32) -> bool {
+    unknown_fields.get(number)
+        .and_then(|values| values.varint.last())
+        .map(|&value| value != 0)
+        .unwrap_or(false)
+}
+
+/// Replaces the declared name, the last segment of `path`, with `(tst.name)`.
+fn renamed(mut path: Vec<String>, unknown_fields: &protobuf::UnknownFields) -> Vec<String> {
+    if let (Some(name), Some(segment)) = (string_option(unknown_fields, OPTION_NAME), path.last_mut()) {
+        *segment = name;
+    }
+    path
+}
+
+/// Fully-qualified names of the messages and enums marked `(tst.skip)`.
+fn skipped_types(proto_files: &[FileDescriptorProto]) -> BTreeSet<String> {
+    fn add_message(full_name: String, message_type: &DescriptorProto, skipped: &mut BTreeSet<String>) {
+        if bool_option(message_type.get_options().get_unknown_fields(), OPTION_SKIP) {
+            skipped.insert(full_name.clone());
+        }
+        message_type.get_nested_type().iter().for_each(|nested_type| {
+            add_message(format!("{}.{}", full_name, nested_type.get_name()), nested_type, skipped);
+        });
+        message_type.get_enum_type().iter()
+            .filter(|enum_type| bool_option(enum_type.get_options().get_unknown_fields(), OPTION_SKIP))
+            .for_each(|enum_type| {
+                skipped.insert(format!("{}.{}", full_name, enum_type.get_name()));
+            });
+    }
+    let mut skipped = BTreeSet::new();
+    proto_files.iter().for_each(|proto_file| {
+        proto_file.get_message_type().iter().for_each(|message_type| {
+            add_message(qualified_name(proto_file.get_package(), message_type.get_name()), message_type, &mut skipped);
+        });
+        proto_file.get_enum_type().iter()
+            .filter(|enum_type| bool_option(enum_type.get_options().get_unknown_fields(), OPTION_SKIP))
+            .for_each(|enum_type| {
+                skipped.insert(qualified_name(proto_file.get_package(), enum_type.get_name()));
+            });
+    });
+    skipped
+}
+
+fn is_skipped_type(type_name: &str, skipped: &BTreeSet<String>) -> bool {
+    skipped.iter().any(|skipped_name|
+        type_name == skipped_name ||
+            (type_name.starts_with(skipped_name.as_str()) && type_name[skipped_name.len()..].starts_with('.'))
+    )
+}
+
+/// Removes the fields, messages and enums marked `(tst.skip)` from
+/// `message_type`, along with the fields that refer to skipped types.
+fn strip_skipped_message(message_type: &DescriptorProto, skipped: &BTreeSet<String>) -> DescriptorProto {
+    let mut stripped = message_type.clone();
+    stripped.set_field(message_type.get_field()
+        .iter()
+        .filter(|field|
+            !bool_option(field.get_options().get_unknown_fields(), OPTION_SKIP) &&
+                !field_references(field, message_type).iter().any(|type_name| is_skipped_type(type_name, skipped))
+        )
+        .cloned()
+        .collect());
+    stripped.set_nested_type(message_type.get_nested_type()
+        .iter()
+        .filter(|nested_type| !bool_option(nested_type.get_options().get_unknown_fields(), OPTION_SKIP))
+        .map(|nested_type| strip_skipped_message(nested_type, skipped))
+        .collect());
+    stripped.set_enum_type(message_type.get_enum_type()
+        .iter()
+        .filter(|enum_type| !bool_option(enum_type.get_options().get_unknown_fields(), OPTION_SKIP))
+        .cloned()
+        .collect());
+    stripped
+}
+
+fn strip_skipped(proto_file: &FileDescriptorProto, skipped: &BTreeSet<String>) -> FileDescriptorProto {
+    let mut stripped = proto_file.clone();
+    stripped.set_message_type(proto_file.get_message_type()
+        .iter()
+        .filter(|message_type| !bool_option(message_type.get_options().get_unknown_fields(), OPTION_SKIP))
+        .map(|message_type| strip_skipped_message(message_type, skipped))
+        .collect());
+    stripped.set_enum_type(proto_file.get_enum_type()
+        .iter()
+        .filter(|enum_type| !bool_option(enum_type.get_options().get_unknown_fields(), OPTION_SKIP))
+        .cloned()
+        .collect());
+    stripped
+}
+
+fn with_proto2_presence_message(message_type: &DescriptorProto) -> DescriptorProto {
+    let mut marked = message_type.clone();
+    if !message_type.get_options().get_map_entry() {
+        marked.mut_field()
+            .iter_mut()
+            .filter(|field|
+                field.get_label() == FieldDescriptorProto_Label::LABEL_OPTIONAL &&
+                    !field.has_oneof_index() &&
+                    !matches!(
+                        field.get_field_type(),
+                        FieldDescriptorProto_Type::TYPE_MESSAGE | FieldDescriptorProto_Type::TYPE_GROUP
+                    )
+            )
+            .for_each(|field| field.mut_unknown_fields().add_varint(FIELD_PROTO3_OPTIONAL, 1));
+    }
+    marked.set_nested_type(message_type.get_nested_type().iter().map(with_proto2_presence_message).collect());
+    marked
+}
+
+/// Marks the `optional` scalar and enum fields of a proto2 file the way
+/// protoc marks proto3 `optional` fields, since both track presence and are
+/// generated alike. Files of an edition get the same from their features.
+fn with_explicit_presence(proto_file: &FileDescriptorProto) -> FileDescriptorProto {
+    match proto_file.get_syntax() {
+        "" | "proto2" => {
+            let mut marked = proto_file.clone();
+            marked.set_message_type(proto_file.get_message_type().iter().map(with_proto2_presence_message).collect());
+            marked
+        },
+        "editions" => editions::resolve_features(proto_file),
+        _ => proto_file.clone()
+    }
+}
+
+/// Words that cannot name a TypeScript type: reserved words and the names of
+/// predefined types.
+const RESERVED_TYPE_NAMES: &[&str] = &[
+    "any", "bigint", "boolean", "break", "case", "catch", "class", "const", "continue", "debugger",
+    "default", "delete", "do", "else", "enum", "export", "extends", "false", "finally", "for",
+    "function", "if", "implements", "import", "in", "instanceof", "interface", "let", "never", "new",
+    "null", "number", "object", "package", "private", "protected", "public", "return", "static",
+    "string", "super", "switch", "symbol", "this", "throw", "true", "try", "typeof", "undefined",
+    "unknown", "var", "void", "while", "with", "yield"
+];
+
+fn escape_type_name(name: &str) -> String {
+    match RESERVED_TYPE_NAMES.contains(&name) {
+        true => format!("{}_", name),
+        false => name.to_string()
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$' => (),
+        _ => return false
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Renders `key` as an object type key, quoting it when it is not a valid
+/// identifier.
+fn property_key(key: &str) -> String {
+    match is_identifier(key) {
+        true => key.to_string(),
+        false => format!("\"{}\"", key.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+struct TypeResolver {
+    paths: HashMap<String, Vec<String>>,
+    files: HashMap<String, String>,
+    packages: HashMap<String, String>,
+    enums: HashMap<String, EnumDescriptorProto>,
+    messages: HashMap<String, DescriptorProto>,
+    comments: HashMap<String, String>,
+    /// `file:line` of the declaration of each element, named as comments.
+    sources: HashMap<String, String>,
+    renames: BTreeMap<String, String>,
+    /// Output paths of proto files renamed with `collisions=rename`.
+    output_paths: HashMap<String, String>,
+    /// Output stems of declarations renamed with `collisions=rename`, by
+    /// proto file and declaration name.
+    declaration_stems: HashMap<(String, String), String>,
+    /// Extensions declared anywhere in the request, with their full names,
+    /// by extendee.
+    extensions: HashMap<String, Vec<(String, FieldDescriptorProto)>>,
+    /// Other proto files whose types the fields of each proto file refer to.
+    file_dependencies: HashMap<String, BTreeSet<String>>
+}
+
+/// Whether `to` can be reached from `from` following `edges`.
+fn reaches(from: &str, to: &str, edges: impl Fn(&str) -> Vec<String>) -> bool {
+    let mut visited = BTreeSet::new();
+    let mut pending = vec![from.to_string()];
+    while let Some(node) = pending.pop() {
+        if node == to {
+            return true;
+        }
+        if visited.insert(node.clone()) {
+            pending.extend(edges(&node));
+        }
+    }
+    false
+}
+
+impl TypeResolver {
+    fn new(proto_files: &[FileDescriptorProto], options: &Options) -> TypeResolver {
+        let mut packages_by_name = BTreeMap::<&str, BTreeSet<&str>>::new();
+        proto_files.iter().for_each(|proto_file| {
+            proto_file.get_message_type()
+                .iter()
+                .map(|message_type| message_type.get_name())
+                .chain(proto_file.get_enum_type().iter().map(|enum_type| enum_type.get_name()))
+                .for_each(|name| {
+                    packages_by_name.entry(name).or_default().insert(proto_file.get_package());
+                });
+        });
+        let mut resolver = TypeResolver{
+            paths: HashMap::new(),
+            files: HashMap::new(),
+            packages: proto_files.iter()
+                .map(|proto_file| (proto_file.get_name().to_string(), proto_file.get_package().to_string()))
+                .collect(),
+            enums: HashMap::new(),
+            messages: HashMap::new(),
+            comments: HashMap::new(),
+            sources: HashMap::new(),
+            renames: BTreeMap::new(),
+            output_paths: HashMap::new(),
+            declaration_stems: HashMap::new(),
+            extensions: HashMap::new(),
+            file_dependencies: HashMap::new()
+        };
+        proto_files.iter().for_each(|proto_file| {
+            let package = proto_file.get_package();
+            // Namespaces tell packages apart, unless the name is also global.
+            let top_level_name = |name: &str| match packages_by_name[name].len() {
+                1 => name.to_string(),
+                _ if package.is_empty() => name.to_string(),
+                _ if options.namespaces && !packages_by_name[name].contains("") => name.to_string(),
+                _ => format!("{}_{}", package.replace('.', "_"), name)
+            };
+            proto_file.get_message_type().iter().for_each(|message_type| {
+                resolver.add_message(
+                    proto_file.get_name(),
+                    qualified_name(package, message_type.get_name()),
+                    vec![top_level_name(message_type.get_name())],
+                    message_type
+                );
+            });
+            proto_file.get_enum_type().iter().for_each(|enum_type| {
+                resolver.add_enum(
+                    proto_file.get_name(),
+                    qualified_name(package, enum_type.get_name()),
+                    vec![top_level_name(enum_type.get_name())],
+                    enum_type
+                );
+            });
+            let (comments, sources) = file_comments(proto_file);
+            resolver.comments.extend(comments);
+            resolver.sources.extend(sources);
+            for (full_name, extension) in extensions::file_extensions(proto_file) {
+                resolver.extensions
+                    .entry(extension.get_extendee().to_string())
+                    .or_default()
+                    .push((full_name, extension.clone()));
+            }
+        });
+        resolver.file_dependencies = proto_files.iter()
+            .map(|proto_file| (
+                proto_file.get_name().to_string(),
+                proto_file.get_message_type()
+                    .iter()
+                    .flat_map(message_fields)
+                    .filter_map(|field| resolver.file(field.get_type_name()))
+                    .filter(|file_name| *file_name != proto_file.get_name())
+                    .map(str::to_string)
+                    .collect()
+            ))
+            .collect();
+        resolver
+    }
+
+    /// Messages the fields of `full_name` refer to, map entries included.
+    fn message_dependencies(&self, full_name: &str) -> Vec<String> {
+        self.message_type(full_name)
+            .map(|message_type| message_type.get_field()
+                .iter()
+                .map(|field| field.get_type_name())
+                .filter(|type_name| self.messages.contains_key(*type_name))
+                .map(str::to_string)
+                .collect()
+            )
+            .unwrap_or_default()
+    }
+
+    /// Whether the fields of `full_name` lead back to it, directly or through
+    /// other messages, so that runtime code has to refer to it lazily.
+    fn is_recursive(&self, full_name: &str) -> bool {
+        self.message_dependencies(full_name)
+            .iter()
+            .any(|type_name| reaches(type_name, full_name, |next| self.message_dependencies(next)))
+    }
+
+    /// Whether the distinct proto files `a` and `b` refer to each other,
+    /// directly or through other files, making their modules import each
+    /// other.
+    fn is_import_cycle(&self, a: &str, b: &str) -> bool {
+        let dependencies = |file_name: &str| self.file_dependencies
+            .get(file_name)
+            .map(|dependencies| dependencies.iter().cloned().collect())
+            .unwrap_or_default();
+        a != b && reaches(a, b, dependencies) && reaches(b, a, dependencies)
+    }
+
+    fn extensions(&self, extendee: &str) -> &[(String, FieldDescriptorProto)] {
+        self.extensions.get(extendee).map_or(&[], Vec::as_slice)
+    }
+
+    /// Escapes the declared name of `full_name`, the last segment of `path`.
+    fn escape(&mut self, full_name: &str, mut path: Vec<String>) -> Vec<String> {
+        if let Some(name) = path.last_mut() {
+            let escaped = escape_type_name(name);
+            if escaped != *name {
+                self.renames.insert(full_name.to_string(), escaped.clone());
+                *name = escaped;
+            }
+        }
+        path
+    }
+
+    fn add(&mut self, file_name: &str, full_name: String, path: Vec<String>) {
+        self.files.insert(full_name.clone(), file_name.to_string());
+        self.paths.insert(full_name, path);
+    }
+
+    fn add_enum(
+        &mut self,
+        file_name: &str,
+        full_name: String,
+        path: Vec<String>,
+        enum_type: &EnumDescriptorProto
+    ) {
+        let path = self.escape(&full_name, renamed(path, enum_type.get_options().get_unknown_fields()));
+        self.enums.insert(full_name.clone(), enum_type.clone());
+        self.add(file_name, full_name, path);
+    }
+
+    fn add_message(
+        &mut self,
+        file_name: &str,
+        full_name: String,
+        path: Vec<String>,
+        message_type: &DescriptorProto
+    ) {
+        let path = self.escape(&full_name, renamed(path, message_type.get_options().get_unknown_fields()));
+        message_type.get_nested_type().iter().for_each(|nested_type| {
+            let mut nested_path = path.clone();
+            nested_path.push(nested_type.get_name().to_string());
+            self.add_message(
+                file_name,
+                format!("{}.{}", full_name, nested_type.get_name()),
+                nested_path,
+                nested_type
+            );
+        });
+        message_type.get_enum_type().iter().for_each(|enum_type| {
+            let mut nested_path = path.clone();
+            nested_path.push(enum_type.get_name().to_string());
+            self.add_enum(
+                file_name,
+                format!("{}.{}", full_name, enum_type.get_name()),
+                nested_path,
+                enum_type
+            );
+        });
+        self.messages.insert(full_name.clone(), message_type.clone());
+        if !message_type.get_options().get_map_entry() {
+            self.add(file_name, full_name, path);
+        }
+    }
+
+    /// Documentation comment attached to the element named `full_name`. Fields,
+    /// enum values and methods are named by appending their name to the
+    /// full name of their parent.
+    fn comment(&self, full_name: &str) -> Option<&str> {
+        self.comments.get(full_name).map(String::as_str)
+    }
+
+    /// `file:line` of the element named `full_name` for `source_info=true`,
+    /// named as for `comment`.
+    fn source(&self, full_name: &str, options: &Options) -> Option<String> {
+        self.sources.get(full_name).filter(|_| options.source_info).cloned()
+    }
+
+    /// Identifiers changed to avoid reserved words and distinct types that
+    /// still resolve to the same identifier, logged as warnings.
+    fn report(&self, options: &Options) -> Vec<String> {
+        let mut by_name = BTreeMap::<String, BTreeSet<&str>>::new();
+        self.paths.keys().for_each(|full_name| {
+            by_name.entry(self.resolve(full_name, options)).or_default().insert(full_name.as_str());
+        });
+        self.renames.iter()
+            .map(|(full_name, name)| format!("renamed {} to {} to avoid a reserved word", full_name, name))
+            .chain(by_name.iter()
+                .filter(|(_, full_names)| full_names.len() > 1)
+                .map(|(name, full_names)| format!(
+                    "{} resolve to the same identifier {}",
+                    full_names.iter().cloned().collect::<Vec<_>>().join(", "),
+                    name
+                ))
+            )
+            .collect()
+    }
+
+    /// The identifier and proto file each type resolves to, for `log=debug`.
+    fn decisions(&self, options: &Options) -> Vec<String> {
+        self.paths.keys()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|full_name| format!(
+                "{} resolves to {} in {}",
+                full_name.trim_start_matches('.'),
+                self.resolve(full_name, options),
+                self.file(full_name).unwrap_or("?")
+            ))
+            .collect()
+    }
+
+    fn enum_type(&self, type_name: &str) -> Option<&EnumDescriptorProto> {
+        self.enums.get(type_name)
+    }
+
+    fn message_type(&self, type_name: &str) -> Option<&DescriptorProto> {
+        self.messages.get(type_name)
+    }
+
+    /// Flattened identifier used to name generated runtime functions.
+    fn function_prefix(&self, type_name: &str) -> String {
+        self.path(type_name).join("_")
+    }
+
+    fn file(&self, type_name: &str) -> Option<&str> {
+        self.files.get(type_name).map(|file_name| file_name.as_str())
+    }
+
+    /// Output path of the proto file `file_name` without extension, placed
+    /// by `paths` either next to the proto or under its package directory.
+    fn output_path(&self, file_name: &str, options: &Options) -> String {
+        if let Some(path) = self.output_paths.get(file_name) {
+            return path.clone();
+        }
+        let path = file_name.trim_end_matches(".proto");
+        match (options.path_style, self.packages.get(file_name)) {
+            (PathStyle::Package, Some(package)) => {
+                let base = path.rsplit('/').next().unwrap_or(path);
+                match package.is_empty() {
+                    true => base.to_string(),
+                    false => format!("{}/{}", package.replace('.', "/"), base)
+                }
+            },
+            _ => path.to_string()
+        }
+    }
+
+    /// Identifier that has to be imported to refer to `type_name`, i.e. the
+    /// flattened name or the outermost namespace.
+    fn import_name(&self, type_name: &str, options: &Options) -> String {
+        let path = self.path(type_name);
+        match options.nested_style {
+            NestedStyle::Flat => path.join("_"),
+            NestedStyle::Namespace => path[0].clone()
+        }
+    }
+
+    /// Output stem of the declaration `name` of the proto file `file_name`
+    /// with `output_mode=per_message`.
+    fn declaration_stem(&self, file_name: &str, name: &str) -> String {
+        self.declaration_stems
+            .get(&(file_name.to_string(), name.to_string()))
+            .cloned()
+            .unwrap_or_else(|| name.to_string())
+    }
+
+    fn path(&self, type_name: &str) -> Vec<String> {
+        match self.paths.get(type_name) {
+            Some(path) => path.clone(),
+            None => vec![type_name.rsplit('.').next().unwrap_or(type_name).to_string()]
+        }
+    }
+
+    fn resolve(&self, type_name: &str, options: &Options) -> String {
+        let path = self.path(type_name);
+        let name = match options.nested_style {
+            NestedStyle::Flat => path.join("_"),
+            NestedStyle::Namespace => path.join(".")
+        };
+        match self.namespace(type_name, options) {
+            Some(namespace) => format!("{}.{}", namespace, name),
+            None => name
+        }
+    }
+
+    /// The namespace `type_name` is declared in with `namespaces=true`: its
+    /// package, unless it has none.
+    fn namespace(&self, type_name: &str, options: &Options) -> Option<&str> {
+        self.file(type_name)
+            .and_then(|file_name| self.packages.get(file_name))
+            .map(String::as_str)
+            .filter(|package| options.namespaces && !package.is_empty())
+    }
+}
+
+enum TsType {
+    Boolean,
+    Number,
+    BigInt,
+    String,
+    Uint8Array,
+    Never,
+    Object(String),
+    Nullable(Box<TsType>)
+}
+
+impl fmt::Display for TsType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TsType::Boolean => write!(f, "boolean"),
+            TsType::Number => write!(f, "number"),
+            TsType::BigInt => write!(f, "bigint"),
+            TsType::String => write!(f, "string"),
+            TsType::Uint8Array => write!(f, "Uint8Array"),
+            TsType::Never => write!(f, "never"),
+            TsType::Object(name) => write!(f, "{}", name),
+            TsType::Nullable(ts_type) => write!(f, "{} | null", ts_type)
+        }
+    }
+}
+
+/// The name of a scalar type as written in a `.proto` file.
+fn scalar_name(field_type: FieldDescriptorProto_Type) -> &'static str {
+    match field_type {
+        FieldDescriptorProto_Type::TYPE_DOUBLE => "double",
+        FieldDescriptorProto_Type::TYPE_FLOAT => "float",
+        FieldDescriptorProto_Type::TYPE_INT32 => "int32",
+        FieldDescriptorProto_Type::TYPE_INT64 => "int64",
+        FieldDescriptorProto_Type::TYPE_UINT32 => "uint32",
+        FieldDescriptorProto_Type::TYPE_UINT64 => "uint64",
+        FieldDescriptorProto_Type::TYPE_SINT32 => "sint32",
+        FieldDescriptorProto_Type::TYPE_SINT64 => "sint64",
+        FieldDescriptorProto_Type::TYPE_FIXED32 => "fixed32",
+        FieldDescriptorProto_Type::TYPE_FIXED64 => "fixed64",
+        FieldDescriptorProto_Type::TYPE_SFIXED32 => "sfixed32",
+        FieldDescriptorProto_Type::TYPE_SFIXED64 => "sfixed64",
+        FieldDescriptorProto_Type::TYPE_BOOL => "bool",
+        FieldDescriptorProto_Type::TYPE_STRING => "string",
+        FieldDescriptorProto_Type::TYPE_BYTES => "bytes",
+        FieldDescriptorProto_Type::TYPE_ENUM => "enum",
+        FieldDescriptorProto_Type::TYPE_MESSAGE => "message",
+        FieldDescriptorProto_Type::TYPE_GROUP => "group"
+    }
+}
+
+fn scalar_to_ts_type(field_type: FieldDescriptorProto_Type, options: &Options) -> TsType {
+    if let Some(type_override) = options.type_overrides.get(scalar_name(field_type)) {
+        return TsType::Object(type_override.ts_type.clone());
+    }
+    match field_type {
+        FieldDescriptorProto_Type::TYPE_DOUBLE |
+            FieldDescriptorProto_Type::TYPE_FLOAT |
+            FieldDescriptorProto_Type::TYPE_INT32 |
+            FieldDescriptorProto_Type::TYPE_FIXED32 |
+            FieldDescriptorProto_Type::TYPE_UINT32 |
+            FieldDescriptorProto_Type::TYPE_SFIXED32 |
+            FieldDescriptorProto_Type::TYPE_SINT32 => TsType::Number,
+        FieldDescriptorProto_Type::TYPE_INT64 |
+            FieldDescriptorProto_Type::TYPE_UINT64 |
+            FieldDescriptorProto_Type::TYPE_FIXED64 |
+            FieldDescriptorProto_Type::TYPE_SFIXED64 |
+            FieldDescriptorProto_Type::TYPE_SINT64 => match options.int64_style {
+                Int64Style::String => TsType::String,
+                Int64Style::BigInt => TsType::BigInt,
+                Int64Style::Number => TsType::Number
+            },
+        FieldDescriptorProto_Type::TYPE_STRING => TsType::String,
+        FieldDescriptorProto_Type::TYPE_BYTES => match options.bytes_style {
+            BytesStyle::Base64String => TsType::String,
+            BytesStyle::Uint8Array => TsType::Uint8Array
+        },
+        FieldDescriptorProto_Type::TYPE_BOOL => TsType::Boolean,
+        FieldDescriptorProto_Type::TYPE_ENUM |
+            FieldDescriptorProto_Type::TYPE_MESSAGE |
+            FieldDescriptorProto_Type::TYPE_GROUP => TsType::Never
+    }
+}
+
+/// Scalar type carried by a `google.protobuf` wrapper message.
+fn wrapped_type(type_name: &str) -> Option<FieldDescriptorProto_Type> {
+    match type_name {
+        ".google.protobuf.DoubleValue" => Some(FieldDescriptorProto_Type::TYPE_DOUBLE),
+        ".google.protobuf.FloatValue" => Some(FieldDescriptorProto_Type::TYPE_FLOAT),
+        ".google.protobuf.Int64Value" => Some(FieldDescriptorProto_Type::TYPE_INT64),
+        ".google.protobuf.UInt64Value" => Some(FieldDescriptorProto_Type::TYPE_UINT64),
+        ".google.protobuf.Int32Value" => Some(FieldDescriptorProto_Type::TYPE_INT32),
+        ".google.protobuf.UInt32Value" => Some(FieldDescriptorProto_Type::TYPE_UINT32),
+        ".google.protobuf.BoolValue" => Some(FieldDescriptorProto_Type::TYPE_BOOL),
+        ".google.protobuf.StringValue" => Some(FieldDescriptorProto_Type::TYPE_STRING),
+        ".google.protobuf.BytesValue" => Some(FieldDescriptorProto_Type::TYPE_BYTES),
+        _ => None
+    }
+}
+
+fn well_known_type_to_ts_type(type_name: &str, options: &Options) -> Option<TsType> {
+    if let Some(type_override) = options.type_overrides.get(type_name) {
+        return Some(TsType::Object(type_override.ts_type.clone()));
+    }
+    // `toObject()` converts well-known types like any other message.
+    if options.compat_style.is_some() {
+        return None;
+    }
+    if let Some(field_type) = wrapped_type(type_name) {
+        return Some(TsType::Nullable(Box::new(scalar_to_ts_type(field_type, options))));
+    }
+    match type_name {
+        ".google.protobuf.Timestamp" | ".google.protobuf.Duration" => Some(TsType::String),
+        ".google.protobuf.Struct" => Some(TsType::Object("JsonObject".to_string())),
+        ".google.protobuf.Value" => Some(TsType::Object("JsonValue".to_string())),
+        ".google.protobuf.ListValue" => Some(TsType::Object("ReadonlyArray<JsonValue>".to_string())),
+        ".google.protobuf.NullValue" => Some(TsType::Object("null".to_string())),
+        ".google.protobuf.Any" => Some(TsType::Object(
+            "Readonly<{ \"@type\": string } & Record<string, unknown>>".to_string()
+        )),
+        _ => None
+    }
+}
+
+const JSON_VALUE_TYPES: &[&str] = &[
+    ".google.protobuf.Struct",
+    ".google.protobuf.Value",
+    ".google.protobuf.ListValue"
+];
+
+const JSON_VALUE_FILE_STEM: &str = "JsonValue";
+
+fn json_value_declaration(options: &Options) -> String {
+    let export = TsModifiers::top_level(options).export();
+    format!(
+        "{0}type JsonValue = null | boolean | number | string | ReadonlyArray<JsonValue> | JsonObject;\n\
+        {0}type JsonObject = {{ readonly [key: string]: JsonValue }};\n",
+        export
+    )
+}
+
+/// Reference to the `Mutable<T>` helper emitted with `mutable_types=true`.
+/// Proto type names are always fully qualified, so it cannot collide.
+const MUTABLE_TYPE: &str = "Mutable";
+
+const MUTABLE_FILE_STEM: &str = "Mutable";
+
+/// Whether `mutable_types=true` has anything to undo.
+fn has_mutable_types(options: &Options) -> bool {
+    options.mutable_types && options.readonly
+}
+
+/// `Mutable` followed by the declared `name` in PascalCase. Prefixed, a
+/// reserved word needs no escape, so `default_` gives `MutableDefault`.
+fn mutable_type_name(name: &str) -> String {
+    let name = match name.strip_suffix('_') {
+        Some(reserved) if RESERVED_TYPE_NAMES.contains(&reserved) => reserved,
+        _ => name
+    };
+    let mut chars = name.chars();
+    let first = chars.next().map(|c| c.to_uppercase().collect::<String>()).unwrap_or_default();
+    format!("{}{}{}", MUTABLE_TYPE, first, chars.as_str())
+}
+
+fn mutable_declaration(options: &Options) -> String {
+    format!(
+        "{}type Mutable<T> = T extends Uint8Array | Date\n\
+        \x20 ? T\n\
+        \x20 : T extends ReadonlyArray<infer U>\n\
+        \x20   ? Array<Mutable<U>>\n\
+        \x20   : T extends ReadonlyMap<infer K, infer V>\n\
+        \x20     ? Map<K, Mutable<V>>\n\
+        \x20     : T extends object\n\
+        \x20       ? {{ -readonly [P in keyof T]: Mutable<T[P]> }}\n\
+        \x20       : T;\n",
+        TsModifiers::top_level(options).export()
+    )
+}
+
+/// References to the `DeepPartial<T>` and `Exact<P, I>` helpers emitted
+/// with `partials`.
+const DEEP_PARTIAL_TYPE: &str = "DeepPartial";
+const EXACT_TYPE: &str = "Exact";
+
+const PARTIALS_FILE_STEM: &str = "DeepPartial";
+const PATCH_SUFFIX: &str = "Patch";
+
+fn partials_declaration(style: PartialStyle, options: &Options) -> String {
+    let export = TsModifiers::top_level(options).export();
+    let readonly = match options.readonly {
+        true => "readonly ",
+        false => ""
+    };
+    let mut declaration = format!(
+        "{0}type DeepPartial<T> = T extends Uint8Array | Date\n\
+        \x20 ? T\n\
+        \x20 : T extends ReadonlyArray<infer U>\n\
+        \x20   ? ReadonlyArray<DeepPartial<U>>\n\
+        \x20   : T extends ReadonlyMap<infer K, infer V>\n\
+        \x20     ? ReadonlyMap<K, DeepPartial<V>>\n\
+        \x20     : T extends object\n\
+        \x20       ? {{ {1}[P in keyof T]?: DeepPartial<T[P]> }}\n\
+        \x20       : T;\n",
+        export,
+        readonly
+    );
+    if style == PartialStyle::Exact {
+        declaration.push_str(&format!(
+            "\n{}type Exact<P, I extends P> = P extends Uint8Array | Date | bigint | string | number | boolean | null | undefined\n\
+            \x20 ? P\n\
+            \x20 : P & {{ [K in keyof P]: Exact<P[K], I[K]> }} & {{ [K in Exclude<keyof I, P extends P ? keyof P : never>]: never }};\n",
+            export
+        ));
+    }
+    declaration
+}
+
+/// Integer scalars declared as branded numbers with `branded_ints=true`,
+/// named like the scalars so that fields refer to them by their scalar name.
+const BRANDED_INT_TYPES: &[FieldDescriptorProto_Type] = &[
+    FieldDescriptorProto_Type::TYPE_INT32,
+    FieldDescriptorProto_Type::TYPE_UINT32,
+    FieldDescriptorProto_Type::TYPE_SINT32,
+    FieldDescriptorProto_Type::TYPE_FIXED32,
+    FieldDescriptorProto_Type::TYPE_SFIXED32,
+    FieldDescriptorProto_Type::TYPE_INT64,
+    FieldDescriptorProto_Type::TYPE_UINT64,
+    FieldDescriptorProto_Type::TYPE_SINT64,
+    FieldDescriptorProto_Type::TYPE_FIXED64,
+    FieldDescriptorProto_Type::TYPE_SFIXED64
+];
+
+const BRANDED_INTS_FILE_STEM: &str = "BrandedInts";
+
+/// Whether the scalar `type_name` is declared as a branded number, which
+/// its type overrides take precedence over.
+fn is_branded_int(type_name: &str, options: &Options) -> bool {
+    options.branded_ints &&
+        BRANDED_INT_TYPES.iter().any(|field_type| scalar_name(*field_type) == type_name) &&
+        !options.type_overrides.contains_key(type_name)
+}
+
+fn branded_ints_declaration(options: &Options) -> String {
+    let export = TsModifiers::top_level(options).export();
+    BRANDED_INT_TYPES.iter()
+        .map(|field_type| format!(
+            "{0}type {1} = {2} & {{ __{1}: never }};\n",
+            export,
+            scalar_name(*field_type),
+            scalar_to_ts_type(*field_type, options)
+        ))
+        .collect()
+}
+
+fn uses_branded_ints(file: &ir::File, options: &Options) -> bool {
+    file.messages
+        .iter()
+        .flat_map(|message| message.fields.iter())
+        .flat_map(|field| match &field.cardinality {
+            ir::Cardinality::Map{ key } => vec![key, &field.value],
+            _ => vec![&field.value]
+        })
+        .any(|value| is_branded_int(scalar_name(value.field_type), options))
+}
+
+/// Whether `type_name` is declared as one of the JSON value types.
+fn is_json_value_type(type_name: &str, options: &Options) -> bool {
+    JSON_VALUE_TYPES.contains(&type_name) &&
+        !options.type_overrides.contains_key(type_name) &&
+        options.compat_style.is_none()
+}
+
+fn json_value_import_name(type_name: &str) -> &'static str {
+    match type_name {
+        ".google.protobuf.Struct" => "JsonObject",
+        _ => "JsonValue"
+    }
+}
+
+fn message_fields(message_type: &DescriptorProto) -> Vec<&FieldDescriptorProto> {
+    message_type.get_field()
+        .iter()
+        .chain(message_type.get_nested_type().iter().flat_map(message_fields))
+        .collect()
+}
+
+fn nested_messages(
+    full_name: String,
+    message_type: &DescriptorProto
+) -> Vec<(String, &DescriptorProto)> {
+    let mut messages = vec![(full_name.clone(), message_type)];
+    for nested_type in message_type.get_nested_type()
+        .iter()
+        .filter(|nested_type| !nested_type.get_options().get_map_entry()) {
+        messages.extend(nested_messages(
+            format!("{}.{}", full_name, nested_type.get_name()),
+            nested_type
+        ));
+    }
+    messages
+}
+
+/// All messages declared in `proto_file` in declaration order, keyed by their
+/// fully-qualified name. Synthetic map entries are skipped.
+fn file_messages(proto_file: &FileDescriptorProto) -> Vec<(String, &DescriptorProto)> {
+    proto_file.get_message_type()
+        .iter()
+        .flat_map(|message_type| nested_messages(
+            qualified_name(proto_file.get_package(), message_type.get_name()),
+            message_type
+        ))
+        .collect()
+}
+
+fn file_enums(proto_file: &FileDescriptorProto) -> Vec<(String, &EnumDescriptorProto)> {
+    file_messages(proto_file)
+        .into_iter()
+        .flat_map(|(full_name, message_type)|
+            message_type.get_enum_type()
+                .iter()
+                .map(move |enum_type| (format!("{}.{}", full_name, enum_type.get_name()), enum_type))
+        )
+        .chain(proto_file.get_enum_type().iter().map(|enum_type|
+            (qualified_name(proto_file.get_package(), enum_type.get_name()), enum_type)
+        ))
+        .collect()
+}
+
+fn uses_json_value(file: &ir::File, options: &Options) -> bool {
+    file.messages
+        .iter()
+        .flat_map(|message| message.fields.iter())
+        .any(|field| is_json_value_type(&field.value.type_name, options))
+}
+
+fn type_name_to_ts_type(type_name: &str, resolver: &TypeResolver, options: &Options) -> TsType {
+    well_known_type_to_ts_type(type_name, options).unwrap_or_else(||
+        TsType::Object(resolver.resolve(type_name, options))
+    )
+}
+
+/// The TypeScript type a field declares through `(tst.ts_type)`, either `Type`
+/// or `Type from "module"`. For maps it replaces the value type.
+fn field_ts_type_option(field: &FieldDescriptorProto) -> Option<String> {
+    string_option(field.get_options().get_unknown_fields(), OPTION_TS_TYPE)
+}
+
+fn field_type_to_ts_type(
+    field: &FieldDescriptorProto,
+    resolver: &TypeResolver,
+    options: &Options
+) -> TsType {
+    if let Some(ts_type) = field_ts_type_option(field) {
+        return TsType::Object(TypeOverride::parse(&ts_type).ts_type);
+    }
+    match field.get_field_type() {
+        FieldDescriptorProto_Type::TYPE_ENUM |
+            FieldDescriptorProto_Type::TYPE_MESSAGE |
+            FieldDescriptorProto_Type::TYPE_GROUP =>
+            type_name_to_ts_type(field.get_type_name(), resolver, options),
+        // google-protobuf reads 64-bit integers as strings when asked to.
+        field_type if options.compat_style.is_some() &&
+            field.get_options().get_jstype() == FieldOptions_JSType::JS_STRING &&
+            scalar_name(field_type).ends_with("64") &&
+            !options.type_overrides.contains_key(scalar_name(field_type)) => TsType::String,
+        field_type if is_branded_int(scalar_name(field_type), options) => TsType::Object(scalar_name(field_type).to_string()),
+        field_type => scalar_to_ts_type(field_type, options)
+    }
+}
+
+enum TsFieldType {
+    Single(TsType),
+    Array(TsType),
+    Record(TsType, TsType),
+    Map(TsType, TsType)
+}
+
+impl TsFieldType {
+    fn display(&self, is_readonly: bool) -> TsFieldTypeDisplay<'_> {
+        TsFieldTypeDisplay{ ts_type: self, is_readonly }
+    }
+}
+
+impl fmt::Display for TsFieldType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display(true))
+    }
+}
+
+struct TsFieldTypeDisplay<'a> {
+    ts_type: &'a TsFieldType,
+    is_readonly: bool
+}
+
+impl<'a> fmt::Display for TsFieldTypeDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.ts_type, self.is_readonly) {
+            (TsFieldType::Single(ts_type), _) => write!(f, "{}", ts_type),
+            (TsFieldType::Array(ts_type), true) => write!(f, "ReadonlyArray<{}>", ts_type),
+            (TsFieldType::Array(ts_type), false) => write!(f, "Array<{}>", ts_type),
+            (TsFieldType::Record(key, value), true) => write!(f, "Readonly<Record<{}, {}>>", key, value),
+            (TsFieldType::Record(key, value), false) => write!(f, "Record<{}, {}>", key, value),
+            (TsFieldType::Map(key, value), true) => write!(f, "ReadonlyMap<{}, {}>", key, value),
+            (TsFieldType::Map(key, value), false) => write!(f, "Map<{}, {}>", key, value)
+        }
+    }
+}
+
+/// An `@example` section showing `code` fenced as TypeScript, unless it
+/// is fenced already.
+fn example_tag(code: &str) -> String {
+    match code.trim_start().starts_with("```") {
+        true => format!("@example\n{}", code),
+        false => format!("@example\n```ts\n{}\n```", code)
+    }
+}
+
+fn is_example_marker(line: &str) -> bool {
+    let line = line.trim();
+    line.eq_ignore_ascii_case("example:") || line.eq_ignore_ascii_case("examples:")
+}
+
+/// Splits `comment` into its prose and the `@example` sections of the code
+/// below its `Example:` lines: the following lines up to a blank line, or
+/// the indented lines when the code is indented. Examples go after the
+/// prose since an `@example` section runs up to the next tag.
+fn comment_examples(comment: &str) -> (String, Vec<String>) {
+    let lines = comment.lines().collect::<Vec<_>>();
+    let is_indented = |line: &&str| line.starts_with(char::is_whitespace);
+    let mut prose = Vec::<&str>::new();
+    let mut examples = Vec::new();
+    let mut start = 0;
+    while start < lines.len() {
+        let line = lines[start];
+        start += 1;
+        if !is_example_marker(line) || lines.get(start).is_none_or(|line| line.trim().is_empty()) {
+            if !line.is_empty() || prose.last().is_some_and(|last| !last.is_empty()) {
+                prose.push(line);
+            }
+            continue;
+        }
+        let mut end = match is_indented(&lines[start]) {
+            true => lines[start..].iter().position(|line| !line.trim().is_empty() && !is_indented(line)),
+            false => lines[start..].iter().position(|line| line.trim().is_empty())
+        }.map_or(lines.len(), |length| start + length);
+        while lines[end - 1].trim().is_empty() {
+            end -= 1;
+        }
+        let code = &lines[start..end];
+        let indent = code.iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.len() - line.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        let code = code.iter()
+            .map(|line| line.get(indent..).unwrap_or("").trim_end())
+            .collect::<Vec<_>>()
+            .join("\n");
+        examples.push(example_tag(&code));
+        start = end;
+    }
+    (prose.join("\n").trim_end().to_string(), examples)
+}
+
+/// Documentation of a declaration: its proto comment followed by the
+/// examples of the comment and of the `(tst.example)` options, and by a
+/// `@deprecated` tag when the element is marked deprecated.
+fn ts_doc(comment: Option<&str>, is_deprecated: bool, unknown_fields: &protobuf::UnknownFields) -> Option<String> {
+    let (prose, examples) = comment.map(comment_examples).unwrap_or_default();
+    let sections = Some(prose)
+        .filter(|prose| !prose.is_empty())
+        .into_iter()
+        .chain(examples)
+        .chain(string_options(unknown_fields, OPTION_EXAMPLE).iter().map(|code| example_tag(code.trim_matches('\n'))))
+        .chain(match is_deprecated {
+            true => Some("@deprecated".to_string()),
+            false => None
+        })
+        .collect::<Vec<_>>();
+    match sections.is_empty() {
+        true => None,
+        false => Some(sections.join("\n\n"))
+    }
+}
+
+/// The `[default = ...]` of a proto2 field as a `@defaultValue` tag. Bytes
+/// are already escaped by protoc, strings are quoted like JSON.
+fn default_value_tag(field: &FieldDescriptorProto) -> Option<String> {
+    if !field.has_default_value() {
+        return None;
+    }
+    let value = field.get_default_value();
+    Some(match field.get_field_type() {
+        FieldDescriptorProto_Type::TYPE_STRING => format!("@defaultValue {}", jsonschema::json_string(value)),
+        FieldDescriptorProto_Type::TYPE_BYTES => format!("@defaultValue \"{}\"", value),
+        _ => format!("@defaultValue {}", value)
+    })
+}
+
+/// Documentation of `field`, followed by tags describing its validation
+/// rules and its proto2 default value.
+fn field_doc(
+    full_name: &str,
+    field: &FieldDescriptorProto,
+    message_type: &DescriptorProto,
+    resolver: &TypeResolver
+) -> Option<String> {
+    let comment = resolver.comment(&format!("{}.{}", full_name, field.get_name()));
+    let mut tags = validate::field_constraints(field, message_type)
+        .map(|constraints| validate::doc_tags(&constraints, map_entry_type(field, message_type).is_some()))
+        .unwrap_or_default();
+    tags.extend(default_value_tag(field));
+    let comment = match (comment, tags.is_empty()) {
+        (comment, true) => comment.map(str::to_string),
+        (Some(comment), false) => Some(format!("{}\n\n{}", comment, tags.join("\n"))),
+        (None, false) => Some(tags.join("\n"))
+    };
+    ts_doc(comment.as_deref(), field.get_options().get_deprecated(), field.get_options().get_unknown_fields())
+}
+
+struct TsDoc<'a> {
+    comment: Option<&'a str>,
+    /// `file:line` of the proto definition, written as a breadcrumb above
+    /// the documentation.
+    source: Option<&'a str>,
+    indent: &'a str
+}
+
+impl<'a> fmt::Display for TsDoc<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(source) = self.source {
+            writeln!(f, "{}// source: {}", self.indent, source)?;
+        }
+        let comment = match self.comment {
+            Some(comment) => comment.replace("*/", "*\\/"),
+            None => return Ok(())
+        };
+        let lines = comment.lines().collect::<Vec<_>>();
+        if let [line] = lines[..] {
+            return writeln!(f, "{}/** {} */", self.indent, line);
+        }
+        writeln!(f, "{}/**", self.indent)?;
+        for line in lines {
+            match line.is_empty() {
+                true => writeln!(f, "{} *", self.indent)?,
+                false => writeln!(f, "{} * {}", self.indent, line)?
+            }
+        }
+        writeln!(f, "{} */", self.indent)
+    }
+}
+
+struct TsField {
+    key: String,
+    ts_type: String,
+    is_required: bool,
+    is_nullable: bool,
+    doc: Option<String>,
+    source: Option<String>
+}
+
+impl TsField {
+    fn doc<'a>(&'a self, indent: &'a str) -> TsDoc<'a> {
+        TsDoc{ comment: self.doc.as_deref(), source: self.source.as_deref(), indent }
+    }
+}
+
+impl fmt::Display for TsField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nullable = match self.is_nullable {
+            true => " | null",
+            false => ""
+        };
+        let key = property_key(&self.key);
+        match self.is_required {
+            true => writeln!(f, "{}: {}{};", key, self.ts_type, nullable),
+            false => writeln!(f, "{}?: {}{};", key, self.ts_type, nullable)
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct TsModifiers {
+    is_exported: bool,
+    is_ambient: bool
+}
+
+impl TsModifiers {
+    fn top_level(options: &Options) -> TsModifiers {
+        if options.namespaces {
+            return TsModifiers::nested();
+        }
+        match options.module_style {
+            ModuleStyle::Esm => TsModifiers{ is_exported: true, is_ambient: false },
+            ModuleStyle::Global => TsModifiers{ is_exported: false, is_ambient: true },
+            ModuleStyle::Module => TsModifiers{ is_exported: true, is_ambient: true }
+        }
+    }
+
+    fn nested() -> TsModifiers {
+        TsModifiers{ is_exported: true, is_ambient: true }
+    }
+
+    fn export(&self) -> &'static str {
+        if self.is_exported { "export " } else { "" }
+    }
+
+    fn declare(&self) -> &'static str {
+        if self.is_ambient { "" } else { "declare " }
+    }
+}
+
+struct TsObjectType {
+    modifiers: TsModifiers,
+    name: String,
+    doc: Option<String>,
+    source: Option<String>,
+    is_readonly: bool,
+    is_interface: bool,
+    /// The type declared when there are neither fields nor oneofs.
+    empty: String,
+    fields: Vec<TsField>,
+    oneof_list: Vec<Vec<TsField>>,
+    /// Whether each oneof union ends with a variant where no member is set.
+    is_oneof_unset_variant: bool
+}
+
+impl fmt::Display for TsObjectType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let oneof_list_len = self.oneof_list.len();
+        let fields_len = self.fields.len();
+        write!(f, "{}", TsDoc{ comment: self.doc.as_deref(), source: self.source.as_deref(), indent: "" })?;
+        // An interface cannot express the exclusive unions of property-style
+        // oneofs, so such messages stay type aliases.
+        if fields_len == 0 && oneof_list_len == 0 {
+            return match self.is_interface && self.empty == "{}" {
+                true => writeln!(f, "{}interface {} {{}}", self.modifiers.export(), self.name),
+                false => writeln!(f, "{}type {} = {};", self.modifiers.export(), self.name, self.empty)
+            };
+        }
+        if self.is_interface && oneof_list_len == 0 {
+            let readonly = match self.is_readonly {
+                true => "readonly ",
+                false => ""
+            };
+            writeln!(f, "{}interface {} {{", self.modifiers.export(), self.name)?;
+            for field in self.fields.iter() {
+                write!(f, "{}  {}{}", field.doc("  "), readonly, field)?;
+            }
+            return writeln!(f, "}}");
+        }
+        write!(f, "{}type {} = ", self.modifiers.export(), self.name)?;
+        let (open, close) = match self.is_readonly {
+            true => ("Readonly<", ">"),
+            false => ("", "")
+        };
+        if fields_len > 0 {
+            writeln!(f, "{}{{", open)?;
+        }
+        for field in self.fields.iter() {
+            write!(f, "{}  {}", field.doc("  "), field)?;
+        }
+        if fields_len > 0 {
+            write!(f, "}}{}", close)?;
+            if oneof_list_len > 0 { write!(f, " & ")?; }
+        }
+        for (i, oneof) in self.oneof_list.iter().enumerate() {
+            let oneof_last_index = oneof.len() - 1;
+            match self.is_readonly {
+                true => writeln!(f, "Readonly<")?,
+                false => writeln!(f, "(")?
+            }
+            for (j, field_i) in oneof.iter().enumerate() {
+                writeln!(f, "    {{")?;
+                for field_j in oneof.iter() {
+                    if field_i.key == field_j.key {
+                        write!(f, "{}      {}", field_j.doc("      "), field_j)?;
+                    } else {
+                        write!(
+                            f,
+                            "      {}",
+                            TsField{
+                                key: field_j.key.clone(),
+                                ts_type: TsType::Never.to_string(),
+                                is_required: false,
+                                is_nullable: false,
+                                doc: None,
+                                source: None
+                            }
+                        )?;
+                    }
+                }
+                write!(f, "    }}")?;
+                if j < oneof_last_index || self.is_oneof_unset_variant { write!(f, " |")?; }
+                writeln!(f)?;
+            }
+            if self.is_oneof_unset_variant {
+                writeln!(f, "    {{")?;
+                for field in oneof.iter() {
+                    writeln!(f, "      {}?: never;", property_key(&field.key))?;
+                }
+                writeln!(f, "    }}")?;
+            }
+            match self.is_readonly {
+                true => write!(f, "  >")?,
+                false => write!(f, "  )")?
+            }
+            if i < oneof_list_len - 1 { write!(f, " & ")?; }
+        }
+        writeln!(f, ";")?;
+        Ok(())
+    }
+}
+
+struct TsEnumValue {
+    name: String,
+    number: i32,
+    doc: Option<String>
+}
+
+struct TsEnum {
+    modifiers: TsModifiers,
+    name: String,
+    doc: Option<String>,
+    source: Option<String>,
+    values: Vec<TsEnumValue>,
+    style: EnumStyle
+}
+
+impl fmt::Display for TsEnum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", TsDoc{ comment: self.doc.as_deref(), source: self.source.as_deref(), indent: "" })?;
+        match self.style {
+            EnumStyle::Union => {
+                write!(f, "{}type {} = ", self.modifiers.export(), self.name)?;
+                if self.values.is_empty() { write!(f, "never")?; }
+                for (i, value) in self.values.iter().enumerate() {
+                    if i > 0 { write!(f, " | ")?; }
+                    write!(f, "\"{}\"", value.name)?;
+                }
+                writeln!(f, ";")
+            },
+            EnumStyle::Enum => {
+                writeln!(
+                    f,
+                    "{}{}enum {} {{",
+                    self.modifiers.export(),
+                    self.modifiers.declare(),
+                    self.name
+                )?;
+                for value in self.values.iter() {
+                    let doc = TsDoc{ comment: value.doc.as_deref(), source: None, indent: "  " };
+                    writeln!(f, "{}  {} = {},", doc, value.name, value.number)?;
+                }
+                writeln!(f, "}}")
+            },
+            EnumStyle::Const => {
+                writeln!(
+                    f,
+                    "{}{}const {}: {{",
+                    self.modifiers.export(),
+                    self.modifiers.declare(),
+                    self.name
+                )?;
+                for value in self.values.iter() {
+                    let doc = TsDoc{ comment: value.doc.as_deref(), source: None, indent: "  " };
+                    writeln!(f, "{}  readonly {}: {};", doc, value.name, value.number)?;
+                }
+                writeln!(f, "}};")?;
+                writeln!(
+                    f,
+                    "{}type {1} = typeof {1}[keyof typeof {1}];",
+                    self.modifiers.export(),
+                    self.name
+                )
+            }
+        }
+    }
+}
+
+struct TsMethod {
+    name: String,
+    doc: Option<String>,
+    request: String,
+    response: String,
+    client_streaming: bool,
+    server_streaming: bool
+}
+
+impl fmt::Display for TsMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}(request: ", self.name)?;
+        match self.client_streaming {
+            true => write!(f, "AsyncIterable<{}>", self.request)?,
+            false => write!(f, "{}", self.request)?
+        }
+        match self.server_streaming {
+            true => writeln!(f, "): AsyncIterable<{}>;", self.response),
+            false => writeln!(f, "): Promise<{}>;", self.response)
+        }
+    }
+}
+
+/// The server side of `method`, taking the context of the call and
+/// returning the response or its stream.
+struct TsHandlerMethod<'a>(&'a TsMethod);
+
+impl<'a> fmt::Display for TsHandlerMethod<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let method = self.0;
+        match method.client_streaming {
+            true => write!(f, "{}(requests: AsyncIterable<{}>", method.name, method.request)?,
+            false => write!(f, "{}(request: {}", method.name, method.request)?
+        }
+        match method.server_streaming {
+            true => writeln!(f, ", context: Context): AsyncIterable<{}>;", method.response),
+            false => writeln!(f, ", context: Context): Promise<{0}> | {0};", method.response)
+        }
+    }
+}
+
+struct TsService {
+    modifiers: TsModifiers,
+    name: String,
+    doc: Option<String>,
+    source: Option<String>,
+    methods: Vec<TsMethod>,
+    /// Whether this is the handlers interface, generic over the context its
+    /// server passes.
+    is_handlers: bool
+}
+
+impl fmt::Display for TsService {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", TsDoc{ comment: self.doc.as_deref(), source: self.source.as_deref(), indent: "" })?;
+        match self.is_handlers {
+            true => writeln!(f, "{}interface {}<Context = unknown> {{", self.modifiers.export(), self.name)?,
+            false => writeln!(f, "{}interface {} {{", self.modifiers.export(), self.name)?
+        }
+        for method in self.methods.iter() {
+            write!(f, "{}  ", TsDoc{ comment: method.doc.as_deref(), source: None, indent: "  " })?;
+            match self.is_handlers {
+                true => write!(f, "{}", TsHandlerMethod(method))?,
+                false => write!(f, "{}", method)?
+            }
+        }
+        writeln!(f, "}}")
+    }
+}
+
+fn lower_camel_case(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new()
+    }
+}
+
+/// Converts a snake_case proto name to lowerCamelCase the way protoc derives
+/// `json_name`.
+fn snake_to_lower_camel_case(name: &str) -> String {
+    let mut is_upper = false;
+    let mut result = String::new();
+    for c in name.chars() {
+        match (c, is_upper) {
+            ('_', _) => is_upper = true,
+            (c, true) => {
+                result.extend(c.to_uppercase());
+                is_upper = false;
+            },
+            (c, false) => result.push(c)
+        }
+    }
+    result
+}
+
+/// Property key of `field` in generated types. `camel` derives the key from
+/// the proto name and ignores an explicit `json_name`.
+fn field_key(field: &FieldDescriptorProto, options: &Options) -> String {
+    if let Some(name) = string_option(field.get_options().get_unknown_fields(), OPTION_NAME) {
+        return name;
+    }
+    match options.field_name_style {
+        FieldNameStyle::Json => field.get_json_name().to_string(),
+        FieldNameStyle::Original => field.get_name().to_string(),
+        FieldNameStyle::Camel => snake_to_lower_camel_case(field.get_name())
+    }
+}
+
+fn oneof_key(oneof: &OneofDescriptorProto, options: &Options) -> String {
+    match options.field_name_style {
+        FieldNameStyle::Original => oneof.get_name().to_string(),
+        FieldNameStyle::Json | FieldNameStyle::Camel => snake_to_lower_camel_case(oneof.get_name())
+    }
+}
+
+const FIELD_PROTO3_OPTIONAL: u32 = 17;
+const RESPONSE_SUPPORTED_FEATURES: u32 = 2;
+const FEATURE_PROTO3_OPTIONAL: u64 = 1;
+
+fn is_proto3_optional(field: &FieldDescriptorProto) -> bool {
+    field.get_unknown_fields()
+        .get(FIELD_PROTO3_OPTIONAL)
+        .map(|values| values.varint.iter().any(|&value| value != 0))
+        .unwrap_or(false)
+}
+
+/// Singular message fields, unless proto2 `required`, have explicit presence
+/// and are optional unless `force_message_required=true`. Wrapper types
+/// already model absence as `null`. Oneof members are absent whenever a
+/// sibling is set instead.
+fn is_optional_message(field: &FieldDescriptorProto, options: &Options) -> bool {
+    !options.force_message_required &&
+        !field.has_oneof_index() &&
+        field.get_label() == FieldDescriptorProto_Label::LABEL_OPTIONAL &&
+        match field.get_field_type() {
+            FieldDescriptorProto_Type::TYPE_MESSAGE | FieldDescriptorProto_Type::TYPE_GROUP =>
+                !matches!(
+                    well_known_type_to_ts_type(field.get_type_name(), options),
+                    Some(TsType::Nullable(_))
+                ),
+            _ => false
+        }
+}
+
+/// Whether `field` is emitted as an optional property outside of a oneof.
+/// Its absence is spelled according to `optional_style`.
+fn is_optional_field(field: &FieldDescriptorProto, options: &Options) -> bool {
+    is_proto3_optional(field) || is_optional_message(field, options)
+}
+
+/// Condition under which the optional property `value` holds no value.
+fn absent_check(value: &str, options: &Options) -> String {
+    match options.optional_style {
+        OptionalStyle::Undefined => format!("{} === undefined", value),
+        OptionalStyle::Null => format!("{} === null", value),
+        OptionalStyle::Both => format!("{} == null", value)
+    }
+}
+
+/// Condition under which the optional property `value` holds a value.
+fn present_check(value: &str, options: &Options) -> String {
+    match options.optional_style {
+        OptionalStyle::Undefined => format!("{} !== undefined", value),
+        OptionalStyle::Null => format!("{} !== null", value),
+        OptionalStyle::Both => format!("{} != null", value)
+    }
+}
+
+/// A property of a generated message type: a single field, or with
+/// `oneof=discriminated` all members of a oneof grouped under its name.
+enum MessageProperty<'a> {
+    Field(&'a FieldDescriptorProto),
+    Oneof {
+        name: &'a str,
+        key: String,
+        fields: Vec<&'a FieldDescriptorProto>
+    }
+}
+
+fn discriminated_oneof<'a>(
+    field: &FieldDescriptorProto,
+    message_type: &'a DescriptorProto,
+    options: &Options
+) -> Option<&'a OneofDescriptorProto> {
+    match options.oneof_style {
+        OneofStyle::Discriminated if field.has_oneof_index() && !is_proto3_optional(field) =>
+            message_type.get_oneof_decl().get(field.get_oneof_index() as usize),
+        _ => None
+    }
+}
+
+/// Key of the discriminated oneof property `field` of a message with
+/// `oneofs` is stored under, if it is stored under one.
+fn discriminated_oneof_key<'a>(field: &ir::Field, oneofs: &'a [ir::Oneof], options: &Options) -> Option<&'a str> {
+    match options.oneof_style {
+        OneofStyle::Discriminated => field.oneof.map(|index| oneofs[index].key.as_str()),
+        _ => None
+    }
+}
+
+/// Condition and expression selecting `field` from the discriminated oneof
+/// property of `message` in generated runtime code.
+fn discriminated_oneof_access(
+    message: &str,
+    field: &ir::Field,
+    oneofs: &[ir::Oneof],
+    options: &Options
+) -> Option<(String, String)> {
+    let key = discriminated_oneof_key(field, oneofs, options)?;
+    Some((
+        format!("{}[\"{}\"]?.$case === \"{}\"", message, key, field.key),
+        format!("{}[\"{}\"][\"{}\"]", message, key, field.key)
+    ))
+}
+
+/// Statement storing `value` as `field` on `message`, replacing any other
+/// member of its discriminated oneof.
+fn discriminated_oneof_assign(
+    message: &str,
+    field: &ir::Field,
+    oneofs: &[ir::Oneof],
+    value: &str,
+    options: &Options
+) -> Option<String> {
+    let key = discriminated_oneof_key(field, oneofs, options)?;
+    Some(format!("{}[\"{}\"] = {{ $case: \"{2}\", {2}: {3} }}", message, key, field.key, value))
+}
+
+fn message_properties<'a>(message_type: &'a DescriptorProto, options: &Options) -> Vec<MessageProperty<'a>> {
+    let mut properties = Vec::<MessageProperty>::new();
+    for field in message_type.get_field() {
+        let oneof = match discriminated_oneof(field, message_type, options) {
+            Some(oneof) => oneof,
+            None => {
+                properties.push(MessageProperty::Field(field));
+                continue;
+            }
+        };
+        let grouped = properties.iter_mut().find_map(|property| match property {
+            MessageProperty::Oneof{ name, fields, .. } if *name == oneof.get_name() => Some(fields),
+            _ => None
+        });
+        match grouped {
+            Some(fields) => fields.push(field),
+            None => properties.push(MessageProperty::Oneof{
+                name: oneof.get_name(),
+                key: oneof_key(oneof, options),
+                fields: vec![field]
+            })
+        }
+    }
+    properties
+}
+
+fn map_entry_type<'a>(
+    field: &FieldDescriptorProto,
+    message_type: &'a DescriptorProto
+) -> Option<&'a DescriptorProto> {
+    if field.get_field_type() != FieldDescriptorProto_Type::TYPE_MESSAGE ||
+        field.get_label() != FieldDescriptorProto_Label::LABEL_REPEATED {
+        return None;
+    }
+    let entry_name = field.get_type_name().rsplit('.').next().unwrap_or("");
+    message_type.get_nested_type()
+        .iter()
+        .find(|nested_type|
+            nested_type.get_options().get_map_entry() && nested_type.get_name() == entry_name
+        )
+}
+
+/// Fills the value placeholder `$v` of an expression template generated for
+/// a field, such as a guard, factory or codec of its values.
+fn apply(template: &str, value: &str) -> String {
+    template.replace("$v", value)
+}
+
+const FIELD_PATH_SUFFIX: &str = "FieldPath";
+
+/// The message whose paths a `google.protobuf.FieldMask` field holds, named
+/// by `(tst.field_mask)` and known to the generator.
+fn field_mask_target(field: &FieldDescriptorProto, resolver: &TypeResolver) -> Option<String> {
+    if field.get_type_name() != ".google.protobuf.FieldMask" ||
+        field.get_label() == FieldDescriptorProto_Label::LABEL_REPEATED {
+        return None;
+    }
+    let target = string_option(field.get_options().get_unknown_fields(), OPTION_FIELD_MASK)?;
+    let target = format!(".{}", target.trim().trim_start_matches('.'));
+    resolver.message_type(&target).map(|_| target)
+}
+
+/// Reference to the `<Message>FieldPath` alias of `type_name`, resolved by
+/// `import_source`.
+fn field_path_reference(type_name: &str) -> String {
+    format!("{}#{}", type_name, FIELD_PATH_SUFFIX)
+}
+
+/// Union of the field mask paths of `message`, descending into singular
+/// message fields until `depth` segments.
+fn field_path_union(message: &ir::Message, depth: usize, types: &ir::Types) -> String {
+    let paths = message.fields
+        .iter()
+        .flat_map(|field| {
+            let nested = masks::nested_message(field, types)
+                .filter(|nested_type| depth > 1 && !nested_type.fields.is_empty())
+                .map(|nested_type| format!(
+                    "`{}.${{{}}}`",
+                    field.name,
+                    field_path_union(nested_type, depth - 1, types)
+                ));
+            vec![format!("\"{}\"", field.name)].into_iter().chain(nested)
+        })
+        .collect::<Vec<_>>();
+    match paths.is_empty() {
+        true => "never".to_string(),
+        false => paths.join(" | ")
+    }
+}
+
+fn field_to_ts_field_type(
+    field: &FieldDescriptorProto,
+    message_type: &DescriptorProto,
+    resolver: &TypeResolver,
+    options: &Options
+) -> TsFieldType {
+    if let Some(target) = field_mask_target(field, resolver).filter(|_| options.field_paths) {
+        return TsFieldType::Array(TsType::Object(
+            format!("{}{}", resolver.resolve(&target, options), FIELD_PATH_SUFFIX)
+        ));
+    }
+    if let Some(entry) = map_entry_type(field, message_type) {
+        let entry_field = |number: i32| entry.get_field()
+            .iter()
+            .find(|entry_field| entry_field.get_number() == number)
+            .map(|entry_field| field_type_to_ts_type(entry_field, resolver, options))
+            .unwrap_or(TsType::Never);
+        let value = match field_ts_type_option(field) {
+            Some(ts_type) => TsType::Object(TypeOverride::parse(&ts_type).ts_type),
+            None => entry_field(2)
+        };
+        let key = entry_field(1);
+        if options.compat_style.is_some() {
+            return TsFieldType::Array(TsType::Object(format!("[{}, {}]", key, value)));
+        }
+        return match options.map_style {
+            MapStyle::Record => TsFieldType::Record(
+                match key {
+                    TsType::Boolean | TsType::BigInt => TsType::String,
+                    key => key
+                },
+                value
+            ),
+            MapStyle::Map => TsFieldType::Map(key, value)
+        };
+    }
+    match field.get_label() {
+        FieldDescriptorProto_Label::LABEL_OPTIONAL |
+            FieldDescriptorProto_Label::LABEL_REQUIRED =>
+            TsFieldType::Single(field_type_to_ts_type(field, resolver, options)),
+        FieldDescriptorProto_Label::LABEL_REPEATED =>
+            TsFieldType::Array(field_type_to_ts_type(field, resolver, options))
+    }
+}
+
+struct TsNamespace<'a> {
+    modifiers: TsModifiers,
+    scope: &'a [String],
+    content: String
+}
+
+impl<'a> fmt::Display for TsNamespace<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{}{}namespace {} {{",
+            self.modifiers.export(),
+            self.modifiers.declare(),
+            self.scope.join(".")
+        )?;
+        CodeWriter::new(&mut *f).indented(|writer| fmt::Write::write_str(writer, &self.content))?;
+        writeln!(f, "}}")
+    }
+}
+
+fn declaration_modifiers(path: &[String], options: &Options) -> TsModifiers {
+    match options.nested_style {
+        NestedStyle::Namespace if path.len() > 1 => TsModifiers::nested(),
+        _ => TsModifiers::top_level(options)
+    }
+}
+
+fn declared_name(path: &[String], options: &Options) -> String {
+    match options.nested_style {
+        NestedStyle::Namespace => path[path.len() - 1].clone(),
+        NestedStyle::Flat => path.join("_")
+    }
+}
+
+struct TsDeclaration {
+    name: String,
+    content: String,
+    references: Vec<String>,
+    source: Option<String>
+}
+
+fn gen_declaration(
+    path: &[String],
+    content: String,
+    references: Vec<String>,
+    options: &Options
+) -> TsDeclaration {
+    let scope = &path[..path.len() - 1];
+    match options.nested_style {
+        NestedStyle::Namespace if !scope.is_empty() => TsDeclaration{
+            name: path[0].clone(),
+            content: format!("{}", TsNamespace{
+                modifiers: TsModifiers::top_level(options),
+                scope,
+                content
+            }),
+            references,
+            source: None
+        },
+        _ => TsDeclaration{ name: path.join("_"), content, references, source: None }
+    }
+}
+
+/// Types a field refers to. Scalars are referred to by their name, so that
+/// overrides of them can be imported, and `(tst.ts_type)` by its value.
+fn field_references(field: &FieldDescriptorProto, message_type: &DescriptorProto) -> Vec<String> {
+    let reference = |field: &FieldDescriptorProto| match field.get_type_name() {
+        "" => scalar_name(field.get_field_type()).to_string(),
+        type_name => type_name.to_string()
+    };
+    let ts_type = field_ts_type_option(field);
+    match map_entry_type(field, message_type) {
+        Some(entry) => entry.get_field()
+            .iter()
+            .map(|entry_field| match (entry_field.get_number(), &ts_type) {
+                (2, Some(ts_type)) => ts_type.clone(),
+                _ => reference(entry_field)
+            })
+            .collect(),
+        None => vec![ts_type.unwrap_or_else(|| reference(field))]
+    }
+}
+
+/// Type of a discriminated oneof property. Interfaces reference the union
+/// through a `<Message>_<oneof>` helper alias collected in `helpers`.
+fn discriminated_union(
+    message_name: &str,
+    oneof_name: &str,
+    union: String,
+    helpers: &mut Vec<String>,
+    options: &Options
+) -> String {
+    match options.declaration_style {
+        DeclarationStyle::Type => union,
+        DeclarationStyle::Interface => {
+            let helper = format!("{}_{}", message_name, oneof_name);
+            helpers.push(format!("type {} = {};", helper, union));
+            helper
+        }
+    }
+}
+
+/// Key of the optional property branding message types. Values without it
+/// still satisfy the type, values branded as another message do not.
+const BRAND_KEY: &str = "__typename";
+
+/// The type of a message without fields, per `empty`.
+fn empty_message_type(full_name: &str, options: &Options) -> String {
+    match options.empty_style {
+        EmptyStyle::Record => "Record<string, never>".to_string(),
+        EmptyStyle::Object => "{}".to_string(),
+        EmptyStyle::Branded => format!("{{ readonly {}?: \"{}\" }}", BRAND_KEY, full_name.trim_start_matches('.'))
+    }
+}
+
+/// The brand making the type of `message` nominal, with `nominal=true` or
+/// `(tst.nominal)`.
+fn nominal_brand(message: &ir::Message) -> Option<TsField> {
+    if !message.is_nominal {
+        return None;
+    }
+    Some(TsField{
+        key: BRAND_KEY.to_string(),
+        ts_type: format!("\"{}\"", message.full_name.trim_start_matches('.')),
+        is_required: false,
+        is_nullable: false,
+        doc: None,
+        source: None
+    })
+}
+
+/// Key of the property naming the message a value is of, with
+/// `type_field=true`.
+const TYPE_FIELD_KEY: &str = "$type";
+
+/// The literal runtime modes set `$type` to on values of `full_name`, with
+/// `type_field=true`.
+fn type_field_value(full_name: &str, options: &Options) -> Option<String> {
+    match options.type_field {
+        true => Some(format!("\"{}\"", full_name.trim_start_matches('.'))),
+        false => None
+    }
+}
+
+fn type_field(full_name: &str, options: &Options) -> Option<TsField> {
+    Some(TsField{
+        key: TYPE_FIELD_KEY.to_string(),
+        ts_type: type_field_value(full_name, options)?,
+        is_required: false,
+        is_nullable: false,
+        doc: None,
+        source: None
+    })
+}
+
+fn gen_message_declaration(
+    message: &ir::Message,
+    types: &ir::Types,
+    resolver: &TypeResolver,
+    options: &Options
+) -> TsDeclaration {
+    let full_name = message.full_name.as_str();
+    let path = resolver.path(full_name);
+    let source = |name: &str| resolver.source(&format!("{}.{}", full_name, name), options);
+    // `toObject()` sets oneof members as independent, optional properties.
+    let oneof_list = match matches!(options.oneof_style, OneofStyle::Discriminated) || options.compat_style.is_some() {
+        true => Vec::new(),
+        false => (0..message.oneofs.len())
+            .map(|index| message.oneof_fields(index)
+                .map(|field| TsField{
+                    key: field.key.clone(),
+                    ts_type: field.ts_type.clone(),
+                    is_required: options.oneof_allow_unset,
+                    is_nullable: false,
+                    doc: field.doc.clone(),
+                    source: source(&field.name)
+                })
+                .collect::<Vec<_>>())
+            .filter(|oneof| !oneof.is_empty())
+            .collect()
+    };
+    let name = declared_name(&path, options);
+    let mut oneof_helpers = Vec::<String>::new();
+    let mut ts_object_type = TsObjectType{
+        modifiers: declaration_modifiers(&path, options),
+        name: name.clone(),
+        doc: message.doc.clone(),
+        source: resolver.source(full_name, options),
+        is_readonly: options.readonly,
+        is_interface: matches!(options.declaration_style, DeclarationStyle::Interface),
+        empty: empty_message_type(full_name, options),
+        fields: message.properties(options)
+            .into_iter()
+            .filter_map(|property| match property {
+                ir::Property::Field(field) if field.oneof.is_some() && options.compat_style.is_none() => None,
+                ir::Property::Field(field) => Some(TsField{
+                    key: match options.compat_style {
+                        Some(CompatStyle::GrpcWebAsObject) => compat::as_object_key(field),
+                        None => field.key.clone()
+                    },
+                    ts_type: field.ts_type.clone(),
+                    is_required: !field.has_presence() || options.optional_style == OptionalStyle::Null,
+                    is_nullable: field.is_optional && options.optional_style != OptionalStyle::Undefined,
+                    doc: field.doc.clone(),
+                    source: source(&field.name)
+                }),
+                ir::Property::Oneof{ oneof, fields, .. } => Some(TsField{
+                    key: oneof.key.clone(),
+                    ts_type: discriminated_union(
+                        &name,
+                        &oneof.name,
+                        fields.iter()
+                            .map(|field| format!(
+                                "{{ {0}$case: \"{1}\"; {0}{2}: {3} }}",
+                                match options.readonly {
+                                    true => "readonly ",
+                                    false => ""
+                                },
+                                field.key,
+                                property_key(&field.key),
+                                field.ts_type
+                            ))
+                            .collect::<Vec<_>>()
+                            .join(" | "),
+                        &mut oneof_helpers,
+                        options
+                    ),
+                    is_required: false,
+                    is_nullable: false,
+                    doc: oneof.doc.clone(),
+                    source: source(&oneof.name)
+                })
+            })
+            .collect(),
+        oneof_list,
+        is_oneof_unset_variant: options.oneof_allow_unset
+    };
+    ts_object_type.fields.splice(0..0, nominal_brand(message).into_iter().chain(type_field(full_name, options)));
+    let mut content = CodeWriter::for_message(message);
+    content.push(&ts_object_type);
+    for union in oneof_helpers.iter() {
+        content.push(format_args!("{}{}\n", ts_object_type.modifiers.export(), union));
+    }
+    let mut references = message.fields
+        .iter()
+        .flat_map(|field| match field.field_mask.as_deref().filter(|_| options.field_paths) {
+            Some(target) => vec![field_path_reference(target)],
+            None => field.references()
+        })
+        .collect::<Vec<_>>();
+    if options.field_paths || options.field_masks {
+        content.push(format_args!(
+            "{}type {}{} = {};\n",
+            ts_object_type.modifiers.export(),
+            ts_object_type.name,
+            FIELD_PATH_SUFFIX,
+            field_path_union(message, options.field_path_depth, types)
+        ));
+    }
+    if has_mutable_types(options) {
+        content.push(format_args!(
+            "{}type {} = Mutable<{}>;\n",
+            ts_object_type.modifiers.export(),
+            mutable_type_name(&ts_object_type.name),
+            ts_object_type.name
+        ));
+        references.push(MUTABLE_TYPE.to_string());
+    }
+    if let Some(style) = options.partial_style {
+        content.push(format_args!(
+            "{}type {1}{2} = DeepPartial<{1}>;\n",
+            ts_object_type.modifiers.export(),
+            ts_object_type.name,
+            PATCH_SUFFIX
+        ));
+        references.push(DEEP_PARTIAL_TYPE.to_string());
+        if style == PartialStyle::Exact {
+            content.push(format_args!(
+                "{}type Exact{1}{2}<I extends {1}{2}> = Exact<{1}{2}, I>;\n",
+                ts_object_type.modifiers.export(),
+                ts_object_type.name,
+                PATCH_SUFFIX
+            ));
+            references.push(EXACT_TYPE.to_string());
+        }
+    }
+    gen_declaration(&path, content.into_string(), references, options)
+}
+
+/// Sentinel added to enums with `open_enums=true` for values unknown to the
+/// schema, which proto3 enums may carry.
+const UNRECOGNIZED: &str = "UNRECOGNIZED";
+const UNRECOGNIZED_NUMBER: i32 = -1;
+
+/// `PhoneType` becomes `PHONE_TYPE_`, the prefix proto style requires on
+/// the values of the enum.
+fn enum_value_prefix(enum_name: &str) -> String {
+    let mut prefix = String::new();
+    let mut previous: Option<char> = None;
+    for c in enum_name.chars() {
+        if c.is_uppercase() && previous.is_some_and(|previous| previous.is_lowercase() || previous.is_ascii_digit()) {
+            prefix.push('_');
+        }
+        prefix.extend(c.to_uppercase());
+        previous = Some(c);
+    }
+    prefix.push('_');
+    prefix
+}
+
+/// Names of the values of `enum_type` in generated code, following
+/// `enum_strip_prefix` and `enum_value_case`. Values are only stripped when
+/// a valid name remains. JSON keeps using the proto names.
+/// `items`, zipped with the values of `enum_type`, without those of the
+/// values `allow_alias` gives the number of an earlier one. Generated code
+/// keeps the first name of a number, like protobuf runtimes do.
+pub(crate) fn without_aliases<'a, T: 'a>(
+    enum_type: &'a EnumDescriptorProto,
+    items: impl IntoIterator<Item = T> + 'a
+) -> impl Iterator<Item = T> + 'a {
+    let mut numbers = BTreeSet::new();
+    enum_type.get_value()
+        .iter()
+        .zip(items)
+        .filter(move |(value, _)| numbers.insert(value.get_number()))
+        .map(|(_, item)| item)
+}
+
+/// The other names `allow_alias` gives the number of `value`.
+pub(crate) fn enum_aliases<'a>(enum_type: &'a EnumDescriptorProto, value: &EnumValueDescriptorProto) -> Vec<&'a str> {
+    enum_type.get_value()
+        .iter()
+        .filter(|alias| alias.get_number() == value.get_number() && alias.get_name() != value.get_name())
+        .map(|alias| alias.get_name())
+        .collect()
+}
+
+/// The comment of an enum value followed by its aliases.
+pub(crate) fn enum_value_doc(
+    comment: Option<&str>,
+    aliases: &[&str],
+    value: &EnumValueDescriptorProto
+) -> Option<String> {
+    let aliases = aliases.iter().map(|alias| format!("`{}`", alias)).collect::<Vec<_>>().join(", ");
+    let comment = match (comment, aliases.is_empty()) {
+        (comment, true) => comment.map(str::to_string),
+        (Some(comment), false) => Some(format!("{}\n\nAliases: {}", comment, aliases)),
+        (None, false) => Some(format!("Aliases: {}", aliases))
+    };
+    ts_doc(comment.as_deref(), value.get_options().get_deprecated(), value.get_options().get_unknown_fields())
+}
+
+fn enum_value_names(enum_type: &EnumDescriptorProto, options: &Options) -> Vec<String> {
+    let prefix = enum_value_prefix(enum_type.get_name());
+    enum_type.get_value()
+        .iter()
+        .map(|value| {
+            let name = match value.get_name().strip_prefix(prefix.as_str()) {
+                Some(stripped) if options.enum_strip_prefix &&
+                    stripped.starts_with(|c: char| c.is_ascii_alphabetic()) => stripped,
+                _ => value.get_name()
+            };
+            let words = name.split('_').filter(|word| !word.is_empty()).map(|word| word.to_lowercase());
+            let capitalize = |word: String| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                    None => String::new()
+                }
+            };
+            match options.enum_value_case {
+                EnumValueCase::Screaming => name.to_string(),
+                EnumValueCase::Lower => words.collect::<Vec<_>>().join("_"),
+                EnumValueCase::Pascal => words.map(capitalize).collect(),
+                EnumValueCase::Camel => lower_camel_case(&words.map(capitalize).collect::<String>())
+            }
+        })
+        .collect()
+}
+
+fn gen_enum_declaration(enum_type: &ir::Enum, resolver: &TypeResolver, options: &Options) -> TsDeclaration {
+    let path = resolver.path(&enum_type.full_name);
+    let ts_enum = TsEnum{
+        modifiers: declaration_modifiers(&path, options),
+        name: declared_name(&path, options),
+        doc: enum_type.doc.clone(),
+        source: resolver.source(&enum_type.full_name, options),
+        values: enum_type.values
+            .iter()
+            .map(|value| TsEnumValue{ name: value.ts_name.clone(), number: value.number, doc: value.doc.clone() })
+            .chain(match options.open_enums {
+                true => Some(TsEnumValue{ name: UNRECOGNIZED.to_string(), number: UNRECOGNIZED_NUMBER, doc: None }),
+                false => None
+            })
+            .collect(),
+        style: options.enum_style
+    };
+    gen_declaration(
+        &path,
+        format!("{}", ts_enum),
+        vec![],
+        options
+    )
+}
+
+/// The `<Service>Client` interface, or with `is_handlers` the
+/// `<Service>Handlers` interface servers implement.
+fn gen_service_declaration(
+    service: &ir::Service,
+    is_handlers: bool,
+    resolver: &TypeResolver,
+    options: &Options
+) -> TsDeclaration {
+    let name = match is_handlers {
+        true => format!("{}Handlers", service.name),
+        false => format!("{}Client", service.name)
+    };
+    let ts_service = TsService{
+        modifiers: TsModifiers::top_level(options),
+        name: name.clone(),
+        doc: service.doc.clone(),
+        source: resolver.source(&service.full_name, options),
+        methods: service.methods
+            .iter()
+            .map(|method| TsMethod{
+                name: lower_camel_case(&method.name),
+                doc: method.doc.clone(),
+                request: method.input.ts_type.clone(),
+                response: method.output.ts_type.clone(),
+                client_streaming: method.client_streaming,
+                server_streaming: method.server_streaming
+            })
+            .collect(),
+        is_handlers
+    };
+    TsDeclaration{
+        name,
+        content: format!("{}", ts_service),
+        references: service.methods
+            .iter()
+            .flat_map(|method| vec![method.input.type_name.clone(), method.output.type_name.clone()])
+            .collect(),
+        source: None
+    }
+}
+
+/// The declarations of `file`. Enums follow the message they are nested in,
+/// before its nested messages, as in the proto.
+fn gen_file_declarations(
+    file: &ir::File,
+    types: &ir::Types,
+    resolver: &TypeResolver,
+    options: &Options
+) -> Vec<TsDeclaration> {
+    let mut declarations = Vec::new();
+    for message in file.messages.iter() {
+        declarations.push(gen_message_declaration(message, types, resolver, options));
+        declarations.extend(file.enums_in(Some(&message.full_name))
+            .map(|enum_type| gen_enum_declaration(enum_type, resolver, options)));
+    }
+    declarations.extend(file.enums_in(None).map(|enum_type| gen_enum_declaration(enum_type, resolver, options)));
+    declarations.extend(extensions::gen_extension_declarations(file, resolver, options));
+    for service in file.services.iter() {
+        declarations.push(gen_service_declaration(service, false, resolver, options));
+        if options.handlers {
+            declarations.push(gen_service_declaration(service, true, resolver, options));
+        }
+    }
+    declarations
+}
+
+const SINGLE_FILE_STEM: &str = "types";
+
+/// Splits `file_suffix` into the part appended to the stem of declaration
+/// files and their extension, which import specifiers leave out.
+fn declaration_suffix(options: &Options) -> (&str, &str) {
+    let extension = match options.file_suffix.ends_with(".d.ts") {
+        true => ".d.ts",
+        false => ".ts"
+    };
+    (&options.file_suffix[..options.file_suffix.len() - extension.len()], extension)
+}
+
+/// Splits a file base name into words at `_`, `-`, `.` and lower to upper
+/// case transitions.
+fn file_name_words(name: &str) -> Vec<String> {
+    let mut words = Vec::<String>::new();
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        match c {
+            '_' | '-' | '.' => words.push(String::new()),
+            c => {
+                let is_boundary = c.is_uppercase()
+                    && previous.is_some_and(|previous| previous.is_lowercase() || previous.is_ascii_digit());
+                match words.last_mut() {
+                    Some(word) if !is_boundary => word.push(c),
+                    _ => words.push(c.to_string())
+                }
+            }
+        }
+        previous = Some(c);
+    }
+    words.into_iter().filter(|word| !word.is_empty()).collect()
+}
+
+fn convert_file_case(name: &str, options: &Options) -> String {
+    let words = file_name_words(name);
+    match options.file_case {
+        FileCase::Preserve => name.to_string(),
+        FileCase::Snake => words.iter().map(|word| word.to_lowercase()).collect::<Vec<_>>().join("_"),
+        FileCase::Kebab => words.iter().map(|word| word.to_lowercase()).collect::<Vec<_>>().join("-"),
+        FileCase::Pascal => words.iter()
+            .map(|word| {
+                let word = word.to_lowercase();
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => String::new()
+                }
+            })
+            .collect()
+    }
+}
+
+/// The stem of an output file generated for the proto file `file_name`. The
+/// base name follows `file_case` while directories are kept as they are.
+fn output_file_stem(file_name: &str, suffix: &str, resolver: &TypeResolver, options: &Options) -> String {
+    output_path_stem(&resolver.output_path(file_name, options), suffix, options)
+}
+
+fn output_path_stem(path: &str, suffix: &str, options: &Options) -> String {
+    let (dir, base) = match path.rfind('/') {
+        Some(index) => (&path[..=index], &path[index + 1..]),
+        None => ("", path)
+    };
+    format!("{}{}{}", dir, convert_file_case(base, options), suffix)
+}
+
+fn proto_file_stem(file_name: &str, resolver: &TypeResolver, options: &Options) -> String {
+    output_file_stem(file_name, declaration_suffix(options).0, resolver, options)
+}
+
+/// The specifier of the output `to_stem`, a file ending in `to_extension`,
+/// relative to the output `from_stem` or under `alias_prefix`.
+fn relative_import(from_stem: &str, to_stem: &str, to_extension: &str, options: &Options) -> String {
+    let extension = match options.import_extension {
+        ImportExtension::None => "",
+        ImportExtension::Js => ".js",
+        ImportExtension::Ts => to_extension
+    };
+    if let Some(prefix) = &options.alias_prefix {
+        return format!("{}/{}{}", prefix, to_stem, extension);
+    }
+    let from_dir = from_stem.split('/').collect::<Vec<_>>();
+    let from_dir = &from_dir[..from_dir.len() - 1];
+    let to = to_stem.split('/').collect::<Vec<_>>();
+    let common = from_dir.iter()
+        .zip(to.iter())
+        .take_while(|(from, to)| from == to)
+        .count();
+    let ups = from_dir.len() - common;
+    let rest = to[common..].join("/");
+    match ups {
+        0 => format!("./{}{}", rest, extension),
+        _ => format!("{}{}{}", "../".repeat(ups), rest, extension)
+    }
+}
+
+/// Returns the identifier and output file stem that provide `type_name`, if
+/// it has to be imported at all.
+fn import_source(
+    type_name: &str,
+    resolver: &TypeResolver,
+    options: &Options
+) -> Option<(String, String)> {
+    if type_name == MUTABLE_TYPE {
+        return Some((MUTABLE_TYPE.to_string(), MUTABLE_FILE_STEM.to_string()));
+    }
+    if type_name == DEEP_PARTIAL_TYPE || type_name == EXACT_TYPE {
+        return Some((type_name.to_string(), PARTIALS_FILE_STEM.to_string()));
+    }
+    if is_json_value_type(type_name, options) {
+        return Some((json_value_import_name(type_name).to_string(), JSON_VALUE_FILE_STEM.to_string()));
+    }
+    if is_branded_int(type_name, options) {
+        return Some((type_name.to_string(), BRANDED_INTS_FILE_STEM.to_string()));
+    }
+    if let Some(message_name) = type_name.strip_suffix(&format!("#{}", FIELD_PATH_SUFFIX)) {
+        let (import_name, source_stem) = import_source(message_name, resolver, options)?;
+        return Some(match import_name == resolver.resolve(message_name, options) {
+            true => (format!("{}{}", import_name, FIELD_PATH_SUFFIX), source_stem),
+            false => (import_name, source_stem)
+        });
+    }
+    if well_known_type_to_ts_type(type_name, options).is_some() {
+        return None;
+    }
+    let file_name = resolver.file(type_name)?;
+    let import_name = resolver.import_name(type_name, options);
+    match options.output_mode {
+        OutputMode::PerFile => Some((import_name, proto_file_stem(file_name, resolver, options))),
+        OutputMode::PerMessage => Some((import_name.clone(), resolver.declaration_stem(file_name, &import_name))),
+        OutputMode::SingleFile => Some((import_name, SINGLE_FILE_STEM.to_string()))
+    }
+}
+
+/// The identifier and module to import for an overridden type, where a
+/// reference to a `(tst.ts_type)` is that override itself.
+fn override_import(type_name: &str, options: &Options) -> Option<(String, String)> {
+    let parsed;
+    let type_override = match options.type_overrides.get(type_name) {
+        Some(type_override) => type_override,
+        None => {
+            parsed = TypeOverride::parse(type_name);
+            &parsed
+        }
+    };
+    Some((type_override.import_name(), type_override.module.clone()?))
+}
+
+fn gen_imports<'a>(
+    stem: &str,
+    references: impl Iterator<Item = &'a String>,
+    resolver: &TypeResolver,
+    options: &Options
+) -> String {
+    let mut imports = BTreeMap::<String, BTreeSet<String>>::new();
+    if let ModuleStyle::Global = options.module_style {
+        return String::new();
+    }
+    references
+        .filter_map(|type_name| match override_import(type_name, options) {
+            Some(import) => Some(import),
+            None => import_source(type_name, resolver, options)
+                .filter(|(_, source_stem)| source_stem != stem)
+                .map(|(import_name, source_stem)| match options.module_style {
+                    ModuleStyle::Module => (import_name, source_stem),
+                    _ => (import_name, relative_import(stem, &source_stem, declaration_suffix(options).1, options))
+                })
+        })
+        .for_each(|(import_name, specifier)| {
+            imports.entry(specifier).or_default().insert(import_name);
+        });
+    imports.iter()
+        .map(|(specifier, names)| format!(
+            "import {}{{ {} }} from \"{}\";\n",
+            options.ts_syntax.type_only(),
+            names.iter().cloned().collect::<Vec<_>>().join(", "),
+            specifier
+        ))
+        .collect()
+}
+
+const BASE64_HELPERS: &str = "\
+function bytesToBase64(bytes: Uint8Array): string {
+  let binary = \"\";
+  bytes.forEach((byte) => {
+    binary += String.fromCharCode(byte);
+  });
+  return btoa(binary);
+}
+
+function base64ToBytes(base64: string): Uint8Array {
+  const binary = atob(base64.replace(/-/g, \"+\").replace(/_/g, \"/\"));
+  const bytes = new Uint8Array(binary.length);
+  for (let i = 0; i < binary.length; i++) {
+    bytes[i] = binary.charCodeAt(i);
+  }
+  return bytes;
+}
+";
+
+/// Value imports of generated runtime functions for `references` declared
+/// in other proto files. `names` lists the functions needed per referenced
+/// type given its function prefix and whether it is an enum.
+fn gen_function_imports(
+    stem: &str,
+    file: &ir::File,
+    references: &[String],
+    suffix: &str,
+    names: impl Fn(&str, bool) -> Vec<String>,
+    resolver: &TypeResolver,
+    options: &Options
+) -> String {
+    let mut imports = BTreeMap::<String, BTreeSet<String>>::new();
+    references.iter()
+        .filter(|type_name| well_known_type_to_ts_type(type_name, options).is_none())
+        .filter_map(|type_name| resolver.file(type_name).map(|file_name| (type_name, file_name)))
+        .filter(|(_, file_name)| *file_name != file.name)
+        .for_each(|(type_name, file_name)| {
+            let source_stem = output_file_stem(file_name, &format!("_{}", suffix), resolver, options);
+            imports.entry(relative_import(stem, &source_stem, ".ts", options))
+                .or_default()
+                .extend(names(
+                    &resolver.function_prefix(type_name),
+                    resolver.enum_type(type_name).is_some()
+                ));
+        });
+    imports.iter()
+        .map(|(specifier, names)| format!(
+            "import {{ {} }} from \"{}\";\n",
+            names.iter().cloned().collect::<Vec<_>>().join(", "),
+            specifier
+        ))
+        .collect()
+}
+
+fn gen_output_file(
+    stem: &str,
+    declarations: &[TsDeclaration],
+    resolver: &TypeResolver,
+    options: &Options
+) -> CodeGeneratorResponse_File {
+    let imports = gen_imports(
+        stem,
+        declarations.iter().flat_map(|declaration| declaration.references.iter()),
+        resolver,
+        options
+    );
+    let sources = declarations.iter()
+        .filter_map(|declaration| declaration.source.as_deref())
+        .collect::<BTreeSet<_>>();
+    let size = BANNER_SIZE + imports.len() + declarations.iter().map(|declaration| declaration.content.len() + 1).sum::<usize>();
+    let mut writer = CodeWriter::with_capacity(match options.module_style {
+        ModuleStyle::Esm => size,
+        _ => size + size / 4
+    });
+    write_banner(&mut writer, sources, options);
+    let write_body = |writer: &mut CodeWriter| for (i, declaration) in declarations.iter().enumerate() {
+        if i > 0 {
+            writer.push('\n');
+        }
+        writer.push(&declaration.content);
+    };
+    match options.module_style {
+        ModuleStyle::Esm => {
+            if !imports.is_empty() {
+                writer.push(&imports);
+                writer.push('\n');
+            }
+            write_body(&mut writer);
+        },
+        ModuleStyle::Global if options.namespaces => {
+            writer.push("export {};\n\ndeclare global {\n");
+            writer.indented(|writer| write_namespaces(writer, declarations, resolver));
+            writer.push("}\n");
+        },
+        ModuleStyle::Global => {
+            writer.push("export {};\n\ndeclare global {\n");
+            writer.indented(write_body);
+            writer.push("}\n");
+        },
+        ModuleStyle::Module => {
+            writer.push(format_args!("declare module \"{}\" {{\n", stem));
+            writer.indented(|writer| {
+                if !imports.is_empty() {
+                    writer.push(&imports);
+                    writer.push('\n');
+                }
+                write_body(writer);
+            });
+            writer.push("}\n");
+        }
+    }
+    gen_resp_file(format!("{}{}", stem, declaration_suffix(options).1), writer.into_string())
+}
+
+/// Writes `declarations` grouped into a namespace per proto package, after
+/// those of no package.
+fn write_namespaces(writer: &mut CodeWriter, declarations: &[TsDeclaration], resolver: &TypeResolver) {
+    let mut by_package = BTreeMap::<&str, Vec<&TsDeclaration>>::new();
+    for declaration in declarations {
+        let package = declaration.source.as_ref()
+            .and_then(|file_name| resolver.packages.get(file_name))
+            .map_or("", String::as_str);
+        by_package.entry(package).or_default().push(declaration);
+    }
+    let write_body = |writer: &mut CodeWriter, declarations: &[&TsDeclaration]| for (i, declaration) in declarations.iter().enumerate() {
+        if i > 0 {
+            writer.push('\n');
+        }
+        writer.push(&declaration.content);
+    };
+    for (i, (package, declarations)) in by_package.iter().enumerate() {
+        if i > 0 {
+            writer.push('\n');
+        }
+        match package.is_empty() {
+            true => write_body(writer, declarations),
+            false => {
+                writer.push(format_args!("namespace {} {{\n", package));
+                writer.indented(|writer| write_body(writer, declarations));
+                writer.push("}\n");
+            }
+        }
+    }
+}
+
+const INDEX_FILE_STEM: &str = "index";
+
+/// Names declared by the top-level `export` statements of `content`.
+fn exported_names(content: &str) -> impl Iterator<Item = &str> {
+    content.lines()
+        .filter_map(|line| line.strip_prefix("export "))
+        .filter_map(|line| {
+            let line = line.strip_prefix("declare ").unwrap_or(line);
+            let line = line.strip_prefix("const enum ")
+                .or_else(|| ["type ", "interface ", "enum ", "namespace ", "const ", "class ", "function "]
+                    .iter()
+                    .find_map(|keyword| line.strip_prefix(keyword)))?;
+            let end = line.find(|c: char| !c.is_alphanumeric() && c != '_' && c != '$').unwrap_or(line.len());
+            Some(&line[..end])
+        })
+}
+
+/// A barrel file re-exporting the types of every declaration file. Global
+/// declarations need no imports, so there is nothing to re-export. Type-only
+/// re-exports name the types, as `export type *` needs TypeScript 5.0.
+fn gen_index_file(outputs: &[(String, Vec<TsDeclaration>)], options: &Options) -> Option<CodeGeneratorResponse_File> {
+    let content = outputs.iter()
+        .map(|(stem, declarations)| {
+            let specifier = match options.module_style {
+                ModuleStyle::Module => stem.clone(),
+                _ => relative_import(INDEX_FILE_STEM, stem, declaration_suffix(options).1, options)
+            };
+            match options.ts_syntax {
+                TsSyntax::Modern => format!(
+                    "export type {{ {} }} from \"{}\";\n",
+                    declarations.iter()
+                        .flat_map(|declaration| exported_names(&declaration.content))
+                        .collect::<BTreeSet<_>>()
+                        .into_iter()
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    specifier
+                ),
+                TsSyntax::Legacy => format!("export * from \"{}\";\n", specifier)
+            }
+        })
+        .collect::<String>();
+    match (options.module_style, content.is_empty()) {
+        (ModuleStyle::Global, _) | (_, true) => None,
+        _ => Some(with_banner(gen_resp_file(format!("{}.ts", INDEX_FILE_STEM), content), None, options))
+    }
+}
+
+struct GrpcWebMethod {
+    name: String,
+    path: String,
+    request: String,
+    response: String,
+    server_streaming: bool
+}
+
+struct GrpcWebClient {
+    name: String,
+    methods: Vec<GrpcWebMethod>
+}
+
+impl fmt::Display for GrpcWebClient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "export class {} {{", self.name)?;
+        writeln!(f, "  private readonly client: grpcWeb.GrpcWebClientBase;")?;
+        writeln!(f)?;
+        writeln!(f, "  constructor(")?;
+        writeln!(f, "    private readonly hostname: string,")?;
+        writeln!(f, "    options: grpcWeb.GrpcWebClientBaseOptions = {{}}")?;
+        writeln!(f, "  ) {{")?;
+        writeln!(f, "    this.client = new grpcWeb.GrpcWebClientBase(options);")?;
+        writeln!(f, "  }}")?;
+        for method in self.methods.iter() {
+            writeln!(f)?;
+            writeln!(
+                f,
+                "  private static readonly {}Descriptor = new grpcWeb.MethodDescriptor<{}, {}>(",
+                method.name,
+                method.request,
+                method.response
+            )?;
+            writeln!(f, "    \"{}\",", method.path)?;
+            match method.server_streaming {
+                true => writeln!(f, "    grpcWeb.MethodType.SERVER_STREAMING,")?,
+                false => writeln!(f, "    grpcWeb.MethodType.UNARY,")?
+            }
+            writeln!(f, "    Object as never,")?;
+            writeln!(f, "    Object as never,")?;
+            writeln!(f, "    serialize,")?;
+            writeln!(f, "    deserialize")?;
+            writeln!(f, "  );")?;
+            writeln!(f)?;
+            let (result, call) = match method.server_streaming {
+                true => (format!("grpcWeb.ClientReadableStream<{}>", method.response), "serverStreaming"),
+                false => (format!("Promise<{}>", method.response), "thenableCall")
+            };
+            writeln!(
+                f,
+                "  {}(request: {}, metadata: grpcWeb.Metadata = {{}}): {} {{",
+                method.name,
+                method.request,
+                result
+            )?;
+            writeln!(f, "    return this.client.{}(", call)?;
+            writeln!(f, "      this.hostname + \"{}\",", method.path)?;
+            writeln!(f, "      request,")?;
+            writeln!(f, "      metadata,")?;
+            writeln!(f, "      {}.{}Descriptor", self.name, method.name)?;
+            writeln!(f, "    );")?;
+            writeln!(f, "  }}")?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+const GRPC_WEB_JSON_CODEC: &str = "\
+const encoder = new TextEncoder();
+const decoder = new TextDecoder();
+
+function serialize(message: unknown): Uint8Array {
+  return encoder.encode(JSON.stringify(message));
+}
+
+function deserialize<T>(bytes: Uint8Array): T {
+  return JSON.parse(decoder.decode(bytes)) as T;
+}
+";
+
+fn method_path(
+    proto_file: &FileDescriptorProto,
+    service: &ServiceDescriptorProto,
+    method: &MethodDescriptorProto
+) -> String {
+    format!(
+        "/{}/{}",
+        qualified_name(proto_file.get_package(), service.get_name()).trim_start_matches('.'),
+        method.get_name()
+    )
+}
+
+fn gen_service_file(
+    file: &ir::File,
+    suffix: &str,
+    header: &str,
+    prelude: &str,
+    gen_service: impl Fn(&ir::Service) -> String,
+    resolver: &TypeResolver,
+    options: &Options
+) -> Vec<CodeGeneratorResponse_File> {
+    if file.services.is_empty() {
+        return vec![];
+    }
+    let stem = output_file_stem(&file.name, &format!("_{}", suffix), resolver, options);
+    let references = file.services
+        .iter()
+        .flat_map(|service| service.methods.iter())
+        .flat_map(|method| vec![method.input.type_name.clone(), method.output.type_name.clone()])
+        .collect::<Vec<_>>();
+    let mut content = String::from(header);
+    content.push_str(&gen_imports(&stem, references.iter(), resolver, options));
+    if !prelude.is_empty() {
+        content.push('\n');
+        content.push_str(prelude);
+    }
+    for service in file.services.iter() {
+        content.push('\n');
+        content.push_str(&gen_service(service));
+    }
+    vec![gen_resp_file(format!("{}.ts", stem), content)]
+}
+
+/// The `(path, method)` of each method of `service`, as clients declare them.
+fn ts_methods(service: &ir::Service) -> Vec<(String, TsMethod)> {
+    service.methods
+        .iter()
+        .map(|method| (method.path.clone(), TsMethod{
+            name: lower_camel_case(&method.name),
+            doc: None,
+            request: method.input.ts_type.clone(),
+            response: method.output.ts_type.clone(),
+            client_streaming: method.client_streaming,
+            server_streaming: method.server_streaming
+        }))
+        .collect()
+}
+
+/// gRPC-web clients of a file.
+struct GrpcWeb;
+
+impl Renderer for GrpcWeb {
+    fn render(
+        &self,
+        file: &ir::File,
+        _: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File> {
+        gen_service_file(
+            file,
+            "grpc_web",
+            "import * as grpcWeb from \"grpc-web\";\n",
+            GRPC_WEB_JSON_CODEC,
+            |service| format!("{}", GrpcWebClient{
+                name: format!("{}GrpcWebClient", service.name),
+                methods: service.methods
+                    .iter()
+                    .filter(|method| !method.client_streaming)
+                    .map(|method| GrpcWebMethod{
+                        name: lower_camel_case(&method.name),
+                        path: method.path.clone(),
+                        request: method.input.ts_type.clone(),
+                        response: method.output.ts_type.clone(),
+                        server_streaming: method.server_streaming
+                    })
+                    .collect()
+            }),
+            resolver,
+            options
+        )
+    }
+}
+
+struct GrpcJsService {
+    name: String,
+    methods: Vec<(String, TsMethod)>
+}
+
+impl fmt::Display for GrpcJsService {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "export interface {}Server extends grpc.UntypedServiceImplementation {{", self.name)?;
+        for (_, method) in self.methods.iter() {
+            let handler = match (method.client_streaming, method.server_streaming) {
+                (false, false) => "handleUnaryCall",
+                (false, true) => "handleServerStreamingCall",
+                (true, false) => "handleClientStreamingCall",
+                (true, true) => "handleBidiStreamingCall"
+            };
+            writeln!(
+                f,
+                "  {}: grpc.{}<{}, {}>;",
+                method.name,
+                handler,
+                method.request,
+                method.response
+            )?;
+        }
+        writeln!(f, "}}")?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "export const {0}Service: grpc.ServiceDefinition<{0}Server> = {{",
+            self.name
+        )?;
+        for (path, method) in self.methods.iter() {
+            writeln!(f, "  {}: {{", method.name)?;
+            writeln!(f, "    path: \"{}\",", path)?;
+            writeln!(f, "    requestStream: {},", method.client_streaming)?;
+            writeln!(f, "    responseStream: {},", method.server_streaming)?;
+            writeln!(f, "    requestSerialize: (value: {}) => serialize(value),", method.request)?;
+            writeln!(f, "    requestDeserialize: (bytes: Buffer) => deserialize<{}>(bytes),", method.request)?;
+            writeln!(f, "    responseSerialize: (value: {}) => serialize(value),", method.response)?;
+            writeln!(f, "    responseDeserialize: (bytes: Buffer) => deserialize<{}>(bytes)", method.response)?;
+            writeln!(f, "  }},")?;
+        }
+        writeln!(f, "}};")
+    }
+}
+
+const GRPC_JS_JSON_CODEC: &str = "\
+function serialize(message: unknown): Buffer {
+  return Buffer.from(JSON.stringify(message));
+}
+
+function deserialize<T>(bytes: Buffer): T {
+  return JSON.parse(bytes.toString()) as T;
+}
+";
+
+/// `@grpc/grpc-js` service definitions of a file.
+struct GrpcJs;
+
+impl Renderer for GrpcJs {
+    fn render(
+        &self,
+        file: &ir::File,
+        _: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File> {
+        gen_service_file(
+            file,
+            "grpc_js",
+            &format!("import {}* as grpc from \"@grpc/grpc-js\";\n", options.ts_syntax.type_only()),
+            GRPC_JS_JSON_CODEC,
+            |service| format!("{}", GrpcJsService{ name: service.name.clone(), methods: ts_methods(service) }),
+            resolver,
+            options
+        )
+    }
+}
+
+/// `<Service>Methods`, the gRPC paths of the methods by name, with the
+/// union of the names and their request and response types.
+struct ServicePaths {
+    name: String,
+    methods: Vec<(String, TsMethod)>
+}
+
+impl fmt::Display for ServicePaths {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "export const {}Methods = {{", self.name)?;
+        for (path, method) in self.methods.iter() {
+            writeln!(f, "  {}: \"{}\",", method.name, path)?;
+        }
+        writeln!(f, "}} as const;")?;
+        writeln!(f)?;
+        writeln!(f, "export type {0}MethodName = keyof typeof {0}Methods;", self.name)?;
+        writeln!(f)?;
+        writeln!(f, "export type {}MethodTypes = {{", self.name)?;
+        for (_, method) in self.methods.iter() {
+            writeln!(
+                f,
+                "  readonly {}: {{ readonly request: {}; readonly response: {} }};",
+                method.name,
+                method.request,
+                method.response
+            )?;
+        }
+        writeln!(f, "}};")
+    }
+}
+
+/// The gRPC paths of the methods of a file.
+struct ServicePathsFile;
+
+impl Renderer for ServicePathsFile {
+    fn render(
+        &self,
+        file: &ir::File,
+        _: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File> {
+        gen_service_file(
+            file,
+            "services",
+            "",
+            "",
+            |service| format!("{}", ServicePaths{ name: service.name.clone(), methods: ts_methods(service) }),
+            resolver,
+            options
+        )
+    }
+}
+
+/// Whether `file_name` is generated: asked for by protoc, and neither
+/// excluded nor generated elsewhere according to `import_map`.
+fn is_target(req: &CodeGeneratorRequest, file_name: &str, options: &Options) -> bool {
+    req.get_file_to_generate().iter().any(|name| name == file_name) &&
+        !options.excludes.iter().any(|pattern| config::glob_match(pattern, file_name)) &&
+        options.mapped_module(file_name).is_none()
+}
+
+/// Generates all output files of `req`. Files are sorted by name and the
+/// declarations within a file follow the declaration order of the proto, so
+/// the output does not depend on the order in which protoc lists its inputs.
+fn gen_files(req: &CodeGeneratorRequest, options: &Options) -> Result<Vec<CodeGeneratorResponse_File>> {
+    let logger = log::Logger::new(options);
+    req.get_proto_file().iter().try_for_each(descriptors::check_descriptor)?;
+    let all_files = req.get_proto_file().iter().map(with_explicit_presence).collect::<Vec<_>>();
+    let mut resolver = TypeResolver::new(&all_files, options);
+    let skipped = skipped_types(&all_files);
+    resolver.report(options).iter().for_each(|message| logger.warn(message));
+    if logger.is_enabled(log::LogLevel::Debug) {
+        resolver.decisions(options).iter().for_each(|message| logger.debug(message));
+    }
+    let is_target = |file_name: &str| is_target(req, file_name, options);
+    for proto_file in all_files.iter() {
+        let file_name = proto_file.get_name();
+        match (req.get_file_to_generate().iter().any(|name| name == file_name), options.mapped_module(file_name)) {
+            (true, Some(module)) => logger.info(format_args!("skipped {}: imported from {}", file_name, module)),
+            (true, None) if !is_target(file_name) => logger.info(format_args!("skipped {}: excluded", file_name)),
+            (false, _) => logger.debug(format_args!("skipped {}: only a dependency", file_name)),
+            _ => {}
+        }
+    }
+    skipped.iter().for_each(|full_name| logger.info(format_args!("skipped {}: (tst.skip)", full_name.trim_start_matches('.'))));
+    if options.strict {
+        let problems = all_files
+            .iter()
+            .filter(|proto_file| is_target(proto_file.get_name()))
+            .flat_map(|proto_file| strict::unsupported_constructs(proto_file, &resolver, &skipped, options))
+            .collect::<Vec<_>>();
+        if !problems.is_empty() {
+            return Err(match options.error_format {
+                ErrorFormat::Text => Error::Unsupported(problems),
+                ErrorFormat::Machine => Error::Diagnostics(problems)
+            });
+        }
+    }
+    let stripped_files = all_files.iter().map(|proto_file| strip_skipped(proto_file, &skipped)).collect::<Vec<_>>();
+    let mut proto_files = stripped_files.iter().filter(|proto_file| is_target(proto_file.get_name())).collect::<Vec<_>>();
+    proto_files.sort_by_key(|proto_file| proto_file.get_name());
+    check_collisions_of(&proto_files, &mut resolver, options)?;
+    let mut timings = BTreeMap::<&str, std::time::Duration>::new();
+    let built = parallel::map_timed(&stripped_files, options, |proto_file| ir::build(proto_file, &resolver, options));
+    let mut ir_files = Vec::with_capacity(built.len());
+    for (proto_file, (file, duration)) in stripped_files.iter().zip(built) {
+        if is_target(proto_file.get_name()) {
+            *timings.entry(proto_file.get_name()).or_default() += duration;
+        }
+        ir_files.push(file);
+    }
+    let types = ir::Types::new(&ir_files);
+    let mut targets = ir_files.iter().filter(|file| is_target(&file.name)).collect::<Vec<_>>();
+    targets.sort_by(|a, b| a.name.cmp(&b.name));
+    if let Target::JsonSchema | Target::OpenApi = options.target {
+        // JSON documents have no room for a banner.
+        let mut files = Vec::new();
+        for (file, (rendered, duration)) in targets.iter().zip(render_files(&targets, &types, &resolver, options)?) {
+            files.extend(rendered);
+            *timings.entry(file.name.as_str()).or_default() += duration;
+        }
+        files.sort_by(|a, b| a.get_name().cmp(b.get_name()));
+        log_outputs(&logger, &timings, &files);
+        return Ok(files);
+    }
+    let mut outputs = Vec::<(String, Vec<TsDeclaration>)>::new();
+    let generated = parallel::map_timed(&targets, options, |file| gen_file_declarations(file, &types, &resolver, options));
+    for (file, (mut declarations, duration)) in targets.iter().zip(generated) {
+        declarations.iter_mut().for_each(|declaration| declaration.source = Some(file.name.clone()));
+        if !declarations.is_empty() {
+            outputs.push((proto_file_stem(&file.name, &resolver, options), declarations));
+        }
+        *timings.entry(file.name.as_str()).or_default() += duration;
+    }
+    if targets.iter().any(|file| uses_json_value(file, options)) {
+        outputs.push((JSON_VALUE_FILE_STEM.to_string(), vec![TsDeclaration{
+            name: JSON_VALUE_FILE_STEM.to_string(),
+            content: json_value_declaration(options),
+            references: vec![],
+            source: None
+        }]));
+    }
+    if targets.iter().any(|file| uses_branded_ints(file, options)) {
+        outputs.push((BRANDED_INTS_FILE_STEM.to_string(), vec![TsDeclaration{
+            name: BRANDED_INTS_FILE_STEM.to_string(),
+            content: branded_ints_declaration(options),
+            references: vec![],
+            source: None
+        }]));
+    }
+    if has_mutable_types(options) && targets.iter().any(|file| !file.messages.is_empty()) {
+        outputs.push((MUTABLE_FILE_STEM.to_string(), vec![TsDeclaration{
+            name: MUTABLE_FILE_STEM.to_string(),
+            content: mutable_declaration(options),
+            references: vec![],
+            source: None
+        }]));
+    }
+    if let Some(style) = options.partial_style.filter(|_| targets.iter().any(|file| !file.messages.is_empty())) {
+        outputs.push((PARTIALS_FILE_STEM.to_string(), vec![TsDeclaration{
+            name: PARTIALS_FILE_STEM.to_string(),
+            content: partials_declaration(style, options),
+            references: vec![],
+            source: None
+        }]));
+    }
+    let outputs = match options.output_mode {
+        OutputMode::PerFile => outputs,
+        OutputMode::PerMessage => {
+            collisions::check_declarations(&proto_files, &outputs, &mut resolver, options)?;
+            let mut by_stem = Vec::<(String, Vec<TsDeclaration>)>::new();
+            for declaration in outputs.into_iter().flat_map(|(_, declarations)| declarations) {
+                let stem = match &declaration.source {
+                    Some(file_name) => resolver.declaration_stem(file_name, &declaration.name),
+                    None => declaration.name.clone()
+                };
+                match by_stem.iter_mut().find(|(name, _)| *name == stem) {
+                    Some((_, declarations)) => declarations.push(declaration),
+                    None => by_stem.push((stem, vec![declaration]))
+                }
+            }
+            by_stem
+        },
+        OutputMode::SingleFile => {
+            let declarations = outputs.into_iter()
+                .flat_map(|(_, declarations)| declarations)
+                .collect::<Vec<_>>();
+            match declarations.is_empty() {
+                true => vec![],
+                false => vec![(SINGLE_FILE_STEM.to_string(), declarations)]
+            }
+        }
+    };
+    let mut files = outputs.iter()
+        .map(|(stem, declarations)| gen_output_file(stem, declarations, &resolver, options))
+        .collect::<Vec<_>>();
+    if options.index {
+        files.extend(gen_index_file(&outputs, options));
+    }
+    if options.any_registry {
+        files.extend(registry::gen_any_registry_file(&targets, &resolver, options));
+    }
+    files.extend(runtime::gen_runtime_file(&targets, options));
+    for (file, (rendered, duration)) in targets.iter().zip(render_files(&targets, &types, &resolver, options)?) {
+        files.extend(rendered.into_iter().map(|output| with_banner(output, Some(file.name.as_str()), options)));
+        *timings.entry(file.name.as_str()).or_default() += duration;
+    }
+    files.sort_by(|a, b| a.get_name().cmp(b.get_name()));
+    log_outputs(&logger, &timings, &files);
+    Ok(files)
+}
+
+/// Renders `targets` with the renderers `options` ask for, along with the
+/// time each file took.
+fn render_files(
+    targets: &[&ir::File],
+    types: &ir::Types,
+    resolver: &TypeResolver,
+    options: &Options
+) -> Result<Vec<(Vec<CodeGeneratorResponse_File>, std::time::Duration)>> {
+    let renderers = renderers(options)?;
+    Ok(parallel::map_timed(targets, options, |file| renderers.iter()
+        .flat_map(|renderer| renderer.render(file, types, resolver, options))
+        .collect()
+    ))
+}
+
+/// Fails on proto files generating the same outputs and on types or fields
+/// generating the same names.
+fn check_collisions_of(
+    proto_files: &[&FileDescriptorProto],
+    resolver: &mut TypeResolver,
+    options: &Options
+) -> Result<()> {
+    collisions::check_proto_files(proto_files, resolver, options)?;
+    if let Target::TypeScript = options.target {
+        collisions::check_names(proto_files, resolver, options)?;
+    }
+    Ok(())
+}
+
+/// Outputs generated from the IR of each proto file, at most one for most
+/// renderers. Proto files are rendered in parallel.
+trait Renderer: Sync {
+    fn render(
+        &self,
+        file: &ir::File,
+        types: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File>;
+}
+
+/// The per-file outputs `options` ask for, reading the templates up front so
+/// that a broken one fails before anything is rendered.
+fn renderers(options: &Options) -> Result<Vec<Box<dyn Renderer>>> {
+    match options.target {
+        Target::TypeScript => {},
+        Target::JsonSchema => return Ok(vec![Box::new(jsonschema::JsonSchema)]),
+        Target::OpenApi => return Ok(vec![Box::new(openapi::OpenApi)])
+    }
+    let mut renderers = Vec::<Box<dyn Renderer>>::new();
+    match options.client_style {
+        Some(ClientStyle::GrpcWeb) => renderers.push(Box::new(GrpcWeb)),
+        Some(ClientStyle::GrpcJs) => renderers.push(Box::new(GrpcJs)),
+        Some(ClientStyle::Fetch) => renderers.push(Box::new(fetch::Fetch)),
+        Some(ClientStyle::Connect) => renderers.push(Box::new(connect::Connect)),
+        None => {}
+    }
+    if options.service_paths {
+        renderers.push(Box::new(ServicePathsFile));
+    }
+    if options.runtime_json {
+        renderers.push(Box::new(json::Json));
+    }
+    if options.runtime_binary {
+        renderers.push(Box::new(binary::Binary));
+    }
+    if let Some(SchemaStyle::Zod) = options.schema_style {
+        renderers.push(Box::new(zod::Zod));
+    }
+    if let Some(HookStyle::ReactQuery) = options.hook_style {
+        renderers.push(Box::new(hooks::ReactQuery));
+    }
+    if let Some(MockStyle::Msw) = options.mock_style {
+        renderers.push(Box::new(msw::Msw));
+    }
+    if options.guards {
+        renderers.push(Box::new(guards::Guards));
+    }
+    if options.equality_helpers || options.clone_helpers || options.match_helpers {
+        renderers.push(Box::new(helpers::Helpers{
+            equality: options.equality_helpers,
+            clone: options.clone_helpers,
+            matching: options.match_helpers
+        }));
+    }
+    if options.field_masks {
+        renderers.push(Box::new(masks::Masks));
+    }
+    if options.factories {
+        renderers.push(Box::new(factories::Factories));
+    }
+    if options.fixtures {
+        renderers.push(Box::new(fixtures::Fixtures));
+    }
+    if options.enum_functions {
+        renderers.push(Box::new(enums::Enums));
+    }
+    if let Some(style) = options.metadata_style {
+        renderers.push(Box::new(metadata::Metadata(style)));
+    }
+    for path in options.templates.iter() {
+        renderers.push(Box::new(template::Template::read(path)?));
+    }
+    Ok(renderers)
+}
+
+fn log_outputs(
+    logger: &log::Logger,
+    timings: &BTreeMap<&str, std::time::Duration>,
+    files: &[CodeGeneratorResponse_File]
+) {
+    timings.iter().for_each(|(file_name, duration)| logger.info(format_args!(
+        "processed {} in {:.3}ms",
+        file_name,
+        duration.as_secs_f64() * 1000.0
+    )));
+    files.iter().for_each(|file| logger.debug(format_args!(
+        "wrote {} ({} bytes)",
+        file.get_name(),
+        file.get_content().len()
+    )));
+}
+
+/// Name of the file listing the outputs with `list_only=true`.
+pub const FILE_LIST_NAME: &str = "tst_files.txt";
+
+/// The `<name>\t<bytes>` lines of `files`, in place of their contents.
+fn gen_file_list(files: &[CodeGeneratorResponse_File]) -> CodeGeneratorResponse_File {
+    let content = files.iter()
+        .map(|file| format!("{}\t{}\n", file.get_name(), file.get_content().len()))
+        .collect::<String>();
+    gen_resp_file(FILE_LIST_NAME.to_string(), content)
+}
+
+/// Generates TypeScript for `request` with `options`, ignoring the parameter
+/// carried by the request.
+pub fn generate(request: CodeGeneratorRequest, mut options: Options) -> Result<CodeGeneratorResponse> {
+    options.map_imports(request.get_proto_file());
+    let mut resp = CodeGeneratorResponse::new();
+    resp.mut_unknown_fields().add_varint(RESPONSE_SUPPORTED_FEATURES, FEATURE_PROTO3_OPTIONAL);
+    let files = gen_files(&request, &options)?;
+    match options.list_only {
+        true => resp.set_file(vec![gen_file_list(&files)].into()),
+        false => resp.set_file(files.into())
+    }
+    Ok(resp)
+}
+
+/// Fails where generating all of `request` would on colliding outputs or
+/// names, for callers that generate its files one at a time.
+pub fn check_collisions(request: &CodeGeneratorRequest, mut options: Options) -> Result<()> {
+    options.map_imports(request.get_proto_file());
+    request.get_proto_file().iter().try_for_each(descriptors::check_descriptor)?;
+    let all_files = request.get_proto_file().iter().map(with_explicit_presence).collect::<Vec<_>>();
+    let mut resolver = TypeResolver::new(&all_files, &options);
+    let skipped = skipped_types(&all_files);
+    let stripped_files = all_files
+        .iter()
+        .filter(|proto_file| is_target(request, proto_file.get_name(), &options))
+        .map(|proto_file| strip_skipped(proto_file, &skipped))
+        .collect::<Vec<_>>();
+    let mut proto_files = stripped_files.iter().collect::<Vec<_>>();
+    proto_files.sort_by_key(|proto_file| proto_file.get_name());
+    check_collisions_of(&proto_files, &mut resolver, &options)
+}
+
+/// The intermediate representation of the files `request` generates, as
+/// the renderers built on it see it with `options`.
+pub fn intermediate_representation(request: &CodeGeneratorRequest, mut options: Options) -> Result<Vec<ir::File>> {
+    options.map_imports(request.get_proto_file());
+    request.get_proto_file().iter().try_for_each(descriptors::check_descriptor)?;
+    let all_files = request.get_proto_file().iter().map(with_explicit_presence).collect::<Vec<_>>();
+    let resolver = TypeResolver::new(&all_files, &options);
+    let skipped = skipped_types(&all_files);
+    let mut files = all_files
+        .iter()
+        .filter(|proto_file| is_target(request, proto_file.get_name(), &options))
+        .map(|proto_file| ir::build(&strip_skipped(proto_file, &skipped), &resolver, &options))
+        .collect::<Vec<_>>();
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(files)
+}
+
+/// Handles `request` the way protoc expects from a plugin: options are read
+/// from its parameter and errors are reported in the response.
+pub fn process_request(request: CodeGeneratorRequest) -> CodeGeneratorResponse {
+    Options::parse(request.get_parameter())
+        .and_then(|options| {
+            if let Some(path) = &options.dump_request {
+                dump_request(&request, path)?;
+            }
+            generate(request, options)
+        })
+        .unwrap_or_else(|error| error_response(&error))
+}
+
+/// Writes `request` to `path` for the standalone mode to replay with
+/// `--request`.
+fn dump_request(request: &CodeGeneratorRequest, path: &str) -> Result<()> {
+    request.write_to_bytes()
+        .map_err(std::io::Error::other)
+        .and_then(|bytes| std::fs::write(path, bytes))
+        .map_err(|error| Error::Io{ path: path.to_string(), error })
+}
+
+pub fn error_response(error: &Error) -> CodeGeneratorResponse {
+    let mut resp = CodeGeneratorResponse::new();
+    resp.mut_unknown_fields().add_varint(RESPONSE_SUPPORTED_FEATURES, FEATURE_PROTO3_OPTIONAL);
+    resp.set_error(error.to_string());
+    resp
+}
+
+/// Transport of the plugin protocol. The generator itself makes no process
+/// specific assumptions, so hosts such as WASI runtimes can supply their own.
+pub trait PluginIo {
+    fn read_request(&mut self) -> Result<CodeGeneratorRequest>;
+    fn write_response(&mut self, resp: &CodeGeneratorResponse) -> Result<()>;
+}
+
+/// Reads the request from stdin and writes the response to stdout, as protoc
+/// and `wasm32-wasi` hosts expect.
+pub struct StdIo;
+
+impl PluginIo for StdIo {
+    fn read_request(&mut self) -> Result<CodeGeneratorRequest> {
+        protobuf::parse_from_reader(&mut std::io::stdin()).map_err(Error::Decode)
+    }
+
+    fn write_response(&mut self, resp: &CodeGeneratorResponse) -> Result<()> {
+        resp.write_to_writer(&mut std::io::stdout()).map_err(Error::Encode)
+    }
+}
+
+/// Runs one plugin invocation over `io`. Only failing to write the response
+/// is returned as an error; everything else is reported to protoc.
+pub fn run_plugin(io: &mut impl PluginIo) -> Result<()> {
+    let resp = match io.read_request() {
+        Ok(req) => process_request(req),
+        Err(error) => error_response(&error)
+    };
+    io.write_response(&resp)
+}
+
+/// Prepends the banner comment marking `file` as generated from `sources`.
+fn with_banner<'a>(
+    mut file: CodeGeneratorResponse_File,
+    sources: impl IntoIterator<Item = &'a str>,
+    options: &Options
+) -> CodeGeneratorResponse_File {
+    if options.banner.is_some() {
+        let mut writer = CodeWriter::with_capacity(BANNER_SIZE + file.get_content().len());
+        write_banner(&mut writer, sources, options);
+        writer.push(file.get_content());
+        file.set_content(writer.into_string());
+    }
+    file
+}
+
+/// Room for the banner when sizing an output file.
+const BANNER_SIZE: usize = 256;
+
+fn write_banner<'a>(writer: &mut CodeWriter, sources: impl IntoIterator<Item = &'a str>, options: &Options) {
+    if let Some(banner) = &options.banner {
+        writer.push(format_args!("// {}\n// @generated\n", banner));
+        sources.into_iter().for_each(|source| writer.push(format_args!("// source: {}\n", source)));
+        writer.push('\n');
+    }
+}
+
+fn gen_resp_file(name: String, content: String) -> CodeGeneratorResponse_File {
+    let mut file = CodeGeneratorResponse_File::new();
+    file.set_name(name);
+    file.set_content(content);
+    file
+}
@@ -0,0 +1,73 @@
+use super::*;
+
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+pub(crate) enum LogLevel {
+    Off,
+    Warn,
+    Info,
+    Debug
+}
+
+impl LogLevel {
+    pub(crate) fn parse(value: &str) -> Option<LogLevel> {
+        match value {
+            "off" => Some(LogLevel::Off),
+            "warn" => Some(LogLevel::Warn),
+            "info" => Some(LogLevel::Info),
+            "debug" => Some(LogLevel::Debug),
+            _ => None
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            LogLevel::Off => "off",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug"
+        }
+    }
+}
+
+/// Diagnostics on stderr, the only channel protoc leaves a plugin. The level
+/// is the more verbose of the `log` parameter and the `TST_LOG` variable.
+pub(crate) struct Logger {
+    level: LogLevel
+}
+
+impl Logger {
+    pub(crate) fn new(options: &Options) -> Logger {
+        let env_level = std::env::var("TST_LOG")
+            .ok()
+            .and_then(|value| LogLevel::parse(value.trim()))
+            .unwrap_or(LogLevel::Off);
+        Logger{
+            level: match env_level > options.log_level {
+                true => env_level,
+                false => options.log_level
+            }
+        }
+    }
+
+    pub(crate) fn is_enabled(&self, level: LogLevel) -> bool {
+        level != LogLevel::Off && level <= self.level
+    }
+
+    pub(crate) fn log(&self, level: LogLevel, message: impl fmt::Display) {
+        if self.is_enabled(level) {
+            eprintln!("protoc-gen-tst: {}: {}", level.name(), message);
+        }
+    }
+
+    pub(crate) fn warn(&self, message: impl fmt::Display) {
+        self.log(LogLevel::Warn, message);
+    }
+
+    pub(crate) fn info(&self, message: impl fmt::Display) {
+        self.log(LogLevel::Info, message);
+    }
+
+    pub(crate) fn debug(&self, message: impl fmt::Display) {
+        self.log(LogLevel::Debug, message);
+    }
+}
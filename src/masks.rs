@@ -0,0 +1,338 @@
+use super::*;
+
+/// The message type of `field` when it is a singular message whose own
+/// fields merge and mask paths descend into.
+pub(crate) fn nested_message<'a>(field: &ir::Field, types: &ir::Types<'a>) -> Option<&'a ir::Message> {
+    match (field.cardinality == ir::Cardinality::Single, field.value.kind, field.value.field_type) {
+        (true, ir::ValueKind::Message, FieldDescriptorProto_Type::TYPE_MESSAGE) => types.message(&field.value.type_name),
+        _ => None
+    }
+}
+
+/// Whether mask paths descend into `field`, as its `FieldPath` alias does.
+fn has_nested_paths(field: &ir::Field, types: &ir::Types) -> bool {
+    nested_message(field, types).is_some_and(|nested_type| !nested_type.fields.is_empty())
+}
+
+/// Statement resetting `field` to its absent state.
+fn clear_statement(field: &ir::Field, types: &ir::Types, resolver: &TypeResolver, options: &Options) -> String {
+    match json::field_default(field, types, resolver, options) {
+        Some(default) => format!("message[\"{}\"] = {};", field.key, default),
+        None => format!("delete message[\"{}\"];", field.key)
+    }
+}
+
+/// The other members of the oneof `field` of `message` belongs to, which
+/// setting it clears when oneofs are plain properties.
+fn oneof_siblings<'a>(field: &'a ir::Field, message: &'a ir::Message) -> Vec<&'a ir::Field> {
+    match field.oneof {
+        Some(index) => message.oneof_fields(index).filter(|sibling| sibling.number != field.number).collect(),
+        None => Vec::new()
+    }
+}
+
+struct MessageMasks<'a> {
+    prefix: String,
+    message: &'a ir::Message,
+    types: &'a ir::Types<'a>,
+    resolver: &'a TypeResolver,
+    options: &'a Options
+}
+
+impl<'a> MessageMasks<'a> {
+    /// Writes `assignment` of `field` when `condition` holds, also clearing
+    /// the other members of its oneof, and `otherwise` when it does not.
+    fn write_set(
+        &self,
+        f: &mut fmt::Formatter,
+        field: &ir::Field,
+        condition: &str,
+        assignment: &str,
+        otherwise: Option<String>,
+        indent: &str
+    ) -> fmt::Result {
+        let siblings = oneof_siblings(field, self.message);
+        if siblings.is_empty() {
+            writeln!(f, "{}if ({}) {}", indent, condition, assignment)?;
+            return match otherwise {
+                Some(otherwise) => writeln!(f, "{}else {}", indent, otherwise),
+                None => Ok(())
+            };
+        }
+        writeln!(f, "{}if ({}) {{", indent, condition)?;
+        writeln!(f, "{}  {}", indent, assignment)?;
+        for sibling in siblings {
+            writeln!(f, "{}  {}", indent, clear_statement(sibling, self.types, self.resolver, self.options))?;
+        }
+        match otherwise {
+            Some(otherwise) => writeln!(f, "{}}} else {}", indent, otherwise),
+            None => writeln!(f, "{}}}", indent)
+        }
+    }
+
+    fn write_merge(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ts_type = &self.message.ts_name;
+        if self.message.fields.is_empty() {
+            writeln!(f, "export function merge{}(target: {1}, _source: {1}): {1} {{", self.prefix, ts_type)?;
+            writeln!(f, "  return {{ ...target }};")?;
+            return writeln!(f, "}}");
+        }
+        writeln!(f, "export function merge{}(target: {1}, source: {1}): {1} {{", self.prefix, ts_type)?;
+        writeln!(f, "  const message: Record<string, unknown> = {{ ...target }};")?;
+        for property in self.message.properties(self.options) {
+            match property {
+                ir::Property::Field(field) => {
+                    let key = &field.key;
+                    let (source, target) = (format!("source[\"{}\"]", key), format!("target[\"{}\"]", key));
+                    if let ir::Cardinality::Map{ .. } = field.cardinality {
+                        match self.options.map_style {
+                            MapStyle::Record => writeln!(f, "  message[\"{}\"] = {{ ...{}, ...{} }};", key, target, source)?,
+                            MapStyle::Map => writeln!(f, "  message[\"{}\"] = new Map([...{}, ...{}]);", key, target, source)?
+                        }
+                        continue;
+                    }
+                    if field.cardinality == ir::Cardinality::Repeated {
+                        writeln!(f, "  message[\"{}\"] = [...{}, ...{}];", key, target, source)?;
+                        continue;
+                    }
+                    let is_set = |value: &str| json::field_is_set(field, value, self.types, self.resolver, self.options);
+                    let value = match nested_message(field, self.types) {
+                        Some(_) => format!(
+                            "{} ? merge{}({}, {}) : {}",
+                            is_set(&target),
+                            self.resolver.function_prefix(&field.value.type_name),
+                            target,
+                            source,
+                            source
+                        ),
+                        None => source.clone()
+                    };
+                    self.write_set(f, field, &is_set(&source), &format!("message[\"{}\"] = {};", key, value), None, "  ")?;
+                },
+                ir::Property::Oneof{ index, oneof, fields } => {
+                    let key = &oneof.key;
+                    let merged = fields.iter()
+                        .filter(|field| nested_message(field, self.types).is_some())
+                        .collect::<Vec<_>>();
+                    if merged.is_empty() {
+                        writeln!(f, "  if (source[\"{0}\"] !== undefined) message[\"{0}\"] = source[\"{0}\"];", key)?;
+                        continue;
+                    }
+                    let (source, target) = (format!("source{}", index), format!("target{}", index));
+                    writeln!(f, "  const {} = source[\"{}\"], {} = target[\"{}\"];", source, key, target, key)?;
+                    writeln!(f, "  if ({} !== undefined) {{", source)?;
+                    let mut branch = "if";
+                    for field in merged {
+                        writeln!(
+                            f,
+                            "    {0} ({1}.$case === \"{3}\" && {2}?.$case === \"{3}\") \
+                                message[\"{4}\"] = {{ $case: \"{3}\", \"{3}\": merge{5}({2}[\"{3}\"], {1}[\"{3}\"]) }};",
+                            branch,
+                            source,
+                            target,
+                            field.key,
+                            key,
+                            self.resolver.function_prefix(&field.value.type_name)
+                        )?;
+                        branch = "else if";
+                    }
+                    writeln!(f, "    else message[\"{}\"] = {};", key, source)?;
+                    writeln!(f, "  }}")?;
+                }
+            }
+        }
+        writeln!(f, "  return message as unknown as {};", ts_type)?;
+        writeln!(f, "}}")
+    }
+
+    /// Expression applying the rest of `path` below `field` to the values
+    /// `current` and `from` of the nested message.
+    fn nested_apply(&self, field: &ir::Field, current: &str, from: &str) -> String {
+        format!(
+            "apply{}FieldMask({}, {}, [path.slice(dot + 1) as {}{}])",
+            self.resolver.function_prefix(&field.value.type_name),
+            current,
+            from,
+            self.resolver.resolve(&field.value.type_name, self.options),
+            FIELD_PATH_SUFFIX
+        )
+    }
+
+    fn write_apply(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (ts_type, fields) = (&self.message.ts_name, &self.message.fields);
+        if fields.is_empty() {
+            writeln!(
+                f,
+                "export function apply{0}FieldMask(target: {1} | undefined, _source: {1} | undefined, _mask: ReadonlyArray<{1}{2}>): {1} {{",
+                self.prefix,
+                ts_type,
+                FIELD_PATH_SUFFIX
+            )?;
+            writeln!(f, "  return {{ ...target }} as {};", ts_type)?;
+            return writeln!(f, "}}");
+        }
+        writeln!(
+            f,
+            "export function apply{0}FieldMask(target: {1} | undefined, source: {1} | undefined, mask: ReadonlyArray<{1}{2}>): {1} {{",
+            self.prefix,
+            ts_type,
+            FIELD_PATH_SUFFIX
+        )?;
+        let properties = self.message.properties(self.options);
+        let defaults = properties.iter()
+            .filter_map(|property| match property {
+                ir::Property::Field(field) => json::field_default(field, self.types, self.resolver, self.options)
+                    .map(|default| format!("\"{}\": {}", field.key, default)),
+                ir::Property::Oneof{ .. } => None
+            })
+            .collect::<Vec<_>>();
+        match defaults.is_empty() {
+            true => writeln!(f, "  const empty: {} = {{}};", ts_type)?,
+            false => writeln!(f, "  const empty: {} = {{ {} }};", ts_type, defaults.join(", "))?
+        }
+        writeln!(f, "  const from = source ?? empty;")?;
+        writeln!(f, "  const message: Record<string, unknown> = {{ ...(target ?? empty) }};")?;
+        writeln!(f, "  for (const path of mask) {{")?;
+        let is_nested = fields.iter().any(|field| has_nested_paths(field, self.types));
+        if is_nested {
+            writeln!(f, "    const dot = path.indexOf(\".\");")?;
+            writeln!(f, "    switch (dot < 0 ? path : path.slice(0, dot)) {{")?;
+        } else {
+            writeln!(f, "    switch (path) {{")?;
+        }
+        for property in properties {
+            match property {
+                ir::Property::Field(field) => {
+                    let key = &field.key;
+                    let from = format!("from[\"{}\"]", key);
+                    writeln!(f, "      case \"{}\":", field.name)?;
+                    let indent = match has_nested_paths(field, self.types) {
+                        true => {
+                            writeln!(f, "        if (dot >= 0) {{")?;
+                            writeln!(f, "          message[\"{}\"] = {};", key, self.nested_apply(
+                                field,
+                                &format!("(message[\"{}\"] as {}[\"{}\"]) ?? undefined", key, ts_type, key),
+                                &format!("{} ?? undefined", from)
+                            ))?;
+                            for sibling in oneof_siblings(field, self.message) {
+                                writeln!(f, "          {}", clear_statement(sibling, self.types, self.resolver, self.options))?;
+                            }
+                            writeln!(f, "          break;")?;
+                            writeln!(f, "        }}")?;
+                            "        "
+                        },
+                        false => "        "
+                    };
+                    match field.has_presence() {
+                        true => {
+                            let is_set = json::field_is_set(field, &from, self.types, self.resolver, self.options);
+                            self.write_set(
+                                f,
+                                field,
+                                &is_set,
+                                &format!("message[\"{}\"] = {};", key, from),
+                                Some(clear_statement(field, self.types, self.resolver, self.options)),
+                                indent
+                            )?;
+                        },
+                        false => writeln!(f, "{}message[\"{}\"] = {};", indent, key, from)?
+                    }
+                    writeln!(f, "        break;")?;
+                },
+                ir::Property::Oneof{ oneof, fields, .. } => {
+                    let key = &oneof.key;
+                    let current = format!("message[\"{}\"] as {}[\"{}\"]", key, ts_type, key);
+                    let from = format!("from[\"{}\"]", key);
+                    for field in fields {
+                        let member = &field.key;
+                        writeln!(f, "      case \"{}\":", field.name)?;
+                        if has_nested_paths(field, self.types) {
+                            writeln!(f, "        if (dot >= 0) {{")?;
+                            writeln!(f, "          const current = {};", current)?;
+                            writeln!(f, "          message[\"{}\"] = {{ $case: \"{}\", \"{}\": {} }};", key, member, member, self.nested_apply(
+                                field,
+                                &format!("current?.$case === \"{0}\" ? current[\"{0}\"] : undefined", member),
+                                &format!("{0}?.$case === \"{1}\" ? {0}[\"{1}\"] : undefined", from, member)
+                            ))?;
+                            writeln!(f, "          break;")?;
+                            writeln!(f, "        }}")?;
+                        }
+                        writeln!(f, "        if ({}?.$case === \"{}\") message[\"{}\"] = {};", from, member, key, from)?;
+                        writeln!(f, "        else if (({})?.$case === \"{}\") delete message[\"{}\"];", current, member, key)?;
+                        writeln!(f, "        break;")?;
+                    }
+                }
+            }
+        }
+        writeln!(f, "    }}")?;
+        writeln!(f, "  }}")?;
+        writeln!(f, "  return message as unknown as {};", ts_type)?;
+        writeln!(f, "}}")
+    }
+}
+
+impl<'a> fmt::Display for MessageMasks<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_merge(f)?;
+        writeln!(f)?;
+        self.write_apply(f)
+    }
+}
+
+fn masks_file_stem(file_name: &str, resolver: &TypeResolver, options: &Options) -> String {
+    output_file_stem(file_name, "_masks", resolver, options)
+}
+
+/// `google.protobuf.FieldMask` update and merge functions of the messages
+/// of a file.
+pub(crate) struct Masks;
+
+impl Renderer for Masks {
+    fn render(
+        &self,
+        file: &ir::File,
+        types: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File> {
+        if file.messages.is_empty() {
+            return vec![];
+        }
+        let stem = masks_file_stem(&file.name, resolver, options);
+        let fields = file.messages.iter().flat_map(|message| message.fields.iter());
+        let nested_references = fields.clone()
+            .filter(|field| nested_message(field, types).is_some())
+            .map(|field| field.value.type_name.clone())
+            .collect::<Vec<_>>();
+        let type_references = file.messages
+            .iter()
+            .flat_map(|message| vec![message.full_name.clone(), field_path_reference(&message.full_name)])
+            .chain(fields
+                .filter(|field| has_nested_paths(field, types))
+                .map(|field| field_path_reference(&field.value.type_name))
+            )
+            .collect::<Vec<_>>();
+        let mut content = CodeWriter::for_file(file);
+        content.push(gen_imports(&stem, type_references.iter(), resolver, options));
+        content.push(gen_function_imports(
+            &stem,
+            file,
+            &nested_references,
+            "masks",
+            |prefix, _| vec![format!("merge{}", prefix), format!("apply{}FieldMask", prefix)],
+            resolver,
+            options
+        ));
+        for message in file.messages.iter() {
+            content.push('\n');
+            content.push(MessageMasks{
+                prefix: resolver.function_prefix(&message.full_name),
+                message,
+                types,
+                resolver,
+                options
+            });
+        }
+        vec![gen_resp_file(format!("{}.ts", stem), content.into_string())]
+    }
+}
@@ -0,0 +1,127 @@
+use super::*;
+
+/// Field numbers of a message keyed by the generated property names.
+struct FieldNumbers<'a> {
+    prefix: String,
+    message: &'a ir::Message
+}
+
+impl<'a> fmt::Display for FieldNumbers<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.message.fields.is_empty() {
+            return writeln!(f, "export const {}Fields = {{}} as const;", self.prefix);
+        }
+        writeln!(f, "export const {}Fields = {{", self.prefix)?;
+        for field in self.message.fields.iter() {
+            writeln!(f, "  {}: {},", property_key(&field.key), field.number)?;
+        }
+        writeln!(f, "}} as const;")
+    }
+}
+
+fn type_name_value(value: &ir::Value) -> Option<String> {
+    match value.type_name.as_str() {
+        "" => None,
+        type_name => Some(format!("\"{}\"", type_name.trim_start_matches('.')))
+    }
+}
+
+/// Minimal reflection data of a message: proto names, numbers and types of
+/// its fields, without the rest of the descriptor.
+struct MessageDescriptor<'a> {
+    prefix: String,
+    message: &'a ir::Message
+}
+
+/// The custom options `values` as an object literal, if any.
+fn custom_options_value(values: &[(String, String)]) -> Option<String> {
+    match values.is_empty() {
+        true => None,
+        false => Some(format!(
+            "{{ {} }}",
+            values.iter()
+                .map(|(key, value)| format!("\"{}\": {}", key, value))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    }
+}
+
+impl<'a> fmt::Display for MessageDescriptor<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "export const {}Descriptor = {{", self.prefix)?;
+        writeln!(f, "  typeName: \"{}\",", self.message.full_name.trim_start_matches('.'))?;
+        if let Some(value) = custom_options_value(&self.message.custom_options) {
+            writeln!(f, "  options: {},", value)?;
+        }
+        if self.message.fields.is_empty() {
+            writeln!(f, "  fields: [],")?;
+            return writeln!(f, "}} as const;");
+        }
+        writeln!(f, "  fields: [")?;
+        for field in self.message.fields.iter() {
+            let mut properties = vec![
+                format!("name: \"{}\"", field.name),
+                format!("localName: \"{}\"", field.key),
+                format!("jsonName: \"{}\"", field.json_name),
+                format!("number: {}", field.number)
+            ];
+            match &field.cardinality {
+                ir::Cardinality::Map{ key } => {
+                    properties.push("type: \"map\"".to_string());
+                    properties.push(format!("mapKey: \"{}\"", scalar_name(key.field_type)));
+                    properties.push(format!("mapValue: \"{}\"", scalar_name(field.value.field_type)));
+                    properties.extend(type_name_value(&field.value).map(|type_name| format!("typeName: {}", type_name)));
+                },
+                cardinality => {
+                    properties.push(format!("type: \"{}\"", scalar_name(field.value.field_type)));
+                    properties.extend(type_name_value(&field.value).map(|type_name| format!("typeName: {}", type_name)));
+                    properties.push(format!("repeated: {}", *cardinality == ir::Cardinality::Repeated));
+                }
+            }
+            if let Some(index) = field.oneof {
+                properties.push(format!("oneof: \"{}\"", self.message.oneofs[index].name));
+            }
+            properties.extend(custom_options_value(&field.custom_options).map(|value| format!("options: {}", value)));
+            writeln!(f, "    {{ {} }},", properties.join(", "))?;
+        }
+        writeln!(f, "  ],")?;
+        writeln!(f, "}} as const;")
+    }
+}
+
+fn metadata_file_stem(file_name: &str, resolver: &TypeResolver, options: &Options) -> String {
+    output_file_stem(file_name, "_meta", resolver, options)
+}
+
+/// Field numbers, and with `metadata=descriptors` reflection data, of the
+/// messages of a file.
+pub(crate) struct Metadata(pub(crate) MetadataStyle);
+
+impl Renderer for Metadata {
+    fn render(
+        &self,
+        file: &ir::File,
+        _: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File> {
+        if file.messages.is_empty() {
+            return vec![];
+        }
+        let stem = metadata_file_stem(&file.name, resolver, options);
+        let mut content = CodeWriter::for_file(file);
+        for message in file.messages.iter() {
+            let prefix = resolver.function_prefix(&message.full_name);
+            if !content.as_str().is_empty() {
+                content.push('\n');
+            }
+            content.push(FieldNumbers{ prefix: prefix.clone(), message });
+            if self.0 == MetadataStyle::Descriptors {
+                content.push('\n');
+                content.push(MessageDescriptor{ prefix, message });
+            }
+        }
+        vec![gen_resp_file(format!("{}.ts", stem), content.into_string())]
+    }
+}
@@ -0,0 +1,92 @@
+use super::*;
+
+const MSW_PRELUDE: &str = "\
+export type MockResolver<I, O> = (request: I, info: { readonly request: Request }) => O | Promise<O>;
+
+export interface MockHandlerOptions {
+  readonly baseUrl?: string;
+  readonly prefix?: string;
+}
+
+function jsonResponse(message: unknown): Response {
+  return new HttpResponse(JSON.stringify(message), { headers: { \"Content-Type\": \"application/json\" } });
+}
+";
+
+struct MswMethod {
+    name: String,
+    path: String,
+    request: String,
+    response: String
+}
+
+/// Handlers answering the unary methods of a service at
+/// `<baseUrl><prefix>/<package.Service>/<Method>`, the path shared by
+/// gRPC-web, Connect and Twirp, where Twirp uses the `/twirp` prefix.
+struct MswHandlers {
+    name: String,
+    methods: Vec<MswMethod>
+}
+
+impl fmt::Display for MswHandlers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "export interface {}MockResolvers {{", self.name)?;
+        for method in self.methods.iter() {
+            writeln!(f, "  readonly {}?: MockResolver<{}, {}>;", method.name, method.request, method.response)?;
+        }
+        writeln!(f, "}}")?;
+        writeln!(f)?;
+        writeln!(f, "export function create{0}Handlers(", self.name)?;
+        writeln!(f, "  resolvers: {}MockResolvers,", self.name)?;
+        writeln!(f, "  options: MockHandlerOptions = {{}}")?;
+        writeln!(f, "): HttpHandler[] {{")?;
+        writeln!(f, "  const base = (options.baseUrl ?? \"\") + (options.prefix ?? \"\");")?;
+        writeln!(f, "  const handlers: HttpHandler[] = [];")?;
+        for method in self.methods.iter() {
+            writeln!(f, "  const {0} = resolvers.{0};", method.name)?;
+            writeln!(f, "  if ({} !== undefined) {{", method.name)?;
+            writeln!(f, "    handlers.push(http.post(`${{base}}{}`, async ({{ request }}) => {{", method.path)?;
+            writeln!(f, "      const input = (await request.json()) as {};", method.request)?;
+            writeln!(f, "      return jsonResponse(await {}(input, {{ request }}));", method.name)?;
+            writeln!(f, "    }}));")?;
+            writeln!(f, "  }}")?;
+        }
+        writeln!(f, "  return handlers;")?;
+        writeln!(f, "}}")
+    }
+}
+
+/// MSW request handlers of the unary methods of a file.
+pub(crate) struct Msw;
+
+impl Renderer for Msw {
+    fn render(
+        &self,
+        file: &ir::File,
+        _: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File> {
+        gen_service_file(
+            file,
+            "msw",
+            &options.ts_syntax.imports("msw", &["http", "HttpResponse"], &["HttpHandler"]),
+            MSW_PRELUDE,
+            |service| format!("{}", MswHandlers{
+                name: service.name.clone(),
+                methods: service.methods
+                    .iter()
+                    .filter(|method| method.is_unary())
+                    .map(|method| MswMethod{
+                        name: lower_camel_case(&method.name),
+                        path: method.path.clone(),
+                        request: method.input.ts_type.clone(),
+                        response: method.output.ts_type.clone()
+                    })
+                    .collect()
+            }),
+            resolver,
+            options
+        )
+    }
+}
@@ -0,0 +1,290 @@
+use super::*;
+use super::jsonschema::{JsonNode, SchemaBuilder};
+
+const OPENAPI_VERSION: &str = "3.1.0";
+
+// `google.api.http` and the fields of `google.api.HttpRule`.
+const METHOD_HTTP: u32 = 72295728;
+const HTTP_RULE_GET: u32 = 2;
+const HTTP_RULE_PUT: u32 = 3;
+const HTTP_RULE_POST: u32 = 4;
+const HTTP_RULE_DELETE: u32 = 5;
+const HTTP_RULE_PATCH: u32 = 6;
+const HTTP_RULE_BODY: u32 = 7;
+const HTTP_RULE_CUSTOM: u32 = 8;
+const HTTP_RULE_ADDITIONAL_BINDINGS: u32 = 11;
+const HTTP_RULE_RESPONSE_BODY: u32 = 12;
+const CUSTOM_HTTP_PATTERN_KIND: u32 = 1;
+const CUSTOM_HTTP_PATTERN_PATH: u32 = 2;
+
+const SCALAR_WELL_KNOWN_TYPES: &[&str] = &[
+    ".google.protobuf.Timestamp",
+    ".google.protobuf.Duration",
+    ".google.protobuf.FieldMask"
+];
+
+const HTTP_VERBS: &[&str] = &["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+/// One HTTP binding of a method, from its `google.api.http` rule or one of
+/// the rule's additional bindings.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct HttpBinding {
+    pub(crate) verb: String,
+    pub(crate) path: String,
+    pub(crate) body: String,
+    pub(crate) response_body: String
+}
+
+fn parse_http_rule(bytes: &[u8], bindings: &mut Vec<HttpBinding>) {
+    let fields = match unknown_fields(bytes) {
+        Some(fields) => fields,
+        None => return
+    };
+    let string = |fields: &protobuf::UnknownFields, number| string_option(fields, number).unwrap_or_default();
+    let pattern = vec![
+        ("get", HTTP_RULE_GET),
+        ("put", HTTP_RULE_PUT),
+        ("post", HTTP_RULE_POST),
+        ("delete", HTTP_RULE_DELETE),
+        ("patch", HTTP_RULE_PATCH)
+    ]
+        .into_iter()
+        .find_map(|(verb, number)| string_option(&fields, number).map(|path| (verb.to_string(), path)))
+        .or_else(|| {
+            let custom = fields.get(HTTP_RULE_CUSTOM)?.length_delimited.last().and_then(|bytes| unknown_fields(bytes))?;
+            Some((string(&custom, CUSTOM_HTTP_PATTERN_KIND).to_lowercase(), string(&custom, CUSTOM_HTTP_PATTERN_PATH)))
+        });
+    if let Some((verb, path)) = pattern.filter(|(verb, path)| HTTP_VERBS.contains(&verb.as_str()) && !path.is_empty()) {
+        bindings.push(HttpBinding{
+            verb,
+            path,
+            body: string(&fields, HTTP_RULE_BODY),
+            response_body: string(&fields, HTTP_RULE_RESPONSE_BODY)
+        });
+    }
+    if let Some(values) = fields.get(HTTP_RULE_ADDITIONAL_BINDINGS) {
+        values.length_delimited.iter().for_each(|bytes| parse_http_rule(bytes, bindings));
+    }
+}
+
+pub(crate) fn http_bindings(method: &MethodDescriptorProto) -> Vec<HttpBinding> {
+    let mut bindings = Vec::new();
+    if let Some(values) = method.get_options().get_unknown_fields().get(METHOD_HTTP) {
+        values.length_delimited.iter().for_each(|bytes| parse_http_rule(bytes, &mut bindings));
+    }
+    bindings
+}
+
+/// The OpenAPI path of a binding template with its variables, where
+/// `{name=shelves/*}` becomes `{name}`.
+pub(crate) fn path_template(template: &str) -> (String, Vec<String>) {
+    let mut path = String::new();
+    let mut variables = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        path.push_str(&rest[..start]);
+        let end = rest[start..].find('}').map_or(rest.len(), |end| start + end);
+        let variable = rest[start + 1..end].split('=').next().unwrap_or("").trim().to_string();
+        path.push_str(&format!("{{{}}}", variable));
+        variables.push(variable);
+        rest = rest.get(end + 1..).unwrap_or("");
+    }
+    path.push_str(rest);
+    (path, variables)
+}
+
+/// The field a dotted `path` of proto field names selects.
+pub(crate) fn field_path<'a>(path: &str, message: &'a ir::Message, types: &ir::Types<'a>) -> Option<&'a ir::Field> {
+    let (name, rest) = match path.split_once('.') {
+        Some((name, rest)) => (name, Some(rest)),
+        None => (path, None)
+    };
+    let field = message.fields.iter().find(|field| field.name == name)?;
+    match rest {
+        Some(rest) => field_path(rest, types.message(&field.value.type_name)?, types),
+        None => Some(field)
+    }
+}
+
+fn parameter(name: &str, location: &str, is_required: bool, schema: JsonNode) -> JsonNode {
+    let mut entries = vec![("name", JsonNode::string(name)), ("in", JsonNode::string(location))];
+    if is_required {
+        entries.push(("required", JsonNode::Bool(true)));
+    }
+    entries.push(("schema", schema));
+    JsonNode::object(entries)
+}
+
+fn json_content(schema: JsonNode) -> JsonNode {
+    JsonNode::object(vec![("application/json", JsonNode::object(vec![("schema", schema)]))])
+}
+
+/// Schema of the message `value`, or of its field `field_name` when the
+/// binding selects one.
+fn body_schema(value: &ir::Value, field_name: &str, builder: &mut SchemaBuilder) -> JsonNode {
+    let types = builder.types;
+    match types.message(&value.type_name).and_then(|message| field_path(field_name, message, types)) {
+        Some(field) => builder.field_schema(field),
+        None => builder.value_schema(value)
+    }
+}
+
+/// Request fields outside the path and body bind to query parameters. Only
+/// fields with a scalar representation can, so other messages are left out.
+pub(crate) fn query_fields<'a>(
+    message: &'a ir::Message,
+    binding: &HttpBinding,
+    variables: &[String]
+) -> Vec<&'a ir::Field> {
+    if binding.body == "*" {
+        return vec![];
+    }
+    message.fields
+        .iter()
+        .filter(|field| field.name != binding.body)
+        .filter(|field| !variables.iter().any(|variable| variable.split('.').next() == Some(field.name.as_str())))
+        .filter(|field| !matches!(field.cardinality, ir::Cardinality::Map{ .. }))
+        .filter(|field| match field.value.field_type {
+            FieldDescriptorProto_Type::TYPE_MESSAGE | FieldDescriptorProto_Type::TYPE_GROUP =>
+                wrapped_type(&field.value.type_name).is_some() ||
+                    SCALAR_WELL_KNOWN_TYPES.contains(&field.value.type_name.as_str()),
+            _ => true
+        })
+        .collect()
+}
+
+fn query_parameters(
+    message: &ir::Message,
+    binding: &HttpBinding,
+    variables: &[String],
+    builder: &mut SchemaBuilder
+) -> Vec<JsonNode> {
+    query_fields(message, binding, variables)
+        .into_iter()
+        .map(|field| parameter(&field.json_name, "query", false, builder.field_schema(field)))
+        .collect()
+}
+
+fn operation(
+    service: &ir::Service,
+    method: &ir::Method,
+    operation_id: String,
+    binding: &HttpBinding,
+    variables: &[String],
+    builder: &mut SchemaBuilder
+) -> JsonNode {
+    let types = builder.types;
+    let input = types.message(&method.input.type_name);
+    let mut entries = vec![
+        ("operationId", JsonNode::String(operation_id)),
+        ("tags", JsonNode::strings(vec![service.name.as_str()].into_iter()))
+    ];
+    if let Some(comment) = builder.resolver.comment(&format!("{}.{}", service.full_name, method.name)) {
+        entries.push(("description", JsonNode::string(comment)));
+    }
+    let mut parameters = variables.iter()
+        .map(|variable| {
+            let schema = match input.and_then(|message| field_path(variable, message, types)) {
+                Some(field) => builder.field_schema(field),
+                None => JsonNode::object(vec![("type", JsonNode::string("string"))])
+            };
+            parameter(variable, "path", true, schema)
+        })
+        .collect::<Vec<_>>();
+    if let Some(message) = input {
+        parameters.extend(query_parameters(message, binding, variables, builder));
+    }
+    if !parameters.is_empty() {
+        entries.push(("parameters", JsonNode::Array(parameters)));
+    }
+    if !binding.body.is_empty() {
+        let body = match binding.body.as_str() {
+            "*" => builder.value_schema(&method.input),
+            field_name => body_schema(&method.input, field_name, builder)
+        };
+        entries.push(("requestBody", JsonNode::object(vec![
+            ("required", JsonNode::Bool(true)),
+            ("content", json_content(body))
+        ])));
+    }
+    let response = match binding.response_body.as_str() {
+        "" => builder.value_schema(&method.output),
+        field_name => body_schema(&method.output, field_name, builder)
+    };
+    entries.push(("responses", JsonNode::object(vec![
+        ("200", JsonNode::object(vec![
+            ("description", JsonNode::string("OK")),
+            ("content", json_content(response))
+        ]))
+    ])));
+    JsonNode::object(entries)
+}
+
+fn openapi_file_stem(file_name: &str, resolver: &TypeResolver, options: &Options) -> String {
+    resolver.output_path(file_name, options)
+}
+
+/// An OpenAPI 3.1 document of the methods in a file bound to HTTP through
+/// `google.api.http`, if there are any, with `target=openapi`.
+pub(crate) struct OpenApi;
+
+impl Renderer for OpenApi {
+    fn render(
+        &self,
+        file: &ir::File,
+        types: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File> {
+        let mut builder = SchemaBuilder{
+            root: None,
+            ref_prefix: "#/components/schemas/",
+            types,
+            resolver,
+            references: BTreeSet::new()
+        };
+        let mut paths = Vec::<(String, Vec<(String, JsonNode)>)>::new();
+        for service in file.services.iter() {
+            for method in service.methods.iter() {
+                for (i, binding) in method.http_bindings.iter().enumerate() {
+                    let (path, variables) = path_template(&binding.path);
+                    let operation_id = match i {
+                        0 => format!("{}_{}", service.name, method.name),
+                        _ => format!("{}_{}{}", service.name, method.name, i)
+                    };
+                    let operation = operation(service, method, operation_id, binding, &variables, &mut builder);
+                    match paths.iter_mut().find(|(existing, _)| *existing == path) {
+                        Some((_, operations)) => operations.push((binding.verb.clone(), operation)),
+                        None => paths.push((path, vec![(binding.verb.clone(), operation)]))
+                    }
+                }
+            }
+        }
+        if paths.is_empty() {
+            return vec![];
+        }
+        let title = match file.package.as_str() {
+            "" => file.name.as_str(),
+            package => package
+        };
+        let mut entries = vec![
+            ("openapi", JsonNode::string(OPENAPI_VERSION)),
+            ("info", JsonNode::object(vec![
+                ("title", JsonNode::string(title)),
+                ("version", JsonNode::string("0.0.0"))
+            ])),
+            ("paths", JsonNode::Object(paths.into_iter()
+                .map(|(path, operations)| (path, JsonNode::Object(
+                    operations.into_iter().collect()
+                )))
+                .collect()
+            ))
+        ];
+        let schemas = builder.definitions();
+        if !schemas.is_empty() {
+            entries.push(("components", JsonNode::object(vec![("schemas", JsonNode::Object(schemas))])));
+        }
+        let stem = openapi_file_stem(&file.name, resolver, options);
+        vec![gen_resp_file(format!("{}.openapi.json", stem), format!("{}", JsonNode::object(entries)))]
+    }
+}
@@ -0,0 +1,52 @@
+use super::*;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Number of threads for `jobs=auto`, one per available CPU.
+fn available_jobs() -> usize {
+    std::thread::available_parallelism().map(|jobs| jobs.get()).unwrap_or(1)
+}
+
+/// Maps `f` over `items` on up to `options.jobs` threads, each taking the
+/// next item when done with one. Results keep the order of `items`, along
+/// with the time each took, so that output never depends on scheduling.
+pub(crate) fn map_timed<'a, T, R, F>(items: &'a [T], options: &Options, f: F) -> Vec<(R, Duration)>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&'a T) -> R + Sync
+{
+    let run = |item: &'a T| {
+        let start = Instant::now();
+        let result = f(item);
+        (result, start.elapsed())
+    };
+    let jobs = match options.jobs {
+        0 => available_jobs(),
+        jobs => jobs
+    }.min(items.len());
+    if jobs <= 1 {
+        return items.iter().map(run).collect();
+    }
+    let next = AtomicUsize::new(0);
+    let mut results = std::thread::scope(|scope| {
+        let workers = (0..jobs)
+            .map(|_| scope.spawn(|| {
+                let mut results = Vec::new();
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    match items.get(index) {
+                        Some(item) => results.push((index, run(item))),
+                        None => return results
+                    }
+                }
+            }))
+            .collect::<Vec<_>>();
+        workers.into_iter()
+            .flat_map(|worker| worker.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+            .collect::<Vec<_>>()
+    });
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
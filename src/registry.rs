@@ -0,0 +1,135 @@
+use super::*;
+
+const REGISTRY_FILE_STEM: &str = "any_registry";
+const TYPE_URL_PREFIX: &str = "type.googleapis.com/";
+
+struct RegistryEntry {
+    full_name: String,
+    ts_type: String,
+    prefix: String
+}
+
+impl RegistryEntry {
+    fn type_url(&self) -> String {
+        format!("{}{}", TYPE_URL_PREFIX, self.full_name.trim_start_matches('.'))
+    }
+}
+
+struct TypeRegistry<'a> {
+    entries: &'a [RegistryEntry],
+    runtime_json: bool,
+    runtime_binary: bool
+}
+
+impl<'a> fmt::Display for TypeRegistry<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "/** Messages of the schema by the type URL `google.protobuf.Any` packs them with. */")?;
+        writeln!(f, "export interface TypeRegistry {{")?;
+        for entry in self.entries.iter() {
+            writeln!(f, "  \"{}\": {};", entry.type_url(), entry.ts_type)?;
+        }
+        writeln!(f, "}}")?;
+        writeln!(f)?;
+        writeln!(f, "/** An `Any` holding a message of the schema, in its JSON form. */")?;
+        writeln!(f, "export type AnyKnownMessage = {{")?;
+        writeln!(f, "  [K in keyof TypeRegistry]: Readonly<{{ \"@type\": K }}> & TypeRegistry[K];")?;
+        writeln!(f, "}}[keyof TypeRegistry];")?;
+        writeln!(f)?;
+        writeln!(f, "export const typeRegistry = {{")?;
+        for entry in self.entries.iter() {
+            write!(f, "  \"{}\": {{ typeName: \"{}\"", entry.type_url(), entry.full_name.trim_start_matches('.'))?;
+            if self.runtime_json {
+                write!(f, ", fromJSON: {0}FromJSON, toJSON: {0}ToJSON", entry.prefix)?;
+            }
+            if self.runtime_binary {
+                write!(f, ", decode: decode{0}, encode: encode{0}", entry.prefix)?;
+            }
+            writeln!(f, " }},")?;
+        }
+        writeln!(f, "}} as const;")?;
+        writeln!(f)?;
+        writeln!(f, "export function isAnyKnownMessage(value: {{ readonly \"@type\": string }}): value is AnyKnownMessage {{")?;
+        writeln!(f, "  return Object.prototype.hasOwnProperty.call(typeRegistry, value[\"@type\"]);")?;
+        writeln!(f, "}}")
+    }
+}
+
+/// Imports of the runtime functions of `entries` from the `_<suffix>` files
+/// generated next to their proto files.
+fn runtime_imports(
+    stem: &str,
+    entries: &[(&str, &RegistryEntry)],
+    suffix: &str,
+    names: impl Fn(&str) -> Vec<String>,
+    resolver: &TypeResolver,
+    options: &Options
+) -> String {
+    let mut imports = BTreeMap::<String, BTreeSet<String>>::new();
+    for (file_name, entry) in entries.iter() {
+        let source_stem = output_file_stem(file_name, &format!("_{}", suffix), resolver, options);
+        imports.entry(relative_import(stem, &source_stem, ".ts", options))
+            .or_default()
+            .extend(names(&entry.prefix));
+    }
+    imports.iter()
+        .map(|(specifier, names)| format!(
+            "import {{ {} }} from \"{}\";\n",
+            names.iter().cloned().collect::<Vec<_>>().join(", "),
+            specifier
+        ))
+        .collect()
+}
+
+/// A registry of the messages of every generated proto file by their
+/// `Any` type URL, so that packed payloads can be narrowed and unpacked.
+pub(crate) fn gen_any_registry_file(
+    files: &[&ir::File],
+    resolver: &TypeResolver,
+    options: &Options
+) -> Option<CodeGeneratorResponse_File> {
+    let mut entries = files.iter()
+        .flat_map(|file| file.messages
+            .iter()
+            .map(move |message| (file.name.as_str(), RegistryEntry{
+                full_name: message.full_name.clone(),
+                ts_type: message.ts_name.clone(),
+                prefix: resolver.function_prefix(&message.full_name)
+            }))
+        )
+        .collect::<Vec<_>>();
+    if entries.is_empty() {
+        return None;
+    }
+    entries.sort_by(|(_, a), (_, b)| a.full_name.cmp(&b.full_name));
+    let entries_by_file = entries.iter().map(|(file_name, entry)| (*file_name, entry)).collect::<Vec<_>>();
+    let mut content = CodeWriter::with_capacity(entries.len() * 256);
+    content.push(gen_imports(
+        REGISTRY_FILE_STEM,
+        entries.iter().map(|(_, entry)| &entry.full_name),
+        resolver,
+        options
+    ));
+    if options.runtime_json {
+        content.push(runtime_imports(REGISTRY_FILE_STEM, &entries_by_file, "json", |prefix| vec![
+            format!("{}FromJSON", prefix),
+            format!("{}ToJSON", prefix)
+        ], resolver, options));
+    }
+    if options.runtime_binary {
+        content.push(runtime_imports(REGISTRY_FILE_STEM, &entries_by_file, "binary", |prefix| vec![
+            format!("decode{}", prefix),
+            format!("encode{}", prefix)
+        ], resolver, options));
+    }
+    if !content.as_str().is_empty() {
+        content.push('\n');
+    }
+    let entries = entries.into_iter().map(|(_, entry)| entry).collect::<Vec<_>>();
+    content.push(TypeRegistry{
+        entries: &entries,
+        runtime_json: options.runtime_json,
+        runtime_binary: options.runtime_binary
+    });
+    let sources = files.iter().map(|file| file.name.as_str());
+    Some(with_banner(gen_resp_file(format!("{}.ts", REGISTRY_FILE_STEM), content.into_string()), sources, options))
+}
@@ -0,0 +1,112 @@
+use super::*;
+
+const RUNTIME_FILE_STEM: &str = "runtime";
+
+/// The module runtime files import their helpers from, unless inlined.
+/// Global declarations have no imports, so they keep helpers inline.
+fn runtime_specifier(stem: &str, options: &Options) -> Option<String> {
+    match (&options.runtime_module, options.module_style) {
+        (_, ModuleStyle::Global) | (None, _) => None,
+        (Some(RuntimeModule::Package(package)), _) => Some(package.clone()),
+        (Some(RuntimeModule::Shared), ModuleStyle::Module) => Some(RUNTIME_FILE_STEM.to_string()),
+        (Some(RuntimeModule::Shared), _) => Some(relative_import(stem, RUNTIME_FILE_STEM, ".ts", options))
+    }
+}
+
+/// Names of the classes, functions and types `helper` declares, with
+/// whether each is a type.
+fn declared_names(helper: &str) -> Vec<(&str, bool)> {
+    helper.lines()
+        .filter_map(|line| {
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (rest, is_type) = match line.split_once(' ') {
+                Some(("class", rest)) | Some(("function", rest)) => (rest, false),
+                Some(("type", rest)) => (rest, true),
+                _ => return None
+            };
+            let end = rest.find(|c: char| !c.is_alphanumeric() && c != '_' && c != '$')?;
+            Some((&rest[..end], is_type))
+        })
+        .collect()
+}
+
+/// The `helpers` of the runtime file `stem`: inline, or imported from the
+/// shared runtime module. Helpers a file exports are re-exported, so that
+/// sharing them leaves the API of the file unchanged.
+pub(crate) fn gen_helpers(stem: &str, helpers: &[&str], options: &Options) -> String {
+    let specifier = match runtime_specifier(stem, options) {
+        Some(specifier) => specifier,
+        None => return helpers.iter()
+            .map(|helper| format!("\n{}", helper))
+            .collect()
+    };
+    let names = helpers.iter().flat_map(|helper| declared_names(helper)).collect::<Vec<_>>();
+    let list = |is_type: bool| names.iter()
+        .filter(|(_, is_name_type)| *is_name_type == is_type)
+        .map(|(name, _)| *name)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut content = String::new();
+    if names.iter().any(|(_, is_type)| !is_type) {
+        content.push_str(&format!("import {{ {} }} from \"{}\";\n", list(false), specifier));
+    }
+    if names.iter().any(|(_, is_type)| *is_type) {
+        content.push_str(&format!("import {}{{ {} }} from \"{}\";\n", options.ts_syntax.type_only(), list(true), specifier));
+    }
+    let exported = helpers.iter()
+        .flat_map(|helper| helper.lines().filter_map(|line| line.strip_prefix("export ")))
+        .flat_map(declared_names)
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+    if !exported.is_empty() {
+        content.push_str(&format!("export {}{{ {} }};\n", options.ts_syntax.type_only(), exported.join(", ")));
+    }
+    content
+}
+
+/// `helper` with its classes and functions exported.
+fn exported(helper: &str) -> String {
+    helper.lines()
+        .map(|line| match line.starts_with("class ") || line.starts_with("function ") {
+            true => format!("export {}\n", line),
+            false => format!("{}\n", line)
+        })
+        .collect()
+}
+
+/// The shared runtime module of `runtime_module=shared`, holding the helpers
+/// of every runtime mode generated.
+pub(crate) fn gen_runtime_file(
+    files: &[&ir::File],
+    options: &Options
+) -> Option<CodeGeneratorResponse_File> {
+    let is_shared = matches!(options.runtime_module, Some(RuntimeModule::Shared)) &&
+        !matches!(options.module_style, ModuleStyle::Global);
+    let has_messages = files.iter().any(|file| !file.messages.is_empty() || !file.enums.is_empty());
+    if !is_shared || !has_messages {
+        return None;
+    }
+    let mut helpers = Vec::new();
+    if options.runtime_binary {
+        helpers.extend(binary::HELPERS);
+    }
+    if options.runtime_json {
+        helpers.push(json::JSON_HELPERS);
+    }
+    if options.runtime_json || options.runtime_binary {
+        helpers.push(BASE64_HELPERS);
+    }
+    if options.factories {
+        helpers.push(factories::DEEP_PARTIAL);
+    }
+    if helpers.is_empty() {
+        return None;
+    }
+    let content = helpers.iter()
+        .map(|helper| exported(helper))
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some(with_banner(gen_resp_file(format!("{}.ts", RUNTIME_FILE_STEM), content), None, options))
+}
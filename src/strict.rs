@@ -0,0 +1,89 @@
+use super::*;
+
+/// Collects the constructs of a proto file the generated code cannot
+/// express, which otherwise degrade silently.
+struct StrictChecker<'a> {
+    proto_file: &'a FileDescriptorProto,
+    locations: HashMap<&'a [i32], &'a SourceCodeInfo_Location>,
+    resolver: &'a TypeResolver,
+    skipped: &'a BTreeSet<String>,
+    options: &'a Options,
+    problems: Vec<String>
+}
+
+impl<'a> StrictChecker<'a> {
+    /// Reports at `path`, or at the innermost element around it protoc kept
+    /// a location for.
+    fn report(&mut self, path: &[i32], full_name: &str, reason: &str) {
+        let span = (1..=path.len() / 2)
+            .rev()
+            .find_map(|depth| self.locations.get(&path[..depth * 2]))
+            .map(|location| location.get_span());
+        let location = match (span, self.options.error_format) {
+            (Some([line, column, ..]), _) => format!("{}:{}:{}", self.proto_file.get_name(), line + 1, column + 1),
+            (_, ErrorFormat::Machine) => format!("{}:1:1", self.proto_file.get_name()),
+            (_, ErrorFormat::Text) => self.proto_file.get_name().to_string()
+        };
+        self.problems.push(format!("{}: {}: {}", location, full_name.trim_start_matches('.'), reason));
+    }
+
+    fn check_field(&mut self, path: &[i32], full_name: &str, field: &FieldDescriptorProto) {
+        if bool_option(field.get_options().get_unknown_fields(), OPTION_SKIP) {
+            return;
+        }
+        let type_name = field.get_type_name();
+        if !type_name.is_empty() &&
+            self.resolver.message_type(type_name).is_none() &&
+            self.resolver.enum_type(type_name).is_none() &&
+            well_known_type_to_ts_type(type_name, self.options).is_none() &&
+            field_ts_type_option(field).is_none() {
+            self.report(path, full_name, &format!("type {} cannot be resolved", type_name.trim_start_matches('.')));
+        }
+    }
+
+    fn check_message(&mut self, path: Vec<i32>, full_name: String, message_type: &DescriptorProto) {
+        if self.skipped.contains(&full_name) {
+            return;
+        }
+        for (i, field) in message_type.get_field().iter().enumerate() {
+            let mut field_path = path.clone();
+            field_path.extend(vec![MESSAGE_FIELD, i as i32]);
+            self.check_field(&field_path, &format!("{}.{}", full_name, field.get_name()), field);
+        }
+        for (i, nested_type) in message_type.get_nested_type().iter().enumerate() {
+            let mut nested_path = path.clone();
+            nested_path.extend(vec![MESSAGE_NESTED_TYPE, i as i32]);
+            self.check_message(nested_path, format!("{}.{}", full_name, nested_type.get_name()), nested_type);
+        }
+    }
+}
+
+/// Descriptions of every unsupported construct in `proto_file`, located by
+/// the source positions protoc provides.
+pub(crate) fn unsupported_constructs(
+    proto_file: &FileDescriptorProto,
+    resolver: &TypeResolver,
+    skipped: &BTreeSet<String>,
+    options: &Options
+) -> Vec<String> {
+    let mut checker = StrictChecker{
+        proto_file,
+        locations: proto_file.get_source_code_info()
+            .get_location()
+            .iter()
+            .map(|location| (location.get_path(), location))
+            .collect(),
+        resolver,
+        skipped,
+        options,
+        problems: Vec::new()
+    };
+    for (i, message_type) in proto_file.get_message_type().iter().enumerate() {
+        checker.check_message(
+            vec![FILE_MESSAGE_TYPE, i as i32],
+            qualified_name(proto_file.get_package(), message_type.get_name()),
+            message_type
+        );
+    }
+    checker.problems
+}
@@ -0,0 +1,441 @@
+use super::*;
+
+/// Data exposed to templates, shaped like the JSON a Handlebars template
+/// would be rendered with.
+#[derive(Clone)]
+enum Data {
+    Null,
+    Bool(bool),
+    Number(i64),
+    String(String),
+    List(Vec<Data>),
+    Object(Vec<(&'static str, Data)>)
+}
+
+impl Data {
+    fn string(value: &str) -> Data {
+        Data::String(value.to_string())
+    }
+
+    fn optional(value: &Option<String>) -> Data {
+        value.as_deref().map(Data::string).unwrap_or(Data::Null)
+    }
+
+    fn get(&self, key: &str) -> Option<&Data> {
+        match self {
+            Data::Object(entries) => entries.iter().find(|(name, _)| *name == key).map(|(_, value)| value),
+            Data::List(items) => key.parse::<usize>().ok().and_then(|index| items.get(index)),
+            _ => None
+        }
+    }
+
+    fn is_truthy(&self) -> bool {
+        match self {
+            Data::Null => false,
+            Data::Bool(value) => *value,
+            Data::Number(value) => *value != 0,
+            Data::String(value) => !value.is_empty(),
+            Data::List(items) => !items.is_empty(),
+            Data::Object(_) => true
+        }
+    }
+}
+
+impl fmt::Display for Data {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Data::Null => Ok(()),
+            Data::Bool(value) => write!(f, "{}", value),
+            Data::Number(value) => write!(f, "{}", value),
+            Data::String(value) => write!(f, "{}", value),
+            Data::List(items) => write!(f, "{}", items.iter().map(|item| item.to_string()).collect::<Vec<_>>().join(",")),
+            Data::Object(_) => write!(f, "[object Object]")
+        }
+    }
+}
+
+fn value_data(value: &ir::Value) -> Data {
+    Data::Object(vec![
+        ("kind", Data::string(match value.kind {
+            ir::ValueKind::Scalar(_) => "scalar",
+            ir::ValueKind::Enum => "enum",
+            ir::ValueKind::Message => "message",
+            ir::ValueKind::WellKnown => "wellKnown"
+        })),
+        ("scalar", match value.kind {
+            ir::ValueKind::Scalar(field_type) => Data::string(scalar_name(field_type)),
+            _ => Data::Null
+        }),
+        ("typeName", Data::string(value.type_name.trim_start_matches('.'))),
+        ("tsType", Data::string(&value.ts_type))
+    ])
+}
+
+fn message_data(message: &ir::Message) -> Data {
+    Data::Object(vec![
+        ("fullName", Data::string(message.full_name.trim_start_matches('.'))),
+        ("name", Data::string(&message.ts_name)),
+        ("doc", Data::optional(&message.doc)),
+        ("fields", Data::List(message.fields.iter().map(|field| Data::Object(vec![
+            ("name", Data::string(&field.name)),
+            ("key", Data::string(&field.key)),
+            ("number", Data::Number(field.number as i64)),
+            ("tsType", Data::string(&field.ts_type)),
+            ("value", value_data(&field.value)),
+            ("isRepeated", Data::Bool(field.cardinality == ir::Cardinality::Repeated)),
+            ("isMap", Data::Bool(matches!(field.cardinality, ir::Cardinality::Map{ .. }))),
+            ("mapKey", match &field.cardinality {
+                ir::Cardinality::Map{ key } => value_data(key),
+                _ => Data::Null
+            }),
+            ("isOptional", Data::Bool(field.is_optional)),
+            ("oneof", field.oneof.map(|index| Data::string(&message.oneofs[index].name)).unwrap_or(Data::Null)),
+            ("doc", Data::optional(&field.doc))
+        ])).collect())),
+        ("oneofs", Data::List(message.oneofs
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| message.oneof_fields(*index).next().is_some())
+            .map(|(index, oneof)| Data::Object(vec![
+                ("name", Data::string(&oneof.name)),
+                ("key", Data::string(&oneof.key)),
+                ("doc", Data::optional(&oneof.doc)),
+                ("fields", Data::List(message.oneof_fields(index).map(|field| Data::string(&field.name)).collect()))
+            ]))
+            .collect()
+        ))
+    ])
+}
+
+fn enum_data(enum_type: &ir::Enum) -> Data {
+    Data::Object(vec![
+        ("fullName", Data::string(enum_type.full_name.trim_start_matches('.'))),
+        ("name", Data::string(&enum_type.ts_name)),
+        ("doc", Data::optional(&enum_type.doc)),
+        ("values", Data::List(enum_type.values.iter().map(|value| Data::Object(vec![
+            ("name", Data::string(&value.name)),
+            ("tsName", Data::string(&value.ts_name)),
+            ("number", Data::Number(value.number as i64)),
+            ("aliases", Data::List(value.aliases.iter().map(|alias| Data::string(alias)).collect())),
+            ("doc", Data::optional(&value.doc))
+        ])).collect()))
+    ])
+}
+
+fn service_data(service: &ir::Service) -> Data {
+    Data::Object(vec![
+        ("fullName", Data::string(service.full_name.trim_start_matches('.'))),
+        ("name", Data::string(&service.name)),
+        ("doc", Data::optional(&service.doc)),
+        ("methods", Data::List(service.methods.iter().map(|method| Data::Object(vec![
+            ("name", Data::string(&method.name)),
+            ("path", Data::string(&method.path)),
+            ("input", value_data(&method.input)),
+            ("output", value_data(&method.output)),
+            ("clientStreaming", Data::Bool(method.client_streaming)),
+            ("serverStreaming", Data::Bool(method.server_streaming)),
+            ("doc", Data::optional(&method.doc))
+        ])).collect()))
+    ])
+}
+
+enum Node {
+    Text(String),
+    Value(String),
+    Block {
+        helper: String,
+        path: String,
+        body: Vec<Node>,
+        inverse: Vec<Node>
+    }
+}
+
+enum Tag {
+    Text(String),
+    Value(String),
+    Open(String, String),
+    Else,
+    Close(String),
+    Comment
+}
+
+impl Tag {
+    /// Whether the tag disappears with its line when it stands alone on it.
+    fn is_standalone(&self) -> bool {
+        !matches!(self, Tag::Text(_) | Tag::Value(_))
+    }
+}
+
+/// Drops the lines of block tags and comments standing alone on them, as
+/// Handlebars does, so that they leave no blank lines behind.
+fn strip_standalone(tags: &mut [Tag]) {
+    let mut at_line_start = vec![false; tags.len()];
+    for i in (1..tags.len()).step_by(2) {
+        if !tags[i].is_standalone() {
+            continue;
+        }
+        let before = match &tags[i - 1] {
+            Tag::Text(text) => match text.rfind('\n') {
+                Some(index) => text[index + 1..].trim().is_empty(),
+                None => text.trim().is_empty() && (i == 1 || at_line_start[i - 1])
+            },
+            _ => false
+        };
+        let after = match tags.get(i + 1) {
+            Some(Tag::Text(text)) => match text.find('\n') {
+                Some(index) => text[..index].trim().is_empty(),
+                None => text.trim().is_empty() && i + 2 >= tags.len()
+            },
+            _ => true
+        };
+        if !(before && after) {
+            continue;
+        }
+        if let Tag::Text(text) = &mut tags[i - 1] {
+            text.truncate(text.rfind('\n').map(|index| index + 1).unwrap_or(0));
+        }
+        if let Some(Tag::Text(text)) = tags.get_mut(i + 1) {
+            *text = text.find('\n').map(|index| text[index + 1..].to_string()).unwrap_or_default();
+            at_line_start[i + 1] = true;
+        }
+    }
+}
+
+/// Splits a template into text and `{{...}}` tags, applying the `~`
+/// whitespace control of Handlebars. Text and tags alternate, starting and
+/// ending with text.
+fn tokenize(template: &str) -> std::result::Result<Vec<Tag>, String> {
+    let mut tags = Vec::new();
+    let mut rest = template;
+    let mut trim_next = false;
+    loop {
+        let start = rest.find("{{").unwrap_or(rest.len());
+        let mut text = &rest[..start];
+        if trim_next {
+            text = text.trim_start();
+        }
+        if start == rest.len() {
+            tags.push(Tag::Text(text.to_string()));
+            break;
+        }
+        let is_triple = rest[start..].starts_with("{{{");
+        let inner_start = start + if is_triple { 3 } else { 2 };
+        let marker = rest[inner_start..].trim_start_matches('~');
+        let (close, is_comment) = match marker.starts_with("!--") {
+            true => ("--}}", true),
+            false if is_triple => ("}}}", false),
+            false => ("}}", marker.starts_with('!'))
+        };
+        let end = rest[inner_start..].find(close)
+            .map(|index| inner_start + index)
+            .ok_or_else(|| format!("unclosed tag at {}", rest[start..].lines().next().unwrap_or("")))?;
+        let mut inner = &rest[inner_start..end];
+        if let Some(trimmed) = inner.strip_prefix('~') {
+            text = text.trim_end();
+            inner = trimmed;
+        }
+        trim_next = inner.ends_with('~');
+        let inner = inner.trim_end_matches('~').trim();
+        tags.push(Tag::Text(text.to_string()));
+        rest = &rest[end + close.len()..];
+        tags.push(match inner.chars().next() {
+            _ if is_comment => Tag::Comment,
+            Some('#') => {
+                let mut words = inner[1..].split_whitespace();
+                let helper = words.next().unwrap_or("").to_string();
+                Tag::Open(helper, words.collect::<Vec<_>>().join(" "))
+            },
+            Some('/') => Tag::Close(inner[1..].trim().to_string()),
+            _ if inner == "else" || inner == "^" => Tag::Else,
+            _ => Tag::Value(inner.to_string())
+        });
+    }
+    strip_standalone(&mut tags);
+    Ok(tags)
+}
+
+fn parse_nodes(tags: &mut std::vec::IntoIter<Tag>, closing: Option<&str>) -> std::result::Result<(Vec<Node>, Vec<Node>), String> {
+    let mut body = Vec::new();
+    let mut inverse = Vec::new();
+    let mut in_inverse = false;
+    while let Some(tag) = tags.next() {
+        let nodes = match in_inverse {
+            true => &mut inverse,
+            false => &mut body
+        };
+        match tag {
+            Tag::Text(text) => if !text.is_empty() {
+                nodes.push(Node::Text(text));
+            },
+            Tag::Value(path) => nodes.push(Node::Value(path)),
+            Tag::Comment => {},
+            Tag::Open(helper, path) => {
+                if !["each", "if", "unless", "with"].contains(&helper.as_str()) {
+                    return Err(format!("unknown helper #{}", helper));
+                }
+                let (block_body, block_inverse) = parse_nodes(tags, Some(&helper))?;
+                nodes.push(Node::Block{ helper, path, body: block_body, inverse: block_inverse });
+            },
+            Tag::Else if closing.is_some() && !in_inverse => in_inverse = true,
+            Tag::Else => return Err("unexpected {{else}}".to_string()),
+            Tag::Close(helper) => return match closing {
+                Some(expected) if expected == helper => Ok((body, inverse)),
+                Some(expected) => Err(format!("{{{{/{}}}}} closes {{{{#{}}}}}", helper, expected)),
+                None => Err(format!("unexpected {{{{/{}}}}}", helper))
+            }
+        }
+    }
+    match closing {
+        Some(helper) => Err(format!("unclosed {{{{#{}}}}}", helper)),
+        None => Ok((body, inverse))
+    }
+}
+
+/// A Handlebars template, supporting paths, `#each`, `#if`, `#unless`,
+/// `#with`, `else`, comments and whitespace control. Values are inserted
+/// as they are, since TypeScript needs no HTML escaping.
+pub(crate) struct Template {
+    path: String,
+    nodes: Vec<Node>
+}
+
+/// A frame of the rendering context: the current value and the data
+/// variables `#each` defines.
+struct Frame<'a> {
+    this: &'a Data,
+    index: Option<(usize, usize)>,
+    key: Option<&'a str>
+}
+
+impl Template {
+    pub(crate) fn parse(path: &str, template: &str) -> Result<Template> {
+        let error = |reason| Error::Template{ path: path.to_string(), reason };
+        let tags = tokenize(template).map_err(error)?;
+        let (nodes, _) = parse_nodes(&mut tags.into_iter(), None).map_err(error)?;
+        Ok(Template{ path: path.to_string(), nodes })
+    }
+
+    pub(crate) fn read(path: &str) -> Result<Template> {
+        let template = std::fs::read_to_string(path).map_err(|error| Error::Io{ path: path.to_string(), error })?;
+        Template::parse(path, &template)
+    }
+
+    /// Name of the output, `wrappers.ts` of `templates/wrappers.ts.hbs`.
+    fn output_name(&self) -> &str {
+        let name = self.path.rsplit('/').next().unwrap_or(&self.path);
+        name.strip_suffix(".hbs").unwrap_or(name)
+    }
+
+    fn resolve<'a>(&self, path: &str, frames: &[Frame<'a>]) -> Option<&'a Data> {
+        let mut depth = frames.len() - 1;
+        let mut path = path;
+        while let Some(rest) = path.strip_prefix("../") {
+            depth = depth.checked_sub(1)?;
+            path = rest;
+        }
+        let frame = &frames[depth];
+        let path = path.strip_prefix("this.").or_else(|| path.strip_prefix("./")).unwrap_or(path);
+        if path == "this" || path == "." {
+            return Some(frame.this);
+        }
+        let mut segments = path.split('.');
+        let first = segments.next()?;
+        let mut value = match first {
+            "@index" | "@first" | "@last" | "@key" => return None,
+            _ => frame.this.get(first)?
+        };
+        for segment in segments {
+            value = value.get(segment)?;
+        }
+        Some(value)
+    }
+
+    fn render_nodes<'a>(&self, nodes: &[Node], frames: &mut Vec<Frame<'a>>, out: &mut String) {
+        for node in nodes {
+            match node {
+                Node::Text(text) => out.push_str(text),
+                Node::Value(path) => {
+                    let frame = &frames[frames.len() - 1];
+                    match (path.as_str(), frame.index) {
+                        ("@index", Some((index, _))) => out.push_str(&index.to_string()),
+                        ("@first", Some((index, _))) => out.push_str(&(index == 0).to_string()),
+                        ("@last", Some((index, len))) => out.push_str(&(index + 1 == len).to_string()),
+                        ("@key", _) => out.push_str(frame.key.unwrap_or("")),
+                        _ => if let Some(value) = self.resolve(path, frames) {
+                            out.push_str(&value.to_string());
+                        }
+                    }
+                },
+                Node::Block{ helper, path, body, inverse } => {
+                    let value = self.resolve(path, frames);
+                    let is_truthy = match (path.as_str(), frames[frames.len() - 1].index) {
+                        ("@first", Some((index, _))) => index == 0,
+                        ("@last", Some((index, len))) => index + 1 == len,
+                        _ => value.is_some_and(Data::is_truthy)
+                    };
+                    match helper.as_str() {
+                        "each" => match value {
+                            Some(Data::List(items)) if !items.is_empty() => for (index, item) in items.iter().enumerate() {
+                                frames.push(Frame{ this: item, index: Some((index, items.len())), key: None });
+                                self.render_nodes(body, frames, out);
+                                frames.pop();
+                            },
+                            Some(Data::Object(entries)) if !entries.is_empty() => for (index, (key, item)) in entries.iter().enumerate() {
+                                frames.push(Frame{ this: item, index: Some((index, entries.len())), key: Some(key) });
+                                self.render_nodes(body, frames, out);
+                                frames.pop();
+                            },
+                            _ => self.render_nodes(inverse, frames, out)
+                        },
+                        "with" => match value.filter(|value| value.is_truthy()) {
+                            Some(value) => {
+                                frames.push(Frame{ this: value, index: None, key: None });
+                                self.render_nodes(body, frames, out);
+                                frames.pop();
+                            },
+                            None => self.render_nodes(inverse, frames, out)
+                        },
+                        "unless" => self.render_nodes(if is_truthy { inverse } else { body }, frames, out),
+                        _ => self.render_nodes(if is_truthy { body } else { inverse }, frames, out)
+                    }
+                }
+            }
+        }
+    }
+
+    fn render(&self, data: &Data) -> String {
+        let mut out = String::new();
+        self.render_nodes(&self.nodes, &mut vec![Frame{ this: data, index: None, key: None }], &mut out);
+        out
+    }
+}
+
+impl Renderer for Template {
+    fn render(
+        &self,
+        file: &ir::File,
+        _: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File> {
+        let name = output_file_stem(&file.name, &format!("_{}", self.output_name()), resolver, options);
+        let data = Data::Object(vec![
+            ("name", Data::string(&file.name)),
+            ("package", Data::string(&file.package)),
+            ("typesModule", Data::string(&relative_import(
+                &name,
+                &proto_file_stem(&file.name, resolver, options),
+                declaration_suffix(options).1,
+                options
+            ))),
+            ("messages", Data::List(file.messages.iter().map(message_data).collect())),
+            ("enums", Data::List(file.enums.iter().map(enum_data).collect())),
+            ("services", Data::List(file.services.iter().map(service_data).collect()))
+        ]);
+        let content = Template::render(self, &data);
+        match content.trim().is_empty() {
+            true => vec![],
+            false => vec![gen_resp_file(name, content)]
+        }
+    }
+}
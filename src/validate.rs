@@ -0,0 +1,208 @@
+use super::*;
+
+// `buf.validate.field` of protovalidate and `validate.rules` of
+// protoc-gen-validate. Both number the rules of each type alike.
+const FIELD_PROTOVALIDATE: u32 = 1159;
+const FIELD_PGV: u32 = 1071;
+
+const RULES_STRING: u32 = 14;
+const RULES_REPEATED: u32 = 18;
+const RULES_MAP: u32 = 19;
+// `required` of protovalidate, and `message.required` of protoc-gen-validate.
+const RULES_REQUIRED: u32 = 25;
+const RULES_MESSAGE: u32 = 17;
+const MESSAGE_RULES_REQUIRED: u32 = 2;
+
+const NUMBER_LT: u32 = 2;
+const NUMBER_LTE: u32 = 3;
+const NUMBER_GT: u32 = 4;
+const NUMBER_GTE: u32 = 5;
+
+const STRING_MIN_LEN: u32 = 2;
+const STRING_MAX_LEN: u32 = 3;
+const STRING_PATTERN: u32 = 6;
+const STRING_LEN: u32 = 19;
+const STRING_FORMATS: &[(u32, &str)] = &[
+    (12, "email"),
+    (13, "hostname"),
+    (15, "ipv4"),
+    (16, "ipv6"),
+    (17, "uri"),
+    (18, "uri-reference"),
+    (22, "uuid")
+];
+
+const REPEATED_MIN_ITEMS: u32 = 1;
+const REPEATED_MAX_ITEMS: u32 = 2;
+const REPEATED_UNIQUE: u32 = 3;
+const REPEATED_ITEMS: u32 = 4;
+const MAP_MIN_PAIRS: u32 = 1;
+const MAP_MAX_PAIRS: u32 = 2;
+const MAP_VALUES: u32 = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Bound {
+    pub(crate) value: f64,
+    pub(crate) is_exclusive: bool
+}
+
+/// Constraints on a single value: the field itself, or each item of a
+/// repeated field and each value of a map.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct ValueConstraints {
+    pub(crate) min_len: Option<u64>,
+    pub(crate) max_len: Option<u64>,
+    pub(crate) pattern: Option<String>,
+    /// Name of the string format in JSON Schema.
+    pub(crate) format: Option<&'static str>,
+    pub(crate) minimum: Option<Bound>,
+    pub(crate) maximum: Option<Bound>
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct FieldConstraints {
+    pub(crate) required: bool,
+    pub(crate) value: ValueConstraints,
+    /// Bounds on the items of a repeated field, or the pairs of a map.
+    pub(crate) min_items: Option<u64>,
+    pub(crate) max_items: Option<u64>,
+    pub(crate) unique_items: bool
+}
+
+fn rules(fields: &protobuf::UnknownFields, number: u32) -> Option<protobuf::UnknownFields> {
+    unknown_fields(&fields.get(number)?.length_delimited.concat())
+}
+
+fn varint(fields: &protobuf::UnknownFields, number: u32) -> Option<u64> {
+    fields.get(number)?.varint.last().copied()
+}
+
+/// Field of `FieldRules` holding the rules of values of `field_type`.
+fn number_rules(field_type: FieldDescriptorProto_Type) -> Option<u32> {
+    match field_type {
+        FieldDescriptorProto_Type::TYPE_FLOAT => Some(1),
+        FieldDescriptorProto_Type::TYPE_DOUBLE => Some(2),
+        FieldDescriptorProto_Type::TYPE_INT32 => Some(3),
+        FieldDescriptorProto_Type::TYPE_INT64 => Some(4),
+        FieldDescriptorProto_Type::TYPE_UINT32 => Some(5),
+        FieldDescriptorProto_Type::TYPE_UINT64 => Some(6),
+        FieldDescriptorProto_Type::TYPE_SINT32 => Some(7),
+        FieldDescriptorProto_Type::TYPE_SINT64 => Some(8),
+        FieldDescriptorProto_Type::TYPE_FIXED32 => Some(9),
+        FieldDescriptorProto_Type::TYPE_FIXED64 => Some(10),
+        FieldDescriptorProto_Type::TYPE_SFIXED32 => Some(11),
+        FieldDescriptorProto_Type::TYPE_SFIXED64 => Some(12),
+        _ => None
+    }
+}
+
+/// A bound of number rules, encoded with the wire type of `field_type`.
+fn number(fields: &protobuf::UnknownFields, number: u32, field_type: FieldDescriptorProto_Type) -> Option<f64> {
+    let values = fields.get(number)?;
+    Some(match field_type {
+        FieldDescriptorProto_Type::TYPE_FLOAT => f32::from_bits(*values.fixed32.last()?) as f64,
+        FieldDescriptorProto_Type::TYPE_DOUBLE => f64::from_bits(*values.fixed64.last()?),
+        FieldDescriptorProto_Type::TYPE_INT32 | FieldDescriptorProto_Type::TYPE_INT64 => *values.varint.last()? as i64 as f64,
+        FieldDescriptorProto_Type::TYPE_SINT32 | FieldDescriptorProto_Type::TYPE_SINT64 => {
+            let value = *values.varint.last()?;
+            ((value >> 1) as i64 ^ -((value & 1) as i64)) as f64
+        },
+        FieldDescriptorProto_Type::TYPE_FIXED32 => *values.fixed32.last()? as f64,
+        FieldDescriptorProto_Type::TYPE_SFIXED32 => *values.fixed32.last()? as i32 as f64,
+        FieldDescriptorProto_Type::TYPE_FIXED64 => *values.fixed64.last()? as f64,
+        FieldDescriptorProto_Type::TYPE_SFIXED64 => *values.fixed64.last()? as i64 as f64,
+        _ => *values.varint.last()? as f64
+    })
+}
+
+fn bound(fields: &protobuf::UnknownFields, inclusive: u32, exclusive: u32, field_type: FieldDescriptorProto_Type) -> Option<Bound> {
+    number(fields, inclusive, field_type)
+        .map(|value| Bound{ value, is_exclusive: false })
+        .or_else(|| number(fields, exclusive, field_type).map(|value| Bound{ value, is_exclusive: true }))
+}
+
+/// Constraints of `field_rules` on values of `field`, which may be a
+/// wrapper of a scalar.
+fn value_constraints(field_rules: &protobuf::UnknownFields, field: &FieldDescriptorProto) -> ValueConstraints {
+    let mut constraints = ValueConstraints::default();
+    let field_type = wrapped_type(field.get_type_name()).unwrap_or_else(|| field.get_field_type());
+    if let Some(string) = rules(field_rules, RULES_STRING) {
+        let len = varint(&string, STRING_LEN);
+        constraints.min_len = len.or_else(|| varint(&string, STRING_MIN_LEN));
+        constraints.max_len = len.or_else(|| varint(&string, STRING_MAX_LEN));
+        constraints.pattern = string_option(&string, STRING_PATTERN);
+        constraints.format = STRING_FORMATS.iter()
+            .find(|(number, _)| bool_option(&string, *number))
+            .map(|(_, format)| *format);
+    }
+    if let Some(numbers) = number_rules(field_type).and_then(|number| rules(field_rules, number)) {
+        constraints.minimum = bound(&numbers, NUMBER_GTE, NUMBER_GT, field_type);
+        constraints.maximum = bound(&numbers, NUMBER_LTE, NUMBER_LT, field_type);
+    }
+    constraints
+}
+
+/// Validation rules of `field` from protovalidate, or protoc-gen-validate
+/// when it has none.
+pub(crate) fn field_constraints(field: &FieldDescriptorProto, message_type: &DescriptorProto) -> Option<FieldConstraints> {
+    let options = field.get_options().get_unknown_fields();
+    let field_rules = rules(options, FIELD_PROTOVALIDATE).or_else(|| rules(options, FIELD_PGV))?;
+    let mut constraints = FieldConstraints{
+        required: bool_option(&field_rules, RULES_REQUIRED) ||
+            rules(&field_rules, RULES_MESSAGE).is_some_and(|message| bool_option(&message, MESSAGE_RULES_REQUIRED)),
+        ..FieldConstraints::default()
+    };
+    if let Some(entry) = map_entry_type(field, message_type) {
+        if let Some(map) = rules(&field_rules, RULES_MAP) {
+            constraints.min_items = varint(&map, MAP_MIN_PAIRS);
+            constraints.max_items = varint(&map, MAP_MAX_PAIRS);
+            if let (Some(values), Some(value)) = (rules(&map, MAP_VALUES), entry.get_field().get(1)) {
+                constraints.value = value_constraints(&values, value);
+            }
+        }
+        return Some(constraints);
+    }
+    match field.get_label() {
+        FieldDescriptorProto_Label::LABEL_REPEATED => if let Some(repeated) = rules(&field_rules, RULES_REPEATED) {
+            constraints.min_items = varint(&repeated, REPEATED_MIN_ITEMS);
+            constraints.max_items = varint(&repeated, REPEATED_MAX_ITEMS);
+            constraints.unique_items = bool_option(&repeated, REPEATED_UNIQUE);
+            if let Some(items) = rules(&repeated, REPEATED_ITEMS) {
+                constraints.value = value_constraints(&items, field);
+            }
+        },
+        _ => constraints.value = value_constraints(&field_rules, field)
+    }
+    Some(constraints)
+}
+
+/// TSDoc tags named after the JSON Schema keywords of the constraints.
+pub(crate) fn doc_tags(constraints: &FieldConstraints, is_map: bool) -> Vec<String> {
+    let value = &constraints.value;
+    let mut tags = Vec::new();
+    if constraints.required {
+        tags.push("@required".to_string());
+    }
+    let (min_items, max_items) = match is_map {
+        true => ("minProperties", "maxProperties"),
+        false => ("minItems", "maxItems")
+    };
+    tags.extend(constraints.min_items.map(|n| format!("@{} {}", min_items, n)));
+    tags.extend(constraints.max_items.map(|n| format!("@{} {}", max_items, n)));
+    if constraints.unique_items {
+        tags.push("@uniqueItems".to_string());
+    }
+    tags.extend(value.min_len.map(|n| format!("@minLength {}", n)));
+    tags.extend(value.max_len.map(|n| format!("@maxLength {}", n)));
+    tags.extend(value.pattern.as_ref().map(|pattern| format!("@pattern {}", pattern)));
+    tags.extend(value.format.map(|format| format!("@format {}", format)));
+    tags.extend(value.minimum.map(|bound| match bound.is_exclusive {
+        true => format!("@exclusiveMinimum {}", bound.value),
+        false => format!("@minimum {}", bound.value)
+    }));
+    tags.extend(value.maximum.map(|bound| match bound.is_exclusive {
+        true => format!("@exclusiveMaximum {}", bound.value),
+        false => format!("@maximum {}", bound.value)
+    }));
+    tags
+}
@@ -0,0 +1,75 @@
+use super::*;
+
+const INDENT: &str = "  ";
+
+/// Rough size of the code generated per declaration and per field, used to
+/// size the buffer of an output file up front.
+const DECLARATION_SIZE: usize = 256;
+const FIELD_SIZE: usize = 96;
+
+/// Writes generated code into `out`, indenting lines by the current level as
+/// they are started rather than re-indenting finished code. Blank lines are
+/// left unindented.
+pub(crate) struct CodeWriter<W = String> {
+    out: W,
+    level: usize,
+    is_line_start: bool
+}
+
+impl<W: fmt::Write> CodeWriter<W> {
+    pub(crate) fn new(out: W) -> CodeWriter<W> {
+        CodeWriter{ out, level: 0, is_line_start: true }
+    }
+
+    /// Writes with `write` one level deeper.
+    pub(crate) fn indented<R>(&mut self, write: impl FnOnce(&mut CodeWriter<W>) -> R) -> R {
+        self.level += 1;
+        let result = write(self);
+        self.level -= 1;
+        result
+    }
+}
+
+impl<W: fmt::Write> fmt::Write for CodeWriter<W> {
+    fn write_str(&mut self, code: &str) -> fmt::Result {
+        for line in code.split_inclusive('\n') {
+            if self.is_line_start && line != "\n" {
+                (0..self.level).try_for_each(|_| self.out.write_str(INDENT))?;
+            }
+            self.out.write_str(line)?;
+            self.is_line_start = line.ends_with('\n');
+        }
+        Ok(())
+    }
+}
+
+impl CodeWriter {
+    pub(crate) fn with_capacity(capacity: usize) -> CodeWriter {
+        CodeWriter::new(String::with_capacity(capacity))
+    }
+
+    /// A writer sized for the code generated from `file`.
+    pub(crate) fn for_file(file: &ir::File) -> CodeWriter {
+        let declarations = file.messages.len() + file.enums.len();
+        let fields = file.messages.iter().map(|message| message.fields.len()).sum::<usize>();
+        CodeWriter::with_capacity(declarations * DECLARATION_SIZE + fields * FIELD_SIZE)
+    }
+
+    /// A writer sized for the declarations of `message`.
+    pub(crate) fn for_message(message: &ir::Message) -> CodeWriter {
+        CodeWriter::with_capacity(DECLARATION_SIZE + message.fields.len() * FIELD_SIZE)
+    }
+
+    /// Appends `code`, which cannot fail on a `String`.
+    pub(crate) fn push(&mut self, code: impl fmt::Display) {
+        fmt::Write::write_fmt(self, format_args!("{}", code)).expect("writing to a String cannot fail")
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        &self.out
+    }
+
+    pub(crate) fn into_string(self) -> String {
+        self.out
+    }
+}
@@ -0,0 +1,375 @@
+use super::*;
+use super::jsonschema::json_string;
+use super::validate::ValueConstraints;
+
+fn ts_type_schema(ts_type: &TsType) -> String {
+    match ts_type {
+        TsType::Boolean => "z.boolean()".to_string(),
+        TsType::Number => "z.number()".to_string(),
+        TsType::BigInt => "z.bigint()".to_string(),
+        TsType::String => "z.string()".to_string(),
+        TsType::Uint8Array => "z.instanceof(Uint8Array)".to_string(),
+        TsType::Never => "z.never()".to_string(),
+        TsType::Object(name) => format!("z.custom<{}>()", name),
+        TsType::Nullable(inner) => format!("{}.nullable()", ts_type_schema(inner))
+    }
+}
+
+fn well_known_schema(type_name: &str, options: &Options) -> Option<String> {
+    let ts_type = well_known_type_to_ts_type(type_name, options)?;
+    if let Some(override_type) = options.type_overrides.get(type_name).map(|type_override| type_override.ts_type.as_str()) {
+        return Some(match (type_name, override_type) {
+            (".google.protobuf.Timestamp", "Date") => "z.date()".to_string(),
+            _ => ts_type_schema(&ts_type)
+        });
+    }
+    Some(match type_name {
+        ".google.protobuf.Struct" => "JsonObjectSchema".to_string(),
+        ".google.protobuf.Value" => "JsonValueSchema".to_string(),
+        ".google.protobuf.ListValue" => "z.array(JsonValueSchema).readonly()".to_string(),
+        ".google.protobuf.NullValue" => "z.null()".to_string(),
+        ".google.protobuf.Any" => "z.object({ \"@type\": z.string() }).passthrough().readonly()".to_string(),
+        _ => ts_type_schema(&ts_type)
+    })
+}
+
+/// Schema of a single `value` in a message of `file_name`. Enums of files
+/// importing `file_name` in turn are referred to lazily, since either module
+/// may be evaluated first.
+fn value_schema(value: &ir::Value, file_name: &str, resolver: &TypeResolver, options: &Options) -> String {
+    if let ir::ValueKind::Scalar(field_type) = value.kind {
+        return ts_type_schema(&scalar_to_ts_type(field_type, options));
+    }
+    if let Some(schema) = well_known_schema(&value.type_name, options) {
+        return schema;
+    }
+    let prefix = resolver.function_prefix(&value.type_name);
+    match value.kind {
+        ir::ValueKind::Enum if !resolver.file(&value.type_name)
+            .is_some_and(|enum_file| resolver.is_import_cycle(file_name, enum_file)) =>
+            format!("{}Schema", prefix),
+        _ => format!("z.lazy(() => {}Schema)", prefix)
+    }
+}
+
+/// Adds refinements for validation rules of a value to its scalar `schema`.
+fn constrain(schema: String, constraints: &ValueConstraints) -> String {
+    let (base, nullable) = match schema.strip_suffix(".nullable()") {
+        Some(base) => (base, ".nullable()"),
+        None => (schema.as_str(), "")
+    };
+    let mut refined = base.to_string();
+    match base {
+        "z.string()" => {
+            match (constraints.min_len, constraints.max_len) {
+                (Some(min), Some(max)) if min == max => refined.push_str(&format!(".length({})", min)),
+                (min, max) => {
+                    refined.extend(min.map(|n| format!(".min({})", n)));
+                    refined.extend(max.map(|n| format!(".max({})", n)));
+                }
+            }
+            if let Some(pattern) = &constraints.pattern {
+                refined.push_str(&format!(".regex(new RegExp({}))", json_string(pattern)));
+            }
+            refined.push_str(match constraints.format {
+                Some("email") => ".email()",
+                Some("ipv4") => ".ip({ version: \"v4\" })",
+                Some("ipv6") => ".ip({ version: \"v6\" })",
+                Some("uri") => ".url()",
+                Some("uuid") => ".uuid()",
+                _ => ""
+            });
+        },
+        "z.number()" | "z.bigint()" => {
+            let literal = |value: f64| match base {
+                "z.bigint()" => format!("BigInt({})", value),
+                _ => value.to_string()
+            };
+            refined.extend(constraints.minimum.map(|bound| match bound.is_exclusive {
+                true => format!(".gt({})", literal(bound.value)),
+                false => format!(".gte({})", literal(bound.value))
+            }));
+            refined.extend(constraints.maximum.map(|bound| match bound.is_exclusive {
+                true => format!(".lt({})", literal(bound.value)),
+                false => format!(".lte({})", literal(bound.value))
+            }));
+        },
+        _ => {}
+    }
+    refined + nullable
+}
+
+fn record_key_schema(field_type: FieldDescriptorProto_Type, options: &Options) -> &'static str {
+    match field_type {
+        FieldDescriptorProto_Type::TYPE_BOOL | FieldDescriptorProto_Type::TYPE_STRING => "z.string()",
+        field_type => match scalar_to_ts_type(field_type, options) {
+            TsType::Number => "z.coerce.number()",
+            _ => "z.string()"
+        }
+    }
+}
+
+fn field_schema(field: &ir::Field, file_name: &str, resolver: &TypeResolver, options: &Options) -> String {
+    let constraints = field.constraints.clone().unwrap_or_default();
+    let value = constrain(value_schema(&field.value, file_name, resolver, options), &constraints.value);
+    match &field.cardinality {
+        ir::Cardinality::Map{ key } => {
+            let (mut schema, size) = match options.map_style {
+                MapStyle::Record => (
+                    format!("z.record({}, {})", record_key_schema(key.field_type, options), value),
+                    "Object.keys(value).length"
+                ),
+                MapStyle::Map => (format!("z.map({}, {})", value_schema(key, file_name, resolver, options), value), "value.size")
+            };
+            schema.extend(constraints.min_items.map(|n| format!(
+                ".refine((value) => {} >= {1}, {{ message: \"must have at least {1} entries\" }})",
+                size,
+                n
+            )));
+            schema.extend(constraints.max_items.map(|n| format!(
+                ".refine((value) => {} <= {1}, {{ message: \"must have at most {1} entries\" }})",
+                size,
+                n
+            )));
+            format!("{}.readonly()", schema)
+        },
+        ir::Cardinality::Repeated => {
+            let mut schema = format!("z.array({})", value);
+            schema.extend(constraints.min_items.map(|n| format!(".min({})", n)));
+            schema.extend(constraints.max_items.map(|n| format!(".max({})", n)));
+            if constraints.unique_items {
+                schema.push_str(".refine((items) => new Set(items).size === items.length, { message: \"items must be unique\" })");
+            }
+            format!("{}.readonly()", schema)
+        },
+        ir::Cardinality::Single => value
+    }
+}
+
+struct ZodMessageSchema<'a> {
+    prefix: String,
+    /// The declared type annotating the schema of a recursive message, whose
+    /// type could otherwise not be inferred.
+    declared_type: Option<String>,
+    file_name: &'a str,
+    message: &'a ir::Message,
+    resolver: &'a TypeResolver,
+    options: &'a Options
+}
+
+impl<'a> fmt::Display for ZodMessageSchema<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let schema = |field| field_schema(field, self.file_name, self.resolver, self.options);
+        let oneof_list = match self.options.oneof_style {
+            OneofStyle::Discriminated => Vec::new(),
+            _ => (0..self.message.oneofs.len())
+                .map(|index| self.message.oneof_fields(index).collect::<Vec<_>>())
+                .filter(|oneof| !oneof.is_empty())
+                .collect()
+        };
+        let properties = self.message.properties(self.options)
+            .into_iter()
+            .filter(|property| match property {
+                ir::Property::Field(field) => field.oneof.is_none(),
+                ir::Property::Oneof{ .. } => true
+            })
+            .collect::<Vec<_>>();
+        let annotation = match &self.declared_type {
+            Some(declared_type) => format!(": z.ZodType<{}>", declared_type),
+            None => String::new()
+        };
+        match properties.is_empty() {
+            true => write!(f, "export const {}Schema{} = z.object({{", self.prefix, annotation)?,
+            false => writeln!(f, "export const {}Schema{} = z.object({{", self.prefix, annotation)?
+        }
+        for property in properties {
+            match property {
+                ir::Property::Field(field) if field.is_optional &&
+                    !field.constraints.as_ref().is_some_and(|constraints| constraints.required) => writeln!(
+                    f,
+                    "  {}: {}.{}(),",
+                    property_key(&field.key),
+                    schema(field),
+                    match self.options.optional_style {
+                        OptionalStyle::Undefined => "optional",
+                        OptionalStyle::Null => "nullable",
+                        OptionalStyle::Both => "nullish"
+                    }
+                )?,
+                ir::Property::Field(field) => writeln!(f, "  {}: {},", property_key(&field.key), schema(field))?,
+                ir::Property::Oneof{ oneof, fields, .. } => {
+                    writeln!(f, "  {}: z.discriminatedUnion(\"$case\", [", oneof.key)?;
+                    for field in fields {
+                        writeln!(
+                            f,
+                            "    z.object({{ $case: z.literal(\"{}\"), {}: {} }}),",
+                            field.key,
+                            property_key(&field.key),
+                            schema(field)
+                        )?;
+                    }
+                    writeln!(f, "  ]).optional(),")?;
+                }
+            }
+        }
+        write!(f, "}}).readonly()")?;
+        for oneof in oneof_list.iter() {
+            writeln!(f, ".and(z.union([")?;
+            for selected in oneof.iter() {
+                writeln!(f, "  z.object({{")?;
+                for field in oneof.iter() {
+                    match (field.number == selected.number, self.options.oneof_allow_unset) {
+                        (true, true) => writeln!(f, "    {}: {},", property_key(&field.key), schema(field))?,
+                        (true, false) => writeln!(f, "    {}: {}.optional(),", property_key(&field.key), schema(field))?,
+                        (false, _) => writeln!(f, "    {}: z.never().optional(),", property_key(&field.key))?
+                    }
+                }
+                writeln!(f, "  }}).readonly(),")?;
+            }
+            if self.options.oneof_allow_unset {
+                writeln!(f, "  z.object({{")?;
+                for field in oneof.iter() {
+                    writeln!(f, "    {}: z.never().optional(),", property_key(&field.key))?;
+                }
+                writeln!(f, "  }}).readonly(),")?;
+            }
+            write!(f, "]))")?;
+        }
+        writeln!(f, ";")?;
+        writeln!(f)?;
+        writeln!(f, "export type {0} = z.infer<typeof {0}Schema>;", self.prefix)
+    }
+}
+
+struct ZodEnumSchema {
+    prefix: String,
+    names: Vec<String>,
+    numbers: Vec<i32>,
+    style: EnumStyle
+}
+
+impl fmt::Display for ZodEnumSchema {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let schema = match (self.style, self.numbers.len()) {
+            (_, 0) => "z.never()".to_string(),
+            (EnumStyle::Union, _) => format!(
+                "z.enum([{}])",
+                self.names.iter()
+                    .map(|name| format!("\"{}\"", name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            (_, 1) => format!("z.literal({})", self.numbers[0]),
+            (_, _) => format!(
+                "z.union([{}])",
+                self.numbers.iter()
+                    .map(|number| format!("z.literal({})", number))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        };
+        writeln!(f, "export const {}Schema = {};", self.prefix, schema)?;
+        writeln!(f)?;
+        writeln!(f, "export type {0} = z.infer<typeof {0}Schema>;", self.prefix)
+    }
+}
+
+const JSON_VALUE_SCHEMAS: &str = "\
+type JsonValue = null | boolean | number | string | ReadonlyArray<JsonValue> | { readonly [key: string]: JsonValue };
+
+const JsonValueSchema: z.ZodType<JsonValue> = z.lazy(() =>
+  z.union([z.null(), z.boolean(), z.number(), z.string(), z.array(JsonValueSchema).readonly(), JsonObjectSchema])
+);
+
+const JsonObjectSchema = z.record(z.string(), JsonValueSchema).readonly();
+";
+
+/// The declaration of `full_name` as a type importable inline. Global
+/// declarations are shadowed by the inferred type of the same name, so
+/// schemas of those stay unchecked.
+fn declared_type(stem: &str, full_name: &str, resolver: &TypeResolver, options: &Options) -> String {
+    let source_stem = match import_source(full_name, resolver, options) {
+        Some((_, source_stem)) => source_stem,
+        None => return "unknown".to_string()
+    };
+    let specifier = match options.module_style {
+        ModuleStyle::Global => return "unknown".to_string(),
+        ModuleStyle::Module => source_stem,
+        ModuleStyle::Esm => relative_import(stem, &source_stem, declaration_suffix(options).1, options)
+    };
+    format!("import(\"{}\").{}", specifier, resolver.resolve(full_name, options))
+}
+
+fn zod_file_stem(file_name: &str, resolver: &TypeResolver, options: &Options) -> String {
+    output_file_stem(file_name, "_zod", resolver, options)
+}
+
+/// Zod schemas of the messages and enums of a file.
+pub(crate) struct Zod;
+
+impl Renderer for Zod {
+    fn render(
+        &self,
+        file: &ir::File,
+        _: &ir::Types,
+        resolver: &TypeResolver,
+        options: &Options
+    ) -> Vec<CodeGeneratorResponse_File> {
+        if file.messages.is_empty() && file.enums.is_empty() {
+            return vec![];
+        }
+        let stem = zod_file_stem(&file.name, resolver, options);
+        let field_references = file.messages
+            .iter()
+            .flat_map(|message| message.fields.iter())
+            .flat_map(|field| field.references())
+            .collect::<Vec<_>>();
+        let mut content = CodeWriter::for_file(file);
+        content.push("import { z } from \"zod\";\n");
+        content.push(gen_function_imports(
+            &stem,
+            file,
+            &field_references,
+            "zod",
+            |prefix, _| vec![format!("{}Schema", prefix)],
+            resolver,
+            options
+        ));
+        if uses_json_value(file, options) {
+            content.push('\n');
+            content.push(JSON_VALUE_SCHEMAS);
+        }
+        for enum_type in file.enums.iter() {
+            content.push('\n');
+            let sentinel = match options.open_enums {
+                true => Some((UNRECOGNIZED.to_string(), UNRECOGNIZED_NUMBER)),
+                false => None
+            };
+            let (names, numbers) = enum_type.values
+                .iter()
+                .map(|value| (value.ts_name.clone(), value.number))
+                .chain(sentinel)
+                .unzip();
+            content.push(ZodEnumSchema{
+                prefix: resolver.function_prefix(&enum_type.full_name),
+                names,
+                numbers,
+                style: options.enum_style
+            });
+        }
+        for message in file.messages.iter() {
+            content.push('\n');
+            content.push(ZodMessageSchema{
+                prefix: resolver.function_prefix(&message.full_name),
+                declared_type: Some(&message.full_name)
+                    .filter(|full_name| resolver.is_recursive(full_name))
+                    .map(|full_name| declared_type(&stem, full_name, resolver, options)),
+                file_name: &file.name,
+                message,
+                resolver,
+                options
+            });
+        }
+        vec![gen_resp_file(format!("{}.ts", stem), content.into_string())]
+    }
+}
@@ -0,0 +1,111 @@
+#[path = "golden/fixtures.rs"]
+#[allow(dead_code)]
+mod fixtures;
+
+use fixtures::*;
+
+fn case_folded_protos() -> Vec<File> {
+    vec![
+        file("shop/Item.proto", "shop").messages(vec![message("Item", vec![field("id", 1, Type::TYPE_STRING)])]),
+        file("shop/item.proto", "shop.legacy").messages(vec![message("LegacyItem", vec![field("code", 1, Type::TYPE_STRING)])]),
+        file("shop/cart.proto", "shop")
+            .dependencies(&["shop/item.proto"])
+            .messages(vec![message("Cart", vec![typed("item", 1, Type::TYPE_MESSAGE, ".shop.legacy.LegacyItem")])])
+    ]
+}
+
+fn names(resp: &protobuf::plugin::CodeGeneratorResponse) -> Vec<&str> {
+    resp.get_file().iter().map(|file| file.get_name()).collect()
+}
+
+#[test]
+fn colliding_proto_files_are_an_error() {
+    let resp = protoc_gen_tst::process_request(request("", vec![], case_folded_protos()));
+    assert_eq!(resp.get_error(), "output file collisions:\n\
+        shop/Item.proto and shop/item.proto generate the same output shop/Item");
+    assert!(resp.get_file().is_empty());
+}
+
+#[test]
+fn colliding_proto_files_are_renamed() {
+    let resp = protoc_gen_tst::process_request(request("collisions=rename,client=fetch", vec![], case_folded_protos()));
+    assert!(!resp.has_error(), "{}", resp.get_error());
+    assert_eq!(names(&resp), vec!["shop/Item_pb.d.ts", "shop/cart_pb.d.ts", "shop/item_2_pb.d.ts"]);
+    let cart = resp.get_file().iter().find(|file| file.get_name() == "shop/cart_pb.d.ts").unwrap();
+    assert!(cart.get_content().contains("from \"./item_2_pb\";"), "{}", cart.get_content());
+}
+
+#[test]
+fn file_case_can_make_proto_files_collide() {
+    let resp = protoc_gen_tst::process_request(request("file_case=kebab", vec![], vec![
+        file("user_profile.proto", "users").messages(vec![message("Profile", vec![])]),
+        file("userProfile.proto", "users").messages(vec![message("Settings", vec![])])
+    ]));
+    assert_eq!(resp.get_error(), "output file collisions:\n\
+        userProfile.proto and user_profile.proto generate the same output user-profile");
+}
+
+fn case_folded_messages() -> Vec<File> {
+    vec![
+        file("a.proto", "a").messages(vec![message("Item", vec![])]),
+        file("b.proto", "b").messages(vec![message("ITEM", vec![])]).line(&[4, 0], 5),
+        file("c.proto", "c")
+            .dependencies(&["b.proto"])
+            .messages(vec![message("Order", vec![typed("item", 1, Type::TYPE_MESSAGE, ".b.ITEM")])])
+    ]
+}
+
+#[test]
+fn colliding_messages_are_an_error_with_their_locations() {
+    let resp = protoc_gen_tst::process_request(request("output_mode=per_message", vec![], case_folded_messages()));
+    assert_eq!(resp.get_error(), "output file collisions:\n\
+        a.proto: a.Item and b.proto:5:1: b.ITEM generate the same output Item");
+}
+
+#[test]
+fn colliding_messages_are_renamed() {
+    let resp = protoc_gen_tst::process_request(request(
+        "output_mode=per_message,collisions=rename",
+        vec![],
+        case_folded_messages()
+    ));
+    assert!(!resp.has_error(), "{}", resp.get_error());
+    assert_eq!(names(&resp), vec!["ITEM_2.d.ts", "Item.d.ts", "Order.d.ts"]);
+    let order = resp.get_file().iter().find(|file| file.get_name() == "Order.d.ts").unwrap();
+    assert!(order.get_content().contains("import type { ITEM } from \"./ITEM_2\";"), "{}", order.get_content());
+}
+
+fn case_converted_fields() -> File {
+    file("users.proto", "users")
+        .messages(vec![message("User", vec![
+            field("user_id", 1, Type::TYPE_STRING),
+            field("userId", 2, Type::TYPE_STRING)
+        ])])
+        .line(&[4, 0, 2, 0], 4)
+        .line(&[4, 0, 2, 1], 5)
+}
+
+#[test]
+fn fields_colliding_after_case_conversion_are_an_error() {
+    let resp = protoc_gen_tst::process_request(request("", vec![], vec![case_converted_fields()]));
+    assert_eq!(resp.get_error(), "name collisions:\n\
+        users.proto:4:1: users.User.user_id and users.proto:5:1: users.User.userId declare the same key userId");
+    let resp = protoc_gen_tst::process_request(request("field_name=original", vec![], vec![case_converted_fields()]));
+    assert!(!resp.has_error(), "{}", resp.get_error());
+}
+
+fn flattened_types() -> File {
+    file("shapes.proto", "shapes").messages(vec![
+        with_nested(message("Foo", vec![]), vec![message("Bar", vec![])], vec![]),
+        message("Foo_Bar", vec![])
+    ])
+}
+
+#[test]
+fn flattened_types_colliding_with_declared_ones_are_an_error() {
+    let resp = protoc_gen_tst::process_request(request("", vec![], vec![flattened_types()]));
+    assert_eq!(resp.get_error(), "name collisions:\n\
+        shapes.proto: shapes.Foo.Bar and shapes.proto: shapes.Foo_Bar declare the same type Foo_Bar");
+    let resp = protoc_gen_tst::process_request(request("nested=namespace", vec![], vec![flattened_types()]));
+    assert!(!resp.has_error(), "{}", resp.get_error());
+}
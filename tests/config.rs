@@ -0,0 +1,97 @@
+#[path = "golden/fixtures.rs"]
+#[allow(dead_code)]
+mod fixtures;
+
+use fixtures::*;
+
+fn protos() -> Vec<File> {
+    vec![
+        file("billing/user_account.proto", "billing")
+            .dependencies(&["google/protobuf/timestamp.proto"])
+            .messages(vec![message("Account", vec![typed("created_at", 1, Type::TYPE_MESSAGE, ".google.protobuf.Timestamp")])]),
+        file("billing/internal/audit.proto", "billing.internal").messages(vec![message("Audit", vec![])])
+    ]
+}
+
+#[test]
+fn yaml_config_is_merged_with_parameters() {
+    let path = temp_file("merged.yaml", "\
+# naming
+file_case: kebab
+file_suffix: .types.d.ts   # overridden by the parameter
+exclude:
+  - \"**/internal/*.proto\"
+wkt:
+  Timestamp: 'Date'
+");
+    let resp = generate(&format!("config={},file_suffix=_pb.d.ts", path), vec![timestamp_proto()], protos());
+    assert!(!resp.has_error(), "{}", resp.get_error());
+    assert_eq!(resp.get_file().len(), 1);
+    assert!(file_content(&resp, "billing/user-account_pb.d.ts").contains("createdAt?: Date;"));
+}
+
+#[test]
+fn json_config_sets_type_overrides() {
+    let path = temp_file("overrides.json", r#"{
+  "exclude": ["billing/internal/**"],
+  "type_override": {
+    ".google.protobuf.Timestamp": "Instant from \"@js-joda/core\""
+  },
+  "readonly": false
+}"#);
+    let resp = generate(&format!("config={}", path), vec![timestamp_proto()], protos());
+    assert!(!resp.has_error(), "{}", resp.get_error());
+    let content = file_content(&resp, "billing/user_account_pb.d.ts");
+    assert!(content.contains("import type { Instant } from \"@js-joda/core\";"), "{}", content);
+    assert!(content.contains("  createdAt?: Instant;"), "{}", content);
+}
+
+#[test]
+fn invalid_config_options_name_the_file() {
+    let path = temp_file("invalid.yaml", "output_mode: per_package\n");
+    assert_eq!(
+        generate(&format!("config={}", path), vec![timestamp_proto()], protos()).get_error(),
+        format!("{}: invalid option output_mode=per_package", path)
+    );
+    let path = temp_file("malformed.json", "{\"index\": true,}");
+    assert_eq!(
+        generate(&format!("config={}", path), vec![timestamp_proto()], protos()).get_error(),
+        format!("{}: expected '\"' but found '}}'", path)
+    );
+}
+
+#[test]
+fn missing_config_is_reported() {
+    let resp = generate("config=/nonexistent/tst.config.yaml", vec![timestamp_proto()], protos());
+    assert!(resp.get_error().starts_with("/nonexistent/tst.config.yaml: "), "{}", resp.get_error());
+}
+
+#[test]
+fn helper_lists_continue_over_commas() {
+    let path = temp_file("helpers.yaml", "helpers:\n  - equality\n  - clone\n");
+    for parameter in ["helpers=equality,clone,readonly=false".to_string(), format!("config={},readonly=false", path)] {
+        let resp = generate(&parameter, vec![timestamp_proto()], protos());
+        assert!(!resp.has_error(), "{}", resp.get_error());
+        let content = file_content(&resp, "billing/user_account_helpers.ts");
+        assert!(content.contains("export function equalsAccount(a: Account, b: Account): boolean {"), "{}", content);
+        assert!(content.contains("export function cloneAccount(message: Account): Account {"), "{}", content);
+    }
+    assert_eq!(
+        generate("helpers=equality,hash", vec![timestamp_proto()], protos()).get_error(),
+        "invalid parameter: helpers=equality,hash"
+    );
+}
+
+#[test]
+fn namespaces_reject_conflicting_layouts() {
+    assert_eq!(
+        generate("namespaces=true,output_mode=per_file", vec![timestamp_proto()], protos()).get_error(),
+        "invalid parameter: output_mode=per_file conflicts with namespaces=true"
+    );
+    let path = temp_file("namespaces.yaml", "module: esm\n");
+    assert_eq!(
+        generate(&format!("config={},namespaces=true", path), vec![timestamp_proto()], protos()).get_error(),
+        "invalid parameter: module=esm conflicts with namespaces=true"
+    );
+    assert!(!generate("namespaces=true,module=global", vec![timestamp_proto()], protos()).has_error());
+}
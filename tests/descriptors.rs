@@ -0,0 +1,58 @@
+#[path = "golden/fixtures.rs"]
+#[allow(dead_code)]
+mod fixtures;
+
+use fixtures::*;
+
+fn error_of(parameter: &str, files: Vec<File>) -> String {
+    let resp = protoc_gen_tst::process_request(request(parameter, vec![], files));
+    assert!(resp.get_file().is_empty());
+    resp.get_error().to_string()
+}
+
+fn choice(oneof_index: i32) -> File {
+    file("choice.proto", "choice").messages(vec![
+        with_oneofs(message("Choice", vec![
+            in_oneof(field("text", 1, Type::TYPE_STRING), oneof_index)
+        ]), &["value"])
+    ])
+}
+
+#[test]
+fn oneof_index_out_of_range_is_reported() {
+    assert_eq!(error_of("", vec![choice(1)]), "choice.proto: choice.Choice.text: oneof_index 1 is out of range");
+    assert_eq!(error_of("", vec![choice(-1)]), "choice.proto: choice.Choice.text: oneof_index -1 is out of range");
+    assert_eq!(
+        error_of("target=jsonschema", vec![choice(3)]),
+        "choice.proto: choice.Choice.text: oneof_index 3 is out of range"
+    );
+}
+
+#[test]
+fn malformed_map_entry_is_reported() {
+    let (mut entry, map_field) = map(
+        ".maps.Counts",
+        "counts",
+        1,
+        field("key", 1, Type::TYPE_STRING),
+        field("value", 2, Type::TYPE_INT32)
+    );
+    entry.mut_field().truncate(1);
+    let proto = file("maps.proto", "maps").messages(vec![with_nested(message("Counts", vec![map_field]), vec![entry], vec![])]);
+    assert_eq!(
+        error_of("", vec![proto]),
+        "maps.proto: maps.Counts.CountsEntry: map entry has fields [1] instead of key = 1 and value = 2"
+    );
+}
+
+#[test]
+fn fields_without_type_name_are_reported() {
+    let proto = file("refs.proto", "refs").messages(vec![message("Ref", vec![field("target", 1, Type::TYPE_MESSAGE)])]);
+    assert_eq!(error_of("", vec![proto]), "refs.proto: refs.Ref.target: TYPE_MESSAGE field without a type_name");
+}
+
+#[test]
+fn methods_without_types_are_reported() {
+    let proto = file("svc.proto", "svc").services(vec![service("Greeter", vec![method("Greet", "", ".svc.Reply", false, false)])]);
+    assert_eq!(error_of("client=fetch", vec![proto]), "svc.proto: svc.Greeter.Greet: method without an input or output type");
+}
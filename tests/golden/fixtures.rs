@@ -0,0 +1,443 @@
+//! Builders for the descriptors protoc would send, so that fixtures read like
+//! the `.proto` files they stand for.
+
+use protobuf::descriptor::*;
+use protobuf::plugin::*;
+use protobuf::Message;
+
+pub use protobuf::descriptor::FieldDescriptorProto_Type as Type;
+
+const FIELD_PROTO3_OPTIONAL: u32 = 17;
+
+fn json_name(name: &str) -> String {
+    let mut is_upper = false;
+    let mut result = String::new();
+    for c in name.chars() {
+        match (c, is_upper) {
+            ('_', _) => is_upper = true,
+            (c, true) => {
+                result.extend(c.to_uppercase());
+                is_upper = false;
+            },
+            (c, false) => result.push(c)
+        }
+    }
+    result
+}
+
+pub fn field(name: &str, number: i32, field_type: Type) -> FieldDescriptorProto {
+    let mut field = FieldDescriptorProto::new();
+    field.set_name(name.to_string());
+    field.set_json_name(json_name(name));
+    field.set_number(number);
+    field.set_field_type(field_type);
+    field.set_label(FieldDescriptorProto_Label::LABEL_OPTIONAL);
+    field
+}
+
+pub fn typed(name: &str, number: i32, field_type: Type, type_name: &str) -> FieldDescriptorProto {
+    let mut field = field(name, number, field_type);
+    field.set_type_name(type_name.to_string());
+    field
+}
+
+pub fn repeated(mut field: FieldDescriptorProto) -> FieldDescriptorProto {
+    field.set_label(FieldDescriptorProto_Label::LABEL_REPEATED);
+    field
+}
+
+/// A proto2 `required` field.
+pub fn required(mut field: FieldDescriptorProto) -> FieldDescriptorProto {
+    field.set_label(FieldDescriptorProto_Label::LABEL_REQUIRED);
+    field
+}
+
+/// A proto2 field declared with `[default = value]`.
+pub fn with_default(mut field: FieldDescriptorProto, value: &str) -> FieldDescriptorProto {
+    field.set_default_value(value.to_string());
+    field
+}
+
+/// A field of an `extend <extendee>` block.
+pub fn extension(mut field: FieldDescriptorProto, extendee: &str) -> FieldDescriptorProto {
+    field.set_extendee(extendee.to_string());
+    field
+}
+
+pub fn in_oneof(mut field: FieldDescriptorProto, index: i32) -> FieldDescriptorProto {
+    field.set_oneof_index(index);
+    field
+}
+
+/// A proto3 `optional` field. `oneof_index` refers to its synthetic oneof.
+pub fn optional(field: FieldDescriptorProto, oneof_index: i32) -> FieldDescriptorProto {
+    let mut field = in_oneof(field, oneof_index);
+    field.mut_unknown_fields().add_varint(FIELD_PROTO3_OPTIONAL, 1);
+    field
+}
+
+pub fn message(name: &str, fields: Vec<FieldDescriptorProto>) -> DescriptorProto {
+    let mut message_type = DescriptorProto::new();
+    message_type.set_name(name.to_string());
+    message_type.set_field(fields.into());
+    message_type
+}
+
+pub fn with_oneofs(mut message_type: DescriptorProto, names: &[&str]) -> DescriptorProto {
+    message_type.set_oneof_decl(names.iter().map(|name| {
+        let mut oneof = OneofDescriptorProto::new();
+        oneof.set_name(name.to_string());
+        oneof
+    }).collect());
+    message_type
+}
+
+pub fn with_nested(
+    mut message_type: DescriptorProto,
+    nested_types: Vec<DescriptorProto>,
+    enum_types: Vec<EnumDescriptorProto>
+) -> DescriptorProto {
+    message_type.set_nested_type(nested_types.into());
+    message_type.set_enum_type(enum_types.into());
+    message_type
+}
+
+/// The synthetic entry message and field of `map<key, value> name = number`
+/// declared in `message_name`.
+pub fn map(
+    message_name: &str,
+    name: &str,
+    number: i32,
+    key: FieldDescriptorProto,
+    value: FieldDescriptorProto
+) -> (DescriptorProto, FieldDescriptorProto) {
+    let entry_name = format!("{}Entry", json_name(&format!("_{}", name)));
+    let mut entry = message(&entry_name, vec![key, value]);
+    entry.mut_options().set_map_entry(true);
+    let field = repeated(typed(
+        name,
+        number,
+        Type::TYPE_MESSAGE,
+        &format!("{}.{}", message_name, entry_name)
+    ));
+    (entry, field)
+}
+
+pub fn enumeration(name: &str, values: &[(&str, i32)]) -> EnumDescriptorProto {
+    let mut enum_type = EnumDescriptorProto::new();
+    enum_type.set_name(name.to_string());
+    enum_type.set_value(values.iter().map(|(name, number)| {
+        let mut value = EnumValueDescriptorProto::new();
+        value.set_name(name.to_string());
+        value.set_number(*number);
+        value
+    }).collect());
+    enum_type
+}
+
+pub fn method(name: &str, input: &str, output: &str, client_streaming: bool, server_streaming: bool) -> MethodDescriptorProto {
+    let mut method = MethodDescriptorProto::new();
+    method.set_name(name.to_string());
+    method.set_input_type(input.to_string());
+    method.set_output_type(output.to_string());
+    method.set_client_streaming(client_streaming);
+    method.set_server_streaming(server_streaming);
+    method
+}
+
+pub fn service(name: &str, methods: Vec<MethodDescriptorProto>) -> ServiceDescriptorProto {
+    let mut service = ServiceDescriptorProto::new();
+    service.set_name(name.to_string());
+    service.set_method(methods.into());
+    service
+}
+
+pub struct File {
+    pub proto: FileDescriptorProto
+}
+
+pub fn file(name: &str, package: &str) -> File {
+    let mut proto = FileDescriptorProto::new();
+    proto.set_name(name.to_string());
+    proto.set_package(package.to_string());
+    proto.set_syntax("proto3".to_string());
+    File{ proto }
+}
+
+/// `name` in no package, declaring empty messages named `message_names`.
+pub fn declaring(name: &str, message_names: &[&str]) -> File {
+    file(name, "").messages(message_names.iter().map(|name| message(name, vec![])).collect())
+}
+
+impl File {
+    pub fn proto2(mut self) -> File {
+        self.proto.set_syntax("proto2".to_string());
+        self
+    }
+
+    /// A file declared with `edition = "2023";`.
+    pub fn edition_2023(mut self) -> File {
+        self.proto.set_syntax("editions".to_string());
+        self.proto.mut_unknown_fields().add_varint(FILE_EDITION, EDITION_2023);
+        self
+    }
+
+    /// `option features.<feature> = <value>;` for every pair of `features`,
+    /// by `google.protobuf.FeatureSet` field number.
+    pub fn features(mut self, features: &[(u32, u64)]) -> File {
+        self.proto.mut_options().mut_unknown_fields().add_length_delimited(FILE_FEATURES, feature_set(features));
+        self
+    }
+
+    pub fn dependencies(mut self, names: &[&str]) -> File {
+        self.proto.set_dependency(names.iter().map(|name| name.to_string()).collect());
+        self
+    }
+
+    pub fn messages(mut self, message_types: Vec<DescriptorProto>) -> File {
+        self.proto.set_message_type(message_types.into());
+        self
+    }
+
+    pub fn extensions(mut self, extensions: Vec<FieldDescriptorProto>) -> File {
+        self.proto.set_extension(extensions.into());
+        self
+    }
+
+    pub fn enums(mut self, enum_types: Vec<EnumDescriptorProto>) -> File {
+        self.proto.set_enum_type(enum_types.into());
+        self
+    }
+
+    pub fn services(mut self, services: Vec<ServiceDescriptorProto>) -> File {
+        self.proto.set_service(services.into());
+        self
+    }
+
+    /// The location of the element at `path`, which protoc reports once
+    /// with both its span and comments.
+    fn location(&mut self, path: &[i32]) -> &mut SourceCodeInfo_Location {
+        let locations = self.proto.mut_source_code_info().mut_location();
+        let index = match locations.iter().position(|location| location.get_path() == path) {
+            Some(index) => index,
+            None => {
+                let mut location = SourceCodeInfo_Location::new();
+                location.set_path(path.to_vec());
+                locations.push(location);
+                locations.len() - 1
+            }
+        };
+        &mut locations[index]
+    }
+
+    /// Adds `comment` as the leading comment of the element at `path`, the
+    /// lines without the `//` protoc strips.
+    pub fn comment(mut self, path: &[i32], comment: &str) -> File {
+        self.location(path).set_leading_comments(comment.to_string());
+        self
+    }
+
+    /// Declares the element at `path` on the 1-based `line`.
+    pub fn line(mut self, path: &[i32], line: i32) -> File {
+        self.location(path).set_span(vec![line - 1, 0, 1]);
+        self
+    }
+}
+
+/// A request generating `files`, with `dependencies` only providing types.
+pub fn request(parameter: &str, dependencies: Vec<File>, files: Vec<File>) -> CodeGeneratorRequest {
+    let mut req = CodeGeneratorRequest::new();
+    req.set_parameter(parameter.to_string());
+    req.set_file_to_generate(files.iter().map(|file| file.proto.get_name().to_string()).collect());
+    req.set_proto_file(dependencies.into_iter().chain(files).map(|file| file.proto).collect());
+    req
+}
+
+/// The response to a request generating `files`.
+pub fn generate(parameter: &str, dependencies: Vec<File>, files: Vec<File>) -> CodeGeneratorResponse {
+    protoc_gen_tst::process_request(request(parameter, dependencies, files))
+}
+
+/// The content of the generated file `name`, listing the generated files
+/// when it is missing.
+pub fn file_content<'a>(resp: &'a CodeGeneratorResponse, name: &str) -> &'a str {
+    resp.get_file().iter().find(|file| file.get_name() == name).map(|file| file.get_content()).unwrap_or_else(|| {
+        panic!("{} not in {:?}", name, resp.get_file().iter().map(|file| file.get_name()).collect::<Vec<_>>())
+    })
+}
+
+/// Writes `content` to a file unique to the test process, as templates and
+/// configuration files are read by path.
+pub fn temp_file(name: &str, content: &str) -> String {
+    let dir = std::env::temp_dir().join(format!("protoc-gen-tst-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, content).unwrap();
+    path.display().to_string()
+}
+
+pub fn timestamp_proto() -> File {
+    file("google/protobuf/timestamp.proto", "google.protobuf").messages(vec![message("Timestamp", vec![
+        field("seconds", 1, Type::TYPE_INT64),
+        field("nanos", 2, Type::TYPE_INT32)
+    ])])
+}
+
+pub fn wrappers_proto() -> File {
+    file("google/protobuf/wrappers.proto", "google.protobuf").messages(vec![
+        message("StringValue", vec![field("value", 1, Type::TYPE_STRING)]),
+        message("Int64Value", vec![field("value", 1, Type::TYPE_INT64)])
+    ])
+}
+
+pub fn field_mask_proto() -> File {
+    file("google/protobuf/field_mask.proto", "google.protobuf").messages(vec![message("FieldMask", vec![
+        repeated(field("paths", 1, Type::TYPE_STRING))
+    ])])
+}
+
+pub fn struct_proto() -> File {
+    file("google/protobuf/struct.proto", "google.protobuf")
+        .messages(vec![message("Struct", vec![]), message("Value", vec![]), message("ListValue", vec![])])
+        .enums(vec![enumeration("NullValue", &[("NULL_VALUE", 0)])])
+}
+
+const OPTION_TS_TYPE: u32 = 52713;
+const OPTION_SKIP: u32 = 52714;
+const OPTION_NAME: u32 = 52715;
+const OPTION_FIELD_MASK: u32 = 52716;
+const OPTION_EXAMPLE: u32 = 52717;
+const OPTION_NOMINAL: u32 = 52718;
+
+/// A 64-bit field declared with `[jstype = JS_STRING]`.
+pub fn js_string(mut field: FieldDescriptorProto) -> FieldDescriptorProto {
+    field.mut_options().set_jstype(FieldOptions_JSType::JS_STRING);
+    field
+}
+
+/// A field annotated with `[(tst.ts_type) = "<ts_type>"]`.
+pub fn with_ts_type(mut field: FieldDescriptorProto, ts_type: &str) -> FieldDescriptorProto {
+    field.mut_options().mut_unknown_fields().add_length_delimited(OPTION_TS_TYPE, ts_type.as_bytes().to_vec());
+    field
+}
+
+/// A field annotated with `[(tst.skip) = true]`.
+pub fn skipped_field(mut field: FieldDescriptorProto) -> FieldDescriptorProto {
+    field.mut_options().mut_unknown_fields().add_varint(OPTION_SKIP, 1);
+    field
+}
+
+/// A field annotated with `[(tst.name) = "<name>"]`.
+pub fn renamed_field(mut field: FieldDescriptorProto, name: &str) -> FieldDescriptorProto {
+    field.mut_options().mut_unknown_fields().add_length_delimited(OPTION_NAME, name.as_bytes().to_vec());
+    field
+}
+
+/// A message with `option (tst.skip) = true;`.
+pub fn skipped_message(mut message_type: DescriptorProto) -> DescriptorProto {
+    message_type.mut_options().mut_unknown_fields().add_varint(OPTION_SKIP, 1);
+    message_type
+}
+
+/// A message with `option (tst.nominal) = true;`.
+pub fn nominal_message(mut message_type: DescriptorProto) -> DescriptorProto {
+    message_type.mut_options().mut_unknown_fields().add_varint(OPTION_NOMINAL, 1);
+    message_type
+}
+
+/// A message with `option (tst.name) = "<name>";`.
+pub fn renamed_message(mut message_type: DescriptorProto, name: &str) -> DescriptorProto {
+    message_type.mut_options().mut_unknown_fields().add_length_delimited(OPTION_NAME, name.as_bytes().to_vec());
+    message_type
+}
+
+/// An enum with `option (tst.name) = "<name>";`.
+pub fn renamed_enum(mut enum_type: EnumDescriptorProto, name: &str) -> EnumDescriptorProto {
+    enum_type.mut_options().mut_unknown_fields().add_length_delimited(OPTION_NAME, name.as_bytes().to_vec());
+    enum_type
+}
+
+/// A field annotated with `[(tst.field_mask) = "<message>"]`.
+pub fn field_mask_of(mut field: FieldDescriptorProto, message: &str) -> FieldDescriptorProto {
+    field.mut_options().mut_unknown_fields().add_length_delimited(OPTION_FIELD_MASK, message.as_bytes().to_vec());
+    field
+}
+
+/// A field with custom options, the extensions of `FieldOptions` added by
+/// `build`.
+pub fn with_custom_options(mut field: FieldDescriptorProto, build: impl FnOnce(&mut protobuf::UnknownFields)) -> FieldDescriptorProto {
+    build(field.mut_options().mut_unknown_fields());
+    field
+}
+
+/// A field annotated with `[(tst.example) = "<code>"]`.
+pub fn field_example(mut field: FieldDescriptorProto, code: &str) -> FieldDescriptorProto {
+    field.mut_options().mut_unknown_fields().add_length_delimited(OPTION_EXAMPLE, code.as_bytes().to_vec());
+    field
+}
+
+/// A method with `option (tst.example) = "<code>";`.
+pub fn method_example(mut method: MethodDescriptorProto, code: &str) -> MethodDescriptorProto {
+    method.mut_options().mut_unknown_fields().add_length_delimited(OPTION_EXAMPLE, code.as_bytes().to_vec());
+    method
+}
+
+const METHOD_HTTP: u32 = 72295728;
+
+/// A `google.api.HttpRule` binding `path` to the `verb` pattern field, such
+/// as 2 for `get` or 4 for `post`.
+pub fn http_rule(verb: u32, path: &str, body: &str, additional_bindings: Vec<Vec<u8>>) -> Vec<u8> {
+    let mut rule = protobuf::well_known_types::Empty::new();
+    let fields = rule.mut_unknown_fields();
+    fields.add_length_delimited(verb, path.as_bytes().to_vec());
+    if !body.is_empty() {
+        fields.add_length_delimited(7, body.as_bytes().to_vec());
+    }
+    additional_bindings.into_iter().for_each(|binding| fields.add_length_delimited(11, binding));
+    rule.write_to_bytes().unwrap()
+}
+
+/// A method with `option (google.api.http) = { ... };`.
+pub fn with_http(mut method: MethodDescriptorProto, rule: Vec<u8>) -> MethodDescriptorProto {
+    method.mut_options().mut_unknown_fields().add_length_delimited(METHOD_HTTP, rule);
+    method
+}
+
+/// The bytes of a message whose fields are added by `build`, for options
+/// whose types the tests do not compile.
+pub fn encoded(build: impl FnOnce(&mut protobuf::UnknownFields)) -> Vec<u8> {
+    let mut message = protobuf::well_known_types::Empty::new();
+    build(message.mut_unknown_fields());
+    message.write_to_bytes().unwrap()
+}
+
+const FILE_EDITION: u32 = 14;
+const EDITION_2023: u64 = 1000;
+const FILE_FEATURES: u32 = 50;
+const FIELD_FEATURES: u32 = 21;
+
+fn feature_set(features: &[(u32, u64)]) -> Vec<u8> {
+    encoded(|fields| features.iter().for_each(|&(number, value)| fields.add_varint(number, value)))
+}
+
+/// A field with `[features.<feature> = <value>]` for every pair of
+/// `features`.
+pub fn with_features(mut field: FieldDescriptorProto, features: &[(u32, u64)]) -> FieldDescriptorProto {
+    field.mut_options().mut_unknown_fields().add_length_delimited(FIELD_FEATURES, feature_set(features));
+    field
+}
+
+const FIELD_PROTOVALIDATE: u32 = 1159;
+const FIELD_PGV: u32 = 1071;
+
+/// A field with `[(buf.validate.field) = { ... }]`.
+pub fn with_protovalidate(mut field: FieldDescriptorProto, rules: Vec<u8>) -> FieldDescriptorProto {
+    field.mut_options().mut_unknown_fields().add_length_delimited(FIELD_PROTOVALIDATE, rules);
+    field
+}
+
+/// A field with `[(validate.rules) = { ... }]` of protoc-gen-validate.
+pub fn with_pgv(mut field: FieldDescriptorProto, rules: Vec<u8>) -> FieldDescriptorProto {
+    field.mut_options().mut_unknown_fields().add_length_delimited(FIELD_PGV, rules);
+    field
+}
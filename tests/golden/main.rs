@@ -0,0 +1,997 @@
+//! Golden-file tests. Each case feeds a fixture request through
+//! `process_request` and compares every emitted file with its snapshot under
+//! `tests/golden/snapshots/<case>/`. Run with `UPDATE_GOLDEN=1` to rewrite
+//! the snapshots after an intended change and review the diff.
+
+#[allow(dead_code)]
+mod fixtures;
+
+use fixtures::*;
+use protobuf::plugin::*;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn snapshot_dir(case: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden/snapshots").join(case)
+}
+
+fn read_snapshots(dir: &Path, prefix: &str, snapshots: &mut BTreeMap<String, String>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return
+    };
+    for entry in entries.map(|entry| entry.unwrap()) {
+        let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+        match entry.file_type().unwrap().is_dir() {
+            true => read_snapshots(&entry.path(), &format!("{}/", name), snapshots),
+            false => {
+                snapshots.insert(name, fs::read_to_string(entry.path()).unwrap());
+            }
+        }
+    }
+}
+
+fn assert_golden(case: &str, req: CodeGeneratorRequest) {
+    let resp = protoc_gen_tst::process_request(req);
+    assert!(!resp.has_error(), "{}: {}", case, resp.get_error());
+    let generated = resp.get_file()
+        .iter()
+        .map(|file| (file.get_name().to_string(), file.get_content().to_string()))
+        .collect::<BTreeMap<_, _>>();
+    let dir = snapshot_dir(case);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        let _ = fs::remove_dir_all(&dir);
+        for (name, content) in generated.iter() {
+            let path = dir.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        return;
+    }
+    let mut snapshots = BTreeMap::new();
+    read_snapshots(&dir, "", &mut snapshots);
+    assert_eq!(
+        generated.keys().collect::<Vec<_>>(),
+        snapshots.keys().collect::<Vec<_>>(),
+        "{}: generated files differ from snapshots",
+        case
+    );
+    for (name, content) in generated.iter() {
+        assert!(
+            *content == snapshots[name],
+            "{}: {} differs from its snapshot\n--- generated ---\n{}",
+            case,
+            name,
+            content
+        );
+    }
+}
+
+fn scalars_proto() -> File {
+    file("scalars.proto", "scalars").messages(vec![with_oneofs(message("Scalars", vec![
+        field("double_value", 1, Type::TYPE_DOUBLE),
+        field("float_value", 2, Type::TYPE_FLOAT),
+        field("int32_value", 3, Type::TYPE_INT32),
+        field("int64_value", 4, Type::TYPE_INT64),
+        field("uint32_value", 5, Type::TYPE_UINT32),
+        field("uint64_value", 6, Type::TYPE_UINT64),
+        field("sint32_value", 7, Type::TYPE_SINT32),
+        field("sint64_value", 8, Type::TYPE_SINT64),
+        field("fixed32_value", 9, Type::TYPE_FIXED32),
+        field("fixed64_value", 10, Type::TYPE_FIXED64),
+        field("sfixed32_value", 11, Type::TYPE_SFIXED32),
+        field("sfixed64_value", 12, Type::TYPE_SFIXED64),
+        field("bool_value", 13, Type::TYPE_BOOL),
+        field("string_value", 14, Type::TYPE_STRING),
+        field("bytes_value", 15, Type::TYPE_BYTES),
+        repeated(field("repeated_value", 16, Type::TYPE_INT32)),
+        optional(field("optional_value", 17, Type::TYPE_STRING), 0)
+    ]), &["_optional_value"])])
+}
+
+fn enums_proto() -> File {
+    file("enums.proto", "enums")
+        .messages(vec![with_nested(
+            message("Task", vec![
+                typed("state", 1, Type::TYPE_ENUM, ".enums.Task.State"),
+                typed("priority", 2, Type::TYPE_ENUM, ".enums.Priority")
+            ]),
+            vec![],
+            vec![enumeration("State", &[("STATE_UNSPECIFIED", 0), ("STATE_DONE", 1)])]
+        )])
+        .enums(vec![enumeration("Priority", &[("PRIORITY_LOW", 0), ("PRIORITY_HIGH", 2)])])
+}
+
+fn oneofs_proto() -> File {
+    file("oneofs.proto", "oneofs").messages(vec![with_oneofs(message("Contact", vec![
+        field("name", 1, Type::TYPE_STRING),
+        in_oneof(field("email", 2, Type::TYPE_STRING), 0),
+        in_oneof(typed("phone", 3, Type::TYPE_MESSAGE, ".oneofs.Phone"), 0)
+    ]), &["channel"]), message("Phone", vec![field("number", 1, Type::TYPE_STRING)])])
+}
+
+fn maps_proto() -> File {
+    let (labels_entry, labels) = map(
+        ".maps.Inventory",
+        "labels",
+        1,
+        field("key", 1, Type::TYPE_STRING),
+        field("value", 2, Type::TYPE_STRING)
+    );
+    let (items_entry, items) = map(
+        ".maps.Inventory",
+        "items",
+        2,
+        field("key", 1, Type::TYPE_INT32),
+        typed("value", 2, Type::TYPE_MESSAGE, ".maps.Item")
+    );
+    file("maps.proto", "maps").messages(vec![
+        with_nested(message("Inventory", vec![labels, items]), vec![labels_entry, items_entry], vec![]),
+        message("Item", vec![field("name", 1, Type::TYPE_STRING)])
+    ])
+}
+
+fn imports_request(parameter: &str) -> CodeGeneratorRequest {
+    request(parameter, vec![], vec![
+        file("common/user.proto", "common").messages(vec![with_nested(
+            message("User", vec![typed("address", 1, Type::TYPE_MESSAGE, ".common.User.Address")]),
+            vec![message("Address", vec![field("city", 1, Type::TYPE_STRING)])],
+            vec![]
+        )]),
+        file("orders/order.proto", "orders")
+            .dependencies(&["common/user.proto"])
+            .messages(vec![message("Order", vec![
+                typed("buyer", 1, Type::TYPE_MESSAGE, ".common.User"),
+                typed("shipping", 2, Type::TYPE_MESSAGE, ".common.User.Address")
+            ])])
+    ])
+}
+
+fn well_known_types_request(parameter: &str) -> CodeGeneratorRequest {
+    request(parameter, vec![timestamp_proto(), wrappers_proto(), struct_proto()], vec![
+        file("event.proto", "events")
+            .dependencies(&[
+                "google/protobuf/timestamp.proto",
+                "google/protobuf/wrappers.proto",
+                "google/protobuf/struct.proto"
+            ])
+            .messages(vec![message("Event", vec![
+                typed("created_at", 1, Type::TYPE_MESSAGE, ".google.protobuf.Timestamp"),
+                typed("note", 2, Type::TYPE_MESSAGE, ".google.protobuf.StringValue"),
+                typed("count", 3, Type::TYPE_MESSAGE, ".google.protobuf.Int64Value"),
+                typed("payload", 4, Type::TYPE_MESSAGE, ".google.protobuf.Struct"),
+                typed("value", 5, Type::TYPE_MESSAGE, ".google.protobuf.Value"),
+                typed("list", 6, Type::TYPE_MESSAGE, ".google.protobuf.ListValue")
+            ])])
+    ])
+}
+
+fn services_proto() -> File {
+    file("greeter.proto", "greeter")
+        .messages(vec![
+            message("HelloRequest", vec![field("name", 1, Type::TYPE_STRING)]),
+            message("HelloReply", vec![field("message", 1, Type::TYPE_STRING)])
+        ])
+        .services(vec![service("Greeter", vec![
+            method("SayHello", ".greeter.HelloRequest", ".greeter.HelloReply", false, false),
+            method("StreamHellos", ".greeter.HelloRequest", ".greeter.HelloReply", false, true),
+            method("Chat", ".greeter.HelloRequest", ".greeter.HelloReply", true, true)
+        ])])
+}
+
+fn library_proto() -> File {
+    file("library.proto", "library")
+        .dependencies(&["google/protobuf/timestamp.proto"])
+        .messages(vec![
+            message("Book", vec![
+                field("name", 1, Type::TYPE_STRING),
+                field("title", 2, Type::TYPE_STRING),
+                typed("published_at", 3, Type::TYPE_MESSAGE, ".google.protobuf.Timestamp")
+            ]),
+            message("GetBookRequest", vec![
+                field("name", 1, Type::TYPE_STRING),
+                field("revision", 2, Type::TYPE_INT64)
+            ]),
+            message("UpdateBookRequest", vec![
+                typed("book", 1, Type::TYPE_MESSAGE, ".library.Book"),
+                field("validate_only", 2, Type::TYPE_BOOL)
+            ])
+        ])
+        .services(vec![service("Library", vec![
+            with_http(
+                method("GetBook", ".library.GetBookRequest", ".library.Book", false, false),
+                http_rule(2, "/v1/{name=books/*}", "", vec![http_rule(2, "/v1/shelves/-/{name=books/*}", "", vec![])])
+            ),
+            with_http(
+                method("UpdateBook", ".library.UpdateBookRequest", ".library.Book", false, false),
+                http_rule(6, "/v1/{book.name=books/*}", "book", vec![])
+            ),
+            method("StreamBooks", ".library.GetBookRequest", ".library.Book", false, true)
+        ])])
+}
+
+fn validate_proto() -> File {
+    let (labels_entry, labels) = map(
+        ".signup.Signup",
+        "labels",
+        7,
+        field("key", 1, Type::TYPE_STRING),
+        field("value", 2, Type::TYPE_STRING)
+    );
+    let string_rules = |build: fn(&mut protobuf::UnknownFields)| encoded(|rules| rules.add_length_delimited(14, encoded(build)));
+    file("signup.proto", "signup").messages(vec![
+        with_nested(message("Signup", vec![
+            with_protovalidate(field("email", 1, Type::TYPE_STRING), encoded(|rules| {
+                rules.add_varint(25, 1);
+                rules.add_length_delimited(14, encoded(|string| {
+                    string.add_varint(3, 254);
+                    string.add_varint(12, 1);
+                }));
+            })),
+            with_protovalidate(field("name", 2, Type::TYPE_STRING), string_rules(|string| {
+                string.add_varint(2, 1);
+                string.add_varint(3, 64);
+                string.add_length_delimited(6, b"^[A-Za-z ]+$".to_vec());
+            })),
+            with_protovalidate(field("age", 3, Type::TYPE_INT32), encoded(|rules| {
+                rules.add_length_delimited(3, encoded(|int32| {
+                    int32.add_varint(5, 18);
+                    int32.add_varint(2, 150);
+                }));
+            })),
+            with_protovalidate(field("score", 4, Type::TYPE_DOUBLE), encoded(|rules| {
+                rules.add_length_delimited(2, encoded(|double| double.add_fixed64(4, 0.5f64.to_bits())));
+            })),
+            with_protovalidate(repeated(field("tags", 5, Type::TYPE_STRING)), encoded(|rules| {
+                rules.add_length_delimited(18, encoded(|repeated| {
+                    repeated.add_varint(1, 1);
+                    repeated.add_varint(2, 5);
+                    repeated.add_varint(3, 1);
+                    repeated.add_length_delimited(4, string_rules(|string| string.add_varint(2, 1)));
+                }));
+            })),
+            with_pgv(typed("profile", 6, Type::TYPE_MESSAGE, ".signup.Profile"), encoded(|rules| {
+                rules.add_length_delimited(17, encoded(|message| message.add_varint(2, 1)));
+            })),
+            with_protovalidate(labels, encoded(|rules| {
+                rules.add_length_delimited(19, encoded(|map| map.add_varint(2, 10)));
+            }))
+        ]), vec![labels_entry], vec![]),
+        message("Profile", vec![field("bio", 1, Type::TYPE_STRING)])
+    ])
+}
+
+#[test]
+fn scalars() {
+    assert_golden("scalars", request("", vec![], vec![scalars_proto()]));
+}
+
+#[test]
+fn scalars_bigint_uint8array() {
+    assert_golden("scalars_bigint_uint8array", request("int64=bigint,bytes=uint8array", vec![], vec![scalars_proto()]));
+}
+
+#[test]
+fn enums_union() {
+    assert_golden("enums_union", request("enum=union", vec![], vec![enums_proto()]));
+}
+
+#[test]
+fn enums_enum() {
+    assert_golden("enums_enum", request("enum=enum", vec![], vec![enums_proto()]));
+}
+
+#[test]
+fn enums_const() {
+    assert_golden("enums_const", request("enum=const", vec![], vec![enums_proto()]));
+}
+
+#[test]
+fn oneofs_properties() {
+    assert_golden("oneofs_properties", request("oneof=properties", vec![], vec![oneofs_proto()]));
+}
+
+#[test]
+fn oneofs_allow_unset() {
+    assert_golden("oneofs_allow_unset", request("oneof_allow_unset=true,schema=zod", vec![], vec![oneofs_proto()]));
+}
+
+#[test]
+fn oneofs_discriminated() {
+    assert_golden("oneofs_discriminated", request("oneof=discriminated", vec![], vec![oneofs_proto()]));
+}
+
+#[test]
+fn maps_record() {
+    assert_golden("maps_record", request("map=record", vec![], vec![maps_proto()]));
+}
+
+#[test]
+fn maps_map() {
+    assert_golden("maps_map", request("map=map", vec![], vec![maps_proto()]));
+}
+
+#[test]
+fn imports_flat() {
+    assert_golden("imports_flat", imports_request(""));
+}
+
+#[test]
+fn imports_namespace() {
+    assert_golden("imports_namespace", imports_request("nested=namespace"));
+}
+
+#[test]
+fn imports_single_file() {
+    assert_golden("imports_single_file", imports_request("output_mode=single_file"));
+}
+
+#[test]
+fn well_known_types() {
+    assert_golden("well_known_types", well_known_types_request(""));
+}
+
+#[test]
+fn services_grpc_web() {
+    assert_golden("services_grpc_web", request("client=grpc-web", vec![], vec![services_proto()]));
+}
+
+#[test]
+fn runtime_json() {
+    assert_golden("runtime_json", request("runtime=json", vec![], vec![scalars_proto(), oneofs_proto(), maps_proto()]));
+}
+
+#[test]
+fn imports_file_naming() {
+    assert_golden("imports_file_naming", imports_request("file_suffix=.pb.ts,file_case=pascal,runtime=json"));
+}
+
+#[test]
+fn imports_index() {
+    assert_golden("imports_index", imports_request("index=true"));
+}
+
+#[test]
+fn imports_package_paths() {
+    assert_golden("imports_package_paths", request("paths=package,runtime=json", vec![], vec![
+        file("proto/user.proto", "acme.common").messages(vec![message("User", vec![
+            field("name", 1, Type::TYPE_STRING)
+        ])]),
+        file("order.proto", "acme.orders.v1")
+            .dependencies(&["proto/user.proto"])
+            .messages(vec![message("Order", vec![typed("buyer", 1, Type::TYPE_MESSAGE, ".acme.common.User")])])
+    ]));
+}
+
+#[test]
+fn type_overrides() {
+    assert_golden("type_overrides", request(
+        "type_override=int64:Long from \"long\",type_override=.common.User:Account from \"@acme/accounts\",\
+        type_override=.google.protobuf.Timestamp:Date",
+        vec![timestamp_proto()],
+        vec![file("common/account.proto", "common")
+            .dependencies(&["google/protobuf/timestamp.proto"])
+            .messages(vec![message("User", vec![field("id", 1, Type::TYPE_INT64)]), message("Session", vec![
+                typed("user", 1, Type::TYPE_MESSAGE, ".common.User"),
+                typed("expires_at", 2, Type::TYPE_MESSAGE, ".google.protobuf.Timestamp"),
+                repeated(field("tokens", 3, Type::TYPE_INT64))
+            ])])]
+    ));
+}
+
+#[test]
+fn import_map() {
+    let mut req = imports_request("import_map=google/protobuf/*:@bufbuild/protobuf/wkt,import_map=common/**:@acme/common");
+    let mut order = req.take_proto_file().into_vec();
+    order[1].set_dependency(vec!["common/user.proto".to_string(), "google/protobuf/timestamp.proto".to_string()].into());
+    order[1].mut_message_type()[0].mut_field().push(typed("placed_at", 3, Type::TYPE_MESSAGE, ".google.protobuf.Timestamp"));
+    req.set_proto_file(std::iter::once(timestamp_proto().proto).chain(order).collect());
+    assert_golden("import_map", req);
+}
+
+#[test]
+fn any_registry() {
+    assert_golden("any_registry", imports_request("any_registry=true,runtime=json,runtime=binary"));
+}
+
+#[test]
+fn partials() {
+    assert_golden("partials", imports_request("partials=true"));
+}
+
+#[test]
+fn partials_exact() {
+    assert_golden("partials_exact", imports_request("partials=exact,readonly=false"));
+}
+
+#[test]
+fn field_ts_type_option() {
+    let (owners_entry, owners) = map(
+        ".ids.Team",
+        "owners",
+        3,
+        field("key", 1, Type::TYPE_STRING),
+        field("value", 2, Type::TYPE_STRING)
+    );
+    assert_golden("field_ts_type_option", request("", vec![], vec![
+        file("ids.proto", "ids").messages(vec![with_nested(message("Team", vec![
+            with_ts_type(field("id", 1, Type::TYPE_STRING), "TeamId from \"./branded\""),
+            repeated(with_ts_type(field("member_ids", 2, Type::TYPE_STRING), "UserId from \"./branded\"")),
+            with_ts_type(owners, "UserId from \"./branded\""),
+            with_ts_type(field("slug", 4, Type::TYPE_STRING), "Slug")
+        ]), vec![owners_entry], vec![])])
+    ]));
+}
+
+#[test]
+fn skip_and_rename_options() {
+    assert_golden("skip_and_rename_options", request("runtime=json", vec![], vec![
+        file("accounts.proto", "accounts")
+            .messages(vec![
+                renamed_message(message("UserRecord", vec![
+                    field("name", 1, Type::TYPE_STRING),
+                    skipped_field(field("password_hash", 2, Type::TYPE_STRING)),
+                    renamed_field(field("usr_email", 3, Type::TYPE_STRING), "email"),
+                    typed("audit", 4, Type::TYPE_MESSAGE, ".accounts.AuditLog"),
+                    typed("role", 5, Type::TYPE_ENUM, ".accounts.RoleKind")
+                ]), "User"),
+                skipped_message(message("AuditLog", vec![field("entry", 1, Type::TYPE_STRING)]))
+            ])
+            .enums(vec![renamed_enum(enumeration("RoleKind", &[("ROLE_KIND_MEMBER", 0)]), "Role")])
+    ]));
+}
+
+#[test]
+fn enums_strip_prefix_lower() {
+    assert_golden(
+        "enums_strip_prefix_lower",
+        request("enum_strip_prefix=true,enum_value_case=lower,runtime=json", vec![], vec![enums_proto()])
+    );
+}
+
+#[test]
+fn enums_strip_prefix_pascal() {
+    assert_golden(
+        "enums_strip_prefix_pascal",
+        request("enum=enum,enum_strip_prefix=true,enum_value_case=pascal", vec![], vec![enums_proto()])
+    );
+}
+
+#[test]
+fn enum_functions() {
+    assert_golden("enum_functions", request("enum=enum,enum_functions=true", vec![], vec![enums_proto()]));
+}
+
+#[test]
+fn enums_open() {
+    assert_golden("enums_open", request("open_enums=true,runtime=json,guards=true", vec![], vec![enums_proto()]));
+}
+
+#[test]
+fn metadata_descriptors() {
+    assert_golden(
+        "metadata_descriptors",
+        request("metadata=descriptors", vec![], vec![oneofs_proto(), maps_proto()])
+    );
+}
+
+fn people_protos() -> Vec<File> {
+    vec![
+        file("people.proto", "people").messages(vec![
+            with_nested(message("Person", vec![
+                field("name", 1, Type::TYPE_STRING),
+                typed("address", 2, Type::TYPE_MESSAGE, ".people.Person.Address"),
+                typed("manager", 3, Type::TYPE_MESSAGE, ".people.Person"),
+                repeated(field("tags", 4, Type::TYPE_STRING))
+            ]), vec![message("Address", vec![
+                field("street", 1, Type::TYPE_STRING),
+                field("zip_code", 2, Type::TYPE_STRING)
+            ])], vec![])
+        ]),
+        file("people_service.proto", "people")
+            .dependencies(&["google/protobuf/field_mask.proto", "people.proto"])
+            .messages(vec![message("UpdatePersonRequest", vec![
+                typed("person", 1, Type::TYPE_MESSAGE, ".people.Person"),
+                field_mask_of(typed("update_mask", 2, Type::TYPE_MESSAGE, ".google.protobuf.FieldMask"), "people.Person")
+            ])])
+    ]
+}
+
+#[test]
+fn examples() {
+    assert_golden("examples", request("", vec![], vec![file("users.proto", "users")
+        .messages(vec![message("User", vec![
+            field_example(field("id", 1, Type::TYPE_STRING), "\"usr_01H8XGJWBWBAQ4Z1\""),
+            field("email", 2, Type::TYPE_STRING)
+        ])])
+        .services(vec![service("UserService", vec![method_example(
+            method("GetUser", ".users.User", ".users.User", false, false),
+            "```js\nconst user = await client.getUser({ id });\n```"
+        )])])
+        .comment(&[4, 0], " A registered user.\n\n Example:\n   const user: User = {\n     id: \"usr_1\",\n\n     email: \"a@example.com\",\n   };\n\n Users are unique by email.\n")
+        .comment(&[4, 0, 2, 1], " Primary address.\n Example:\n \"a@example.com\"\n")
+        .comment(&[6, 0, 2, 0], " Examples:\n")
+    ]));
+}
+
+#[test]
+fn source_info() {
+    let protos = |parameter| request(parameter, vec![], vec![file("users/v1/user.proto", "users.v1")
+        .messages(vec![with_oneofs(message("User", vec![
+            field("id", 1, Type::TYPE_STRING),
+            in_oneof(field("email", 2, Type::TYPE_STRING), 0),
+            in_oneof(field("phone", 3, Type::TYPE_STRING), 0)
+        ]), &["contact"])])
+        .enums(vec![enumeration("Role", &[("ROLE_UNSPECIFIED", 0), ("ROLE_ADMIN", 1)])])
+        .services(vec![service("UserService", vec![method("GetUser", ".users.v1.User", ".users.v1.User", false, false)])])
+        .line(&[4, 0], 7)
+        .comment(&[4, 0, 2, 0], " Opaque identifier.\n")
+        .line(&[4, 0, 2, 0], 9)
+        .line(&[4, 0, 8, 0], 10)
+        .line(&[4, 0, 2, 1], 11)
+        .line(&[4, 0, 2, 2], 12)
+        .line(&[5, 0], 16)
+        .line(&[6, 0], 21)
+    ]);
+    assert_golden("source_info", protos("source_info=true"));
+    assert_golden("source_info_discriminated", protos("source_info=true,oneof=discriminated,declaration=interface"));
+}
+
+#[test]
+fn proto2() {
+    assert_golden("proto2", request("runtime=json,runtime=binary", vec![], vec![file("legacy.proto", "legacy")
+        .proto2()
+        .messages(vec![with_nested(
+            message("Query", vec![
+                required(field("text", 1, Type::TYPE_STRING)),
+                with_default(field("limit", 2, Type::TYPE_INT32), "10"),
+                with_default(field("prefix", 3, Type::TYPE_STRING), "q:"),
+                with_default(typed("color", 4, Type::TYPE_ENUM, ".legacy.Color"), "RED"),
+                required(typed("page", 5, Type::TYPE_MESSAGE, ".legacy.Page")),
+                typed("result", 6, Type::TYPE_GROUP, ".legacy.Query.Result"),
+                repeated(typed("hit", 7, Type::TYPE_GROUP, ".legacy.Query.Hit"))
+            ]),
+            vec![
+                message("Result", vec![field("url", 8, Type::TYPE_STRING)]),
+                message("Hit", vec![required(field("score", 9, Type::TYPE_DOUBLE))])
+            ],
+            vec![]
+        ), message("Page", vec![field("number", 1, Type::TYPE_UINT32)])])
+        .enums(vec![enumeration("Color", &[("RED", 1), ("GREEN", 2)])])
+    ]));
+}
+
+fn aliases_proto() -> File {
+    let mut status = enumeration("Status", &[("STATUS_UNKNOWN", 0), ("STATUS_STARTED", 1), ("STATUS_RUNNING", 1), ("STATUS_DONE", 2)]);
+    status.mut_options().set_allow_alias(true);
+    file("jobs.proto", "jobs")
+        .messages(vec![message("Job", vec![typed("status", 1, Type::TYPE_ENUM, ".jobs.Status")])])
+        .enums(vec![status])
+}
+
+#[test]
+fn enum_aliases() {
+    assert_golden("enum_aliases", request(
+        "enum=enum,runtime=json,runtime=binary,schema=zod,guards=true,enum_functions=true",
+        vec![],
+        vec![aliases_proto()]
+    ));
+}
+
+#[test]
+fn enum_aliases_union() {
+    assert_golden("enum_aliases_union", request("runtime=json,enum_value_case=pascal", vec![], vec![aliases_proto()]));
+}
+
+#[test]
+fn editions() {
+    const FIELD_PRESENCE: u32 = 1;
+    const REPEATED_FIELD_ENCODING: u32 = 3;
+    const MESSAGE_ENCODING: u32 = 5;
+    assert_golden("editions", request("runtime=json,runtime=binary", vec![], vec![file("catalog.proto", "catalog")
+        .edition_2023()
+        .features(&[(REPEATED_FIELD_ENCODING, 2)])
+        .messages(vec![
+            message("Product", vec![
+                field("sku", 1, Type::TYPE_STRING),
+                with_features(field("title", 2, Type::TYPE_STRING), &[(FIELD_PRESENCE, 2)]),
+                with_features(field("price", 3, Type::TYPE_INT64), &[(FIELD_PRESENCE, 3)]),
+                repeated(field("tags", 4, Type::TYPE_UINT32)),
+                with_features(repeated(field("ratings", 5, Type::TYPE_UINT32)), &[(REPEATED_FIELD_ENCODING, 1)]),
+                typed("stock", 6, Type::TYPE_MESSAGE, ".catalog.Stock"),
+                with_features(typed("vendor", 7, Type::TYPE_MESSAGE, ".catalog.Vendor"), &[(MESSAGE_ENCODING, 2)])
+            ]),
+            message("Stock", vec![field("count", 1, Type::TYPE_UINT32)]),
+            message("Vendor", vec![field("name", 1, Type::TYPE_STRING)])
+        ])
+    ]));
+}
+
+#[test]
+fn extensions() {
+    let title = with_custom_options(field("title", 1, Type::TYPE_STRING), |options| {
+        options.add_length_delimited(50001, b"Title".to_vec());
+        options.add_varint(50002, 1);
+    });
+    let mut scope = message("Scope", vec![field("name", 1, Type::TYPE_STRING)]);
+    scope.set_extension(vec![extension(typed("scope", 101, Type::TYPE_MESSAGE, ".docs.Scope"), ".docs.Query")].into());
+    assert_golden("extensions", request("metadata=descriptors", vec![], vec![file("docs.proto", "docs")
+        .proto2()
+        .messages(vec![message("Query", vec![title]), scope])
+        .enums(vec![enumeration("Level", &[("LEVEL_LOW", 0), ("LEVEL_HIGH", 1)])])
+        .extensions(vec![
+            extension(field("note", 100, Type::TYPE_STRING), ".docs.Query"),
+            extension(repeated(field("tags", 102, Type::TYPE_STRING)), ".docs.Query"),
+            extension(field("label", 50001, Type::TYPE_STRING), ".google.protobuf.FieldOptions"),
+            extension(typed("level", 50002, Type::TYPE_ENUM, ".docs.Level"), ".google.protobuf.FieldOptions")
+        ])
+        .comment(&[7, 0], " Free-form note attached by the editor.\n")
+    ]));
+}
+
+#[test]
+fn field_paths() {
+    assert_golden("field_paths", request("field_paths=true,field_path_depth=2", vec![field_mask_proto()], people_protos()));
+}
+
+#[test]
+fn field_masks() {
+    let mut protos = people_protos();
+    protos.extend(vec![oneofs_proto(), maps_proto()]);
+    assert_golden("field_masks", request("field_masks=true,field_path_depth=2", vec![field_mask_proto()], protos));
+}
+
+#[test]
+fn field_masks_discriminated() {
+    assert_golden("field_masks_discriminated", request(
+        "field_masks=true,oneof=discriminated,map=map,optional_style=null",
+        vec![],
+        vec![oneofs_proto(), scalars_proto()]
+    ));
+}
+
+#[test]
+fn json_schema() {
+    let mut req = well_known_types_request("target=jsonschema");
+    req.mut_proto_file().push(maps_proto().proto);
+    req.mut_file_to_generate().push("maps.proto".to_string());
+    assert_golden("json_schema", req);
+}
+
+#[test]
+fn openapi() {
+    assert_golden("openapi", request("target=openapi", vec![timestamp_proto()], vec![library_proto()]));
+}
+
+#[test]
+fn fetch_client() {
+    assert_golden("fetch_client", request("client=fetch", vec![timestamp_proto()], vec![library_proto()]));
+}
+
+#[test]
+fn services_connect() {
+    assert_golden("services_connect", request("client=connect", vec![], vec![services_proto()]));
+}
+
+#[test]
+fn service_paths() {
+    assert_golden("service_paths", request("service_paths=true", vec![], vec![services_proto()]));
+}
+
+#[test]
+fn services_handlers() {
+    assert_golden("services_handlers", request("handlers=true", vec![], vec![services_proto()]));
+}
+
+#[test]
+fn branded_ints() {
+    assert_golden("branded_ints", request(
+        "branded_ints=true,int64=bigint,runtime=json,factories=true",
+        vec![timestamp_proto()],
+        vec![library_proto()]
+    ));
+}
+
+#[test]
+fn runtime_module_shared() {
+    assert_golden("runtime_module_shared", request(
+        "runtime=json,runtime=binary,factories=true,bytes=uint8array,runtime_module=shared,paths=package",
+        vec![timestamp_proto()],
+        vec![library_proto()]
+    ));
+}
+
+#[test]
+fn runtime_module_package() {
+    assert_golden("runtime_module_package", request(
+        "runtime=json,factories=true,runtime_module=@acme/proto-runtime",
+        vec![timestamp_proto()],
+        vec![library_proto()]
+    ));
+}
+
+#[test]
+fn list_only() {
+    assert_golden("list_only", request("list_only=true,service_paths=true", vec![timestamp_proto()], vec![library_proto()]));
+}
+
+#[test]
+fn services_msw() {
+    assert_golden("services_msw", request("mocks=msw", vec![], vec![services_proto()]));
+}
+
+#[test]
+fn fixtures() {
+    let tree = file("tree.proto", "tree").messages(vec![message("Node", vec![
+        field("label", 1, Type::TYPE_STRING),
+        typed("parent", 2, Type::TYPE_MESSAGE, ".tree.Node"),
+        repeated(typed("children", 3, Type::TYPE_MESSAGE, ".tree.Node"))
+    ])]);
+    assert_golden("fixtures", request(
+        "fixtures=true",
+        vec![],
+        vec![scalars_proto(), enums_proto(), oneofs_proto(), maps_proto(), tree]
+    ));
+}
+
+#[test]
+fn services_react_query() {
+    assert_golden("services_react_query", request("hooks=react-query", vec![], vec![services_proto()]));
+}
+
+#[test]
+fn grpc_web_as_object() {
+    let (labels_entry, labels) = map(
+        ".compat.Account",
+        "labels",
+        5,
+        field("key", 1, Type::TYPE_STRING),
+        field("value", 2, Type::TYPE_INT64)
+    );
+    let account = with_nested(with_oneofs(message("Account", vec![
+        field("display_name", 1, Type::TYPE_STRING),
+        field("HTTP_port", 2, Type::TYPE_UINT32),
+        repeated(field("tags", 3, Type::TYPE_STRING)),
+        js_string(field("balance", 4, Type::TYPE_INT64)),
+        labels,
+        typed("created_at", 6, Type::TYPE_MESSAGE, ".google.protobuf.Timestamp"),
+        typed("nickname", 7, Type::TYPE_MESSAGE, ".google.protobuf.StringValue"),
+        typed("status", 8, Type::TYPE_ENUM, ".compat.Status"),
+        field("default", 9, Type::TYPE_BOOL),
+        field("avatar", 10, Type::TYPE_BYTES),
+        in_oneof(field("email", 11, Type::TYPE_STRING), 0),
+        in_oneof(field("phone", 12, Type::TYPE_STRING), 0)
+    ]), &["contact"]), vec![labels_entry], vec![]);
+    assert_golden("grpc_web_as_object", request(
+        "compat=grpc-web-asobject",
+        vec![timestamp_proto(), wrappers_proto()],
+        vec![file("account.proto", "compat")
+            .dependencies(&["google/protobuf/timestamp.proto", "google/protobuf/wrappers.proto"])
+            .messages(vec![account])
+            .enums(vec![enumeration("Status", &[("STATUS_ACTIVE", 0), ("STATUS_CLOSED", 1)])])]
+    ));
+}
+
+#[test]
+fn ts_proto_compat() {
+    assert_golden("ts_proto_compat", request(
+        "compat=ts-proto",
+        vec![timestamp_proto(), wrappers_proto()],
+        vec![file("account.proto", "compat")
+            .dependencies(&["google/protobuf/timestamp.proto", "google/protobuf/wrappers.proto"])
+            .messages(vec![with_oneofs(message("Account", vec![
+                field("display_name", 1, Type::TYPE_STRING),
+                field("balance", 2, Type::TYPE_INT64),
+                field("avatar", 3, Type::TYPE_BYTES),
+                typed("created_at", 4, Type::TYPE_MESSAGE, ".google.protobuf.Timestamp"),
+                typed("nickname", 5, Type::TYPE_MESSAGE, ".google.protobuf.StringValue"),
+                typed("status", 6, Type::TYPE_ENUM, ".compat.Status"),
+                in_oneof(field("email", 7, Type::TYPE_STRING), 0),
+                in_oneof(field("phone", 8, Type::TYPE_STRING), 0)
+            ]), &["contact"])])
+            .enums(vec![enumeration("Status", &[("STATUS_ACTIVE", 0), ("STATUS_CLOSED", 1)])])]
+    ));
+}
+
+fn ids_proto() -> File {
+    file("ids.proto", "acme").messages(vec![
+        nominal_message(message("UserId", vec![field("value", 1, Type::TYPE_STRING)])),
+        message("AccountId", vec![field("value", 1, Type::TYPE_STRING)]),
+        message("Marker", vec![])
+    ])
+}
+
+#[test]
+fn nominal_messages() {
+    assert_golden("nominal_messages", request("", vec![], vec![ids_proto()]));
+}
+
+#[test]
+fn nominal_interfaces() {
+    assert_golden("nominal_interfaces", request("nominal=true,declaration=interface", vec![], vec![ids_proto()]));
+}
+
+#[test]
+fn type_field() {
+    assert_golden("type_field", request(
+        "type_field=true,runtime=json,runtime=binary,factories=true",
+        vec![],
+        vec![oneofs_proto()]
+    ));
+}
+
+#[test]
+fn validate_rules() {
+    assert_golden("validate_rules", request("schema=zod", vec![], vec![validate_proto()]));
+}
+
+#[test]
+fn recursive_messages() {
+    let tree = file("tree.proto", "tree").messages(vec![message("Node", vec![
+        field("label", 1, Type::TYPE_STRING),
+        repeated(typed("children", 2, Type::TYPE_MESSAGE, ".tree.Node"))
+    ])]);
+    let folder = file("fs/folder.proto", "fs")
+        .dependencies(&["fs/entry.proto"])
+        .messages(vec![message("Folder", vec![
+            field("name", 1, Type::TYPE_STRING),
+            repeated(typed("entries", 2, Type::TYPE_MESSAGE, ".fs.Entry"))
+        ])])
+        .enums(vec![enumeration("Kind", &[("KIND_FILE", 0), ("KIND_LINK", 1)])]);
+    let entry = file("fs/entry.proto", "fs")
+        .dependencies(&["fs/folder.proto"])
+        .messages(vec![message("Entry", vec![
+            typed("kind", 1, Type::TYPE_ENUM, ".fs.Kind"),
+            typed("folder", 2, Type::TYPE_MESSAGE, ".fs.Folder")
+        ])]);
+    assert_golden("recursive_messages", request("schema=zod", vec![], vec![tree, folder, entry]));
+}
+
+#[test]
+fn validate_rules_json_schema() {
+    assert_golden("validate_rules_json_schema", request("target=jsonschema", vec![], vec![validate_proto()]));
+}
+
+#[test]
+fn guards() {
+    assert_golden("guards", request(
+        "guards=true,oneof=discriminated,map=map",
+        vec![timestamp_proto(), wrappers_proto()],
+        vec![scalars_proto(), oneofs_proto(), maps_proto(), file("event.proto", "events")
+            .dependencies(&["google/protobuf/timestamp.proto", "google/protobuf/wrappers.proto", "oneofs.proto"])
+            .messages(vec![with_oneofs(message("Event", vec![
+                typed("at", 1, Type::TYPE_MESSAGE, ".google.protobuf.Timestamp"),
+                typed("note", 2, Type::TYPE_MESSAGE, ".google.protobuf.StringValue"),
+                repeated(typed("contacts", 3, Type::TYPE_MESSAGE, ".oneofs.Contact")),
+                in_oneof(field("code", 4, Type::TYPE_INT32), 0),
+                in_oneof(field("reason", 5, Type::TYPE_STRING), 0)
+            ]), &["status"])])]
+    ));
+}
+
+#[test]
+fn guards_oneof_properties() {
+    assert_golden("guards_oneof_properties", request("guards=true", vec![], vec![oneofs_proto()]));
+}
+
+#[test]
+fn helpers() {
+    assert_golden("helpers", request(
+        "helpers=equality,clone,oneof=discriminated,map=map,bytes=uint8array,int64=bigint",
+        vec![timestamp_proto(), wrappers_proto()],
+        vec![scalars_proto(), oneofs_proto(), maps_proto(), file("event.proto", "events")
+            .dependencies(&["google/protobuf/timestamp.proto", "google/protobuf/wrappers.proto", "oneofs.proto"])
+            .messages(vec![with_oneofs(message("Event", vec![
+                typed("at", 1, Type::TYPE_MESSAGE, ".google.protobuf.Timestamp"),
+                typed("note", 2, Type::TYPE_MESSAGE, ".google.protobuf.StringValue"),
+                repeated(typed("contacts", 3, Type::TYPE_MESSAGE, ".oneofs.Contact")),
+                in_oneof(typed("sender", 4, Type::TYPE_MESSAGE, ".oneofs.Contact"), 0),
+                in_oneof(field("reason", 5, Type::TYPE_STRING), 0)
+            ]), &["status"]), message("Empty", vec![])])]
+    ));
+}
+
+fn empty_proto() -> File {
+    file("google/protobuf/empty.proto", "google.protobuf").messages(vec![message("Empty", vec![])])
+}
+
+#[test]
+fn empty_messages_object() {
+    assert_golden("empty_messages_object", request("empty=object,declaration=interface", vec![], vec![empty_proto()]));
+}
+
+#[test]
+fn empty_messages_branded() {
+    assert_golden("empty_messages_branded", request("empty=branded", vec![], vec![empty_proto()]));
+}
+
+#[test]
+fn helpers_match() {
+    assert_golden("helpers_match", request("helpers=match", vec![], vec![oneofs_proto(), scalars_proto()]));
+}
+
+#[test]
+fn helpers_match_discriminated() {
+    assert_golden("helpers_match_discriminated", request("helpers=match,oneof=discriminated", vec![], vec![oneofs_proto()]));
+}
+
+#[test]
+fn helpers_equality() {
+    assert_golden("helpers_equality", request("helpers=equality", vec![], vec![oneofs_proto(), maps_proto()]));
+}
+
+#[test]
+fn namespaces() {
+    assert_golden("namespaces", request("namespaces=true,enum=enum", vec![], vec![
+        file("common.proto", "acme.common").messages(vec![message("User", vec![
+            field("name", 1, Type::TYPE_STRING)
+        ])]),
+        file("billing.proto", "acme.billing.v1")
+            .dependencies(&["common.proto"])
+            .messages(vec![
+                with_nested(message("Invoice", vec![
+                    typed("payer", 1, Type::TYPE_MESSAGE, ".acme.common.User"),
+                    typed("status", 2, Type::TYPE_ENUM, ".acme.billing.v1.Invoice.Status")
+                ]), vec![], vec![enumeration("Status", &[("STATUS_UNSPECIFIED", 0), ("STATUS_PAID", 1)])]),
+                message("User", vec![typed("account", 1, Type::TYPE_MESSAGE, ".acme.common.User")])
+            ]),
+        file("legacy.proto", "")
+            .dependencies(&["billing.proto"])
+            .messages(vec![message("Legacy", vec![typed("invoice", 1, Type::TYPE_MESSAGE, ".acme.billing.v1.Invoice")])])
+    ]));
+}
+
+#[test]
+fn ts_syntax_legacy() {
+    assert_golden("ts_syntax_legacy", request(
+        "ts_syntax=legacy,client=grpc-js,mocks=msw,hooks=react-query,index=true,factories=true,runtime_module=shared",
+        vec![],
+        vec![services_proto()]
+    ));
+}
+
+#[test]
+fn import_extension_js() {
+    assert_golden("import_extension_js", imports_request("import_extension=js,runtime=json,index=true"));
+}
+
+#[test]
+fn import_extension_ts() {
+    assert_golden("import_extension_ts", imports_request("import_extension=ts,runtime=binary,runtime_module=shared"));
+}
+
+#[test]
+fn alias_prefix() {
+    assert_golden("alias_prefix", imports_request("alias_prefix=@proto/,runtime=json,paths=package"));
+}
+
+#[test]
+fn optional_style_null_runtime() {
+    assert_golden(
+        "optional_style_null_runtime",
+        request("runtime=json,runtime=binary,factories=true,optional_style=null", vec![], vec![oneofs_proto()])
+    );
+}
+
+#[test]
+fn interfaces_with_oneof_properties() {
+    assert_golden("interfaces_with_oneof_properties", request("declaration=interface", vec![], vec![oneofs_proto()]));
+}
+
+fn mutable_proto() -> File {
+    file("settings.proto", "settings").messages(vec![
+        message("Settings", vec![repeated(field("tags", 1, Type::TYPE_STRING))]),
+        message("default", vec![field("name", 1, Type::TYPE_STRING)])
+    ])
+}
+
+#[test]
+fn mutable_types() {
+    assert_golden("mutable_types", request("mutable_types=true", vec![], vec![mutable_proto()]));
+}
+
+#[test]
+fn mutable_types_not_readonly() {
+    assert_golden("mutable_types_not_readonly", request("mutable_types=true,readonly=false", vec![], vec![mutable_proto()]));
+}
@@ -0,0 +1,28 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: orders/order.proto
+
+import type { User, User_Address } from "@proto/common/user_pb";
+import type { Order } from "@proto/orders/order_pb";
+import { UserFromJSON, UserToJSON, User_AddressFromJSON, User_AddressToJSON } from "@proto/common/user_json";
+
+function pick(json: Record<string, unknown>, jsonName: string, protoName: string): unknown {
+  return json[jsonName] ?? json[protoName];
+}
+
+export function OrderToJSON(message: Order): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["buyer"] !== undefined) json["buyer"] = UserToJSON(message["buyer"]);
+  if (message["shipping"] !== undefined) json["shipping"] = User_AddressToJSON(message["shipping"]);
+  return json;
+}
+
+export function OrderFromJSON(value: unknown): Order {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v1 = pick(json, "buyer", "buyer");
+  if (v1 != null) message["buyer"] = UserFromJSON(v1);
+  const v2 = pick(json, "shipping", "shipping");
+  if (v2 != null) message["shipping"] = User_AddressFromJSON(v2);
+  return message as unknown as Order;
+}
@@ -0,0 +1,33 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: common/user.proto
+// source: orders/order.proto
+
+import type { User, User_Address } from "./common/user_pb";
+import type { Order } from "./orders/order_pb";
+import { UserFromJSON, UserToJSON, User_AddressFromJSON, User_AddressToJSON } from "./common/user_json";
+import { OrderFromJSON, OrderToJSON } from "./orders/order_json";
+import { decodeUser, decodeUser_Address, encodeUser, encodeUser_Address } from "./common/user_binary";
+import { decodeOrder, encodeOrder } from "./orders/order_binary";
+
+/** Messages of the schema by the type URL `google.protobuf.Any` packs them with. */
+export interface TypeRegistry {
+  "type.googleapis.com/common.User": User;
+  "type.googleapis.com/common.User.Address": User_Address;
+  "type.googleapis.com/orders.Order": Order;
+}
+
+/** An `Any` holding a message of the schema, in its JSON form. */
+export type AnyKnownMessage = {
+  [K in keyof TypeRegistry]: Readonly<{ "@type": K }> & TypeRegistry[K];
+}[keyof TypeRegistry];
+
+export const typeRegistry = {
+  "type.googleapis.com/common.User": { typeName: "common.User", fromJSON: UserFromJSON, toJSON: UserToJSON, decode: decodeUser, encode: encodeUser },
+  "type.googleapis.com/common.User.Address": { typeName: "common.User.Address", fromJSON: User_AddressFromJSON, toJSON: User_AddressToJSON, decode: decodeUser_Address, encode: encodeUser_Address },
+  "type.googleapis.com/orders.Order": { typeName: "orders.Order", fromJSON: OrderFromJSON, toJSON: OrderToJSON, decode: decodeOrder, encode: encodeOrder },
+} as const;
+
+export function isAnyKnownMessage(value: { readonly "@type": string }): value is AnyKnownMessage {
+  return Object.prototype.hasOwnProperty.call(typeRegistry, value["@type"]);
+}
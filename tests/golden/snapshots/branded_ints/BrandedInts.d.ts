@@ -0,0 +1,13 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+
+export type int32 = number & { __int32: never };
+export type uint32 = number & { __uint32: never };
+export type sint32 = number & { __sint32: never };
+export type fixed32 = number & { __fixed32: never };
+export type sfixed32 = number & { __sfixed32: never };
+export type int64 = bigint & { __int64: never };
+export type uint64 = bigint & { __uint64: never };
+export type sint64 = bigint & { __sint64: never };
+export type fixed64 = bigint & { __fixed64: never };
+export type sfixed64 = bigint & { __sfixed64: never };
@@ -0,0 +1,37 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: library.proto
+
+import type { Book, GetBookRequest, UpdateBookRequest } from "./library_pb";
+
+export type DeepPartial<T> = T extends Uint8Array | Date
+  ? T
+  : T extends ReadonlyArray<infer U>
+    ? ReadonlyArray<DeepPartial<U>>
+    : T extends ReadonlyMap<infer K, infer V>
+      ? ReadonlyMap<K, DeepPartial<V>>
+      : T extends object
+        ? { readonly [P in keyof T]?: DeepPartial<T[P]> }
+        : T;
+
+export function createBook(partial: DeepPartial<Book> = {}): Book {
+  const message: Record<string, unknown> = {};
+  message["name"] = partial["name"] ?? "";
+  message["title"] = partial["title"] ?? "";
+  if (partial["publishedAt"] !== undefined) message["publishedAt"] = partial["publishedAt"];
+  return message as unknown as Book;
+}
+
+export function createGetBookRequest(partial: DeepPartial<GetBookRequest> = {}): GetBookRequest {
+  const message: Record<string, unknown> = {};
+  message["name"] = partial["name"] ?? "";
+  message["revision"] = partial["revision"] ?? BigInt(0);
+  return message as unknown as GetBookRequest;
+}
+
+export function createUpdateBookRequest(partial: DeepPartial<UpdateBookRequest> = {}): UpdateBookRequest {
+  const message: Record<string, unknown> = {};
+  if (partial["book"] !== undefined) message["book"] = createBook(partial["book"]);
+  message["validateOnly"] = partial["validateOnly"] ?? false;
+  return message as unknown as UpdateBookRequest;
+}
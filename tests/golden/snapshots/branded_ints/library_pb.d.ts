@@ -0,0 +1,27 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: library.proto
+
+import type { int64 } from "./BrandedInts";
+
+export type Book = Readonly<{
+  name: string;
+  title: string;
+  publishedAt?: string;
+}>;
+
+export type GetBookRequest = Readonly<{
+  name: string;
+  revision: int64;
+}>;
+
+export type UpdateBookRequest = Readonly<{
+  book?: Book;
+  validateOnly: boolean;
+}>;
+
+export interface LibraryClient {
+  getBook(request: GetBookRequest): Promise<Book>;
+  updateBook(request: UpdateBookRequest): Promise<Book>;
+  streamBooks(request: GetBookRequest): AsyncIterable<Book>;
+}
@@ -0,0 +1,69 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: catalog.proto
+
+import type { Product, Stock, Vendor } from "./catalog_pb";
+
+function pick(json: Record<string, unknown>, jsonName: string, protoName: string): unknown {
+  return json[jsonName] ?? json[protoName];
+}
+
+export function ProductToJSON(message: Product): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["sku"] !== undefined) json["sku"] = message["sku"];
+  if (message["title"] !== "") json["title"] = message["title"];
+  if (message["price"] !== undefined) json["price"] = message["price"];
+  if (message["tags"].length > 0) json["tags"] = message["tags"];
+  if (message["ratings"].length > 0) json["ratings"] = message["ratings"];
+  if (message["stock"] !== undefined) json["stock"] = StockToJSON(message["stock"]);
+  if (message["vendor"] !== undefined) json["vendor"] = VendorToJSON(message["vendor"]);
+  return json;
+}
+
+export function ProductFromJSON(value: unknown): Product {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v1 = pick(json, "sku", "sku");
+  if (v1 != null) message["sku"] = String(v1);
+  const v2 = pick(json, "title", "title");
+  message["title"] = v2 == null ? "" : String(v2);
+  const v3 = pick(json, "price", "price");
+  message["price"] = v3 == null ? "0" : String(v3);
+  const v4 = pick(json, "tags", "tags");
+  message["tags"] = v4 == null ? [] : Array.isArray(v4) ? v4.map((x: unknown) => Number(x)) : [];
+  const v5 = pick(json, "ratings", "ratings");
+  message["ratings"] = v5 == null ? [] : Array.isArray(v5) ? v5.map((x: unknown) => Number(x)) : [];
+  const v6 = pick(json, "stock", "stock");
+  if (v6 != null) message["stock"] = StockFromJSON(v6);
+  const v7 = pick(json, "vendor", "vendor");
+  if (v7 != null) message["vendor"] = VendorFromJSON(v7);
+  return message as unknown as Product;
+}
+
+export function StockToJSON(message: Stock): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["count"] !== undefined) json["count"] = message["count"];
+  return json;
+}
+
+export function StockFromJSON(value: unknown): Stock {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v1 = pick(json, "count", "count");
+  if (v1 != null) message["count"] = Number(v1);
+  return message as unknown as Stock;
+}
+
+export function VendorToJSON(message: Vendor): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["name"] !== undefined) json["name"] = message["name"];
+  return json;
+}
+
+export function VendorFromJSON(value: unknown): Vendor {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v1 = pick(json, "name", "name");
+  if (v1 != null) message["name"] = String(v1);
+  return message as unknown as Vendor;
+}
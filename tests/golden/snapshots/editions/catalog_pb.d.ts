@@ -0,0 +1,21 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: catalog.proto
+
+export type Product = Readonly<{
+  sku?: string;
+  title: string;
+  price: string;
+  tags: ReadonlyArray<number>;
+  ratings: ReadonlyArray<number>;
+  stock?: Stock;
+  vendor?: Vendor;
+}>;
+
+export type Stock = Readonly<{
+  count?: number;
+}>;
+
+export type Vendor = Readonly<{
+  name?: string;
+}>;
@@ -0,0 +1,5 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: google/protobuf/empty.proto
+
+export type Empty = { readonly __typename?: "google.protobuf.Empty" };
@@ -0,0 +1,48 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: jobs.proto
+
+import type { Status } from "./jobs_pb";
+
+export const StatusNameByNumber: Readonly<Record<number, string>> = Object.freeze({
+  0: "STATUS_UNKNOWN",
+  1: "STATUS_STARTED",
+  2: "STATUS_DONE",
+});
+
+export const StatusNumberByName: Readonly<Record<string, number>> = Object.freeze({
+  STATUS_UNKNOWN: 0,
+  STATUS_STARTED: 1,
+  STATUS_RUNNING: 1,
+  STATUS_DONE: 2,
+});
+
+export function StatusToJSON(value: Status): string | number {
+  switch (value) {
+    case 0:
+      return "STATUS_UNKNOWN";
+    case 1:
+      return "STATUS_STARTED";
+    case 2:
+      return "STATUS_DONE";
+    default:
+      return value;
+  }
+}
+
+export function StatusFromJSON(value: unknown): Status {
+  switch (value) {
+    case "STATUS_UNKNOWN":
+    case 0:
+      return 0;
+    case "STATUS_STARTED":
+    case "STATUS_RUNNING":
+    case 1:
+      return 1;
+    case "STATUS_DONE":
+    case 2:
+      return 2;
+    default:
+      return (typeof value === "number" ? value : 0) as Status;
+  }
+}
@@ -0,0 +1,17 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: jobs.proto
+
+import type { Job, Status } from "./jobs_pb";
+
+export function isStatus(value: unknown): value is Status {
+  return value === 0 || value === 1 || value === 2;
+}
+
+export function isJob(value: unknown): value is Job {
+  if (typeof value !== "object" || value === null) return false;
+  const message = value as Record<string, unknown>;
+  return (
+    isStatus(message["status"])
+  );
+}
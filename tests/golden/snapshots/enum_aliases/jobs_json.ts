@@ -0,0 +1,53 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: jobs.proto
+
+import type { Job, Status } from "./jobs_pb";
+
+function pick(json: Record<string, unknown>, jsonName: string, protoName: string): unknown {
+  return json[jsonName] ?? json[protoName];
+}
+
+export function StatusToJSON(value: Status): string | number {
+  switch (value) {
+    case 0:
+      return "STATUS_UNKNOWN";
+    case 1:
+      return "STATUS_STARTED";
+    case 2:
+      return "STATUS_DONE";
+    default:
+      return value;
+  }
+}
+
+export function StatusFromJSON(value: unknown): Status {
+  switch (value) {
+    case "STATUS_UNKNOWN":
+    case 0:
+      return 0;
+    case "STATUS_STARTED":
+    case "STATUS_RUNNING":
+    case 1:
+      return 1;
+    case "STATUS_DONE":
+    case 2:
+      return 2;
+    default:
+      return (typeof value === "number" ? value : 0) as Status;
+  }
+}
+
+export function JobToJSON(message: Job): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["status"] !== 0) json["status"] = StatusToJSON(message["status"]);
+  return json;
+}
+
+export function JobFromJSON(value: unknown): Job {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v1 = pick(json, "status", "status");
+  message["status"] = v1 == null ? 0 : StatusFromJSON(v1);
+  return message as unknown as Job;
+}
@@ -0,0 +1,14 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: jobs.proto
+
+export type Job = Readonly<{
+  status: Status;
+}>;
+
+export declare enum Status {
+  STATUS_UNKNOWN = 0,
+  /** Aliases: `STATUS_RUNNING` */
+  STATUS_STARTED = 1,
+  STATUS_DONE = 2,
+}
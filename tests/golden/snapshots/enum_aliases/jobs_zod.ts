@@ -0,0 +1,15 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: jobs.proto
+
+import { z } from "zod";
+
+export const StatusSchema = z.union([z.literal(0), z.literal(1), z.literal(2)]);
+
+export type Status = z.infer<typeof StatusSchema>;
+
+export const JobSchema = z.object({
+  status: StatusSchema,
+}).readonly();
+
+export type Job = z.infer<typeof JobSchema>;
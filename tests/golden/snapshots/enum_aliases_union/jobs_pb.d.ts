@@ -0,0 +1,10 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: jobs.proto
+
+export type Job = Readonly<{
+  status: Status;
+}>;
+
+/** `STATUS_RUNNING` is an alias of `STATUS_STARTED`. */
+export type Status = "StatusUnknown" | "StatusStarted" | "StatusDone";
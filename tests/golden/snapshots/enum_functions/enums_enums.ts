@@ -0,0 +1,73 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: enums.proto
+
+import type { Priority, Task_State } from "./enums_pb";
+
+export const Task_StateNameByNumber: Readonly<Record<number, string>> = Object.freeze({
+  0: "STATE_UNSPECIFIED",
+  1: "STATE_DONE",
+});
+
+export const Task_StateNumberByName: Readonly<Record<string, number>> = Object.freeze({
+  STATE_UNSPECIFIED: 0,
+  STATE_DONE: 1,
+});
+
+export function Task_StateToJSON(value: Task_State): string | number {
+  switch (value) {
+    case 0:
+      return "STATE_UNSPECIFIED";
+    case 1:
+      return "STATE_DONE";
+    default:
+      return value;
+  }
+}
+
+export function Task_StateFromJSON(value: unknown): Task_State {
+  switch (value) {
+    case "STATE_UNSPECIFIED":
+    case 0:
+      return 0;
+    case "STATE_DONE":
+    case 1:
+      return 1;
+    default:
+      return (typeof value === "number" ? value : 0) as Task_State;
+  }
+}
+
+export const PriorityNameByNumber: Readonly<Record<number, string>> = Object.freeze({
+  0: "PRIORITY_LOW",
+  2: "PRIORITY_HIGH",
+});
+
+export const PriorityNumberByName: Readonly<Record<string, number>> = Object.freeze({
+  PRIORITY_LOW: 0,
+  PRIORITY_HIGH: 2,
+});
+
+export function PriorityToJSON(value: Priority): string | number {
+  switch (value) {
+    case 0:
+      return "PRIORITY_LOW";
+    case 2:
+      return "PRIORITY_HIGH";
+    default:
+      return value;
+  }
+}
+
+export function PriorityFromJSON(value: unknown): Priority {
+  switch (value) {
+    case "PRIORITY_LOW":
+    case 0:
+      return 0;
+    case "PRIORITY_HIGH":
+    case 2:
+      return 2;
+    default:
+      return (typeof value === "number" ? value : 0) as Priority;
+  }
+}
@@ -0,0 +1,20 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: enums.proto
+
+export type Task = Readonly<{
+  state: Task_State;
+  priority: Priority;
+}>;
+
+export declare const Task_State: {
+  readonly STATE_UNSPECIFIED: 0;
+  readonly STATE_DONE: 1;
+};
+export type Task_State = typeof Task_State[keyof typeof Task_State];
+
+export declare const Priority: {
+  readonly PRIORITY_LOW: 0;
+  readonly PRIORITY_HIGH: 2;
+};
+export type Priority = typeof Priority[keyof typeof Priority];
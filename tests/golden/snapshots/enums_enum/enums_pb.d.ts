@@ -0,0 +1,18 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: enums.proto
+
+export type Task = Readonly<{
+  state: Task_State;
+  priority: Priority;
+}>;
+
+export declare enum Task_State {
+  STATE_UNSPECIFIED = 0,
+  STATE_DONE = 1,
+}
+
+export declare enum Priority {
+  PRIORITY_LOW = 0,
+  PRIORITY_HIGH = 2,
+}
@@ -0,0 +1,22 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: enums.proto
+
+import type { Priority, Task, Task_State } from "./enums_pb";
+
+export function isTask_State(value: unknown): value is Task_State {
+  return value === "STATE_UNSPECIFIED" || value === "STATE_DONE" || value === "UNRECOGNIZED";
+}
+
+export function isPriority(value: unknown): value is Priority {
+  return value === "PRIORITY_LOW" || value === "PRIORITY_HIGH" || value === "UNRECOGNIZED";
+}
+
+export function isTask(value: unknown): value is Task {
+  if (typeof value !== "object" || value === null) return false;
+  const message = value as Record<string, unknown>;
+  return (
+    isTask_State(message["state"]) &&
+    isPriority(message["priority"])
+  );
+}
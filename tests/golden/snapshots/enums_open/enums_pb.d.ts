@@ -0,0 +1,12 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: enums.proto
+
+export type Task = Readonly<{
+  state: Task_State;
+  priority: Priority;
+}>;
+
+export type Task_State = "STATE_UNSPECIFIED" | "STATE_DONE" | "UNRECOGNIZED";
+
+export type Priority = "PRIORITY_LOW" | "PRIORITY_HIGH" | "UNRECOGNIZED";
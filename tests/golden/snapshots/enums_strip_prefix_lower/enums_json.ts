@@ -0,0 +1,74 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: enums.proto
+
+import type { Priority, Task, Task_State } from "./enums_pb";
+
+function pick(json: Record<string, unknown>, jsonName: string, protoName: string): unknown {
+  return json[jsonName] ?? json[protoName];
+}
+
+export function Task_StateToJSON(value: Task_State): string {
+  switch (value) {
+    case "unspecified":
+      return "STATE_UNSPECIFIED";
+    case "done":
+      return "STATE_DONE";
+    default:
+      return value;
+  }
+}
+
+export function Task_StateFromJSON(value: unknown): Task_State {
+  switch (value) {
+    case "STATE_UNSPECIFIED":
+    case 0:
+      return "unspecified";
+    case "STATE_DONE":
+    case 1:
+      return "done";
+    default:
+      return "unspecified";
+  }
+}
+
+export function PriorityToJSON(value: Priority): string {
+  switch (value) {
+    case "low":
+      return "PRIORITY_LOW";
+    case "high":
+      return "PRIORITY_HIGH";
+    default:
+      return value;
+  }
+}
+
+export function PriorityFromJSON(value: unknown): Priority {
+  switch (value) {
+    case "PRIORITY_LOW":
+    case 0:
+      return "low";
+    case "PRIORITY_HIGH":
+    case 2:
+      return "high";
+    default:
+      return "low";
+  }
+}
+
+export function TaskToJSON(message: Task): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["state"] !== "unspecified") json["state"] = Task_StateToJSON(message["state"]);
+  if (message["priority"] !== "low") json["priority"] = PriorityToJSON(message["priority"]);
+  return json;
+}
+
+export function TaskFromJSON(value: unknown): Task {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v1 = pick(json, "state", "state");
+  message["state"] = v1 == null ? "unspecified" : Task_StateFromJSON(v1);
+  const v2 = pick(json, "priority", "priority");
+  message["priority"] = v2 == null ? "low" : PriorityFromJSON(v2);
+  return message as unknown as Task;
+}
@@ -0,0 +1,12 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: enums.proto
+
+export type Task = Readonly<{
+  state: Task_State;
+  priority: Priority;
+}>;
+
+export type Task_State = "unspecified" | "done";
+
+export type Priority = "low" | "high";
@@ -0,0 +1,18 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: enums.proto
+
+export type Task = Readonly<{
+  state: Task_State;
+  priority: Priority;
+}>;
+
+export declare enum Task_State {
+  Unspecified = 0,
+  Done = 1,
+}
+
+export declare enum Priority {
+  Low = 0,
+  High = 2,
+}
@@ -0,0 +1,48 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: users.proto
+
+/**
+ * A registered user.
+ *
+ * Users are unique by email.
+ *
+ * @example
+ * ```ts
+ * const user: User = {
+ *   id: "usr_1",
+ *
+ *   email: "a@example.com",
+ * };
+ * ```
+ */
+export type User = Readonly<{
+  /**
+   * @example
+   * ```ts
+   * "usr_01H8XGJWBWBAQ4Z1"
+   * ```
+   */
+  id: string;
+  /**
+   * Primary address.
+   *
+   * @example
+   * ```ts
+   * "a@example.com"
+   * ```
+   */
+  email: string;
+}>;
+
+export interface UserServiceClient {
+  /**
+   * Examples:
+   *
+   * @example
+   * ```js
+   * const user = await client.getUser({ id });
+   * ```
+   */
+  getUser(request: User): Promise<User>;
+}
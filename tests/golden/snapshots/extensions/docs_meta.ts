@@ -0,0 +1,25 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: docs.proto
+
+export const QueryFields = {
+  title: 1,
+} as const;
+
+export const QueryDescriptor = {
+  typeName: "docs.Query",
+  fields: [
+    { name: "title", localName: "title", jsonName: "title", number: 1, type: "string", repeated: false, options: { "[docs.label]": "Title", "[docs.level]": "LEVEL_HIGH" } },
+  ],
+} as const;
+
+export const ScopeFields = {
+  name: 1,
+} as const;
+
+export const ScopeDescriptor = {
+  typeName: "docs.Scope",
+  fields: [
+    { name: "name", localName: "name", jsonName: "name", number: 1, type: "string", repeated: false },
+  ],
+} as const;
@@ -0,0 +1,27 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: docs.proto
+
+export type Query = Readonly<{
+  title?: string;
+}>;
+
+export type Scope = Readonly<{
+  name?: string;
+}>;
+
+export type Level = "LEVEL_LOW" | "LEVEL_HIGH";
+
+/** Extensions of `docs.Query` declared in `docs.proto`. */
+export type QueryExtensions = Readonly<{
+  /** Free-form note attached by the editor. */
+  "[docs.note]"?: string;
+  "[docs.tags]"?: ReadonlyArray<string>;
+  "[docs.Scope.scope]"?: Scope;
+}>;
+
+/** Extensions of `google.protobuf.FieldOptions` declared in `docs.proto`. */
+export type FieldOptionsExtensions = Readonly<{
+  "[docs.label]"?: string;
+  "[docs.level]"?: Level;
+}>;
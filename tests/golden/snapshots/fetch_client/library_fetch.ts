@@ -0,0 +1,83 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: library.proto
+
+import type { Book, GetBookRequest, UpdateBookRequest } from "./library_pb";
+
+export type Fetch = (input: string, init: RequestInit) => Promise<Response>;
+
+export interface FetchClientOptions {
+  readonly fetch?: Fetch;
+  readonly headers?: HeadersInit;
+}
+
+export class FetchError extends Error {
+  constructor(readonly status: number, readonly body: string) {
+    super(`HTTP ${status}: ${body}`);
+  }
+}
+
+function encodePath(value: unknown): string {
+  return String(value).split("/").map(encodeURIComponent).join("/");
+}
+
+function appendQuery(query: URLSearchParams, key: string, value: unknown): void {
+  if (value === undefined || value === null) return;
+  if (Array.isArray(value)) {
+    value.forEach((item) => appendQuery(query, key, item));
+    return;
+  }
+  query.append(key, String(value));
+}
+
+export class LibraryFetchClient {
+  private readonly fetch: Fetch;
+
+  constructor(
+    private readonly baseUrl: string,
+    private readonly options: FetchClientOptions = {}
+  ) {
+    this.fetch = options.fetch ?? ((input, init) => globalThis.fetch(input, init));
+  }
+
+  private async call<T>(
+    method: string,
+    path: string,
+    query: URLSearchParams | undefined,
+    body: unknown,
+    init: RequestInit
+  ): Promise<T> {
+    const search = query === undefined || query.toString() === "" ? "" : `?${query}`;
+    const headers = new Headers(this.options.headers);
+    new Headers(init.headers).forEach((value, key) => headers.set(key, value));
+    if (body !== undefined) headers.set("Content-Type", "application/json");
+    const response = await this.fetch(this.baseUrl + path + search, {
+      ...init,
+      method,
+      headers,
+      body: body === undefined ? undefined : JSON.stringify(body)
+    });
+    if (!response.ok) {
+      throw new FetchError(response.status, await response.text());
+    }
+    return (await response.json()) as T;
+  }
+
+  getBook(request: GetBookRequest, init: RequestInit = {}): Promise<Book> {
+    const query = new URLSearchParams();
+    appendQuery(query, "revision", request["revision"]);
+    return this.call<Book>("GET", `/v1/${encodePath(String(request["name"]))}`, query, undefined, init);
+  }
+
+  getBook1(request: GetBookRequest, init: RequestInit = {}): Promise<Book> {
+    const query = new URLSearchParams();
+    appendQuery(query, "revision", request["revision"]);
+    return this.call<Book>("GET", `/v1/shelves/-/${encodePath(String(request["name"]))}`, query, undefined, init);
+  }
+
+  updateBook(request: UpdateBookRequest, init: RequestInit = {}): Promise<Book> {
+    const query = new URLSearchParams();
+    appendQuery(query, "validateOnly", request["validateOnly"]);
+    return this.call<Book>("PATCH", `/v1/${encodePath(String(request["book"]?.["name"]))}`, query, request["book"], init);
+  }
+}
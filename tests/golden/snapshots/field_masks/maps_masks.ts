@@ -0,0 +1,49 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: maps.proto
+
+import type { Inventory, InventoryFieldPath, Item, ItemFieldPath } from "./maps_pb";
+
+export function mergeInventory(target: Inventory, source: Inventory): Inventory {
+  const message: Record<string, unknown> = { ...target };
+  message["labels"] = { ...target["labels"], ...source["labels"] };
+  message["items"] = { ...target["items"], ...source["items"] };
+  return message as unknown as Inventory;
+}
+
+export function applyInventoryFieldMask(target: Inventory | undefined, source: Inventory | undefined, mask: ReadonlyArray<InventoryFieldPath>): Inventory {
+  const empty: Inventory = { "labels": {}, "items": {} };
+  const from = source ?? empty;
+  const message: Record<string, unknown> = { ...(target ?? empty) };
+  for (const path of mask) {
+    switch (path) {
+      case "labels":
+        message["labels"] = from["labels"];
+        break;
+      case "items":
+        message["items"] = from["items"];
+        break;
+    }
+  }
+  return message as unknown as Inventory;
+}
+
+export function mergeItem(target: Item, source: Item): Item {
+  const message: Record<string, unknown> = { ...target };
+  if (source["name"] !== "") message["name"] = source["name"];
+  return message as unknown as Item;
+}
+
+export function applyItemFieldMask(target: Item | undefined, source: Item | undefined, mask: ReadonlyArray<ItemFieldPath>): Item {
+  const empty: Item = { "name": "" };
+  const from = source ?? empty;
+  const message: Record<string, unknown> = { ...(target ?? empty) };
+  for (const path of mask) {
+    switch (path) {
+      case "name":
+        message["name"] = from["name"];
+        break;
+    }
+  }
+  return message as unknown as Item;
+}
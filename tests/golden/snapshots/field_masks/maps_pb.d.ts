@@ -0,0 +1,14 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: maps.proto
+
+export type Inventory = Readonly<{
+  labels: Readonly<Record<string, string>>;
+  items: Readonly<Record<number, Item>>;
+}>;
+export type InventoryFieldPath = "labels" | "items";
+
+export type Item = Readonly<{
+  name: string;
+}>;
+export type ItemFieldPath = "name";
@@ -0,0 +1,71 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: oneofs.proto
+
+import type { Contact, ContactFieldPath, Phone, PhoneFieldPath } from "./oneofs_pb";
+
+export function mergeContact(target: Contact, source: Contact): Contact {
+  const message: Record<string, unknown> = { ...target };
+  if (source["name"] !== "") message["name"] = source["name"];
+  if (source["email"] !== undefined) {
+    message["email"] = source["email"];
+    delete message["phone"];
+  }
+  if (source["phone"] !== undefined) {
+    message["phone"] = target["phone"] !== undefined ? mergePhone(target["phone"], source["phone"]) : source["phone"];
+    delete message["email"];
+  }
+  return message as unknown as Contact;
+}
+
+export function applyContactFieldMask(target: Contact | undefined, source: Contact | undefined, mask: ReadonlyArray<ContactFieldPath>): Contact {
+  const empty: Contact = { "name": "" };
+  const from = source ?? empty;
+  const message: Record<string, unknown> = { ...(target ?? empty) };
+  for (const path of mask) {
+    const dot = path.indexOf(".");
+    switch (dot < 0 ? path : path.slice(0, dot)) {
+      case "name":
+        message["name"] = from["name"];
+        break;
+      case "email":
+        if (from["email"] !== undefined) {
+          message["email"] = from["email"];
+          delete message["phone"];
+        } else delete message["email"];
+        break;
+      case "phone":
+        if (dot >= 0) {
+          message["phone"] = applyPhoneFieldMask((message["phone"] as Contact["phone"]) ?? undefined, from["phone"] ?? undefined, [path.slice(dot + 1) as PhoneFieldPath]);
+          delete message["email"];
+          break;
+        }
+        if (from["phone"] !== undefined) {
+          message["phone"] = from["phone"];
+          delete message["email"];
+        } else delete message["phone"];
+        break;
+    }
+  }
+  return message as unknown as Contact;
+}
+
+export function mergePhone(target: Phone, source: Phone): Phone {
+  const message: Record<string, unknown> = { ...target };
+  if (source["number"] !== "") message["number"] = source["number"];
+  return message as unknown as Phone;
+}
+
+export function applyPhoneFieldMask(target: Phone | undefined, source: Phone | undefined, mask: ReadonlyArray<PhoneFieldPath>): Phone {
+  const empty: Phone = { "number": "" };
+  const from = source ?? empty;
+  const message: Record<string, unknown> = { ...(target ?? empty) };
+  for (const path of mask) {
+    switch (path) {
+      case "number":
+        message["number"] = from["number"];
+        break;
+    }
+  }
+  return message as unknown as Phone;
+}
@@ -0,0 +1,72 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: people.proto
+
+import type { Person, PersonFieldPath, Person_Address, Person_AddressFieldPath } from "./people_pb";
+
+export function mergePerson(target: Person, source: Person): Person {
+  const message: Record<string, unknown> = { ...target };
+  if (source["name"] !== "") message["name"] = source["name"];
+  if (source["address"] !== undefined) message["address"] = target["address"] !== undefined ? mergePerson_Address(target["address"], source["address"]) : source["address"];
+  if (source["manager"] !== undefined) message["manager"] = target["manager"] !== undefined ? mergePerson(target["manager"], source["manager"]) : source["manager"];
+  message["tags"] = [...target["tags"], ...source["tags"]];
+  return message as unknown as Person;
+}
+
+export function applyPersonFieldMask(target: Person | undefined, source: Person | undefined, mask: ReadonlyArray<PersonFieldPath>): Person {
+  const empty: Person = { "name": "", "tags": [] };
+  const from = source ?? empty;
+  const message: Record<string, unknown> = { ...(target ?? empty) };
+  for (const path of mask) {
+    const dot = path.indexOf(".");
+    switch (dot < 0 ? path : path.slice(0, dot)) {
+      case "name":
+        message["name"] = from["name"];
+        break;
+      case "address":
+        if (dot >= 0) {
+          message["address"] = applyPerson_AddressFieldMask((message["address"] as Person["address"]) ?? undefined, from["address"] ?? undefined, [path.slice(dot + 1) as Person_AddressFieldPath]);
+          break;
+        }
+        if (from["address"] !== undefined) message["address"] = from["address"];
+        else delete message["address"];
+        break;
+      case "manager":
+        if (dot >= 0) {
+          message["manager"] = applyPersonFieldMask((message["manager"] as Person["manager"]) ?? undefined, from["manager"] ?? undefined, [path.slice(dot + 1) as PersonFieldPath]);
+          break;
+        }
+        if (from["manager"] !== undefined) message["manager"] = from["manager"];
+        else delete message["manager"];
+        break;
+      case "tags":
+        message["tags"] = from["tags"];
+        break;
+    }
+  }
+  return message as unknown as Person;
+}
+
+export function mergePerson_Address(target: Person_Address, source: Person_Address): Person_Address {
+  const message: Record<string, unknown> = { ...target };
+  if (source["street"] !== "") message["street"] = source["street"];
+  if (source["zipCode"] !== "") message["zipCode"] = source["zipCode"];
+  return message as unknown as Person_Address;
+}
+
+export function applyPerson_AddressFieldMask(target: Person_Address | undefined, source: Person_Address | undefined, mask: ReadonlyArray<Person_AddressFieldPath>): Person_Address {
+  const empty: Person_Address = { "street": "", "zipCode": "" };
+  const from = source ?? empty;
+  const message: Record<string, unknown> = { ...(target ?? empty) };
+  for (const path of mask) {
+    switch (path) {
+      case "street":
+        message["street"] = from["street"];
+        break;
+      case "zip_code":
+        message["zipCode"] = from["zipCode"];
+        break;
+    }
+  }
+  return message as unknown as Person_Address;
+}
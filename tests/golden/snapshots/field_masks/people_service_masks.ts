@@ -0,0 +1,44 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: people_service.proto
+
+import type { FieldMaskFieldPath } from "./google/protobuf/field_mask_pb";
+import type { PersonFieldPath } from "./people_pb";
+import type { UpdatePersonRequest, UpdatePersonRequestFieldPath } from "./people_service_pb";
+import { applyFieldMaskFieldMask, mergeFieldMask } from "./google/protobuf/field_mask_masks";
+import { applyPersonFieldMask, mergePerson } from "./people_masks";
+
+export function mergeUpdatePersonRequest(target: UpdatePersonRequest, source: UpdatePersonRequest): UpdatePersonRequest {
+  const message: Record<string, unknown> = { ...target };
+  if (source["person"] !== undefined) message["person"] = target["person"] !== undefined ? mergePerson(target["person"], source["person"]) : source["person"];
+  if (source["updateMask"] !== undefined) message["updateMask"] = target["updateMask"] !== undefined ? mergeFieldMask(target["updateMask"], source["updateMask"]) : source["updateMask"];
+  return message as unknown as UpdatePersonRequest;
+}
+
+export function applyUpdatePersonRequestFieldMask(target: UpdatePersonRequest | undefined, source: UpdatePersonRequest | undefined, mask: ReadonlyArray<UpdatePersonRequestFieldPath>): UpdatePersonRequest {
+  const empty: UpdatePersonRequest = {};
+  const from = source ?? empty;
+  const message: Record<string, unknown> = { ...(target ?? empty) };
+  for (const path of mask) {
+    const dot = path.indexOf(".");
+    switch (dot < 0 ? path : path.slice(0, dot)) {
+      case "person":
+        if (dot >= 0) {
+          message["person"] = applyPersonFieldMask((message["person"] as UpdatePersonRequest["person"]) ?? undefined, from["person"] ?? undefined, [path.slice(dot + 1) as PersonFieldPath]);
+          break;
+        }
+        if (from["person"] !== undefined) message["person"] = from["person"];
+        else delete message["person"];
+        break;
+      case "update_mask":
+        if (dot >= 0) {
+          message["updateMask"] = applyFieldMaskFieldMask((message["updateMask"] as UpdatePersonRequest["updateMask"]) ?? undefined, from["updateMask"] ?? undefined, [path.slice(dot + 1) as FieldMaskFieldPath]);
+          break;
+        }
+        if (from["updateMask"] !== undefined) message["updateMask"] = from["updateMask"];
+        else delete message["updateMask"];
+        break;
+    }
+  }
+  return message as unknown as UpdatePersonRequest;
+}
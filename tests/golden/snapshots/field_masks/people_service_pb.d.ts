@@ -0,0 +1,12 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: people_service.proto
+
+import type { FieldMask } from "./google/protobuf/field_mask_pb";
+import type { Person } from "./people_pb";
+
+export type UpdatePersonRequest = Readonly<{
+  person?: Person;
+  updateMask?: FieldMask;
+}>;
+export type UpdatePersonRequestFieldPath = "person" | `person.${"name" | "address" | "manager" | "tags"}` | "update_mask" | `update_mask.${"paths"}`;
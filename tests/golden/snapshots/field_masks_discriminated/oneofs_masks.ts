@@ -0,0 +1,64 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: oneofs.proto
+
+import type { Contact, ContactFieldPath, Phone, PhoneFieldPath } from "./oneofs_pb";
+
+export function mergeContact(target: Contact, source: Contact): Contact {
+  const message: Record<string, unknown> = { ...target };
+  if (source["name"] !== "") message["name"] = source["name"];
+  const source0 = source["channel"], target0 = target["channel"];
+  if (source0 !== undefined) {
+    if (source0.$case === "phone" && target0?.$case === "phone") message["channel"] = { $case: "phone", "phone": mergePhone(target0["phone"], source0["phone"]) };
+    else message["channel"] = source0;
+  }
+  return message as unknown as Contact;
+}
+
+export function applyContactFieldMask(target: Contact | undefined, source: Contact | undefined, mask: ReadonlyArray<ContactFieldPath>): Contact {
+  const empty: Contact = { "name": "" };
+  const from = source ?? empty;
+  const message: Record<string, unknown> = { ...(target ?? empty) };
+  for (const path of mask) {
+    const dot = path.indexOf(".");
+    switch (dot < 0 ? path : path.slice(0, dot)) {
+      case "name":
+        message["name"] = from["name"];
+        break;
+      case "email":
+        if (from["channel"]?.$case === "email") message["channel"] = from["channel"];
+        else if ((message["channel"] as Contact["channel"])?.$case === "email") delete message["channel"];
+        break;
+      case "phone":
+        if (dot >= 0) {
+          const current = message["channel"] as Contact["channel"];
+          message["channel"] = { $case: "phone", "phone": applyPhoneFieldMask(current?.$case === "phone" ? current["phone"] : undefined, from["channel"]?.$case === "phone" ? from["channel"]["phone"] : undefined, [path.slice(dot + 1) as PhoneFieldPath]) };
+          break;
+        }
+        if (from["channel"]?.$case === "phone") message["channel"] = from["channel"];
+        else if ((message["channel"] as Contact["channel"])?.$case === "phone") delete message["channel"];
+        break;
+    }
+  }
+  return message as unknown as Contact;
+}
+
+export function mergePhone(target: Phone, source: Phone): Phone {
+  const message: Record<string, unknown> = { ...target };
+  if (source["number"] !== "") message["number"] = source["number"];
+  return message as unknown as Phone;
+}
+
+export function applyPhoneFieldMask(target: Phone | undefined, source: Phone | undefined, mask: ReadonlyArray<PhoneFieldPath>): Phone {
+  const empty: Phone = { "number": "" };
+  const from = source ?? empty;
+  const message: Record<string, unknown> = { ...(target ?? empty) };
+  for (const path of mask) {
+    switch (path) {
+      case "number":
+        message["number"] = from["number"];
+        break;
+    }
+  }
+  return message as unknown as Phone;
+}
@@ -0,0 +1,14 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: oneofs.proto
+
+export type Contact = Readonly<{
+  name: string;
+  channel?: { readonly $case: "email"; readonly email: string } | { readonly $case: "phone"; readonly phone: Phone };
+}>;
+export type ContactFieldPath = "name" | "email" | "phone" | `phone.${"number"}`;
+
+export type Phone = Readonly<{
+  number: string;
+}>;
+export type PhoneFieldPath = "number";
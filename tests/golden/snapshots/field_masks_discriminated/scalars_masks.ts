@@ -0,0 +1,90 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: scalars.proto
+
+import type { Scalars, ScalarsFieldPath } from "./scalars_pb";
+
+export function mergeScalars(target: Scalars, source: Scalars): Scalars {
+  const message: Record<string, unknown> = { ...target };
+  if (source["doubleValue"] !== 0) message["doubleValue"] = source["doubleValue"];
+  if (source["floatValue"] !== 0) message["floatValue"] = source["floatValue"];
+  if (source["int32Value"] !== 0) message["int32Value"] = source["int32Value"];
+  if (source["int64Value"] !== "0") message["int64Value"] = source["int64Value"];
+  if (source["uint32Value"] !== 0) message["uint32Value"] = source["uint32Value"];
+  if (source["uint64Value"] !== "0") message["uint64Value"] = source["uint64Value"];
+  if (source["sint32Value"] !== 0) message["sint32Value"] = source["sint32Value"];
+  if (source["sint64Value"] !== "0") message["sint64Value"] = source["sint64Value"];
+  if (source["fixed32Value"] !== 0) message["fixed32Value"] = source["fixed32Value"];
+  if (source["fixed64Value"] !== "0") message["fixed64Value"] = source["fixed64Value"];
+  if (source["sfixed32Value"] !== 0) message["sfixed32Value"] = source["sfixed32Value"];
+  if (source["sfixed64Value"] !== "0") message["sfixed64Value"] = source["sfixed64Value"];
+  if (source["boolValue"] !== false) message["boolValue"] = source["boolValue"];
+  if (source["stringValue"] !== "") message["stringValue"] = source["stringValue"];
+  if (source["bytesValue"] !== "") message["bytesValue"] = source["bytesValue"];
+  message["repeatedValue"] = [...target["repeatedValue"], ...source["repeatedValue"]];
+  if (source["optionalValue"] !== null) message["optionalValue"] = source["optionalValue"];
+  return message as unknown as Scalars;
+}
+
+export function applyScalarsFieldMask(target: Scalars | undefined, source: Scalars | undefined, mask: ReadonlyArray<ScalarsFieldPath>): Scalars {
+  const empty: Scalars = { "doubleValue": 0, "floatValue": 0, "int32Value": 0, "int64Value": "0", "uint32Value": 0, "uint64Value": "0", "sint32Value": 0, "sint64Value": "0", "fixed32Value": 0, "fixed64Value": "0", "sfixed32Value": 0, "sfixed64Value": "0", "boolValue": false, "stringValue": "", "bytesValue": "", "repeatedValue": [], "optionalValue": null };
+  const from = source ?? empty;
+  const message: Record<string, unknown> = { ...(target ?? empty) };
+  for (const path of mask) {
+    switch (path) {
+      case "double_value":
+        message["doubleValue"] = from["doubleValue"];
+        break;
+      case "float_value":
+        message["floatValue"] = from["floatValue"];
+        break;
+      case "int32_value":
+        message["int32Value"] = from["int32Value"];
+        break;
+      case "int64_value":
+        message["int64Value"] = from["int64Value"];
+        break;
+      case "uint32_value":
+        message["uint32Value"] = from["uint32Value"];
+        break;
+      case "uint64_value":
+        message["uint64Value"] = from["uint64Value"];
+        break;
+      case "sint32_value":
+        message["sint32Value"] = from["sint32Value"];
+        break;
+      case "sint64_value":
+        message["sint64Value"] = from["sint64Value"];
+        break;
+      case "fixed32_value":
+        message["fixed32Value"] = from["fixed32Value"];
+        break;
+      case "fixed64_value":
+        message["fixed64Value"] = from["fixed64Value"];
+        break;
+      case "sfixed32_value":
+        message["sfixed32Value"] = from["sfixed32Value"];
+        break;
+      case "sfixed64_value":
+        message["sfixed64Value"] = from["sfixed64Value"];
+        break;
+      case "bool_value":
+        message["boolValue"] = from["boolValue"];
+        break;
+      case "string_value":
+        message["stringValue"] = from["stringValue"];
+        break;
+      case "bytes_value":
+        message["bytesValue"] = from["bytesValue"];
+        break;
+      case "repeated_value":
+        message["repeatedValue"] = from["repeatedValue"];
+        break;
+      case "optional_value":
+        if (from["optionalValue"] !== null) message["optionalValue"] = from["optionalValue"];
+        else message["optionalValue"] = null;
+        break;
+    }
+  }
+  return message as unknown as Scalars;
+}
@@ -0,0 +1,24 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: scalars.proto
+
+export type Scalars = Readonly<{
+  doubleValue: number;
+  floatValue: number;
+  int32Value: number;
+  int64Value: string;
+  uint32Value: number;
+  uint64Value: string;
+  sint32Value: number;
+  sint64Value: string;
+  fixed32Value: number;
+  fixed64Value: string;
+  sfixed32Value: number;
+  sfixed64Value: string;
+  boolValue: boolean;
+  stringValue: string;
+  bytesValue: string;
+  repeatedValue: ReadonlyArray<number>;
+  optionalValue: string | null;
+}>;
+export type ScalarsFieldPath = "double_value" | "float_value" | "int32_value" | "int64_value" | "uint32_value" | "uint64_value" | "sint32_value" | "sint64_value" | "fixed32_value" | "fixed64_value" | "sfixed32_value" | "sfixed64_value" | "bool_value" | "string_value" | "bytes_value" | "repeated_value" | "optional_value";
@@ -0,0 +1,17 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: people.proto
+
+export type Person = Readonly<{
+  name: string;
+  address?: Person_Address;
+  manager?: Person;
+  tags: ReadonlyArray<string>;
+}>;
+export type PersonFieldPath = "name" | "address" | `address.${"street" | "zip_code"}` | "manager" | `manager.${"name" | "address" | "manager" | "tags"}` | "tags";
+
+export type Person_Address = Readonly<{
+  street: string;
+  zipCode: string;
+}>;
+export type Person_AddressFieldPath = "street" | "zip_code";
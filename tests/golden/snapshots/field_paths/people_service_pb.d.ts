@@ -0,0 +1,11 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: people_service.proto
+
+import type { Person, PersonFieldPath } from "./people_pb";
+
+export type UpdatePersonRequest = Readonly<{
+  person?: Person;
+  updateMask?: ReadonlyArray<PersonFieldPath>;
+}>;
+export type UpdatePersonRequestFieldPath = "person" | `person.${"name" | "address" | "manager" | "tags"}` | "update_mask" | `update_mask.${"paths"}`;
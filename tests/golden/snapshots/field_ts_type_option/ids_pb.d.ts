@@ -0,0 +1,12 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: ids.proto
+
+import type { TeamId, UserId } from "./branded";
+
+export type Team = Readonly<{
+  id: TeamId;
+  memberIds: ReadonlyArray<UserId>;
+  owners: Readonly<Record<string, UserId>>;
+  slug: Slug;
+}>;
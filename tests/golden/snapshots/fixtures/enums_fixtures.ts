@@ -0,0 +1,47 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: enums.proto
+
+import type { Task } from "./enums_pb";
+
+export interface FixtureOptions {
+  readonly seed?: number;
+  readonly maxDepth?: number;
+}
+
+export interface FixtureContext {
+  readonly next: () => number;
+  readonly depth: number;
+  readonly maxDepth: number;
+}
+
+function fixtureContext(options: FixtureOptions | FixtureContext): FixtureContext {
+  if ("next" in options) return options;
+  let state = (options.seed ?? 1) >>> 0;
+  return {
+    next: () => {
+      state = (state + 0x6d2b79f5) >>> 0;
+      let t = Math.imul(state ^ (state >>> 15), state | 1);
+      t ^= t + Math.imul(t ^ (t >>> 7), t | 61);
+      return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+    },
+    depth: 0,
+    maxDepth: options.maxDepth ?? 3
+  };
+}
+
+function mockPick<T>(context: FixtureContext, values: ReadonlyArray<T>): T {
+  return values[mockInt(context, values.length)];
+}
+
+function mockInt(context: FixtureContext, max: number): number {
+  return Math.floor(context.next() * max);
+}
+
+export function mockTask(overrides: Partial<Task> = {}, options: FixtureOptions | FixtureContext = {}): Task {
+  const context = fixtureContext(options);
+  const message: Record<string, unknown> = {};
+  message["state"] = mockPick(context, ["STATE_UNSPECIFIED", "STATE_DONE"]);
+  message["priority"] = mockPick(context, ["PRIORITY_LOW", "PRIORITY_HIGH"]);
+  return { ...message, ...overrides } as unknown as Task;
+}
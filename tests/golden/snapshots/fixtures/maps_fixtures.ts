@@ -0,0 +1,62 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: maps.proto
+
+import type { Inventory, Item } from "./maps_pb";
+
+export interface FixtureOptions {
+  readonly seed?: number;
+  readonly maxDepth?: number;
+}
+
+export interface FixtureContext {
+  readonly next: () => number;
+  readonly depth: number;
+  readonly maxDepth: number;
+}
+
+function fixtureContext(options: FixtureOptions | FixtureContext): FixtureContext {
+  if ("next" in options) return options;
+  let state = (options.seed ?? 1) >>> 0;
+  return {
+    next: () => {
+      state = (state + 0x6d2b79f5) >>> 0;
+      let t = Math.imul(state ^ (state >>> 15), state | 1);
+      t ^= t + Math.imul(t ^ (t >>> 7), t | 61);
+      return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+    },
+    depth: 0,
+    maxDepth: options.maxDepth ?? 3
+  };
+}
+
+function nested(context: FixtureContext): FixtureContext {
+  return { ...context, depth: context.depth + 1 };
+}
+
+function mockRepeated<T>(context: FixtureContext, value: () => T): T[] {
+  return Array.from({ length: 1 + mockInt(context, 3) }, value);
+}
+
+function mockString(context: FixtureContext, name: string): string {
+  return `${name}-${mockInt(context, 1000)}`;
+}
+
+function mockInt(context: FixtureContext, max: number): number {
+  return Math.floor(context.next() * max);
+}
+
+export function mockInventory(overrides: Partial<Inventory> = {}, options: FixtureOptions | FixtureContext = {}): Inventory {
+  const context = fixtureContext(options);
+  const message: Record<string, unknown> = {};
+  message["labels"] = Object.fromEntries(mockRepeated(context, () => [mockString(context, "key"), mockString(context, "value")] as const));
+  message["items"] = context.depth < context.maxDepth ? Object.fromEntries(mockRepeated(context, () => [String(mockInt(context, 1000)), mockItem({}, nested(context))] as const)) : {};
+  return { ...message, ...overrides } as unknown as Inventory;
+}
+
+export function mockItem(overrides: Partial<Item> = {}, options: FixtureOptions | FixtureContext = {}): Item {
+  const context = fixtureContext(options);
+  const message: Record<string, unknown> = {};
+  message["name"] = mockString(context, "name");
+  return { ...message, ...overrides } as unknown as Item;
+}
@@ -0,0 +1,60 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: oneofs.proto
+
+import type { Contact, Phone } from "./oneofs_pb";
+
+export interface FixtureOptions {
+  readonly seed?: number;
+  readonly maxDepth?: number;
+}
+
+export interface FixtureContext {
+  readonly next: () => number;
+  readonly depth: number;
+  readonly maxDepth: number;
+}
+
+function fixtureContext(options: FixtureOptions | FixtureContext): FixtureContext {
+  if ("next" in options) return options;
+  let state = (options.seed ?? 1) >>> 0;
+  return {
+    next: () => {
+      state = (state + 0x6d2b79f5) >>> 0;
+      let t = Math.imul(state ^ (state >>> 15), state | 1);
+      t ^= t + Math.imul(t ^ (t >>> 7), t | 61);
+      return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+    },
+    depth: 0,
+    maxDepth: options.maxDepth ?? 3
+  };
+}
+
+function nested(context: FixtureContext): FixtureContext {
+  return { ...context, depth: context.depth + 1 };
+}
+
+function mockString(context: FixtureContext, name: string): string {
+  return `${name}-${mockInt(context, 1000)}`;
+}
+
+function mockInt(context: FixtureContext, max: number): number {
+  return Math.floor(context.next() * max);
+}
+
+export function mockContact(overrides: Partial<Contact> = {}, options: FixtureOptions | FixtureContext = {}): Contact {
+  const context = fixtureContext(options);
+  const message: Record<string, unknown> = {};
+  message["name"] = mockString(context, "name");
+  const oneof0 = mockInt(context, 2);
+  if (oneof0 === 0) message["email"] = mockString(context, "email");
+  else if (oneof0 === 1 && context.depth < context.maxDepth) message["phone"] = mockPhone({}, nested(context));
+  return { ...message, ...overrides } as unknown as Contact;
+}
+
+export function mockPhone(overrides: Partial<Phone> = {}, options: FixtureOptions | FixtureContext = {}): Phone {
+  const context = fixtureContext(options);
+  const message: Record<string, unknown> = {};
+  message["number"] = mockString(context, "number");
+  return { ...message, ...overrides } as unknown as Phone;
+}
@@ -0,0 +1,78 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: scalars.proto
+
+import type { Scalars } from "./scalars_pb";
+
+export interface FixtureOptions {
+  readonly seed?: number;
+  readonly maxDepth?: number;
+}
+
+export interface FixtureContext {
+  readonly next: () => number;
+  readonly depth: number;
+  readonly maxDepth: number;
+}
+
+function fixtureContext(options: FixtureOptions | FixtureContext): FixtureContext {
+  if ("next" in options) return options;
+  let state = (options.seed ?? 1) >>> 0;
+  return {
+    next: () => {
+      state = (state + 0x6d2b79f5) >>> 0;
+      let t = Math.imul(state ^ (state >>> 15), state | 1);
+      t ^= t + Math.imul(t ^ (t >>> 7), t | 61);
+      return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+    },
+    depth: 0,
+    maxDepth: options.maxDepth ?? 3
+  };
+}
+
+function mockRepeated<T>(context: FixtureContext, value: () => T): T[] {
+  return Array.from({ length: 1 + mockInt(context, 3) }, value);
+}
+
+function mockString(context: FixtureContext, name: string): string {
+  return `${name}-${mockInt(context, 1000)}`;
+}
+
+function mockBase64(context: FixtureContext): string {
+  return btoa(String.fromCharCode(...mockBytes(context)));
+}
+
+function mockBytes(context: FixtureContext): Uint8Array {
+  return Uint8Array.from({ length: 4 }, () => mockInt(context, 256));
+}
+
+function mockFloat(context: FixtureContext): number {
+  return Math.round(context.next() * 100000) / 100;
+}
+
+function mockInt(context: FixtureContext, max: number): number {
+  return Math.floor(context.next() * max);
+}
+
+export function mockScalars(overrides: Partial<Scalars> = {}, options: FixtureOptions | FixtureContext = {}): Scalars {
+  const context = fixtureContext(options);
+  const message: Record<string, unknown> = {};
+  message["doubleValue"] = mockFloat(context);
+  message["floatValue"] = mockFloat(context);
+  message["int32Value"] = mockInt(context, 1000);
+  message["int64Value"] = String(mockInt(context, 1000));
+  message["uint32Value"] = mockInt(context, 1000);
+  message["uint64Value"] = String(mockInt(context, 1000));
+  message["sint32Value"] = mockInt(context, 1000);
+  message["sint64Value"] = String(mockInt(context, 1000));
+  message["fixed32Value"] = mockInt(context, 1000);
+  message["fixed64Value"] = String(mockInt(context, 1000));
+  message["sfixed32Value"] = mockInt(context, 1000);
+  message["sfixed64Value"] = String(mockInt(context, 1000));
+  message["boolValue"] = mockInt(context, 2) === 1;
+  message["stringValue"] = mockString(context, "string_value");
+  message["bytesValue"] = mockBase64(context);
+  message["repeatedValue"] = mockRepeated(context, () => mockInt(context, 1000));
+  message["optionalValue"] = mockString(context, "optional_value");
+  return { ...message, ...overrides } as unknown as Scalars;
+}
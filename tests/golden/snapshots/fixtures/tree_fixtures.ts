@@ -0,0 +1,56 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: tree.proto
+
+import type { Node } from "./tree_pb";
+
+export interface FixtureOptions {
+  readonly seed?: number;
+  readonly maxDepth?: number;
+}
+
+export interface FixtureContext {
+  readonly next: () => number;
+  readonly depth: number;
+  readonly maxDepth: number;
+}
+
+function fixtureContext(options: FixtureOptions | FixtureContext): FixtureContext {
+  if ("next" in options) return options;
+  let state = (options.seed ?? 1) >>> 0;
+  return {
+    next: () => {
+      state = (state + 0x6d2b79f5) >>> 0;
+      let t = Math.imul(state ^ (state >>> 15), state | 1);
+      t ^= t + Math.imul(t ^ (t >>> 7), t | 61);
+      return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+    },
+    depth: 0,
+    maxDepth: options.maxDepth ?? 3
+  };
+}
+
+function nested(context: FixtureContext): FixtureContext {
+  return { ...context, depth: context.depth + 1 };
+}
+
+function mockRepeated<T>(context: FixtureContext, value: () => T): T[] {
+  return Array.from({ length: 1 + mockInt(context, 3) }, value);
+}
+
+function mockString(context: FixtureContext, name: string): string {
+  return `${name}-${mockInt(context, 1000)}`;
+}
+
+function mockInt(context: FixtureContext, max: number): number {
+  return Math.floor(context.next() * max);
+}
+
+export function mockNode(overrides: Partial<Node> = {}, options: FixtureOptions | FixtureContext = {}): Node {
+  const context = fixtureContext(options);
+  const message: Record<string, unknown> = {};
+  message["label"] = mockString(context, "label");
+  if (context.depth < context.maxDepth) message["parent"] = mockNode({}, nested(context));
+  message["children"] = context.depth < context.maxDepth ? mockRepeated(context, () => mockNode({}, nested(context))) : [];
+  return { ...message, ...overrides } as unknown as Node;
+}
@@ -0,0 +1,26 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: account.proto
+
+import type { Timestamp } from "./google/protobuf/timestamp_pb";
+import type { StringValue } from "./google/protobuf/wrappers_pb";
+
+export type Account = Readonly<{
+  displayName: string;
+  httpPort: number;
+  tagsList: ReadonlyArray<string>;
+  balance: string;
+  labelsMap: ReadonlyArray<[string, number]>;
+  createdAt?: Timestamp;
+  nickname?: StringValue;
+  status: Status;
+  pb_default: boolean;
+  avatar: string;
+  email?: string;
+  phone?: string;
+}>;
+
+export declare enum Status {
+  STATUS_ACTIVE = 0,
+  STATUS_CLOSED = 1,
+}
@@ -0,0 +1,18 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: event.proto
+
+import type { Event } from "./event_pb";
+import { isContact } from "./oneofs_guards";
+
+export function isEvent(value: unknown): value is Event {
+  if (typeof value !== "object" || value === null) return false;
+  const message = value as Record<string, unknown>;
+  const oneof0 = message["status"] as Record<string, unknown> | null | undefined;
+  return (
+    (message["at"] === undefined || typeof message["at"] === "string") &&
+    (message["note"] === null || typeof message["note"] === "string") &&
+    (Array.isArray(message["contacts"]) && message["contacts"].every((x) => isContact(x))) &&
+    (oneof0 === undefined || (typeof oneof0 === "object" && oneof0 !== null && ((oneof0["$case"] === "code" && typeof oneof0["code"] === "number") || (oneof0["$case"] === "reason" && typeof oneof0["reason"] === "string"))))
+  );
+}
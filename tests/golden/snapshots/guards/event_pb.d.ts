@@ -0,0 +1,12 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: event.proto
+
+import type { Contact } from "./oneofs_pb";
+
+export type Event = Readonly<{
+  at?: string;
+  note: string | null;
+  contacts: ReadonlyArray<Contact>;
+  status?: { readonly $case: "code"; readonly code: number } | { readonly $case: "reason"; readonly reason: string };
+}>;
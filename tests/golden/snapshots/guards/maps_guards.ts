@@ -0,0 +1,22 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: maps.proto
+
+import type { Inventory, Item } from "./maps_pb";
+
+export function isInventory(value: unknown): value is Inventory {
+  if (typeof value !== "object" || value === null) return false;
+  const message = value as Record<string, unknown>;
+  return (
+    (message["labels"] instanceof Map && Array.from(message["labels"].values()).every((x) => typeof x === "string")) &&
+    (message["items"] instanceof Map && Array.from(message["items"].values()).every((x) => isItem(x)))
+  );
+}
+
+export function isItem(value: unknown): value is Item {
+  if (typeof value !== "object" || value === null) return false;
+  const message = value as Record<string, unknown>;
+  return (
+    typeof message["name"] === "string"
+  );
+}
@@ -0,0 +1,23 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: oneofs.proto
+
+import type { Contact, Phone } from "./oneofs_pb";
+
+export function isContact(value: unknown): value is Contact {
+  if (typeof value !== "object" || value === null) return false;
+  const message = value as Record<string, unknown>;
+  const oneof0 = message["channel"] as Record<string, unknown> | null | undefined;
+  return (
+    typeof message["name"] === "string" &&
+    (oneof0 === undefined || (typeof oneof0 === "object" && oneof0 !== null && ((oneof0["$case"] === "email" && typeof oneof0["email"] === "string") || (oneof0["$case"] === "phone" && isPhone(oneof0["phone"])))))
+  );
+}
+
+export function isPhone(value: unknown): value is Phone {
+  if (typeof value !== "object" || value === null) return false;
+  const message = value as Record<string, unknown>;
+  return (
+    typeof message["number"] === "string"
+  );
+}
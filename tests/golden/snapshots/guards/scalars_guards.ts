@@ -0,0 +1,29 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: scalars.proto
+
+import type { Scalars } from "./scalars_pb";
+
+export function isScalars(value: unknown): value is Scalars {
+  if (typeof value !== "object" || value === null) return false;
+  const message = value as Record<string, unknown>;
+  return (
+    typeof message["doubleValue"] === "number" &&
+    typeof message["floatValue"] === "number" &&
+    typeof message["int32Value"] === "number" &&
+    typeof message["int64Value"] === "string" &&
+    typeof message["uint32Value"] === "number" &&
+    typeof message["uint64Value"] === "string" &&
+    typeof message["sint32Value"] === "number" &&
+    typeof message["sint64Value"] === "string" &&
+    typeof message["fixed32Value"] === "number" &&
+    typeof message["fixed64Value"] === "string" &&
+    typeof message["sfixed32Value"] === "number" &&
+    typeof message["sfixed64Value"] === "string" &&
+    typeof message["boolValue"] === "boolean" &&
+    typeof message["stringValue"] === "string" &&
+    typeof message["bytesValue"] === "string" &&
+    (Array.isArray(message["repeatedValue"]) && message["repeatedValue"].every((x) => typeof x === "number")) &&
+    (message["optionalValue"] === undefined || typeof message["optionalValue"] === "string")
+  );
+}
@@ -0,0 +1,24 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: oneofs.proto
+
+import type { Contact, Phone } from "./oneofs_pb";
+
+export function isContact(value: unknown): value is Contact {
+  if (typeof value !== "object" || value === null) return false;
+  const message = value as Record<string, unknown>;
+  return (
+    typeof message["name"] === "string" &&
+    (message["email"] === undefined || typeof message["email"] === "string") &&
+    (message["phone"] === undefined || isPhone(message["phone"])) &&
+    [message["email"], message["phone"]].filter((x) => x !== undefined).length <= 1
+  );
+}
+
+export function isPhone(value: unknown): value is Phone {
+  if (typeof value !== "object" || value === null) return false;
+  const message = value as Record<string, unknown>;
+  return (
+    typeof message["number"] === "string"
+  );
+}
@@ -0,0 +1,48 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: event.proto
+
+import type { Empty, Event } from "./event_pb";
+import { cloneContact, equalsContact } from "./oneofs_helpers";
+
+function arrayEqual<T>(a: ReadonlyArray<T>, b: ReadonlyArray<T>, equal: (x: T, y: T) => boolean = (x, y) => x === y): boolean {
+  return a.length === b.length && a.every((x, i) => equal(x, b[i] as T));
+}
+
+export function equalsEvent(a: Event, b: Event): boolean {
+  if (a === b) return true;
+  if (a["at"] !== b["at"]) return false;
+  if (a["note"] !== b["note"]) return false;
+  if (!arrayEqual(a["contacts"], b["contacts"], (x, y) => equalsContact(x, y))) return false;
+  const oneof0a = a["status"], oneof0b = b["status"];
+  if (oneof0a !== oneof0b) {
+    if (oneof0a == null || oneof0b == null || oneof0a.$case !== oneof0b.$case) return false;
+    switch (oneof0a.$case) {
+      case "sender":
+        if (!equalsContact(oneof0a["sender"], (oneof0b as typeof oneof0a)["sender"])) return false;
+        break;
+      case "reason":
+        if (oneof0a["reason"] !== (oneof0b as typeof oneof0a)["reason"]) return false;
+        break;
+    }
+  }
+  return true;
+}
+
+export function cloneEvent(message: Event): Event {
+  const clone: Record<string, unknown> = { ...message };
+  clone["contacts"] = message["contacts"].map((x) => cloneContact(x));
+  const oneof0 = message["status"];
+  if (oneof0?.$case === "sender") clone["status"] = { $case: "sender", "sender": cloneContact(oneof0["sender"]) };
+  else if (oneof0 != null) clone["status"] = { ...oneof0 };
+  return clone as unknown as Event;
+}
+
+export function equalsEmpty(_a: Empty, _b: Empty): boolean {
+  return true;
+}
+
+export function cloneEmpty(message: Empty): Empty {
+  const clone: Record<string, unknown> = { ...message };
+  return clone as unknown as Empty;
+}
@@ -0,0 +1,38 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: maps.proto
+
+import type { Inventory, Item } from "./maps_pb";
+
+function mapEqual<K, T>(a: ReadonlyMap<K, T>, b: ReadonlyMap<K, T>, equal: (x: T, y: T) => boolean = (x, y) => x === y): boolean {
+  if (a.size !== b.size) return false;
+  for (const [k, x] of a) {
+    if (!b.has(k) || !equal(x, b.get(k) as T)) return false;
+  }
+  return true;
+}
+
+export function equalsInventory(a: Inventory, b: Inventory): boolean {
+  if (a === b) return true;
+  if (!mapEqual(a["labels"], b["labels"])) return false;
+  if (!mapEqual(a["items"], b["items"], (x, y) => equalsItem(x, y))) return false;
+  return true;
+}
+
+export function cloneInventory(message: Inventory): Inventory {
+  const clone: Record<string, unknown> = { ...message };
+  clone["labels"] = new Map(message["labels"]);
+  clone["items"] = new Map(Array.from(message["items"], ([k, x]) => [k, cloneItem(x)] as const));
+  return clone as unknown as Inventory;
+}
+
+export function equalsItem(a: Item, b: Item): boolean {
+  if (a === b) return true;
+  if (a["name"] !== b["name"]) return false;
+  return true;
+}
+
+export function cloneItem(message: Item): Item {
+  const clone: Record<string, unknown> = { ...message };
+  return clone as unknown as Item;
+}
@@ -0,0 +1,42 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: oneofs.proto
+
+import type { Contact, Phone } from "./oneofs_pb";
+
+export function equalsContact(a: Contact, b: Contact): boolean {
+  if (a === b) return true;
+  if (a["name"] !== b["name"]) return false;
+  const oneof0a = a["channel"], oneof0b = b["channel"];
+  if (oneof0a !== oneof0b) {
+    if (oneof0a == null || oneof0b == null || oneof0a.$case !== oneof0b.$case) return false;
+    switch (oneof0a.$case) {
+      case "email":
+        if (oneof0a["email"] !== (oneof0b as typeof oneof0a)["email"]) return false;
+        break;
+      case "phone":
+        if (!equalsPhone(oneof0a["phone"], (oneof0b as typeof oneof0a)["phone"])) return false;
+        break;
+    }
+  }
+  return true;
+}
+
+export function cloneContact(message: Contact): Contact {
+  const clone: Record<string, unknown> = { ...message };
+  const oneof0 = message["channel"];
+  if (oneof0?.$case === "phone") clone["channel"] = { $case: "phone", "phone": clonePhone(oneof0["phone"]) };
+  else if (oneof0 != null) clone["channel"] = { ...oneof0 };
+  return clone as unknown as Contact;
+}
+
+export function equalsPhone(a: Phone, b: Phone): boolean {
+  if (a === b) return true;
+  if (a["number"] !== b["number"]) return false;
+  return true;
+}
+
+export function clonePhone(message: Phone): Phone {
+  const clone: Record<string, unknown> = { ...message };
+  return clone as unknown as Phone;
+}
@@ -0,0 +1,44 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: scalars.proto
+
+import type { Scalars } from "./scalars_pb";
+
+function arrayEqual<T>(a: ReadonlyArray<T>, b: ReadonlyArray<T>, equal: (x: T, y: T) => boolean = (x, y) => x === y): boolean {
+  return a.length === b.length && a.every((x, i) => equal(x, b[i] as T));
+}
+
+function bytesEqual(a: Uint8Array | null | undefined, b: Uint8Array | null | undefined): boolean {
+  if (a === b) return true;
+  if (a == null || b == null || a.length !== b.length) return false;
+  return a.every((x, i) => x === b[i]);
+}
+
+export function equalsScalars(a: Scalars, b: Scalars): boolean {
+  if (a === b) return true;
+  if (a["doubleValue"] !== b["doubleValue"]) return false;
+  if (a["floatValue"] !== b["floatValue"]) return false;
+  if (a["int32Value"] !== b["int32Value"]) return false;
+  if (a["int64Value"] !== b["int64Value"]) return false;
+  if (a["uint32Value"] !== b["uint32Value"]) return false;
+  if (a["uint64Value"] !== b["uint64Value"]) return false;
+  if (a["sint32Value"] !== b["sint32Value"]) return false;
+  if (a["sint64Value"] !== b["sint64Value"]) return false;
+  if (a["fixed32Value"] !== b["fixed32Value"]) return false;
+  if (a["fixed64Value"] !== b["fixed64Value"]) return false;
+  if (a["sfixed32Value"] !== b["sfixed32Value"]) return false;
+  if (a["sfixed64Value"] !== b["sfixed64Value"]) return false;
+  if (a["boolValue"] !== b["boolValue"]) return false;
+  if (a["stringValue"] !== b["stringValue"]) return false;
+  if (!bytesEqual(a["bytesValue"], b["bytesValue"])) return false;
+  if (!arrayEqual(a["repeatedValue"], b["repeatedValue"])) return false;
+  if (a["optionalValue"] !== b["optionalValue"]) return false;
+  return true;
+}
+
+export function cloneScalars(message: Scalars): Scalars {
+  const clone: Record<string, unknown> = { ...message };
+  clone["bytesValue"] = message["bytesValue"].slice();
+  clone["repeatedValue"] = message["repeatedValue"].slice();
+  return clone as unknown as Scalars;
+}
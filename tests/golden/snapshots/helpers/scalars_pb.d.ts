@@ -0,0 +1,23 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: scalars.proto
+
+export type Scalars = Readonly<{
+  doubleValue: number;
+  floatValue: number;
+  int32Value: number;
+  int64Value: bigint;
+  uint32Value: number;
+  uint64Value: bigint;
+  sint32Value: number;
+  sint64Value: bigint;
+  fixed32Value: number;
+  fixed64Value: bigint;
+  sfixed32Value: number;
+  sfixed64Value: bigint;
+  boolValue: boolean;
+  stringValue: string;
+  bytesValue: Uint8Array;
+  repeatedValue: ReadonlyArray<number>;
+  optionalValue?: string;
+}>;
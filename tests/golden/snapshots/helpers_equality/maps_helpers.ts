@@ -0,0 +1,28 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: maps.proto
+
+import type { Inventory, Item } from "./maps_pb";
+
+function recordEqual<T>(
+  a: { readonly [key: string]: T },
+  b: { readonly [key: string]: T },
+  equal: (x: T, y: T) => boolean = (x, y) => x === y
+): boolean {
+  const keys = Object.keys(a);
+  return keys.length === Object.keys(b).length &&
+    keys.every((k) => Object.prototype.hasOwnProperty.call(b, k) && equal(a[k] as T, b[k] as T));
+}
+
+export function equalsInventory(a: Inventory, b: Inventory): boolean {
+  if (a === b) return true;
+  if (!recordEqual(a["labels"], b["labels"])) return false;
+  if (!recordEqual(a["items"], b["items"], (x, y) => equalsItem(x, y))) return false;
+  return true;
+}
+
+export function equalsItem(a: Item, b: Item): boolean {
+  if (a === b) return true;
+  if (a["name"] !== b["name"]) return false;
+  return true;
+}
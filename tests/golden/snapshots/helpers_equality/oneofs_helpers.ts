@@ -0,0 +1,19 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: oneofs.proto
+
+import type { Contact, Phone } from "./oneofs_pb";
+
+export function equalsContact(a: Contact, b: Contact): boolean {
+  if (a === b) return true;
+  if (a["name"] !== b["name"]) return false;
+  if (a["email"] !== b["email"]) return false;
+  if (!(a["phone"] === b["phone"] || (a["phone"] != null && b["phone"] != null && equalsPhone(a["phone"], b["phone"])))) return false;
+  return true;
+}
+
+export function equalsPhone(a: Phone, b: Phone): boolean {
+  if (a === b) return true;
+  if (a["number"] !== b["number"]) return false;
+  return true;
+}
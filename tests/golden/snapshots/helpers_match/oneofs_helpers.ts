@@ -0,0 +1,18 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: oneofs.proto
+
+import type { Contact, Phone } from "./oneofs_pb";
+
+export function matchContactChannel<R>(
+  message: Contact,
+  cases: {
+    readonly email: (value: string) => R;
+    readonly phone: (value: Phone) => R;
+    readonly none: () => R;
+  }
+): R {
+  if (message["email"] !== undefined) return cases["email"](message["email"]);
+  if (message["phone"] !== undefined) return cases["phone"](message["phone"]);
+  return cases.none();
+}
@@ -0,0 +1,23 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: oneofs.proto
+
+import type { Contact, Phone } from "./oneofs_pb";
+
+export function matchContactChannel<R>(
+  message: Contact,
+  cases: {
+    readonly email: (value: string) => R;
+    readonly phone: (value: Phone) => R;
+    readonly none: () => R;
+  }
+): R {
+  const oneof = message["channel"];
+  if (oneof != null) {
+    switch (oneof.$case) {
+      case "email": return cases["email"](oneof["email"]);
+      case "phone": return cases["phone"](oneof["phone"]);
+    }
+  }
+  return cases.none();
+}
@@ -0,0 +1,57 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: common/user.proto
+
+import type { User, User_Address } from "./user_pb.d.ts";
+import { Reader, Writer, encodeNested } from "../runtime.ts";
+
+export function encodeUser(message: User): Uint8Array {
+  const writer = new Writer();
+  if (message["address"] !== undefined) {
+    writer.tag(1, 2);
+    writer.bytes(encodeUser_Address(message["address"]));
+  }
+  return writer.finish();
+}
+
+export function decodeUser(bytes: Uint8Array): User {
+  const reader = new Reader(bytes);
+  const message: Record<string, unknown> = {};
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    switch (tag >>> 3) {
+      case 1:
+        message["address"] = decodeUser_Address(reader.bytes());
+        break;
+      default:
+        reader.skip(tag & 7);
+    }
+  }
+  return message as unknown as User;
+}
+
+export function encodeUser_Address(message: User_Address): Uint8Array {
+  const writer = new Writer();
+  if (message["city"] !== "") {
+    writer.tag(1, 2);
+    writer.string(message["city"]);
+  }
+  return writer.finish();
+}
+
+export function decodeUser_Address(bytes: Uint8Array): User_Address {
+  const reader = new Reader(bytes);
+  const message: Record<string, unknown> = {};
+  message["city"] = "";
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    switch (tag >>> 3) {
+      case 1:
+        message["city"] = reader.string();
+        break;
+      default:
+        reader.skip(tag & 7);
+    }
+  }
+  return message as unknown as User_Address;
+}
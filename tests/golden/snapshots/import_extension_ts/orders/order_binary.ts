@@ -0,0 +1,40 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: orders/order.proto
+
+import type { User, User_Address } from "../common/user_pb.d.ts";
+import type { Order } from "./order_pb.d.ts";
+import { decodeUser, decodeUser_Address, encodeUser, encodeUser_Address } from "../common/user_binary.ts";
+import { Reader, Writer, encodeNested } from "../runtime.ts";
+
+export function encodeOrder(message: Order): Uint8Array {
+  const writer = new Writer();
+  if (message["buyer"] !== undefined) {
+    writer.tag(1, 2);
+    writer.bytes(encodeUser(message["buyer"]));
+  }
+  if (message["shipping"] !== undefined) {
+    writer.tag(2, 2);
+    writer.bytes(encodeUser_Address(message["shipping"]));
+  }
+  return writer.finish();
+}
+
+export function decodeOrder(bytes: Uint8Array): Order {
+  const reader = new Reader(bytes);
+  const message: Record<string, unknown> = {};
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    switch (tag >>> 3) {
+      case 1:
+        message["buyer"] = decodeUser(reader.bytes());
+        break;
+      case 2:
+        message["shipping"] = decodeUser_Address(reader.bytes());
+        break;
+      default:
+        reader.skip(tag & 7);
+    }
+  }
+  return message as unknown as Order;
+}
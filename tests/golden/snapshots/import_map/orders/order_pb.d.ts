@@ -0,0 +1,12 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: orders/order.proto
+
+import type { User, User_Address } from "@acme/common";
+import type { Timestamp } from "@bufbuild/protobuf/wkt";
+
+export type Order = Readonly<{
+  buyer?: User;
+  shipping?: User_Address;
+  placedAt?: Timestamp;
+}>;
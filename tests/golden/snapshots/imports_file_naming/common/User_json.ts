@@ -0,0 +1,37 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: common/user.proto
+
+import type { User, User_Address } from "./User.pb";
+
+function pick(json: Record<string, unknown>, jsonName: string, protoName: string): unknown {
+  return json[jsonName] ?? json[protoName];
+}
+
+export function UserToJSON(message: User): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["address"] !== undefined) json["address"] = User_AddressToJSON(message["address"]);
+  return json;
+}
+
+export function UserFromJSON(value: unknown): User {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v1 = pick(json, "address", "address");
+  if (v1 != null) message["address"] = User_AddressFromJSON(v1);
+  return message as unknown as User;
+}
+
+export function User_AddressToJSON(message: User_Address): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["city"] !== "") json["city"] = message["city"];
+  return json;
+}
+
+export function User_AddressFromJSON(value: unknown): User_Address {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v1 = pick(json, "city", "city");
+  message["city"] = v1 == null ? "" : String(v1);
+  return message as unknown as User_Address;
+}
@@ -0,0 +1,10 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: orders/order.proto
+
+import type { User, User_Address } from "../common/User.pb";
+
+export type Order = Readonly<{
+  buyer?: User;
+  shipping?: User_Address;
+}>;
@@ -0,0 +1,11 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: common/user.proto
+
+export type User = Readonly<{
+  address?: User_Address;
+}>;
+
+export type User_Address = Readonly<{
+  city: string;
+}>;
@@ -0,0 +1,5 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+
+export type { User, User_Address } from "./common/user_pb";
+export type { Order } from "./orders/order_pb";
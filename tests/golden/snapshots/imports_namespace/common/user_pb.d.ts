@@ -0,0 +1,13 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: common/user.proto
+
+export type User = Readonly<{
+  address?: User.Address;
+}>;
+
+export declare namespace User {
+  export type Address = Readonly<{
+    city: string;
+  }>;
+}
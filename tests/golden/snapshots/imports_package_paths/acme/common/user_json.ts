@@ -0,0 +1,23 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: proto/user.proto
+
+import type { User } from "./user_pb";
+
+function pick(json: Record<string, unknown>, jsonName: string, protoName: string): unknown {
+  return json[jsonName] ?? json[protoName];
+}
+
+export function UserToJSON(message: User): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["name"] !== "") json["name"] = message["name"];
+  return json;
+}
+
+export function UserFromJSON(value: unknown): User {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v1 = pick(json, "name", "name");
+  message["name"] = v1 == null ? "" : String(v1);
+  return message as unknown as User;
+}
@@ -0,0 +1,7 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: proto/user.proto
+
+export type User = Readonly<{
+  name: string;
+}>;
@@ -0,0 +1,9 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: order.proto
+
+import type { User } from "../../common/user_pb";
+
+export type Order = Readonly<{
+  buyer?: User;
+}>;
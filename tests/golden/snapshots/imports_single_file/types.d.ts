@@ -0,0 +1,17 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: common/user.proto
+// source: orders/order.proto
+
+export type User = Readonly<{
+  address?: User_Address;
+}>;
+
+export type User_Address = Readonly<{
+  city: string;
+}>;
+
+export type Order = Readonly<{
+  buyer?: User;
+  shipping?: User_Address;
+}>;
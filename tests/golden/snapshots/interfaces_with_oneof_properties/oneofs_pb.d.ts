@@ -0,0 +1,20 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: oneofs.proto
+
+export type Contact = Readonly<{
+  name: string;
+}> & Readonly<
+    {
+      email?: string;
+      phone?: never;
+    } |
+    {
+      email?: never;
+      phone?: Phone;
+    }
+  >;
+
+export interface Phone {
+  readonly number: string;
+}
@@ -0,0 +1,12 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: maps.proto
+
+export type Inventory = Readonly<{
+  labels: ReadonlyMap<string, string>;
+  items: ReadonlyMap<number, Item>;
+}>;
+
+export type Item = Readonly<{
+  name: string;
+}>;
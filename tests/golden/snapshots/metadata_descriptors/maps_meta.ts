@@ -0,0 +1,27 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: maps.proto
+
+export const InventoryFields = {
+  labels: 1,
+  items: 2,
+} as const;
+
+export const InventoryDescriptor = {
+  typeName: "maps.Inventory",
+  fields: [
+    { name: "labels", localName: "labels", jsonName: "labels", number: 1, type: "map", mapKey: "string", mapValue: "string" },
+    { name: "items", localName: "items", jsonName: "items", number: 2, type: "map", mapKey: "int32", mapValue: "message", typeName: "maps.Item" },
+  ],
+} as const;
+
+export const ItemFields = {
+  name: 1,
+} as const;
+
+export const ItemDescriptor = {
+  typeName: "maps.Item",
+  fields: [
+    { name: "name", localName: "name", jsonName: "name", number: 1, type: "string", repeated: false },
+  ],
+} as const;
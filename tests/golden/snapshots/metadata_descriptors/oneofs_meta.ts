@@ -0,0 +1,29 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: oneofs.proto
+
+export const ContactFields = {
+  name: 1,
+  email: 2,
+  phone: 3,
+} as const;
+
+export const ContactDescriptor = {
+  typeName: "oneofs.Contact",
+  fields: [
+    { name: "name", localName: "name", jsonName: "name", number: 1, type: "string", repeated: false },
+    { name: "email", localName: "email", jsonName: "email", number: 2, type: "string", repeated: false, oneof: "channel" },
+    { name: "phone", localName: "phone", jsonName: "phone", number: 3, type: "message", typeName: "oneofs.Phone", repeated: false, oneof: "channel" },
+  ],
+} as const;
+
+export const PhoneFields = {
+  number: 1,
+} as const;
+
+export const PhoneDescriptor = {
+  typeName: "oneofs.Phone",
+  fields: [
+    { name: "number", localName: "number", jsonName: "number", number: 1, type: "string", repeated: false },
+  ],
+} as const;
@@ -0,0 +1,12 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+
+export type Mutable<T> = T extends Uint8Array | Date
+  ? T
+  : T extends ReadonlyArray<infer U>
+    ? Array<Mutable<U>>
+    : T extends ReadonlyMap<infer K, infer V>
+      ? Map<K, Mutable<V>>
+      : T extends object
+        ? { -readonly [P in keyof T]: Mutable<T[P]> }
+        : T;
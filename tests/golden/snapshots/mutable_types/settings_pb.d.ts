@@ -0,0 +1,15 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: settings.proto
+
+import type { Mutable } from "./Mutable";
+
+export type Settings = Readonly<{
+  tags: ReadonlyArray<string>;
+}>;
+export type MutableSettings = Mutable<Settings>;
+
+export type default_ = Readonly<{
+  name: string;
+}>;
+export type MutableDefault = Mutable<default_>;
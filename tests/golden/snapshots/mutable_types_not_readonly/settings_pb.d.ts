@@ -0,0 +1,11 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: settings.proto
+
+export type Settings = {
+  tags: Array<string>;
+};
+
+export type default_ = {
+  name: string;
+};
@@ -0,0 +1,35 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: billing.proto
+// source: common.proto
+// source: legacy.proto
+
+export {};
+
+declare global {
+  export type Legacy = Readonly<{
+    invoice?: acme.billing.v1.Invoice;
+  }>;
+
+  namespace acme.billing.v1 {
+    export type Invoice = Readonly<{
+      payer?: acme.common.User;
+      status: acme.billing.v1.Invoice_Status;
+    }>;
+
+    export enum Invoice_Status {
+      STATUS_UNSPECIFIED = 0,
+      STATUS_PAID = 1,
+    }
+
+    export type User = Readonly<{
+      account?: acme.common.User;
+    }>;
+  }
+
+  namespace acme.common {
+    export type User = Readonly<{
+      name: string;
+    }>;
+  }
+}
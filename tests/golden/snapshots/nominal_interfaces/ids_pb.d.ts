@@ -0,0 +1,17 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: ids.proto
+
+export interface UserId {
+  readonly __typename?: "acme.UserId";
+  readonly value: string;
+}
+
+export interface AccountId {
+  readonly __typename?: "acme.AccountId";
+  readonly value: string;
+}
+
+export interface Marker {
+  readonly __typename?: "acme.Marker";
+}
@@ -0,0 +1,14 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: ids.proto
+
+export type UserId = Readonly<{
+  __typename?: "acme.UserId";
+  value: string;
+}>;
+
+export type AccountId = Readonly<{
+  value: string;
+}>;
+
+export type Marker = Record<string, never>;
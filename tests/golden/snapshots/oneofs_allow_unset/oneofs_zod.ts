@@ -0,0 +1,30 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: oneofs.proto
+
+import { z } from "zod";
+
+export const ContactSchema = z.object({
+  name: z.string(),
+}).readonly().and(z.union([
+  z.object({
+    email: z.string(),
+    phone: z.never().optional(),
+  }).readonly(),
+  z.object({
+    email: z.never().optional(),
+    phone: z.lazy(() => PhoneSchema),
+  }).readonly(),
+  z.object({
+    email: z.never().optional(),
+    phone: z.never().optional(),
+  }).readonly(),
+]));
+
+export type Contact = z.infer<typeof ContactSchema>;
+
+export const PhoneSchema = z.object({
+  number: z.string(),
+}).readonly();
+
+export type Phone = z.infer<typeof PhoneSchema>;
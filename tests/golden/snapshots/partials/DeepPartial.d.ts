@@ -0,0 +1,12 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+
+export type DeepPartial<T> = T extends Uint8Array | Date
+  ? T
+  : T extends ReadonlyArray<infer U>
+    ? ReadonlyArray<DeepPartial<U>>
+    : T extends ReadonlyMap<infer K, infer V>
+      ? ReadonlyMap<K, DeepPartial<V>>
+      : T extends object
+        ? { readonly [P in keyof T]?: DeepPartial<T[P]> }
+        : T;
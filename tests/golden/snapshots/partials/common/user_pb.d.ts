@@ -0,0 +1,15 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: common/user.proto
+
+import type { DeepPartial } from "../DeepPartial";
+
+export type User = Readonly<{
+  address?: User_Address;
+}>;
+export type UserPatch = DeepPartial<User>;
+
+export type User_Address = Readonly<{
+  city: string;
+}>;
+export type User_AddressPatch = DeepPartial<User_Address>;
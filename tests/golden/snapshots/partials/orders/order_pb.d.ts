@@ -0,0 +1,12 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: orders/order.proto
+
+import type { DeepPartial } from "../DeepPartial";
+import type { User, User_Address } from "../common/user_pb";
+
+export type Order = Readonly<{
+  buyer?: User;
+  shipping?: User_Address;
+}>;
+export type OrderPatch = DeepPartial<Order>;
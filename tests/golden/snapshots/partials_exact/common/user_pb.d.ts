@@ -0,0 +1,17 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: common/user.proto
+
+import type { DeepPartial, Exact } from "../DeepPartial";
+
+export type User = {
+  address?: User_Address;
+};
+export type UserPatch = DeepPartial<User>;
+export type ExactUserPatch<I extends UserPatch> = Exact<UserPatch, I>;
+
+export type User_Address = {
+  city: string;
+};
+export type User_AddressPatch = DeepPartial<User_Address>;
+export type ExactUser_AddressPatch<I extends User_AddressPatch> = Exact<User_AddressPatch, I>;
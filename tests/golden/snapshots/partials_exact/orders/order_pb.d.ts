@@ -0,0 +1,13 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: orders/order.proto
+
+import type { DeepPartial, Exact } from "../DeepPartial";
+import type { User, User_Address } from "../common/user_pb";
+
+export type Order = {
+  buyer?: User;
+  shipping?: User_Address;
+};
+export type OrderPatch = DeepPartial<Order>;
+export type ExactOrderPatch<I extends OrderPatch> = Exact<OrderPatch, I>;
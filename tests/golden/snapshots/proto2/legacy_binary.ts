@@ -0,0 +1,405 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: legacy.proto
+
+import type { Color, Page, Query, Query_Hit, Query_Result } from "./legacy_pb";
+
+class Writer {
+  private readonly buf: number[] = [];
+
+  tag(fieldNumber: number, wireType: number): void {
+    this.uint32(fieldNumber * 8 + wireType);
+  }
+
+  uint32(value: number): void {
+    value >>>= 0;
+    while (value > 0x7f) {
+      this.buf.push((value & 0x7f) | 0x80);
+      value >>>= 7;
+    }
+    this.buf.push(value);
+  }
+
+  int32(value: number): void {
+    if (value < 0) {
+      this.uint64(BigInt(value));
+    } else {
+      this.uint32(value);
+    }
+  }
+
+  sint32(value: number): void {
+    this.uint32((value << 1) ^ (value >> 31));
+  }
+
+  uint64(value: bigint): void {
+    value = BigInt.asUintN(64, value);
+    while (value > BigInt(0x7f)) {
+      this.buf.push(Number(value & BigInt(0x7f)) | 0x80);
+      value >>= BigInt(7);
+    }
+    this.buf.push(Number(value));
+  }
+
+  int64(value: bigint): void {
+    this.uint64(value);
+  }
+
+  sint64(value: bigint): void {
+    this.uint64((value << BigInt(1)) ^ (value >> BigInt(63)));
+  }
+
+  bool(value: boolean): void {
+    this.buf.push(value ? 1 : 0);
+  }
+
+  fixed32(value: number): void {
+    this.fixed(4, (view) => view.setUint32(0, value, true));
+  }
+
+  sfixed32(value: number): void {
+    this.fixed(4, (view) => view.setInt32(0, value, true));
+  }
+
+  float(value: number): void {
+    this.fixed(4, (view) => view.setFloat32(0, value, true));
+  }
+
+  fixed64(value: bigint): void {
+    this.fixed(8, (view) => view.setBigUint64(0, BigInt.asUintN(64, value), true));
+  }
+
+  sfixed64(value: bigint): void {
+    this.fixed(8, (view) => view.setBigInt64(0, BigInt.asIntN(64, value), true));
+  }
+
+  double(value: number): void {
+    this.fixed(8, (view) => view.setFloat64(0, value, true));
+  }
+
+  string(value: string): void {
+    this.bytes(new TextEncoder().encode(value));
+  }
+
+  bytes(value: Uint8Array): void {
+    this.uint32(value.length);
+    this.raw(value);
+  }
+
+  raw(value: Uint8Array): void {
+    value.forEach((byte) => this.buf.push(byte));
+  }
+
+  finish(): Uint8Array {
+    return Uint8Array.from(this.buf);
+  }
+
+  private fixed(size: number, write: (view: DataView) => void): void {
+    const view = new DataView(new ArrayBuffer(size));
+    write(view);
+    new Uint8Array(view.buffer).forEach((byte) => this.buf.push(byte));
+  }
+}
+
+class Reader {
+  pos = 0;
+  readonly len: number;
+  private readonly view: DataView;
+
+  constructor(private readonly buf: Uint8Array) {
+    this.len = buf.length;
+    this.view = new DataView(buf.buffer, buf.byteOffset, buf.byteLength);
+  }
+
+  uint32(): number {
+    let value = 0;
+    for (let shift = 0; ; shift += 7) {
+      const byte = this.next();
+      if (shift < 32) value |= (byte & 0x7f) << shift;
+      if ((byte & 0x80) === 0) return value >>> 0;
+    }
+  }
+
+  int32(): number {
+    return this.uint32() | 0;
+  }
+
+  sint32(): number {
+    const value = this.uint32();
+    return (value >>> 1) ^ -(value & 1);
+  }
+
+  uint64(): bigint {
+    let value = BigInt(0);
+    for (let shift = BigInt(0); ; shift += BigInt(7)) {
+      const byte = this.next();
+      value |= BigInt(byte & 0x7f) << shift;
+      if ((byte & 0x80) === 0) return BigInt.asUintN(64, value);
+    }
+  }
+
+  int64(): bigint {
+    return BigInt.asIntN(64, this.uint64());
+  }
+
+  sint64(): bigint {
+    const value = this.uint64();
+    return (value >> BigInt(1)) ^ -(value & BigInt(1));
+  }
+
+  bool(): boolean {
+    return this.uint64() !== BigInt(0);
+  }
+
+  fixed32(): number {
+    return this.view.getUint32(this.advance(4), true);
+  }
+
+  sfixed32(): number {
+    return this.view.getInt32(this.advance(4), true);
+  }
+
+  float(): number {
+    return this.view.getFloat32(this.advance(4), true);
+  }
+
+  fixed64(): bigint {
+    return this.view.getBigUint64(this.advance(8), true);
+  }
+
+  sfixed64(): bigint {
+    return this.view.getBigInt64(this.advance(8), true);
+  }
+
+  double(): number {
+    return this.view.getFloat64(this.advance(8), true);
+  }
+
+  string(): string {
+    return new TextDecoder().decode(this.bytes());
+  }
+
+  bytes(): Uint8Array {
+    const length = this.uint32();
+    const start = this.advance(length);
+    return this.buf.subarray(start, start + length);
+  }
+
+  group(fieldNumber: number): Uint8Array {
+    const start = this.pos;
+    let end = start;
+    for (let tag = this.uint32(); tag !== fieldNumber * 8 + 4; tag = this.uint32()) {
+      this.skip(tag & 7);
+      end = this.pos;
+    }
+    return this.buf.subarray(start, end);
+  }
+
+  skip(wireType: number): void {
+    switch (wireType) {
+      case 0:
+        this.uint64();
+        break;
+      case 1:
+        this.advance(8);
+        break;
+      case 2:
+        this.advance(this.uint32());
+        break;
+      case 3:
+        for (let tag = this.uint32(); (tag & 7) !== 4; tag = this.uint32()) {
+          this.skip(tag & 7);
+        }
+        break;
+      case 5:
+        this.advance(4);
+        break;
+      default:
+        throw new Error(`invalid wire type ${wireType}`);
+    }
+  }
+
+  private next(): number {
+    return this.buf[this.advance(1)];
+  }
+
+  private advance(size: number): number {
+    const start = this.pos;
+    if (start + size > this.len) throw new RangeError("premature end of input");
+    this.pos += size;
+    return start;
+  }
+}
+
+function encodeNested(write: (writer: Writer) => void): Uint8Array {
+  const writer = new Writer();
+  write(writer);
+  return writer.finish();
+}
+
+export function ColorToNumber(value: Color): number {
+  switch (value) {
+    case "RED":
+      return 1;
+    case "GREEN":
+      return 2;
+    default:
+      return 0;
+  }
+}
+
+export function ColorFromNumber(value: number): Color {
+  switch (value) {
+    case 1:
+      return "RED";
+    case 2:
+      return "GREEN";
+    default:
+      return "RED";
+  }
+}
+
+export function encodeQuery(message: Query): Uint8Array {
+  const writer = new Writer();
+  if (message["text"] !== undefined) {
+    writer.tag(1, 2);
+    writer.string(message["text"]);
+  }
+  if (message["limit"] !== undefined) {
+    writer.tag(2, 0);
+    writer.int32(message["limit"]);
+  }
+  if (message["prefix"] !== undefined) {
+    writer.tag(3, 2);
+    writer.string(message["prefix"]);
+  }
+  if (message["color"] !== undefined) {
+    writer.tag(4, 0);
+    writer.int32(ColorToNumber(message["color"]));
+  }
+  if (message["page"] !== undefined) {
+    writer.tag(5, 2);
+    writer.bytes(encodePage(message["page"]));
+  }
+  if (message["result"] !== undefined) {
+    writer.tag(6, 3);
+    writer.raw(encodeQuery_Result(message["result"])); writer.tag(6, 4);
+  }
+  for (const x of message["hit"]) {
+    writer.tag(7, 3);
+    writer.raw(encodeQuery_Hit(x)); writer.tag(7, 4);
+  }
+  return writer.finish();
+}
+
+export function decodeQuery(bytes: Uint8Array): Query {
+  const reader = new Reader(bytes);
+  const message: Record<string, unknown> = {};
+  message["text"] = "";
+  message["hit"] = [];
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    switch (tag >>> 3) {
+      case 1:
+        message["text"] = reader.string();
+        break;
+      case 2:
+        message["limit"] = reader.int32();
+        break;
+      case 3:
+        message["prefix"] = reader.string();
+        break;
+      case 4:
+        message["color"] = ColorFromNumber(reader.int32());
+        break;
+      case 5:
+        message["page"] = decodePage(reader.bytes());
+        break;
+      case 6:
+        message["result"] = decodeQuery_Result(reader.group(6));
+        break;
+      case 7:
+        (message["hit"] as unknown[]).push(decodeQuery_Hit(reader.group(7)));
+        break;
+      default:
+        reader.skip(tag & 7);
+    }
+  }
+  return message as unknown as Query;
+}
+
+export function encodeQuery_Result(message: Query_Result): Uint8Array {
+  const writer = new Writer();
+  if (message["url"] !== undefined) {
+    writer.tag(8, 2);
+    writer.string(message["url"]);
+  }
+  return writer.finish();
+}
+
+export function decodeQuery_Result(bytes: Uint8Array): Query_Result {
+  const reader = new Reader(bytes);
+  const message: Record<string, unknown> = {};
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    switch (tag >>> 3) {
+      case 8:
+        message["url"] = reader.string();
+        break;
+      default:
+        reader.skip(tag & 7);
+    }
+  }
+  return message as unknown as Query_Result;
+}
+
+export function encodeQuery_Hit(message: Query_Hit): Uint8Array {
+  const writer = new Writer();
+  if (message["score"] !== undefined) {
+    writer.tag(9, 1);
+    writer.double(message["score"]);
+  }
+  return writer.finish();
+}
+
+export function decodeQuery_Hit(bytes: Uint8Array): Query_Hit {
+  const reader = new Reader(bytes);
+  const message: Record<string, unknown> = {};
+  message["score"] = 0;
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    switch (tag >>> 3) {
+      case 9:
+        message["score"] = reader.double();
+        break;
+      default:
+        reader.skip(tag & 7);
+    }
+  }
+  return message as unknown as Query_Hit;
+}
+
+export function encodePage(message: Page): Uint8Array {
+  const writer = new Writer();
+  if (message["number"] !== undefined) {
+    writer.tag(1, 0);
+    writer.uint32(message["number"]);
+  }
+  return writer.finish();
+}
+
+export function decodePage(bytes: Uint8Array): Page {
+  const reader = new Reader(bytes);
+  const message: Record<string, unknown> = {};
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    switch (tag >>> 3) {
+      case 1:
+        message["number"] = reader.uint32();
+        break;
+      default:
+        reader.skip(tag & 7);
+    }
+  }
+  return message as unknown as Page;
+}
@@ -0,0 +1,100 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: legacy.proto
+
+import type { Color, Page, Query, Query_Hit, Query_Result } from "./legacy_pb";
+
+function pick(json: Record<string, unknown>, jsonName: string, protoName: string): unknown {
+  return json[jsonName] ?? json[protoName];
+}
+
+export function ColorToJSON(value: Color): string {
+  return value;
+}
+
+export function ColorFromJSON(value: unknown): Color {
+  switch (value) {
+    case "RED":
+    case 1:
+      return "RED";
+    case "GREEN":
+    case 2:
+      return "GREEN";
+    default:
+      return "RED";
+  }
+}
+
+export function QueryToJSON(message: Query): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["text"] !== undefined) json["text"] = message["text"];
+  if (message["limit"] !== undefined) json["limit"] = message["limit"];
+  if (message["prefix"] !== undefined) json["prefix"] = message["prefix"];
+  if (message["color"] !== undefined) json["color"] = ColorToJSON(message["color"]);
+  if (message["page"] !== undefined) json["page"] = PageToJSON(message["page"]);
+  if (message["result"] !== undefined) json["result"] = Query_ResultToJSON(message["result"]);
+  if (message["hit"].length > 0) json["hit"] = message["hit"].map((x) => Query_HitToJSON(x));
+  return json;
+}
+
+export function QueryFromJSON(value: unknown): Query {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v1 = pick(json, "text", "text");
+  message["text"] = v1 == null ? "" : String(v1);
+  const v2 = pick(json, "limit", "limit");
+  if (v2 != null) message["limit"] = Number(v2);
+  const v3 = pick(json, "prefix", "prefix");
+  if (v3 != null) message["prefix"] = String(v3);
+  const v4 = pick(json, "color", "color");
+  if (v4 != null) message["color"] = ColorFromJSON(v4);
+  const v5 = pick(json, "page", "page");
+  if (v5 != null) message["page"] = PageFromJSON(v5);
+  const v6 = pick(json, "result", "result");
+  if (v6 != null) message["result"] = Query_ResultFromJSON(v6);
+  const v7 = pick(json, "hit", "hit");
+  message["hit"] = v7 == null ? [] : Array.isArray(v7) ? v7.map((x: unknown) => Query_HitFromJSON(x)) : [];
+  return message as unknown as Query;
+}
+
+export function Query_ResultToJSON(message: Query_Result): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["url"] !== undefined) json["url"] = message["url"];
+  return json;
+}
+
+export function Query_ResultFromJSON(value: unknown): Query_Result {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v8 = pick(json, "url", "url");
+  if (v8 != null) message["url"] = String(v8);
+  return message as unknown as Query_Result;
+}
+
+export function Query_HitToJSON(message: Query_Hit): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["score"] !== undefined) json["score"] = message["score"];
+  return json;
+}
+
+export function Query_HitFromJSON(value: unknown): Query_Hit {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v9 = pick(json, "score", "score");
+  message["score"] = v9 == null ? 0 : Number(v9);
+  return message as unknown as Query_Hit;
+}
+
+export function PageToJSON(message: Page): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["number"] !== undefined) json["number"] = message["number"];
+  return json;
+}
+
+export function PageFromJSON(value: unknown): Page {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v1 = pick(json, "number", "number");
+  if (v1 != null) message["number"] = Number(v1);
+  return message as unknown as Page;
+}
@@ -0,0 +1,30 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: legacy.proto
+
+export type Query = Readonly<{
+  text: string;
+  /** @defaultValue 10 */
+  limit?: number;
+  /** @defaultValue "q:" */
+  prefix?: string;
+  /** @defaultValue RED */
+  color?: Color;
+  page: Page;
+  result?: Query_Result;
+  hit: ReadonlyArray<Query_Hit>;
+}>;
+
+export type Query_Result = Readonly<{
+  url?: string;
+}>;
+
+export type Query_Hit = Readonly<{
+  score: number;
+}>;
+
+export type Page = Readonly<{
+  number?: number;
+}>;
+
+export type Color = "RED" | "GREEN";
@@ -0,0 +1,10 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: fs/entry.proto
+
+import type { Folder, Kind } from "./folder_pb";
+
+export type Entry = Readonly<{
+  kind: Kind;
+  folder?: Folder;
+}>;
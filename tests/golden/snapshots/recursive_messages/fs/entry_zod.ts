@@ -0,0 +1,13 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: fs/entry.proto
+
+import { z } from "zod";
+import { FolderSchema, KindSchema } from "./folder_zod";
+
+export const EntrySchema: z.ZodType<import("./entry_pb").Entry> = z.object({
+  kind: z.lazy(() => KindSchema),
+  folder: z.lazy(() => FolderSchema).optional(),
+}).readonly();
+
+export type Entry = z.infer<typeof EntrySchema>;
@@ -0,0 +1,12 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: fs/folder.proto
+
+import type { Entry } from "./entry_pb";
+
+export type Folder = Readonly<{
+  name: string;
+  entries: ReadonlyArray<Entry>;
+}>;
+
+export type Kind = "KIND_FILE" | "KIND_LINK";
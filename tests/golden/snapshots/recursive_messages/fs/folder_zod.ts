@@ -0,0 +1,17 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: fs/folder.proto
+
+import { z } from "zod";
+import { EntrySchema } from "./entry_zod";
+
+export const KindSchema = z.enum(["KIND_FILE", "KIND_LINK"]);
+
+export type Kind = z.infer<typeof KindSchema>;
+
+export const FolderSchema: z.ZodType<import("./folder_pb").Folder> = z.object({
+  name: z.string(),
+  entries: z.array(z.lazy(() => EntrySchema)).readonly(),
+}).readonly();
+
+export type Folder = z.infer<typeof FolderSchema>;
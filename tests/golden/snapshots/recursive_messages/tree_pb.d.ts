@@ -0,0 +1,8 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: tree.proto
+
+export type Node = Readonly<{
+  label: string;
+  children: ReadonlyArray<Node>;
+}>;
@@ -0,0 +1,12 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: tree.proto
+
+import { z } from "zod";
+
+export const NodeSchema: z.ZodType<import("./tree_pb").Node> = z.object({
+  label: z.string(),
+  children: z.array(z.lazy(() => NodeSchema)).readonly(),
+}).readonly();
+
+export type Node = z.infer<typeof NodeSchema>;
@@ -0,0 +1,40 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: maps.proto
+
+import type { Inventory, Item } from "./maps_pb";
+
+function pick(json: Record<string, unknown>, jsonName: string, protoName: string): unknown {
+  return json[jsonName] ?? json[protoName];
+}
+
+export function InventoryToJSON(message: Inventory): unknown {
+  const json: Record<string, unknown> = {};
+  if (Object.keys(message["labels"]).length > 0) json["labels"] = message["labels"];
+  if (Object.keys(message["items"]).length > 0) json["items"] = Object.fromEntries(Object.entries(message["items"]).map(([k, x]) => [k, ItemToJSON(x)]));
+  return json;
+}
+
+export function InventoryFromJSON(value: unknown): Inventory {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v1 = pick(json, "labels", "labels");
+  message["labels"] = v1 == null ? {} : Object.fromEntries(Object.entries(v1 as Record<string, unknown>).map(([k, x]) => [k, String(x)] as const));
+  const v2 = pick(json, "items", "items");
+  message["items"] = v2 == null ? {} : Object.fromEntries(Object.entries(v2 as Record<string, unknown>).map(([k, x]) => [k, ItemFromJSON(x)] as const));
+  return message as unknown as Inventory;
+}
+
+export function ItemToJSON(message: Item): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["name"] !== "") json["name"] = message["name"];
+  return json;
+}
+
+export function ItemFromJSON(value: unknown): Item {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v1 = pick(json, "name", "name");
+  message["name"] = v1 == null ? "" : String(v1);
+  return message as unknown as Item;
+}
@@ -0,0 +1,43 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: oneofs.proto
+
+import type { Contact, Phone } from "./oneofs_pb";
+
+function pick(json: Record<string, unknown>, jsonName: string, protoName: string): unknown {
+  return json[jsonName] ?? json[protoName];
+}
+
+export function ContactToJSON(message: Contact): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["name"] !== "") json["name"] = message["name"];
+  if (message["email"] !== undefined) json["email"] = message["email"];
+  if (message["phone"] !== undefined) json["phone"] = PhoneToJSON(message["phone"]);
+  return json;
+}
+
+export function ContactFromJSON(value: unknown): Contact {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v1 = pick(json, "name", "name");
+  message["name"] = v1 == null ? "" : String(v1);
+  const v2 = pick(json, "email", "email");
+  if (v2 != null) message["email"] = String(v2);
+  const v3 = pick(json, "phone", "phone");
+  if (v3 != null) message["phone"] = PhoneFromJSON(v3);
+  return message as unknown as Contact;
+}
+
+export function PhoneToJSON(message: Phone): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["number"] !== "") json["number"] = message["number"];
+  return json;
+}
+
+export function PhoneFromJSON(value: unknown): Phone {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v1 = pick(json, "number", "number");
+  message["number"] = v1 == null ? "" : String(v1);
+  return message as unknown as Phone;
+}
@@ -0,0 +1,71 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: scalars.proto
+
+import type { Scalars } from "./scalars_pb";
+
+function pick(json: Record<string, unknown>, jsonName: string, protoName: string): unknown {
+  return json[jsonName] ?? json[protoName];
+}
+
+export function ScalarsToJSON(message: Scalars): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["doubleValue"] !== 0) json["doubleValue"] = message["doubleValue"];
+  if (message["floatValue"] !== 0) json["floatValue"] = message["floatValue"];
+  if (message["int32Value"] !== 0) json["int32Value"] = message["int32Value"];
+  if (message["int64Value"] !== "0") json["int64Value"] = message["int64Value"];
+  if (message["uint32Value"] !== 0) json["uint32Value"] = message["uint32Value"];
+  if (message["uint64Value"] !== "0") json["uint64Value"] = message["uint64Value"];
+  if (message["sint32Value"] !== 0) json["sint32Value"] = message["sint32Value"];
+  if (message["sint64Value"] !== "0") json["sint64Value"] = message["sint64Value"];
+  if (message["fixed32Value"] !== 0) json["fixed32Value"] = message["fixed32Value"];
+  if (message["fixed64Value"] !== "0") json["fixed64Value"] = message["fixed64Value"];
+  if (message["sfixed32Value"] !== 0) json["sfixed32Value"] = message["sfixed32Value"];
+  if (message["sfixed64Value"] !== "0") json["sfixed64Value"] = message["sfixed64Value"];
+  if (message["boolValue"] !== false) json["boolValue"] = message["boolValue"];
+  if (message["stringValue"] !== "") json["stringValue"] = message["stringValue"];
+  if (message["bytesValue"] !== "") json["bytesValue"] = message["bytesValue"];
+  if (message["repeatedValue"].length > 0) json["repeatedValue"] = message["repeatedValue"];
+  if (message["optionalValue"] !== undefined) json["optionalValue"] = message["optionalValue"];
+  return json;
+}
+
+export function ScalarsFromJSON(value: unknown): Scalars {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v1 = pick(json, "doubleValue", "double_value");
+  message["doubleValue"] = v1 == null ? 0 : Number(v1);
+  const v2 = pick(json, "floatValue", "float_value");
+  message["floatValue"] = v2 == null ? 0 : Number(v2);
+  const v3 = pick(json, "int32Value", "int32_value");
+  message["int32Value"] = v3 == null ? 0 : Number(v3);
+  const v4 = pick(json, "int64Value", "int64_value");
+  message["int64Value"] = v4 == null ? "0" : String(v4);
+  const v5 = pick(json, "uint32Value", "uint32_value");
+  message["uint32Value"] = v5 == null ? 0 : Number(v5);
+  const v6 = pick(json, "uint64Value", "uint64_value");
+  message["uint64Value"] = v6 == null ? "0" : String(v6);
+  const v7 = pick(json, "sint32Value", "sint32_value");
+  message["sint32Value"] = v7 == null ? 0 : Number(v7);
+  const v8 = pick(json, "sint64Value", "sint64_value");
+  message["sint64Value"] = v8 == null ? "0" : String(v8);
+  const v9 = pick(json, "fixed32Value", "fixed32_value");
+  message["fixed32Value"] = v9 == null ? 0 : Number(v9);
+  const v10 = pick(json, "fixed64Value", "fixed64_value");
+  message["fixed64Value"] = v10 == null ? "0" : String(v10);
+  const v11 = pick(json, "sfixed32Value", "sfixed32_value");
+  message["sfixed32Value"] = v11 == null ? 0 : Number(v11);
+  const v12 = pick(json, "sfixed64Value", "sfixed64_value");
+  message["sfixed64Value"] = v12 == null ? "0" : String(v12);
+  const v13 = pick(json, "boolValue", "bool_value");
+  message["boolValue"] = v13 == null ? false : v13 === true;
+  const v14 = pick(json, "stringValue", "string_value");
+  message["stringValue"] = v14 == null ? "" : String(v14);
+  const v15 = pick(json, "bytesValue", "bytes_value");
+  message["bytesValue"] = v15 == null ? "" : String(v15);
+  const v16 = pick(json, "repeatedValue", "repeated_value");
+  message["repeatedValue"] = v16 == null ? [] : Array.isArray(v16) ? v16.map((x: unknown) => Number(x)) : [];
+  const v17 = pick(json, "optionalValue", "optional_value");
+  if (v17 != null) message["optionalValue"] = String(v17);
+  return message as unknown as Scalars;
+}
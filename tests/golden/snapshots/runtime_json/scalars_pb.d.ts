@@ -0,0 +1,23 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: scalars.proto
+
+export type Scalars = Readonly<{
+  doubleValue: number;
+  floatValue: number;
+  int32Value: number;
+  int64Value: string;
+  uint32Value: number;
+  uint64Value: string;
+  sint32Value: number;
+  sint64Value: string;
+  fixed32Value: number;
+  fixed64Value: string;
+  sfixed32Value: number;
+  sfixed64Value: string;
+  boolValue: boolean;
+  stringValue: string;
+  bytesValue: string;
+  repeatedValue: ReadonlyArray<number>;
+  optionalValue?: string;
+}>;
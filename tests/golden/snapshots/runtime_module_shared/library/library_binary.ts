@@ -0,0 +1,114 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: library.proto
+
+import type { Book, GetBookRequest, UpdateBookRequest } from "./library_pb";
+import { Reader, Writer, decodeDuration, decodeTime, decodeTimestamp, encodeDuration, encodeNested, encodeTime, encodeTimestamp, formatNanos, parseNanos } from "../runtime";
+
+export function encodeBook(message: Book): Uint8Array {
+  const writer = new Writer();
+  if (message["name"] !== "") {
+    writer.tag(1, 2);
+    writer.string(message["name"]);
+  }
+  if (message["title"] !== "") {
+    writer.tag(2, 2);
+    writer.string(message["title"]);
+  }
+  if (message["publishedAt"] !== undefined) {
+    writer.tag(3, 2);
+    writer.bytes(encodeTimestamp(message["publishedAt"]));
+  }
+  return writer.finish();
+}
+
+export function decodeBook(bytes: Uint8Array): Book {
+  const reader = new Reader(bytes);
+  const message: Record<string, unknown> = {};
+  message["name"] = "";
+  message["title"] = "";
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    switch (tag >>> 3) {
+      case 1:
+        message["name"] = reader.string();
+        break;
+      case 2:
+        message["title"] = reader.string();
+        break;
+      case 3:
+        message["publishedAt"] = decodeTimestamp(reader.bytes());
+        break;
+      default:
+        reader.skip(tag & 7);
+    }
+  }
+  return message as unknown as Book;
+}
+
+export function encodeGetBookRequest(message: GetBookRequest): Uint8Array {
+  const writer = new Writer();
+  if (message["name"] !== "") {
+    writer.tag(1, 2);
+    writer.string(message["name"]);
+  }
+  if (message["revision"] !== "0") {
+    writer.tag(2, 0);
+    writer.int64(BigInt(message["revision"]));
+  }
+  return writer.finish();
+}
+
+export function decodeGetBookRequest(bytes: Uint8Array): GetBookRequest {
+  const reader = new Reader(bytes);
+  const message: Record<string, unknown> = {};
+  message["name"] = "";
+  message["revision"] = "0";
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    switch (tag >>> 3) {
+      case 1:
+        message["name"] = reader.string();
+        break;
+      case 2:
+        message["revision"] = reader.int64().toString();
+        break;
+      default:
+        reader.skip(tag & 7);
+    }
+  }
+  return message as unknown as GetBookRequest;
+}
+
+export function encodeUpdateBookRequest(message: UpdateBookRequest): Uint8Array {
+  const writer = new Writer();
+  if (message["book"] !== undefined) {
+    writer.tag(1, 2);
+    writer.bytes(encodeBook(message["book"]));
+  }
+  if (message["validateOnly"] !== false) {
+    writer.tag(2, 0);
+    writer.bool(message["validateOnly"]);
+  }
+  return writer.finish();
+}
+
+export function decodeUpdateBookRequest(bytes: Uint8Array): UpdateBookRequest {
+  const reader = new Reader(bytes);
+  const message: Record<string, unknown> = {};
+  message["validateOnly"] = false;
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    switch (tag >>> 3) {
+      case 1:
+        message["book"] = decodeBook(reader.bytes());
+        break;
+      case 2:
+        message["validateOnly"] = reader.bool();
+        break;
+      default:
+        reader.skip(tag & 7);
+    }
+  }
+  return message as unknown as UpdateBookRequest;
+}
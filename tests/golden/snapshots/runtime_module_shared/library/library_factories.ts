@@ -0,0 +1,29 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: library.proto
+
+import type { Book, GetBookRequest, UpdateBookRequest } from "./library_pb";
+import type { DeepPartial } from "../runtime";
+export type { DeepPartial };
+
+export function createBook(partial: DeepPartial<Book> = {}): Book {
+  const message: Record<string, unknown> = {};
+  message["name"] = partial["name"] ?? "";
+  message["title"] = partial["title"] ?? "";
+  if (partial["publishedAt"] !== undefined) message["publishedAt"] = partial["publishedAt"];
+  return message as unknown as Book;
+}
+
+export function createGetBookRequest(partial: DeepPartial<GetBookRequest> = {}): GetBookRequest {
+  const message: Record<string, unknown> = {};
+  message["name"] = partial["name"] ?? "";
+  message["revision"] = partial["revision"] ?? "0";
+  return message as unknown as GetBookRequest;
+}
+
+export function createUpdateBookRequest(partial: DeepPartial<UpdateBookRequest> = {}): UpdateBookRequest {
+  const message: Record<string, unknown> = {};
+  if (partial["book"] !== undefined) message["book"] = createBook(partial["book"]);
+  message["validateOnly"] = partial["validateOnly"] ?? false;
+  return message as unknown as UpdateBookRequest;
+}
@@ -0,0 +1,60 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: library.proto
+
+import type { Book, GetBookRequest, UpdateBookRequest } from "./library_pb";
+import { base64ToBytes, bytesToBase64, pick } from "../runtime";
+
+export function BookToJSON(message: Book): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["name"] !== "") json["name"] = message["name"];
+  if (message["title"] !== "") json["title"] = message["title"];
+  if (message["publishedAt"] !== undefined) json["publishedAt"] = message["publishedAt"];
+  return json;
+}
+
+export function BookFromJSON(value: unknown): Book {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v1 = pick(json, "name", "name");
+  message["name"] = v1 == null ? "" : String(v1);
+  const v2 = pick(json, "title", "title");
+  message["title"] = v2 == null ? "" : String(v2);
+  const v3 = pick(json, "publishedAt", "published_at");
+  if (v3 != null) message["publishedAt"] = String(v3);
+  return message as unknown as Book;
+}
+
+export function GetBookRequestToJSON(message: GetBookRequest): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["name"] !== "") json["name"] = message["name"];
+  if (message["revision"] !== "0") json["revision"] = message["revision"];
+  return json;
+}
+
+export function GetBookRequestFromJSON(value: unknown): GetBookRequest {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v1 = pick(json, "name", "name");
+  message["name"] = v1 == null ? "" : String(v1);
+  const v2 = pick(json, "revision", "revision");
+  message["revision"] = v2 == null ? "0" : String(v2);
+  return message as unknown as GetBookRequest;
+}
+
+export function UpdateBookRequestToJSON(message: UpdateBookRequest): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["book"] !== undefined) json["book"] = BookToJSON(message["book"]);
+  if (message["validateOnly"] !== false) json["validateOnly"] = message["validateOnly"];
+  return json;
+}
+
+export function UpdateBookRequestFromJSON(value: unknown): UpdateBookRequest {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v1 = pick(json, "book", "book");
+  if (v1 != null) message["book"] = BookFromJSON(v1);
+  const v2 = pick(json, "validateOnly", "validate_only");
+  message["validateOnly"] = v2 == null ? false : v2 === true;
+  return message as unknown as UpdateBookRequest;
+}
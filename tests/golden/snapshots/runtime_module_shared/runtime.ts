@@ -0,0 +1,515 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+
+export class Writer {
+  private readonly buf: number[] = [];
+
+  tag(fieldNumber: number, wireType: number): void {
+    this.uint32(fieldNumber * 8 + wireType);
+  }
+
+  uint32(value: number): void {
+    value >>>= 0;
+    while (value > 0x7f) {
+      this.buf.push((value & 0x7f) | 0x80);
+      value >>>= 7;
+    }
+    this.buf.push(value);
+  }
+
+  int32(value: number): void {
+    if (value < 0) {
+      this.uint64(BigInt(value));
+    } else {
+      this.uint32(value);
+    }
+  }
+
+  sint32(value: number): void {
+    this.uint32((value << 1) ^ (value >> 31));
+  }
+
+  uint64(value: bigint): void {
+    value = BigInt.asUintN(64, value);
+    while (value > BigInt(0x7f)) {
+      this.buf.push(Number(value & BigInt(0x7f)) | 0x80);
+      value >>= BigInt(7);
+    }
+    this.buf.push(Number(value));
+  }
+
+  int64(value: bigint): void {
+    this.uint64(value);
+  }
+
+  sint64(value: bigint): void {
+    this.uint64((value << BigInt(1)) ^ (value >> BigInt(63)));
+  }
+
+  bool(value: boolean): void {
+    this.buf.push(value ? 1 : 0);
+  }
+
+  fixed32(value: number): void {
+    this.fixed(4, (view) => view.setUint32(0, value, true));
+  }
+
+  sfixed32(value: number): void {
+    this.fixed(4, (view) => view.setInt32(0, value, true));
+  }
+
+  float(value: number): void {
+    this.fixed(4, (view) => view.setFloat32(0, value, true));
+  }
+
+  fixed64(value: bigint): void {
+    this.fixed(8, (view) => view.setBigUint64(0, BigInt.asUintN(64, value), true));
+  }
+
+  sfixed64(value: bigint): void {
+    this.fixed(8, (view) => view.setBigInt64(0, BigInt.asIntN(64, value), true));
+  }
+
+  double(value: number): void {
+    this.fixed(8, (view) => view.setFloat64(0, value, true));
+  }
+
+  string(value: string): void {
+    this.bytes(new TextEncoder().encode(value));
+  }
+
+  bytes(value: Uint8Array): void {
+    this.uint32(value.length);
+    this.raw(value);
+  }
+
+  raw(value: Uint8Array): void {
+    value.forEach((byte) => this.buf.push(byte));
+  }
+
+  finish(): Uint8Array {
+    return Uint8Array.from(this.buf);
+  }
+
+  private fixed(size: number, write: (view: DataView) => void): void {
+    const view = new DataView(new ArrayBuffer(size));
+    write(view);
+    new Uint8Array(view.buffer).forEach((byte) => this.buf.push(byte));
+  }
+}
+
+export class Reader {
+  pos = 0;
+  readonly len: number;
+  private readonly view: DataView;
+
+  constructor(private readonly buf: Uint8Array) {
+    this.len = buf.length;
+    this.view = new DataView(buf.buffer, buf.byteOffset, buf.byteLength);
+  }
+
+  uint32(): number {
+    let value = 0;
+    for (let shift = 0; ; shift += 7) {
+      const byte = this.next();
+      if (shift < 32) value |= (byte & 0x7f) << shift;
+      if ((byte & 0x80) === 0) return value >>> 0;
+    }
+  }
+
+  int32(): number {
+    return this.uint32() | 0;
+  }
+
+  sint32(): number {
+    const value = this.uint32();
+    return (value >>> 1) ^ -(value & 1);
+  }
+
+  uint64(): bigint {
+    let value = BigInt(0);
+    for (let shift = BigInt(0); ; shift += BigInt(7)) {
+      const byte = this.next();
+      value |= BigInt(byte & 0x7f) << shift;
+      if ((byte & 0x80) === 0) return BigInt.asUintN(64, value);
+    }
+  }
+
+  int64(): bigint {
+    return BigInt.asIntN(64, this.uint64());
+  }
+
+  sint64(): bigint {
+    const value = this.uint64();
+    return (value >> BigInt(1)) ^ -(value & BigInt(1));
+  }
+
+  bool(): boolean {
+    return this.uint64() !== BigInt(0);
+  }
+
+  fixed32(): number {
+    return this.view.getUint32(this.advance(4), true);
+  }
+
+  sfixed32(): number {
+    return this.view.getInt32(this.advance(4), true);
+  }
+
+  float(): number {
+    return this.view.getFloat32(this.advance(4), true);
+  }
+
+  fixed64(): bigint {
+    return this.view.getBigUint64(this.advance(8), true);
+  }
+
+  sfixed64(): bigint {
+    return this.view.getBigInt64(this.advance(8), true);
+  }
+
+  double(): number {
+    return this.view.getFloat64(this.advance(8), true);
+  }
+
+  string(): string {
+    return new TextDecoder().decode(this.bytes());
+  }
+
+  bytes(): Uint8Array {
+    const length = this.uint32();
+    const start = this.advance(length);
+    return this.buf.subarray(start, start + length);
+  }
+
+  group(fieldNumber: number): Uint8Array {
+    const start = this.pos;
+    let end = start;
+    for (let tag = this.uint32(); tag !== fieldNumber * 8 + 4; tag = this.uint32()) {
+      this.skip(tag & 7);
+      end = this.pos;
+    }
+    return this.buf.subarray(start, end);
+  }
+
+  skip(wireType: number): void {
+    switch (wireType) {
+      case 0:
+        this.uint64();
+        break;
+      case 1:
+        this.advance(8);
+        break;
+      case 2:
+        this.advance(this.uint32());
+        break;
+      case 3:
+        for (let tag = this.uint32(); (tag & 7) !== 4; tag = this.uint32()) {
+          this.skip(tag & 7);
+        }
+        break;
+      case 5:
+        this.advance(4);
+        break;
+      default:
+        throw new Error(`invalid wire type ${wireType}`);
+    }
+  }
+
+  private next(): number {
+    return this.buf[this.advance(1)];
+  }
+
+  private advance(size: number): number {
+    const start = this.pos;
+    if (start + size > this.len) throw new RangeError("premature end of input");
+    this.pos += size;
+    return start;
+  }
+}
+
+export function encodeNested(write: (writer: Writer) => void): Uint8Array {
+  const writer = new Writer();
+  write(writer);
+  return writer.finish();
+}
+
+export function wrap<T>(value: T | null, write: (writer: Writer, value: T) => void): Uint8Array {
+  return encodeNested((writer) => {
+    if (value !== null) write(writer, value);
+  });
+}
+
+export function unwrap<T>(bytes: Uint8Array, read: (reader: Reader) => T, fallback: T): T {
+  const reader = new Reader(bytes);
+  let value = fallback;
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    if (tag >>> 3 === 1) {
+      value = read(reader);
+    } else {
+      reader.skip(tag & 7);
+    }
+  }
+  return value;
+}
+
+export function encodeTime(seconds: bigint, nanos: number): Uint8Array {
+  return encodeNested((writer) => {
+    if (seconds !== BigInt(0)) {
+      writer.tag(1, 0);
+      writer.int64(seconds);
+    }
+    if (nanos !== 0) {
+      writer.tag(2, 0);
+      writer.int32(nanos);
+    }
+  });
+}
+
+export function decodeTime(bytes: Uint8Array): [bigint, number] {
+  const reader = new Reader(bytes);
+  let seconds = BigInt(0);
+  let nanos = 0;
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    switch (tag >>> 3) {
+      case 1:
+        seconds = reader.int64();
+        break;
+      case 2:
+        nanos = reader.int32();
+        break;
+      default:
+        reader.skip(tag & 7);
+    }
+  }
+  return [seconds, nanos];
+}
+
+export function parseNanos(fraction: string | undefined): number {
+  return Number((fraction ?? "").padEnd(9, "0").slice(0, 9));
+}
+
+export function formatNanos(nanos: number): string {
+  if (nanos === 0) return "";
+  const digits = nanos.toString().padStart(9, "0");
+  if (nanos % 1000000 === 0) return `.${digits.slice(0, 3)}`;
+  if (nanos % 1000 === 0) return `.${digits.slice(0, 6)}`;
+  return `.${digits}`;
+}
+
+export function encodeTimestamp(value: string): Uint8Array {
+  const fraction = /\.(\d+)/.exec(value);
+  const seconds = Math.floor(Date.parse(value) / 1000);
+  return encodeTime(BigInt(seconds), parseNanos(fraction?.[1]));
+}
+
+export function decodeTimestamp(bytes: Uint8Array): string {
+  const [seconds, nanos] = decodeTime(bytes);
+  const date = new Date(Number(seconds) * 1000).toISOString().replace(/\.\d+Z$/, "");
+  return `${date}${formatNanos(nanos)}Z`;
+}
+
+export function encodeDuration(value: string): Uint8Array {
+  const match = /^(-)?(\d+)(?:\.(\d+))?s$/.exec(value);
+  if (match === null) throw new RangeError(`invalid duration ${value}`);
+  const sign = match[1] === undefined ? 1 : -1;
+  return encodeTime(BigInt(match[2]) * BigInt(sign), parseNanos(match[3]) * sign);
+}
+
+export function decodeDuration(bytes: Uint8Array): string {
+  const [seconds, nanos] = decodeTime(bytes);
+  const sign = seconds < BigInt(0) || nanos < 0 ? "-" : "";
+  const abs = seconds < BigInt(0) ? -seconds : seconds;
+  return `${sign}${abs}${formatNanos(Math.abs(nanos))}s`;
+}
+
+export function encodeValue(value: unknown): Uint8Array {
+  return encodeNested((writer) => {
+    if (typeof value === "number") {
+      writer.tag(2, 1);
+      writer.double(value);
+    } else if (typeof value === "string") {
+      writer.tag(3, 2);
+      writer.string(value);
+    } else if (typeof value === "boolean") {
+      writer.tag(4, 0);
+      writer.bool(value);
+    } else if (Array.isArray(value)) {
+      writer.tag(6, 2);
+      writer.bytes(encodeListValue(value));
+    } else if (typeof value === "object" && value !== null) {
+      writer.tag(5, 2);
+      writer.bytes(encodeStruct(value));
+    } else {
+      writer.tag(1, 0);
+      writer.int32(0);
+    }
+  });
+}
+
+export function encodeStruct(value: unknown): Uint8Array {
+  return encodeNested((writer) => {
+    Object.entries(value as Record<string, unknown>).forEach(([key, field]) => {
+      writer.tag(1, 2);
+      writer.bytes(encodeNested((entry) => {
+        entry.tag(1, 2);
+        entry.string(key);
+        entry.tag(2, 2);
+        entry.bytes(encodeValue(field));
+      }));
+    });
+  });
+}
+
+export function encodeListValue(value: unknown): Uint8Array {
+  return encodeNested((writer) => {
+    (value as unknown[]).forEach((element) => {
+      writer.tag(1, 2);
+      writer.bytes(encodeValue(element));
+    });
+  });
+}
+
+export function decodeValue(bytes: Uint8Array): unknown {
+  const reader = new Reader(bytes);
+  let value: unknown = null;
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    switch (tag >>> 3) {
+      case 1:
+        reader.int32();
+        value = null;
+        break;
+      case 2:
+        value = reader.double();
+        break;
+      case 3:
+        value = reader.string();
+        break;
+      case 4:
+        value = reader.bool();
+        break;
+      case 5:
+        value = decodeStruct(reader.bytes());
+        break;
+      case 6:
+        value = decodeListValue(reader.bytes());
+        break;
+      default:
+        reader.skip(tag & 7);
+    }
+  }
+  return value;
+}
+
+export function decodeStruct(bytes: Uint8Array): unknown {
+  const reader = new Reader(bytes);
+  const struct: Record<string, unknown> = {};
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    if (tag >>> 3 !== 1) {
+      reader.skip(tag & 7);
+      continue;
+    }
+    const entry = new Reader(reader.bytes());
+    let key = "";
+    let value: unknown = null;
+    while (entry.pos < entry.len) {
+      const entryTag = entry.uint32();
+      switch (entryTag >>> 3) {
+        case 1:
+          key = entry.string();
+          break;
+        case 2:
+          value = decodeValue(entry.bytes());
+          break;
+        default:
+          entry.skip(entryTag & 7);
+      }
+    }
+    struct[key] = value;
+  }
+  return struct;
+}
+
+export function decodeListValue(bytes: Uint8Array): unknown {
+  const reader = new Reader(bytes);
+  const values: unknown[] = [];
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    if (tag >>> 3 === 1) {
+      values.push(decodeValue(reader.bytes()));
+    } else {
+      reader.skip(tag & 7);
+    }
+  }
+  return values;
+}
+
+export function encodeAny(value: Readonly<{ "@type": string } & Record<string, unknown>>): Uint8Array {
+  const payload = value["value"];
+  return encodeNested((writer) => {
+    writer.tag(1, 2);
+    writer.string(value["@type"]);
+    if (typeof payload === "string") {
+      writer.tag(2, 2);
+      writer.bytes(base64ToBytes(payload));
+    } else if (payload instanceof Uint8Array) {
+      writer.tag(2, 2);
+      writer.bytes(payload);
+    }
+  });
+}
+
+export function decodeAny(bytes: Uint8Array): unknown {
+  const reader = new Reader(bytes);
+  let typeUrl = "";
+  let payload = new Uint8Array(0);
+  while (reader.pos < reader.len) {
+    const tag = reader.uint32();
+    switch (tag >>> 3) {
+      case 1:
+        typeUrl = reader.string();
+        break;
+      case 2:
+        payload = reader.bytes();
+        break;
+      default:
+        reader.skip(tag & 7);
+    }
+  }
+  return { "@type": typeUrl, value: bytesToBase64(payload) };
+}
+
+export function pick(json: Record<string, unknown>, jsonName: string, protoName: string): unknown {
+  return json[jsonName] ?? json[protoName];
+}
+
+export function bytesToBase64(bytes: Uint8Array): string {
+  let binary = "";
+  bytes.forEach((byte) => {
+    binary += String.fromCharCode(byte);
+  });
+  return btoa(binary);
+}
+
+export function base64ToBytes(base64: string): Uint8Array {
+  const binary = atob(base64.replace(/-/g, "+").replace(/_/g, "/"));
+  const bytes = new Uint8Array(binary.length);
+  for (let i = 0; i < binary.length; i++) {
+    bytes[i] = binary.charCodeAt(i);
+  }
+  return bytes;
+}
+
+export type DeepPartial<T> = T extends Uint8Array | Date
+  ? T
+  : T extends ReadonlyArray<infer U>
+    ? ReadonlyArray<DeepPartial<U>>
+    : T extends ReadonlyMap<infer K, infer V>
+      ? ReadonlyMap<K, DeepPartial<V>>
+      : T extends object
+        ? { readonly [P in keyof T]?: DeepPartial<T[P]> }
+        : T;
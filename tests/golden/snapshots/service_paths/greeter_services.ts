@@ -0,0 +1,19 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: greeter.proto
+
+import type { HelloReply, HelloRequest } from "./greeter_pb";
+
+export const GreeterMethods = {
+  sayHello: "/greeter.Greeter/SayHello",
+  streamHellos: "/greeter.Greeter/StreamHellos",
+  chat: "/greeter.Greeter/Chat",
+} as const;
+
+export type GreeterMethodName = keyof typeof GreeterMethods;
+
+export type GreeterMethodTypes = {
+  readonly sayHello: { readonly request: HelloRequest; readonly response: HelloReply };
+  readonly streamHellos: { readonly request: HelloRequest; readonly response: HelloReply };
+  readonly chat: { readonly request: HelloRequest; readonly response: HelloReply };
+};
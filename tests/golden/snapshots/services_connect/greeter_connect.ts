@@ -0,0 +1,66 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: greeter.proto
+
+import { MethodKind } from "@bufbuild/protobuf";
+import type { HelloReply, HelloRequest } from "./greeter_pb";
+
+interface JsonMessageType<T> {
+  new (data?: Partial<T>): T & { toJsonString(): string; toBinary(): Uint8Array };
+  readonly typeName: string;
+  fromJsonString(json: string): T;
+  fromBinary(bytes: Uint8Array): T;
+}
+
+function jsonMessageType<T extends object>(typeName: string): JsonMessageType<T> {
+  return class {
+    static readonly typeName = typeName;
+
+    constructor(data?: Partial<T>) {
+      Object.assign(this, data);
+    }
+
+    toJsonString(): string {
+      return JSON.stringify(this);
+    }
+
+    toBinary(): Uint8Array {
+      throw new Error(`${typeName} supports only the JSON format`);
+    }
+
+    static fromJsonString(json: string): T {
+      return new this(JSON.parse(json)) as unknown as T;
+    }
+
+    static fromBinary(): T {
+      throw new Error(`${typeName} supports only the JSON format`);
+    }
+  } as unknown as JsonMessageType<T>;
+}
+
+const HelloRequestType = jsonMessageType<HelloRequest>("greeter.HelloRequest");
+const HelloReplyType = jsonMessageType<HelloReply>("greeter.HelloReply");
+
+export const Greeter = {
+  typeName: "greeter.Greeter",
+  methods: {
+    sayHello: {
+      name: "SayHello",
+      I: HelloRequestType,
+      O: HelloReplyType,
+      kind: MethodKind.Unary
+    },
+    streamHellos: {
+      name: "StreamHellos",
+      I: HelloRequestType,
+      O: HelloReplyType,
+      kind: MethodKind.ServerStreaming
+    },
+    chat: {
+      name: "Chat",
+      I: HelloRequestType,
+      O: HelloReplyType,
+      kind: MethodKind.BiDiStreaming
+    },
+  }
+} as const;
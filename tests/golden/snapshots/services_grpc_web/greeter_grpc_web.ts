@@ -0,0 +1,64 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: greeter.proto
+
+import * as grpcWeb from "grpc-web";
+import type { HelloReply, HelloRequest } from "./greeter_pb";
+
+const encoder = new TextEncoder();
+const decoder = new TextDecoder();
+
+function serialize(message: unknown): Uint8Array {
+  return encoder.encode(JSON.stringify(message));
+}
+
+function deserialize<T>(bytes: Uint8Array): T {
+  return JSON.parse(decoder.decode(bytes)) as T;
+}
+
+export class GreeterGrpcWebClient {
+  private readonly client: grpcWeb.GrpcWebClientBase;
+
+  constructor(
+    private readonly hostname: string,
+    options: grpcWeb.GrpcWebClientBaseOptions = {}
+  ) {
+    this.client = new grpcWeb.GrpcWebClientBase(options);
+  }
+
+  private static readonly sayHelloDescriptor = new grpcWeb.MethodDescriptor<HelloRequest, HelloReply>(
+    "/greeter.Greeter/SayHello",
+    grpcWeb.MethodType.UNARY,
+    Object as never,
+    Object as never,
+    serialize,
+    deserialize
+  );
+
+  sayHello(request: HelloRequest, metadata: grpcWeb.Metadata = {}): Promise<HelloReply> {
+    return this.client.thenableCall(
+      this.hostname + "/greeter.Greeter/SayHello",
+      request,
+      metadata,
+      GreeterGrpcWebClient.sayHelloDescriptor
+    );
+  }
+
+  private static readonly streamHellosDescriptor = new grpcWeb.MethodDescriptor<HelloRequest, HelloReply>(
+    "/greeter.Greeter/StreamHellos",
+    grpcWeb.MethodType.SERVER_STREAMING,
+    Object as never,
+    Object as never,
+    serialize,
+    deserialize
+  );
+
+  streamHellos(request: HelloRequest, metadata: grpcWeb.Metadata = {}): grpcWeb.ClientReadableStream<HelloReply> {
+    return this.client.serverStreaming(
+      this.hostname + "/greeter.Greeter/StreamHellos",
+      request,
+      metadata,
+      GreeterGrpcWebClient.streamHellosDescriptor
+    );
+  }
+}
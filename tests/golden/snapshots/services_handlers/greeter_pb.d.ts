@@ -0,0 +1,23 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: greeter.proto
+
+export type HelloRequest = Readonly<{
+  name: string;
+}>;
+
+export type HelloReply = Readonly<{
+  message: string;
+}>;
+
+export interface GreeterClient {
+  sayHello(request: HelloRequest): Promise<HelloReply>;
+  streamHellos(request: HelloRequest): AsyncIterable<HelloReply>;
+  chat(request: AsyncIterable<HelloRequest>): AsyncIterable<HelloReply>;
+}
+
+export interface GreeterHandlers<Context = unknown> {
+  sayHello(request: HelloRequest, context: Context): Promise<HelloReply> | HelloReply;
+  streamHellos(request: HelloRequest, context: Context): AsyncIterable<HelloReply>;
+  chat(requests: AsyncIterable<HelloRequest>, context: Context): AsyncIterable<HelloReply>;
+}
@@ -0,0 +1,38 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: greeter.proto
+
+import { http, HttpResponse } from "msw";
+import type { HttpHandler } from "msw";
+import type { HelloReply, HelloRequest } from "./greeter_pb";
+
+export type MockResolver<I, O> = (request: I, info: { readonly request: Request }) => O | Promise<O>;
+
+export interface MockHandlerOptions {
+  readonly baseUrl?: string;
+  readonly prefix?: string;
+}
+
+function jsonResponse(message: unknown): Response {
+  return new HttpResponse(JSON.stringify(message), { headers: { "Content-Type": "application/json" } });
+}
+
+export interface GreeterMockResolvers {
+  readonly sayHello?: MockResolver<HelloRequest, HelloReply>;
+}
+
+export function createGreeterHandlers(
+  resolvers: GreeterMockResolvers,
+  options: MockHandlerOptions = {}
+): HttpHandler[] {
+  const base = (options.baseUrl ?? "") + (options.prefix ?? "");
+  const handlers: HttpHandler[] = [];
+  const sayHello = resolvers.sayHello;
+  if (sayHello !== undefined) {
+    handlers.push(http.post(`${base}/greeter.Greeter/SayHello`, async ({ request }) => {
+      const input = (await request.json()) as HelloRequest;
+      return jsonResponse(await sayHello(input, { request }));
+    }));
+  }
+  return handlers;
+}
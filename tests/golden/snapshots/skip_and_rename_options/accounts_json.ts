@@ -0,0 +1,43 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: accounts.proto
+
+import type { Role, User } from "./accounts_pb";
+
+function pick(json: Record<string, unknown>, jsonName: string, protoName: string): unknown {
+  return json[jsonName] ?? json[protoName];
+}
+
+export function RoleToJSON(value: Role): string {
+  return value;
+}
+
+export function RoleFromJSON(value: unknown): Role {
+  switch (value) {
+    case "ROLE_KIND_MEMBER":
+    case 0:
+      return "ROLE_KIND_MEMBER";
+    default:
+      return "ROLE_KIND_MEMBER";
+  }
+}
+
+export function UserToJSON(message: User): unknown {
+  const json: Record<string, unknown> = {};
+  if (message["name"] !== "") json["name"] = message["name"];
+  if (message["email"] !== "") json["usrEmail"] = message["email"];
+  if (message["role"] !== "ROLE_KIND_MEMBER") json["role"] = RoleToJSON(message["role"]);
+  return json;
+}
+
+export function UserFromJSON(value: unknown): User {
+  const json = (typeof value === "object" && value !== null ? value : {}) as Record<string, unknown>;
+  const message: Record<string, unknown> = {};
+  const v1 = pick(json, "name", "name");
+  message["name"] = v1 == null ? "" : String(v1);
+  const v3 = pick(json, "usrEmail", "usr_email");
+  message["email"] = v3 == null ? "" : String(v3);
+  const v5 = pick(json, "role", "role");
+  message["role"] = v5 == null ? "ROLE_KIND_MEMBER" : RoleFromJSON(v5);
+  return message as unknown as User;
+}
@@ -0,0 +1,11 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: accounts.proto
+
+export type User = Readonly<{
+  name: string;
+  email: string;
+  role: Role;
+}>;
+
+export type Role = "ROLE_KIND_MEMBER";
@@ -0,0 +1,29 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: users/v1/user.proto
+
+// source: users/v1/user.proto:7
+export type User = Readonly<{
+  // source: users/v1/user.proto:9
+  /** Opaque identifier. */
+  id: string;
+}> & Readonly<
+    {
+      // source: users/v1/user.proto:11
+      email?: string;
+      phone?: never;
+    } |
+    {
+      email?: never;
+      // source: users/v1/user.proto:12
+      phone?: string;
+    }
+  >;
+
+// source: users/v1/user.proto:16
+export type Role = "ROLE_UNSPECIFIED" | "ROLE_ADMIN";
+
+// source: users/v1/user.proto:21
+export interface UserServiceClient {
+  getUser(request: User): Promise<User>;
+}
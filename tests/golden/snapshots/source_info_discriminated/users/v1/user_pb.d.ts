@@ -0,0 +1,21 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: users/v1/user.proto
+
+// source: users/v1/user.proto:7
+export interface User {
+  // source: users/v1/user.proto:9
+  /** Opaque identifier. */
+  readonly id: string;
+  // source: users/v1/user.proto:10
+  readonly contact?: User_contact;
+}
+export type User_contact = { readonly $case: "email"; readonly email: string } | { readonly $case: "phone"; readonly phone: string };
+
+// source: users/v1/user.proto:16
+export type Role = "ROLE_UNSPECIFIED" | "ROLE_ADMIN";
+
+// source: users/v1/user.proto:21
+export interface UserServiceClient {
+  getUser(request: User): Promise<User>;
+}
@@ -0,0 +1,18 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: account.proto
+
+export type Account = {
+  displayName: string;
+  balance: string;
+  avatar: Uint8Array;
+  createdAt?: Date;
+  nickname?: string;
+  status: Status;
+  contact?: { $case: "email"; email: string } | { $case: "phone"; phone: string };
+};
+
+export declare enum Status {
+  STATUS_ACTIVE = 0,
+  STATUS_CLOSED = 1,
+}
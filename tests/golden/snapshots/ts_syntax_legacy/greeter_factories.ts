@@ -0,0 +1,19 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: greeter.proto
+
+import { HelloReply, HelloRequest } from "./greeter_pb";
+import { DeepPartial } from "./runtime";
+export { DeepPartial };
+
+export function createHelloRequest(partial: DeepPartial<HelloRequest> = {}): HelloRequest {
+  const message: Record<string, unknown> = {};
+  message["name"] = partial["name"] ?? "";
+  return message as unknown as HelloRequest;
+}
+
+export function createHelloReply(partial: DeepPartial<HelloReply> = {}): HelloReply {
+  const message: Record<string, unknown> = {};
+  message["message"] = partial["message"] ?? "";
+  return message as unknown as HelloReply;
+}
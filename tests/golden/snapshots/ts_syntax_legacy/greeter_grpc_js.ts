@@ -0,0 +1,50 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: greeter.proto
+
+import * as grpc from "@grpc/grpc-js";
+import { HelloReply, HelloRequest } from "./greeter_pb";
+
+function serialize(message: unknown): Buffer {
+  return Buffer.from(JSON.stringify(message));
+}
+
+function deserialize<T>(bytes: Buffer): T {
+  return JSON.parse(bytes.toString()) as T;
+}
+
+export interface GreeterServer extends grpc.UntypedServiceImplementation {
+  sayHello: grpc.handleUnaryCall<HelloRequest, HelloReply>;
+  streamHellos: grpc.handleServerStreamingCall<HelloRequest, HelloReply>;
+  chat: grpc.handleBidiStreamingCall<HelloRequest, HelloReply>;
+}
+
+export const GreeterService: grpc.ServiceDefinition<GreeterServer> = {
+  sayHello: {
+    path: "/greeter.Greeter/SayHello",
+    requestStream: false,
+    responseStream: false,
+    requestSerialize: (value: HelloRequest) => serialize(value),
+    requestDeserialize: (bytes: Buffer) => deserialize<HelloRequest>(bytes),
+    responseSerialize: (value: HelloReply) => serialize(value),
+    responseDeserialize: (bytes: Buffer) => deserialize<HelloReply>(bytes)
+  },
+  streamHellos: {
+    path: "/greeter.Greeter/StreamHellos",
+    requestStream: false,
+    responseStream: true,
+    requestSerialize: (value: HelloRequest) => serialize(value),
+    requestDeserialize: (bytes: Buffer) => deserialize<HelloRequest>(bytes),
+    responseSerialize: (value: HelloReply) => serialize(value),
+    responseDeserialize: (bytes: Buffer) => deserialize<HelloReply>(bytes)
+  },
+  chat: {
+    path: "/greeter.Greeter/Chat",
+    requestStream: true,
+    responseStream: true,
+    requestSerialize: (value: HelloRequest) => serialize(value),
+    requestDeserialize: (bytes: Buffer) => deserialize<HelloRequest>(bytes),
+    responseSerialize: (value: HelloReply) => serialize(value),
+    responseDeserialize: (bytes: Buffer) => deserialize<HelloReply>(bytes)
+  },
+};
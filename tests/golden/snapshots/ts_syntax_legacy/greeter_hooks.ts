@@ -0,0 +1,38 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: greeter.proto
+
+import { useMutation, useQuery, UseMutationOptions, UseMutationResult, UseQueryOptions, UseQueryResult } from "@tanstack/react-query";
+import { HelloReply, HelloRequest } from "./greeter_pb";
+
+export interface RpcTransport {
+  unary<I, O>(path: string, request: I, signal?: AbortSignal): Promise<O>;
+}
+
+export type SayHelloQueryKey = readonly ["greeter.Greeter.SayHello", HelloRequest];
+
+export function sayHelloQueryKey(request: HelloRequest): SayHelloQueryKey {
+  return ["greeter.Greeter.SayHello", request];
+}
+
+export function useSayHelloQuery(
+  transport: RpcTransport,
+  request: HelloRequest,
+  options: Omit<UseQueryOptions<HelloReply, Error, HelloReply, SayHelloQueryKey>, "queryKey" | "queryFn"> = {}
+): UseQueryResult<HelloReply, Error> {
+  return useQuery({
+    ...options,
+    queryKey: sayHelloQueryKey(request),
+    queryFn: ({ signal }) => transport.unary<HelloRequest, HelloReply>("/greeter.Greeter/SayHello", request, signal)
+  });
+}
+
+export function useSayHelloMutation(
+  transport: RpcTransport,
+  options: Omit<UseMutationOptions<HelloReply, Error, HelloRequest>, "mutationFn"> = {}
+): UseMutationResult<HelloReply, Error, HelloRequest> {
+  return useMutation({
+    ...options,
+    mutationFn: (request) => transport.unary<HelloRequest, HelloReply>("/greeter.Greeter/SayHello", request)
+  });
+}
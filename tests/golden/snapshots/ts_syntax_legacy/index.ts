@@ -0,0 +1,4 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+
+export * from "./greeter_pb";
@@ -0,0 +1,31 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: oneofs.proto
+
+import type { Contact, Phone } from "./oneofs_pb";
+
+export type DeepPartial<T> = T extends Uint8Array | Date
+  ? T
+  : T extends ReadonlyArray<infer U>
+    ? ReadonlyArray<DeepPartial<U>>
+    : T extends ReadonlyMap<infer K, infer V>
+      ? ReadonlyMap<K, DeepPartial<V>>
+      : T extends object
+        ? { readonly [P in keyof T]?: DeepPartial<T[P]> }
+        : T;
+
+export function createContact(partial: DeepPartial<Contact> = {}): Contact {
+  const message: Record<string, unknown> = {};
+  message["$type"] = "oneofs.Contact";
+  message["name"] = partial["name"] ?? "";
+  if (partial["email"] !== undefined) message["email"] = partial["email"];
+  if (partial["phone"] !== undefined) message["phone"] = createPhone(partial["phone"]);
+  return message as unknown as Contact;
+}
+
+export function createPhone(partial: DeepPartial<Phone> = {}): Phone {
+  const message: Record<string, unknown> = {};
+  message["$type"] = "oneofs.Phone";
+  message["number"] = partial["number"] ?? "";
+  return message as unknown as Phone;
+}
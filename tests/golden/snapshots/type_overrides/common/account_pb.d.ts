@@ -0,0 +1,16 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: common/account.proto
+
+import type { Account } from "@acme/accounts";
+import type { Long } from "long";
+
+export type User = Readonly<{
+  id: Long;
+}>;
+
+export type Session = Readonly<{
+  user?: Account;
+  expiresAt?: Date;
+  tokens: ReadonlyArray<Long>;
+}>;
@@ -0,0 +1,40 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: signup.proto
+
+export type Signup = Readonly<{
+  /**
+   * @required
+   * @maxLength 254
+   * @format email
+   */
+  email: string;
+  /**
+   * @minLength 1
+   * @maxLength 64
+   * @pattern ^[A-Za-z ]+$
+   */
+  name: string;
+  /**
+   * @minimum 18
+   * @exclusiveMaximum 150
+   */
+  age: number;
+  /** @exclusiveMinimum 0.5 */
+  score: number;
+  /**
+   * @minItems 1
+   * @maxItems 5
+   * @uniqueItems
+   * @minLength 1
+   */
+  tags: ReadonlyArray<string>;
+  /** @required */
+  profile?: Profile;
+  /** @maxProperties 10 */
+  labels: Readonly<Record<string, string>>;
+}>;
+
+export type Profile = Readonly<{
+  bio: string;
+}>;
@@ -0,0 +1,23 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: signup.proto
+
+import { z } from "zod";
+
+export const SignupSchema = z.object({
+  email: z.string().max(254).email(),
+  name: z.string().min(1).max(64).regex(new RegExp("^[A-Za-z ]+$")),
+  age: z.number().gte(18).lt(150),
+  score: z.number().gt(0.5),
+  tags: z.array(z.string().min(1)).min(1).max(5).refine((items) => new Set(items).size === items.length, { message: "items must be unique" }).readonly(),
+  profile: z.lazy(() => ProfileSchema),
+  labels: z.record(z.string(), z.string()).refine((value) => Object.keys(value).length <= 10, { message: "must have at most 10 entries" }).readonly(),
+}).readonly();
+
+export type Signup = z.infer<typeof SignupSchema>;
+
+export const ProfileSchema = z.object({
+  bio: z.string(),
+}).readonly();
+
+export type Profile = z.infer<typeof ProfileSchema>;
@@ -0,0 +1,5 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+
+export type JsonValue = null | boolean | number | string | ReadonlyArray<JsonValue> | JsonObject;
+export type JsonObject = { readonly [key: string]: JsonValue };
@@ -0,0 +1,14 @@
+// Code generated by protoc-gen-tst 0.1.0. DO NOT EDIT.
+// @generated
+// source: event.proto
+
+import type { JsonObject, JsonValue } from "./JsonValue";
+
+export type Event = Readonly<{
+  createdAt?: string;
+  note: string | null;
+  count: string | null;
+  payload?: JsonObject;
+  value?: JsonValue;
+  list?: ReadonlyArray<JsonValue>;
+}>;
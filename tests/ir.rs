@@ -0,0 +1,90 @@
+#[path = "golden/fixtures.rs"]
+#[allow(dead_code)]
+mod fixtures;
+
+use fixtures::*;
+use protobuf::descriptor::*;
+use protoc_gen_tst::ir::{self, Cardinality, Value, ValueKind};
+use protoc_gen_tst::{intermediate_representation, Options};
+
+fn library() -> Vec<File> {
+    let (tags_entry, tags) = map(
+        ".library.Book",
+        "tags",
+        4,
+        field("key", 1, Type::TYPE_STRING),
+        typed("value", 2, Type::TYPE_ENUM, ".library.Genre")
+    );
+    vec![file("library.proto", "library")
+        .dependencies(&["google/protobuf/timestamp.proto"])
+        .messages(vec![with_nested(with_oneofs(message("Book", vec![
+            field("title", 1, Type::TYPE_STRING),
+            optional(field("subtitle", 2, Type::TYPE_STRING), 1),
+            in_oneof(field("isbn", 3, Type::TYPE_STRING), 0),
+            tags,
+            typed("published_at", 5, Type::TYPE_MESSAGE, ".google.protobuf.Timestamp")
+        ]), &["identifier", "_subtitle"]), vec![tags_entry], vec![])])
+        .enums(vec![enumeration("Genre", &[("GENRE_UNSPECIFIED", 0), ("GENRE_POETRY", 1)])])
+        .services(vec![service("Shelf", vec![method("GetBook", ".library.Book", ".library.Book", false, true)])])]
+}
+
+fn files(parameter: &str) -> Vec<ir::File> {
+    intermediate_representation(&request("", vec![timestamp_proto()], library()), Options::parse(parameter).unwrap()).unwrap()
+}
+
+#[test]
+fn resolves_fields() {
+    let files = files("");
+    assert_eq!(files.len(), 1);
+    let book = &files[0].messages[0];
+    assert_eq!(book.ts_name, "Book");
+    assert_eq!(
+        book.fields.iter().map(|field| (field.key.as_str(), field.ts_type.as_str())).collect::<Vec<_>>(),
+        vec![
+            ("title", "string"),
+            ("subtitle", "string"),
+            ("isbn", "string"),
+            ("tags", "Readonly<Record<string, Genre>>"),
+            ("publishedAt", "string")
+        ]
+    );
+    assert_eq!(book.fields[1].oneof, None);
+    assert!(book.fields[1].is_optional);
+    assert_eq!(book.oneof_fields(0).map(|field| field.name.as_str()).collect::<Vec<_>>(), vec!["isbn"]);
+    assert_eq!(book.fields[3].value, Value{
+        kind: ValueKind::Enum,
+        field_type: FieldDescriptorProto_Type::TYPE_ENUM,
+        type_name: ".library.Genre".to_string(),
+        ts_type: "Genre".to_string()
+    });
+    assert_eq!(book.fields[3].cardinality, Cardinality::Map{ key: Value{
+        kind: ValueKind::Scalar(FieldDescriptorProto_Type::TYPE_STRING),
+        field_type: FieldDescriptorProto_Type::TYPE_STRING,
+        type_name: String::new(),
+        ts_type: "string".to_string()
+    }});
+    assert_eq!(book.fields[4].value.kind, ValueKind::WellKnown);
+}
+
+#[test]
+fn follows_options() {
+    let files = files("field_name=original,readonly=false,enum_strip_prefix=true,enum_value_case=lower,map=map");
+    let book = &files[0].messages[0];
+    assert_eq!(book.fields[3].key, "tags");
+    assert_eq!(book.fields[3].ts_type, "Map<string, Genre>");
+    assert_eq!(book.fields[4].key, "published_at");
+    assert_eq!(
+        files[0].enums[0].values.iter().map(|value| value.ts_name.as_str()).collect::<Vec<_>>(),
+        vec!["unspecified", "poetry"]
+    );
+}
+
+#[test]
+fn resolves_services() {
+    let files = files("");
+    let shelf = &files[0].services[0];
+    assert_eq!(shelf.full_name, ".library.Shelf");
+    assert_eq!(shelf.methods[0].path, "/library.Shelf/GetBook");
+    assert_eq!(shelf.methods[0].output.kind, ValueKind::Message);
+    assert!(shelf.methods[0].server_streaming);
+}
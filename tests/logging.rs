@@ -0,0 +1,97 @@
+#[path = "golden/fixtures.rs"]
+#[allow(dead_code)]
+mod fixtures;
+
+use fixtures::*;
+use protobuf::descriptor::*;
+use protobuf::plugin::*;
+use protobuf::Message;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// A request generating `a.proto` declaring `a`, next to a dependency that
+/// is not generated.
+fn request_declaring(parameter: &str, a: DescriptorProto) -> CodeGeneratorRequest {
+    request(
+        parameter,
+        vec![file("dep.proto", "logging").messages(vec![message("Dep", vec![])])],
+        vec![file("a.proto", "logging").messages(vec![a])]
+    )
+}
+
+fn logging_request(parameter: &str) -> CodeGeneratorRequest {
+    request_declaring(parameter, message("A", vec![]))
+}
+
+/// Runs the plugin and returns its stderr lines without timings.
+fn stderr(req: &CodeGeneratorRequest, tst_log: Option<&str>) -> Vec<String> {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_protoc-gen-tst"));
+    command.env_remove("TST_LOG");
+    if let Some(level) = tst_log {
+        command.env("TST_LOG", level);
+    }
+    let mut child = command.stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(&req.write_to_bytes().unwrap()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    String::from_utf8(output.stderr).unwrap()
+        .lines()
+        .map(|line| match line.rsplit_once(" in ") {
+            Some((message, timing)) if timing.ends_with("ms") => message.to_string(),
+            _ => line.to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn silent_by_default() {
+    assert!(stderr(&logging_request(""), None).is_empty());
+}
+
+#[test]
+fn info_reports_processed_files() {
+    assert_eq!(stderr(&logging_request("log=info"), None), vec!["protoc-gen-tst: info: processed a.proto"]);
+    assert_eq!(stderr(&logging_request("verbose=true"), None), vec!["protoc-gen-tst: info: processed a.proto"]);
+}
+
+#[test]
+fn tst_log_enables_debug() {
+    assert_eq!(stderr(&logging_request(""), Some("debug")), vec![
+        "protoc-gen-tst: debug: logging.A resolves to A in a.proto",
+        "protoc-gen-tst: debug: logging.Dep resolves to Dep in dep.proto",
+        "protoc-gen-tst: debug: skipped dep.proto: only a dependency",
+        "protoc-gen-tst: info: processed a.proto",
+        "protoc-gen-tst: debug: wrote a_pb.d.ts (129 bytes)"
+    ]);
+}
+
+#[test]
+fn unknown_log_level_is_rejected() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_protoc-gen-tst"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(&logging_request("log=trace").write_to_bytes().unwrap()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    let resp: CodeGeneratorResponse = protobuf::parse_from_bytes(&output.stdout).unwrap();
+    assert_eq!(resp.get_error(), "invalid parameter: log=trace");
+}
+
+#[test]
+fn map_entries_are_not_resolved() {
+    let (entry, labels) = map(
+        ".logging.A",
+        "labels",
+        1,
+        field("key", 1, Type::TYPE_STRING),
+        field("value", 2, Type::TYPE_STRING)
+    );
+    let req = request_declaring("", with_nested(message("A", vec![labels]), vec![entry], vec![]));
+    let lines = stderr(&req, Some("debug"));
+    assert!(lines.iter().any(|line| line.ends_with("logging.A resolves to A in a.proto")), "{:?}", lines);
+    assert!(!lines.iter().any(|line| line.contains("LabelsEntry")), "{:?}", lines);
+}
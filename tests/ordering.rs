@@ -0,0 +1,72 @@
+#[path = "golden/fixtures.rs"]
+#[allow(dead_code)]
+mod fixtures;
+
+use fixtures::*;
+use protobuf::plugin::*;
+use protobuf::Message;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn run(req: &CodeGeneratorRequest) -> CodeGeneratorResponse {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_protoc-gen-tst"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(&req.write_to_bytes().unwrap()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    protobuf::parse_from_bytes(&output.stdout).unwrap()
+}
+
+fn json_request(files: Vec<File>) -> CodeGeneratorRequest {
+    request("runtime=json", vec![], files)
+}
+
+#[test]
+fn files_are_sorted_by_name() {
+    let resp = run(&json_request(vec![
+        declaring("b.proto", &["B"]),
+        declaring("a.proto", &["A"])
+    ]));
+    let names = resp.get_file().iter().map(|file| file.get_name()).collect::<Vec<_>>();
+    assert_eq!(names, vec!["a_json.ts", "a_pb.d.ts", "b_json.ts", "b_pb.d.ts"]);
+}
+
+#[test]
+fn output_does_not_depend_on_input_order() {
+    let forward = run(&json_request(vec![
+        declaring("a.proto", &["Zeta", "Alpha"]),
+        declaring("b.proto", &["B"])
+    ]));
+    let backward = run(&json_request(vec![
+        declaring("b.proto", &["B"]),
+        declaring("a.proto", &["Zeta", "Alpha"])
+    ]));
+    assert_eq!(forward.write_to_bytes().unwrap(), backward.write_to_bytes().unwrap());
+    let content = forward.get_file()[1].get_content();
+    assert!(content.find("type Zeta").unwrap() < content.find("type Alpha").unwrap());
+}
+
+#[test]
+fn output_does_not_depend_on_jobs() {
+    let files = || (0..64)
+        .rev()
+        .map(|i| declaring(&format!("pkg{}/file{}.proto", i % 4, i), &[&format!("Message{}", i), "Shared"]))
+        .collect::<Vec<_>>();
+    let sequential = run(&request("runtime=json,guards=true,jobs=1", vec![], files()));
+    assert!(!sequential.has_error(), "{}", sequential.get_error());
+    assert_eq!(sequential.get_file().len(), 64 * 3);
+    for jobs in ["jobs=8", "jobs=auto"] {
+        let parallel = run(&request(&format!("runtime=json,guards=true,{}", jobs), vec![], files()));
+        assert_eq!(parallel.write_to_bytes().unwrap(), sequential.write_to_bytes().unwrap(), "{}", jobs);
+    }
+}
+
+#[test]
+fn invalid_jobs_are_rejected() {
+    for jobs in ["0", "-1", "many"] {
+        let resp = run(&request(&format!("jobs={}", jobs), vec![], vec![declaring("a.proto", &["A"])]));
+        assert_eq!(resp.get_error(), format!("invalid parameter: jobs={}", jobs));
+    }
+}
@@ -0,0 +1,179 @@
+#[path = "golden/fixtures.rs"]
+#[allow(dead_code)]
+mod fixtures;
+
+use fixtures::*;
+use protobuf::descriptor::*;
+use protobuf::Message;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// A scratch directory of its own for `test`.
+fn scratch_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("protoc-gen-tst-{}-{}", test, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Runs the standalone mode against `files` with `--cache`.
+fn cached_output(dir: &Path, files: Vec<File>) -> std::process::Output {
+    let mut descriptor_set = FileDescriptorSet::new();
+    descriptor_set.set_file(files.into_iter().map(|file| file.proto).collect());
+    let descriptor_set_path = dir.join("descriptors.pb");
+    fs::write(&descriptor_set_path, descriptor_set.write_to_bytes().unwrap()).unwrap();
+    Command::new(env!("CARGO_BIN_EXE_protoc-gen-tst"))
+        .arg("--descriptor_set")
+        .arg(&descriptor_set_path)
+        .arg("--out")
+        .arg(dir.join("out"))
+        .arg("--cache")
+        .arg(dir.join("cache"))
+        .output()
+        .unwrap()
+}
+
+fn run_cached(dir: &Path, files: Vec<File>) {
+    let output = cached_output(dir, files);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
+
+fn output(dir: &Path, name: &str) -> String {
+    fs::read_to_string(dir.join("out").join(name)).unwrap()
+}
+
+#[test]
+fn cache_skips_unchanged_files() {
+    let dir = scratch_dir("unchanged");
+    run_cached(&dir, vec![declaring("b.proto", &["B"]), declaring("a.proto", &["A"]).dependencies(&["b.proto"])]);
+    assert!(output(&dir, "a_pb.d.ts").contains("export type A"));
+    fs::write(dir.join("out/a_pb.d.ts"), "stale").unwrap();
+    fs::write(dir.join("out/b_pb.d.ts"), "stale").unwrap();
+    run_cached(&dir, vec![declaring("b.proto", &["B"]), declaring("a.proto", &["A"]).dependencies(&["b.proto"])]);
+    assert_eq!(output(&dir, "a_pb.d.ts"), "stale");
+    assert_eq!(output(&dir, "b_pb.d.ts"), "stale");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn cache_regenerates_files_whose_dependencies_changed() {
+    let dir = scratch_dir("changed");
+    run_cached(&dir, vec![
+        declaring("b.proto", &["B"]),
+        declaring("a.proto", &["A"]).dependencies(&["b.proto"]),
+        declaring("c.proto", &["C"])
+    ]);
+    fs::write(dir.join("out/a_pb.d.ts"), "stale").unwrap();
+    fs::write(dir.join("out/c_pb.d.ts"), "stale").unwrap();
+    run_cached(&dir, vec![
+        declaring("b.proto", &["B", "Other"]),
+        declaring("a.proto", &["A"]).dependencies(&["b.proto"]),
+        declaring("c.proto", &["C"])
+    ]);
+    assert!(output(&dir, "a_pb.d.ts").contains("export type A"));
+    assert!(output(&dir, "b_pb.d.ts").contains("export type Other"));
+    assert_eq!(output(&dir, "c_pb.d.ts"), "stale");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn cache_regenerates_deleted_outputs() {
+    let dir = scratch_dir("deleted");
+    run_cached(&dir, vec![declaring("b.proto", &["B"]), declaring("a.proto", &["A"]).dependencies(&["b.proto"])]);
+    fs::remove_file(dir.join("out/a_pb.d.ts")).unwrap();
+    run_cached(&dir, vec![declaring("b.proto", &["B"]), declaring("a.proto", &["A"]).dependencies(&["b.proto"])]);
+    assert!(output(&dir, "a_pb.d.ts").contains("export type A"));
+    fs::remove_dir_all(dir.join("out")).unwrap();
+    run_cached(&dir, vec![declaring("b.proto", &["B"]), declaring("a.proto", &["A"]).dependencies(&["b.proto"])]);
+    assert!(output(&dir, "a_pb.d.ts").contains("export type A"));
+    assert!(output(&dir, "b_pb.d.ts").contains("export type B"));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn cache_regenerates_files_whose_names_are_prefixed_by_new_files() {
+    let dir = scratch_dir("prefixed");
+    let one = || file("a.proto", "one").messages(vec![message("Address", vec![])]);
+    run_cached(&dir, vec![one()]);
+    assert!(output(&dir, "a_pb.d.ts").contains("export type Address ="));
+    run_cached(&dir, vec![one(), file("b.proto", "two").messages(vec![message("Address", vec![])])]);
+    assert!(output(&dir, "a_pb.d.ts").contains("export type one_Address ="));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn cache_keeps_output_collisions_an_error() {
+    let dir = scratch_dir("collisions");
+    let output = cached_output(&dir, vec![declaring("a.proto", &["A"]), declaring("A.proto", &["B"])]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("A.proto and a.proto generate the same output A"));
+    assert!(!dir.join("out").exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn list_only_prints_files_without_writing() {
+    let dir = scratch_dir("list-only");
+    let mut descriptor_set = FileDescriptorSet::new();
+    descriptor_set.set_file(vec![declaring("a.proto", &["A"]).proto].into());
+    let descriptor_set_path = dir.join("descriptors.pb");
+    fs::write(&descriptor_set_path, descriptor_set.write_to_bytes().unwrap()).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_protoc-gen-tst"))
+        .arg("--descriptor_set")
+        .arg(&descriptor_set_path)
+        .arg("--out")
+        .arg(dir.join("out"))
+        .arg("--parameter")
+        .arg("list_only=true")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout).unwrap().starts_with("a_pb.d.ts\t"));
+    assert!(!dir.join("out").exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn version_lists_the_parameters() {
+    let output = Command::new(env!("CARGO_BIN_EXE_protoc-gen-tst")).arg("--version").output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with(&format!("protoc-gen-tst {}\n", env!("CARGO_PKG_VERSION"))));
+    for (key, values) in protoc_gen_tst::PARAMETERS {
+        assert!(stdout.contains(&format!("{}  ", key)) && stdout.contains(values), "{}", key);
+    }
+}
+
+#[test]
+fn dumped_request_replays() {
+    use protobuf::plugin::*;
+    use std::io::Write;
+    let dir = scratch_dir("replay");
+    let req = request(
+        &format!("dump_request={}", dir.join("request.bin").display()),
+        vec![],
+        vec![declaring("a.proto", &["A"])]
+    );
+    let mut plugin = Command::new(env!("CARGO_BIN_EXE_protoc-gen-tst"))
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    plugin.stdin.take().unwrap().write_all(&req.write_to_bytes().unwrap()).unwrap();
+    let stdout = plugin.wait_with_output().unwrap().stdout;
+    let resp = protobuf::parse_from_bytes::<CodeGeneratorResponse>(&stdout).unwrap();
+    assert_eq!(resp.get_error(), "");
+    let status = Command::new(env!("CARGO_BIN_EXE_protoc-gen-tst"))
+        .arg("--request")
+        .arg(dir.join("request.bin"))
+        .arg("--out")
+        .arg(dir.join("out"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+    for file in resp.get_file() {
+        assert_eq!(output(&dir, file.get_name()), file.get_content());
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
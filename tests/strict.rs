@@ -0,0 +1,72 @@
+#[path = "golden/fixtures.rs"]
+#[allow(dead_code)]
+mod fixtures;
+
+use fixtures::*;
+
+fn legacy_proto() -> File {
+    file("legacy.proto", "legacy")
+        .messages(vec![message("Query", vec![typed("owner", 1, Type::TYPE_MESSAGE, ".missing.Owner")])])
+        .line(&[4, 0, 2, 0], 6)
+}
+
+#[test]
+fn strict_reports_every_unsupported_construct() {
+    let resp = protoc_gen_tst::process_request(request("strict=true", vec![], vec![legacy_proto()]));
+    assert_eq!(resp.get_error(), "unsupported constructs:\n\
+        legacy.proto:6:1: legacy.Query.owner: type missing.Owner cannot be resolved");
+    assert!(resp.get_file().is_empty());
+}
+
+#[test]
+fn strict_reports_machine_readable_lines() {
+    let proto = legacy_proto()
+        .messages(vec![
+            message("Query", vec![typed("owner", 1, Type::TYPE_MESSAGE, ".missing.Owner")]),
+            message("Page", vec![typed("cursor", 1, Type::TYPE_MESSAGE, ".missing.Cursor")])
+        ])
+        .line(&[4, 1], 10);
+    let resp = protoc_gen_tst::process_request(request("strict=true,error_format=machine", vec![], vec![
+        proto,
+        file("other.proto", "other").messages(vec![message("Other", vec![typed("id", 1, Type::TYPE_MESSAGE, ".missing.Id")])])
+    ]));
+    assert_eq!(resp.get_error(), "\
+        legacy.proto:6:1: legacy.Query.owner: type missing.Owner cannot be resolved\n\
+        legacy.proto:10:1: legacy.Page.cursor: type missing.Cursor cannot be resolved\n\
+        other.proto:1:1: other.Other.id: type missing.Id cannot be resolved");
+}
+
+#[test]
+fn constructs_degrade_without_strict() {
+    let resp = protoc_gen_tst::process_request(request("", vec![], vec![legacy_proto()]));
+    assert!(!resp.has_error(), "{}", resp.get_error());
+    assert!(!resp.get_file().is_empty());
+}
+
+#[test]
+fn strict_accepts_supported_protos() {
+    let resp = protoc_gen_tst::process_request(request(
+        "strict=true",
+        vec![timestamp_proto()],
+        vec![file("event.proto", "events")
+            .dependencies(&["google/protobuf/timestamp.proto"])
+            .messages(vec![message("Event", vec![typed("at", 1, Type::TYPE_MESSAGE, ".google.protobuf.Timestamp")])])]
+    ));
+    assert!(!resp.has_error(), "{}", resp.get_error());
+}
+
+#[test]
+fn strict_accepts_proto2_groups_and_defaults() {
+    let proto = file("search.proto", "search").proto2().messages(vec![
+        with_nested(
+            message("Query", vec![
+                typed("result", 1, Type::TYPE_GROUP, ".search.Query.Result"),
+                with_default(field("limit", 2, Type::TYPE_INT32), "10")
+            ]),
+            vec![message("Result", vec![field("url", 2, Type::TYPE_STRING)])],
+            vec![]
+        )
+    ]);
+    let resp = protoc_gen_tst::process_request(request("strict=true", vec![], vec![proto]));
+    assert!(!resp.has_error(), "{}", resp.get_error());
+}
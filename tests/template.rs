@@ -0,0 +1,99 @@
+#[path = "golden/fixtures.rs"]
+#[allow(dead_code)]
+mod fixtures;
+
+use fixtures::*;
+
+fn protos() -> Vec<File> {
+    vec![
+        file("shop/v1/order.proto", "shop.v1")
+            .messages(vec![
+                with_oneofs(message("Order", vec![
+                    field("id", 1, Type::TYPE_STRING),
+                    repeated(typed("lines", 2, Type::TYPE_MESSAGE, ".shop.v1.Line")),
+                    in_oneof(field("card", 3, Type::TYPE_STRING), 0),
+                    in_oneof(field("cash", 4, Type::TYPE_BOOL), 0)
+                ]), &["payment"]),
+                message("Line", vec![field("quantity", 1, Type::TYPE_INT32)])
+            ])
+            .enums(vec![enumeration("Status", &[("STATUS_UNSPECIFIED", 0), ("STATUS_PAID", 1)])])
+            .services(vec![service("OrderService", vec![method("GetOrder", ".shop.v1.Order", ".shop.v1.Order", false, false)])])
+    ]
+}
+
+#[test]
+fn template_renders_the_ir_of_each_file() {
+    let path = temp_file("wrappers.ts.hbs", "\
+{{!-- Company wrappers --}}
+import type { {{#each messages}}{{name}}{{#unless @last}}, {{/unless}}{{/each}} } from \"{{typesModule}}\";
+import { wrap } from \"@acme/runtime\";
+
+{{#each messages}}
+export const wrap{{name}} = wrap<{{name}}>(\"{{fullName}}\", [
+  {{#each fields}}
+  { key: \"{{key}}\", number: {{number}}, type: \"{{tsType}}\"{{#if isRepeated}}, repeated: true{{/if}}{{#if oneof}}, oneof: \"{{oneof}}\"{{/if}} },
+  {{/each}}
+]);
+{{/each}}
+{{#each enums}}
+export const {{name}}Names = [{{#each values}}\"{{name}}\"{{#unless @last}}, {{/unless}}{{/each}}];
+{{/each}}
+{{#each services}}
+{{#each methods}}
+// {{../name}}.{{name}}: {{path}} ({{input.tsType}} -> {{output.tsType}})
+{{/each}}
+{{/each}}
+");
+    let resp = generate(&format!("template={},banner=none", path), vec![], protos());
+    assert!(!resp.has_error(), "{}", resp.get_error());
+    assert_eq!(file_content(&resp, "shop/v1/order_wrappers.ts"), "\
+import type { Order, Line } from \"./order_pb\";
+import { wrap } from \"@acme/runtime\";
+
+export const wrapOrder = wrap<Order>(\"shop.v1.Order\", [
+  { key: \"id\", number: 1, type: \"string\" },
+  { key: \"lines\", number: 2, type: \"ReadonlyArray<Line>\", repeated: true },
+  { key: \"card\", number: 3, type: \"string\", oneof: \"payment\" },
+  { key: \"cash\", number: 4, type: \"boolean\", oneof: \"payment\" },
+]);
+export const wrapLine = wrap<Line>(\"shop.v1.Line\", [
+  { key: \"quantity\", number: 1, type: \"number\" },
+]);
+export const StatusNames = [\"STATUS_UNSPECIFIED\", \"STATUS_PAID\"];
+// OrderService.GetOrder: /shop.v1.OrderService/GetOrder (Order -> Order)
+");
+}
+
+#[test]
+fn template_supports_with_else_and_whitespace_control() {
+    let path = temp_file("summary.txt.hbs", "\
+{{#with services.0~}}
+  service {{name}}
+{{~else}}no services{{/with}}
+{{#each messages}}{{@index}}:{{name}}{{#if @first}}*{{/if}}{{else}}none{{/each}}
+");
+    let resp = generate(&format!("template={},banner=none", path), vec![], protos());
+    assert!(!resp.has_error(), "{}", resp.get_error());
+    assert_eq!(file_content(&resp, "shop/v1/order_summary.txt"), "service OrderService\n0:Order*1:Line\n");
+}
+
+#[test]
+fn broken_templates_are_reported() {
+    let path = temp_file("unclosed.ts.hbs", "{{#each messages}}{{name}}");
+    assert_eq!(
+        generate(&format!("template={}", path), vec![], protos()).get_error(),
+        format!("{}: unclosed {{{{#each}}}}", path)
+    );
+    let path = temp_file("mismatched.ts.hbs", "{{#if enums}}{{/each}}");
+    assert_eq!(
+        generate(&format!("template={}", path), vec![], protos()).get_error(),
+        format!("{}: {{{{/each}}}} closes {{{{#if}}}}", path)
+    );
+    let path = temp_file("helper.ts.hbs", "{{#lookup messages}}{{/lookup}}");
+    assert_eq!(
+        generate(&format!("template={}", path), vec![], protos()).get_error(),
+        format!("{}: unknown helper #lookup", path)
+    );
+    let resp = generate("template=/nonexistent/wrappers.ts.hbs", vec![], protos());
+    assert!(resp.get_error().starts_with("/nonexistent/wrappers.ts.hbs: "), "{}", resp.get_error());
+}